@@ -4,23 +4,64 @@ set -euo pipefail
 ROOT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")/.." && pwd)"
 RAW_DIR="${ROOT_DIR}/data/raw"
 OUT_JSON="${ROOT_DIR}/data/sanctions.json"
+DIFF_JSON="${ROOT_DIR}/data/sanctions.diff.json"
 RAW_CSV="${RAW_DIR}/ofac_sdn.csv"
+RAW_ALT_CSV="${RAW_DIR}/ofac_alt.csv"
+RAW_ADD_CSV="${RAW_DIR}/ofac_add.csv"
+RAW_EU_XML="${RAW_DIR}/eu_consolidated.xml"
+RAW_UN_XML="${RAW_DIR}/un_consolidated.xml"
+RAW_UK_CSV="${RAW_DIR}/uk_hmt.csv"
 
 mkdir -p "${RAW_DIR}"
 
 URL_PRIMARY="${OFAC_SDN_URL:-https://www.treasury.gov/ofac/downloads/sdn.csv}"
 URL_FALLBACK="${OFAC_SDN_FALLBACK_URL:-https://sanctionssearch.ofac.treas.gov/SDN.csv}"
+URL_ALT="${OFAC_ALT_URL:-https://www.treasury.gov/ofac/downloads/alt.csv}"
+URL_ADD="${OFAC_ADD_URL:-https://www.treasury.gov/ofac/downloads/add.csv}"
+URL_UN="${UN_SANCTIONS_URL:-https://scsanctions.un.org/resources/xml/en/consolidated.xml}"
+URL_UK="${UK_HMT_URL:-https://ofsistorage.blob.core.windows.net/publishlive/2022format/ConList.csv}"
+# The EU export link carries a per-user token, so it has no default.
+URL_EU="${EU_SANCTIONS_URL:-}"
+
+# Optional lists: a failed download drops that list from this refresh.
+fetch_optional() {
+  local name="$1" url="$2" out="$3"
+  if [[ -z "${url}" ]]; then
+    echo "no URL for ${name}, skipping"
+    rm -f "${out}"
+    return
+  fi
+  echo "downloading ${name}..."
+  if ! curl -fsSLR "${url}" -o "${out}"; then
+    echo "${name} download failed, continuing without it"
+    rm -f "${out}"
+  fi
+}
 
 echo "downloading OFAC SDN CSV..."
-if ! curl -fsSL "${URL_PRIMARY}" -o "${RAW_CSV}"; then
+if ! curl -fsSLR "${URL_PRIMARY}" -o "${RAW_CSV}"; then
   echo "primary URL failed, trying fallback..."
-  curl -fsSL "${URL_FALLBACK}" -o "${RAW_CSV}"
+  curl -fsSLR "${URL_FALLBACK}" -o "${RAW_CSV}"
 fi
 
+fetch_optional "OFAC alternate names CSV" "${URL_ALT}" "${RAW_ALT_CSV}"
+fetch_optional "OFAC addresses CSV" "${URL_ADD}" "${RAW_ADD_CSV}"
+fetch_optional "EU consolidated XML" "${URL_EU}" "${RAW_EU_XML}"
+fetch_optional "UN consolidated XML" "${URL_UN}" "${RAW_UN_XML}"
+fetch_optional "UK HMT consolidated CSV" "${URL_UK}" "${RAW_UK_CSV}"
+
 echo "normalizing into ${OUT_JSON}..."
 (
   cd "${ROOT_DIR}"
-  OFAC_SDN_CSV_PATH="${RAW_CSV}" SANCTIONS_OUTPUT_PATH="${OUT_JSON}" cargo run --quiet --bin refresh_sanctions
+  OFAC_SDN_CSV_PATH="${RAW_CSV}" \
+    OFAC_ALT_CSV_PATH="${RAW_ALT_CSV}" \
+    OFAC_ADD_CSV_PATH="${RAW_ADD_CSV}" \
+    EU_SANCTIONS_XML_PATH="${RAW_EU_XML}" \
+    UN_SANCTIONS_XML_PATH="${RAW_UN_XML}" \
+    UK_HMT_CSV_PATH="${RAW_UK_CSV}" \
+    SANCTIONS_OUTPUT_PATH="${OUT_JSON}" \
+    SANCTIONS_DIFF_REPORT_PATH="${DIFF_JSON}" \
+    cargo run --quiet --bin refresh_sanctions
 )
 
-echo "done: ${OUT_JSON}"
+echo "done: ${OUT_JSON} (changes in ${DIFF_JSON})"
@@ -1,17 +1,47 @@
 use crate::config::environment::AppConfig;
 use crate::infra::InfraClients;
+use crate::infra::provider_client::{ComplianceProvider, provider_from_config};
 use crate::module::compliance_attestation::route::register_routes;
+use crate::service::attestation_signing_service::AttestationSigner;
+use crate::service::policy_source_service::PolicySource;
+use crate::service::sanctions_store_service::SanctionsStore;
 use axum::Router;
+use std::sync::Arc;
+use std::time::Duration;
 
 #[derive(Debug, Clone)]
 pub struct AppState {
     pub config: AppConfig,
     pub infra: Option<InfraClients>,
+    pub sanctions: Arc<SanctionsStore>,
+    pub policy: Arc<PolicySource>,
+    pub signer: Option<Arc<AttestationSigner>>,
+    pub provider: Option<Arc<dyn ComplianceProvider>>,
 }
 
 impl AppState {
     pub fn new(config: AppConfig, infra: Option<InfraClients>) -> Self {
-        Self { config, infra }
+        let sanctions = Arc::new(SanctionsStore::open(&config.sanctions_data_path));
+        let policy = PolicySource::new(
+            config.policy_snapshot_url.as_deref(),
+            &config.policy_snapshot_path,
+            Duration::from_secs(config.policy_cache_ttl_seconds.max(0) as u64),
+        )
+        .expect("policy source http client");
+        let signer = config
+            .attestation_signing_key_hex
+            .as_deref()
+            .and_then(|hex| AttestationSigner::from_hex(hex).ok())
+            .map(Arc::new);
+        let provider = provider_from_config(&config).unwrap_or_default();
+        Self {
+            config,
+            infra,
+            sanctions,
+            policy: Arc::new(policy),
+            signer,
+            provider,
+        }
     }
 }
 
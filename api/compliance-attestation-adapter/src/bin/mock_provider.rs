@@ -0,0 +1,31 @@
+use compliance_attestation_adapter::infra::mock_provider::{
+    MOCK_BASE_RISK_SCORE, MOCK_PROVIDER_ADDR, MockProvider,
+};
+use std::env;
+use tokio::net::TcpListener;
+
+/// Serves the mock compliance provider for `COMPLIANCE_PROVIDER=mock`.
+/// `MOCK_PROVIDER_ADDR` and `MOCK_PROVIDER_RISK_SCORE` override the
+/// listen address and the score every subject gets.
+#[tokio::main]
+async fn main() {
+    if let Err(e) = run().await {
+        eprintln!("mock_provider error: {e}");
+        std::process::exit(1);
+    }
+}
+
+async fn run() -> Result<(), String> {
+    let addr = env::var("MOCK_PROVIDER_ADDR").unwrap_or_else(|_| MOCK_PROVIDER_ADDR.to_string());
+    let risk_score = match env::var("MOCK_PROVIDER_RISK_SCORE") {
+        Ok(v) => v
+            .parse::<u16>()
+            .map_err(|e| format!("invalid MOCK_PROVIDER_RISK_SCORE: {e}"))?,
+        Err(_) => MOCK_BASE_RISK_SCORE,
+    };
+    let listener = TcpListener::bind(&addr)
+        .await
+        .map_err(|e| format!("bind {addr} failed: {e}"))?;
+    println!("mock compliance provider listening on http://{addr}");
+    MockProvider::new(risk_score).serve(listener).await
+}
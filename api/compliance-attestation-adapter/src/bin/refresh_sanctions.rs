@@ -1,7 +1,11 @@
-use compliance_attestation_adapter::service::sanctions_service::SanctionsEntry;
+use compliance_attestation_adapter::service::sanctions_ingest_service::{
+    EuConsolidatedParser, OfacParser, SanctionsListParser, UkHmtParser, UnConsolidatedParser,
+    diff_lists, merge_lists,
+};
+use compliance_attestation_adapter::service::sanctions_service::load_sanctions_entries;
 use std::env;
 use std::fs;
-use std::io::Write;
+use std::path::{Path, PathBuf};
 
 fn main() {
     if let Err(e) = run() {
@@ -11,59 +15,90 @@ fn main() {
 }
 
 fn run() -> Result<(), String> {
-    let input_path =
-        env::var("OFAC_SDN_CSV_PATH").unwrap_or_else(|_| "data/raw/ofac_sdn.csv".to_string());
-    let output_path =
-        env::var("SANCTIONS_OUTPUT_PATH").unwrap_or_else(|_| "data/sanctions.json".to_string());
+    let output_path = env_path("SANCTIONS_OUTPUT_PATH", "data/sanctions.json");
+    let report_path = env_path("SANCTIONS_DIFF_REPORT_PATH", "data/sanctions.diff.json");
 
-    let mut reader = csv::ReaderBuilder::new()
-        .has_headers(true)
-        .from_path(&input_path)
-        .map_err(|e| format!("failed to open input csv: {e}"))?;
+    let ofac_sdn = env_path("OFAC_SDN_CSV_PATH", "data/raw/ofac_sdn.csv");
+    let parsers: Vec<(PathBuf, Box<dyn SanctionsListParser>)> = vec![
+        (
+            ofac_sdn.clone(),
+            Box::new(OfacParser {
+                sdn_path: ofac_sdn,
+                alt_path: existing(env_path("OFAC_ALT_CSV_PATH", "data/raw/ofac_alt.csv")),
+                add_path: existing(env_path("OFAC_ADD_CSV_PATH", "data/raw/ofac_add.csv")),
+            }),
+        ),
+        {
+            let path = env_path("EU_SANCTIONS_XML_PATH", "data/raw/eu_consolidated.xml");
+            (path.clone(), Box::new(EuConsolidatedParser { path }))
+        },
+        {
+            let path = env_path("UN_SANCTIONS_XML_PATH", "data/raw/un_consolidated.xml");
+            (path.clone(), Box::new(UnConsolidatedParser { path }))
+        },
+        {
+            let path = env_path("UK_HMT_CSV_PATH", "data/raw/uk_hmt.csv");
+            (path.clone(), Box::new(UkHmtParser { path }))
+        },
+    ];
 
-    let mut entries: Vec<SanctionsEntry> = Vec::new();
-    for record in reader.records() {
-        let rec = match record {
-            Ok(r) => r,
-            Err(_) => continue,
-        };
-        let name = rec.get(1).unwrap_or_default().trim();
-        if name.is_empty() {
+    let mut lists = Vec::new();
+    for (input, parser) in &parsers {
+        if !input.exists() {
+            eprintln!(
+                "refresh_sanctions: skipping {} ({} not found)",
+                parser.source(),
+                input.display()
+            );
             continue;
         }
-        let program = rec.get(2).unwrap_or_default().trim();
-        let country = rec.get(8).unwrap_or_default().trim();
-        let address = rec.get(9).unwrap_or_default().trim();
-
-        entries.push(SanctionsEntry {
-            source: "OFAC".to_string(),
-            program: if program.is_empty() {
-                "SDN".to_string()
-            } else {
-                program.to_string()
-            },
-            name: name.to_string(),
-            jurisdiction: if country.is_empty() {
-                None
-            } else {
-                Some(country.to_string())
-            },
-            address: if address.is_empty() {
-                None
-            } else {
-                Some(address.to_string())
-            },
-        });
+        let entries = parser
+            .parse()
+            .map_err(|e| format!("{} list: {e}", parser.source()))?;
+        println!("{}: {} entries", parser.source(), entries.len());
+        lists.push(entries);
+    }
+    if lists.is_empty() {
+        return Err("no sanctions list inputs found".to_string());
     }
+    let entries = merge_lists(lists);
+
+    let previous = if output_path.exists() {
+        load_sanctions_entries(&output_path.to_string_lossy())?
+    } else {
+        Vec::new()
+    };
+    let report = diff_lists(&previous, &entries);
+    println!(
+        "merged {} entries: {} added, {} removed",
+        report.current_count,
+        report.added.len(),
+        report.removed.len()
+    );
 
     let payload = serde_json::to_vec_pretty(&entries)
         .map_err(|e| format!("failed to serialize sanctions entries: {e}"))?;
-    if let Some(parent) = std::path::Path::new(&output_path).parent() {
+    write_atomically(&output_path, &payload)?;
+    let report = serde_json::to_vec_pretty(&report)
+        .map_err(|e| format!("failed to serialize diff report: {e}"))?;
+    write_atomically(&report_path, &report)
+}
+
+fn env_path(key: &str, default: &str) -> PathBuf {
+    PathBuf::from(env::var(key).unwrap_or_else(|_| default.to_string()))
+}
+
+fn existing(path: PathBuf) -> Option<PathBuf> {
+    path.exists().then_some(path)
+}
+
+/// The adapter reloads `sanctions.json` while running, so it must never see a
+/// half-written file.
+fn write_atomically(path: &Path, payload: &[u8]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
         fs::create_dir_all(parent).map_err(|e| format!("failed to create output dir: {e}"))?;
     }
-    let mut file =
-        fs::File::create(&output_path).map_err(|e| format!("failed to create output file: {e}"))?;
-    file.write_all(&payload)
-        .map_err(|e| format!("failed to write output file: {e}"))?;
-    Ok(())
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, payload).map_err(|e| format!("failed to write output file: {e}"))?;
+    fs::rename(&tmp, path).map_err(|e| format!("failed to replace output file: {e}"))
 }
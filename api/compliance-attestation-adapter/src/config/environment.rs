@@ -11,13 +11,24 @@ pub struct AppConfig {
     pub intake_max_age_seconds: i64,
     pub intake_max_future_skew_seconds: i64,
     pub sanctions_data_path: String,
+    pub sanctions_reload_interval_seconds: i64,
+    pub policy_snapshot_url: Option<String>,
+    pub policy_cache_ttl_seconds: i64,
     pub policy_snapshot_path: String,
-    pub policy_version: String,
+    pub policy_version: Option<String>,
     pub attestation_ttl_seconds: i64,
     pub replay_ttl_seconds: i64,
     pub require_internal_signature: bool,
     pub internal_signing_secret: Option<String>,
     pub encryption_key_hex: Option<String>,
+    pub attestation_signing_key_hex: Option<String>,
+    pub compliance_provider: Option<String>,
+    pub provider_base_url: Option<String>,
+    pub provider_auth_scheme: Option<String>,
+    pub provider_api_key: Option<String>,
+    pub provider_api_key_header: String,
+    pub provider_timeout_ms: i64,
+    pub provider_max_retries: i64,
     pub frankfurter_base_url: String,
     pub fx_base_currency: String,
     pub fx_quote_currency: String,
@@ -43,16 +54,30 @@ impl AppConfig {
                 30,
             )?,
             sanctions_data_path: read_optional_string("SANCTIONS_DATA_PATH", "data/sanctions.json"),
+            sanctions_reload_interval_seconds: read_optional_i64(
+                "SANCTIONS_RELOAD_INTERVAL_SECONDS",
+                30,
+            )?,
+            policy_snapshot_url: read_optional_env("POLICY_SNAPSHOT_URL"),
+            policy_cache_ttl_seconds: read_optional_i64("POLICY_CACHE_TTL_SECONDS", 30)?,
             policy_snapshot_path: read_optional_string(
                 "POLICY_SNAPSHOT_PATH",
                 "config/policy_snapshot.json",
             ),
-            policy_version: read_optional_string("POLICY_VERSION", "policy-v1"),
+            policy_version: read_optional_env("POLICY_VERSION"),
             attestation_ttl_seconds: read_optional_i64("ATTESTATION_TTL_SECONDS", 3600)?,
             replay_ttl_seconds: read_optional_i64("REPLAY_TTL_SECONDS", 86400)?,
             require_internal_signature: read_optional_bool("REQUIRE_INTERNAL_SIGNATURE", false),
             internal_signing_secret: read_optional_env("INTERNAL_SIGNING_SECRET"),
             encryption_key_hex: read_optional_env("ENCRYPTION_KEY_HEX"),
+            attestation_signing_key_hex: read_optional_env("ATTESTATION_SIGNING_KEY_HEX"),
+            compliance_provider: read_optional_env("COMPLIANCE_PROVIDER"),
+            provider_base_url: read_optional_env("PROVIDER_BASE_URL"),
+            provider_auth_scheme: read_optional_env("PROVIDER_AUTH_SCHEME"),
+            provider_api_key: read_optional_env("PROVIDER_API_KEY"),
+            provider_api_key_header: read_optional_string("PROVIDER_API_KEY_HEADER", "X-API-Key"),
+            provider_timeout_ms: read_optional_i64("PROVIDER_TIMEOUT_MS", 5000)?,
+            provider_max_retries: read_optional_i64("PROVIDER_MAX_RETRIES", 2)?,
             frankfurter_base_url: read_optional_string(
                 "FRANKFURTER_BASE_URL",
                 "https://api.frankfurter.dev/v1",
@@ -0,0 +1,118 @@
+use crate::infra::provider_client::{AssessResponseBody, SubjectRisk};
+use axum::extract::State;
+use axum::routing::post;
+use axum::{Json, Router};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+
+/// Where `COMPLIANCE_PROVIDER=mock` looks for the server when
+/// `PROVIDER_BASE_URL` is unset; the `mock_provider` bin listens here.
+pub const MOCK_PROVIDER_ADDR: &str = "127.0.0.1:8090";
+pub const MOCK_BASE_RISK_SCORE: u16 = 10;
+
+/// Deterministic provider for local runs and tests, served over the same
+/// `POST /v1/assess` wire format as a real one, so the adapter reaches it
+/// through `HttpComplianceProvider` like any other provider. Every subject
+/// scores the base score unless overridden.
+#[derive(Debug, Clone)]
+pub struct MockProvider {
+    base_risk_score: u16,
+    overrides: HashMap<String, SubjectRisk>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AssessRequest {
+    request_id: String,
+    #[serde(default)]
+    subjects: Vec<AssessSubject>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AssessSubject {
+    subject_id: String,
+}
+
+impl MockProvider {
+    pub fn new(base_risk_score: u16) -> Self {
+        Self {
+            base_risk_score,
+            overrides: HashMap::new(),
+        }
+    }
+
+    pub fn with_subject(mut self, subject_id: &str, risk_score: u16, flags: &[&str]) -> Self {
+        self.overrides.insert(
+            subject_id.to_string(),
+            SubjectRisk {
+                subject_id: subject_id.to_string(),
+                risk_score,
+                flags: flags.iter().map(|f| f.to_string()).collect(),
+            },
+        );
+        self
+    }
+
+    pub fn router(self) -> Router {
+        Router::new()
+            .route("/v1/assess", post(assess))
+            .with_state(Arc::new(self))
+    }
+
+    pub async fn serve(self, listener: TcpListener) -> Result<(), String> {
+        axum::serve(listener, self.router())
+            .await
+            .map_err(|e| format!("mock provider server failed: {e}"))
+    }
+
+    /// Serves on an ephemeral local port in the background and returns the
+    /// base URL to configure as `PROVIDER_BASE_URL`.
+    pub async fn spawn(self) -> Result<String, String> {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .map_err(|e| format!("mock provider bind failed: {e}"))?;
+        let addr = listener
+            .local_addr()
+            .map_err(|e| format!("mock provider addr failed: {e}"))?;
+        tokio::spawn(self.serve(listener));
+        Ok(format!("http://{addr}"))
+    }
+
+    fn respond(&self, req: &AssessRequest) -> AssessResponseBody {
+        let scored = req
+            .subjects
+            .iter()
+            .map(|s| {
+                self.overrides
+                    .get(&s.subject_id)
+                    .cloned()
+                    .unwrap_or_else(|| SubjectRisk {
+                        subject_id: s.subject_id.clone(),
+                        risk_score: self.base_risk_score,
+                        flags: Vec::new(),
+                    })
+            })
+            .collect::<Vec<_>>();
+        AssessResponseBody {
+            reference: format!(
+                "mock_{}",
+                &hex::encode(Sha256::digest(req.request_id.as_bytes()))[..16]
+            ),
+            risk_score: scored
+                .iter()
+                .map(|s| s.risk_score)
+                .max()
+                .unwrap_or(self.base_risk_score),
+            subjects: scored,
+        }
+    }
+}
+
+async fn assess(
+    State(provider): State<Arc<MockProvider>>,
+    Json(req): Json<AssessRequest>,
+) -> Json<AssessResponseBody> {
+    Json(provider.respond(&req))
+}
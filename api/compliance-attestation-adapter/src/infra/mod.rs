@@ -1,3 +1,4 @@
+pub mod mock_provider;
 pub mod mongo;
 pub mod provider_client;
 pub mod redis;
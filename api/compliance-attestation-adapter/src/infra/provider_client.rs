@@ -1 +1,280 @@
-pub struct ProviderClient;
+use crate::config::environment::AppConfig;
+use crate::infra::mock_provider::MOCK_PROVIDER_ADDR;
+use crate::module::compliance_attestation::model::{NormalizedSubject, SubjectType};
+use async_trait::async_trait;
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+
+const MAX_PROVIDER_RISK_SCORE: u16 = 100;
+const DEFAULT_RETRY_BACKOFF: Duration = Duration::from_millis(200);
+
+/// An external KYC/KYT provider scoring the subjects of an intake. The raw
+/// response is kept verbatim so it can be stored encrypted next to the
+/// attestation that relied on it.
+#[async_trait]
+pub trait ComplianceProvider: Send + Sync + std::fmt::Debug {
+    fn name(&self) -> &str;
+
+    async fn assess(
+        &self,
+        request_id: &str,
+        subjects: &[NormalizedSubject],
+    ) -> Result<ProviderAssessment, String>;
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderAssessment {
+    pub provider: String,
+    /// The provider's own case or check id.
+    pub reference: String,
+    pub risk_score: u16,
+    pub subjects: Vec<SubjectRisk>,
+    #[serde(skip)]
+    pub raw_response: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubjectRisk {
+    pub subject_id: String,
+    pub risk_score: u16,
+    #[serde(default)]
+    pub flags: Vec<String>,
+}
+
+/// Wire format of `POST {base_url}/v1/assess` responses.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct AssessResponseBody {
+    pub(crate) reference: String,
+    pub(crate) risk_score: u16,
+    #[serde(default)]
+    pub(crate) subjects: Vec<SubjectRisk>,
+}
+
+#[derive(Debug, Serialize)]
+struct AssessRequestBody<'a> {
+    request_id: &'a str,
+    subjects: Vec<ProviderSubject<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct ProviderSubject<'a> {
+    subject_id: &'a str,
+    subject_type: &'static str,
+    legal_name: Option<&'a str>,
+    jurisdiction: Option<&'a str>,
+    address: Option<&'a str>,
+    wallet_address: Option<&'a str>,
+}
+
+#[derive(Clone)]
+pub enum ProviderAuth {
+    None,
+    Bearer(String),
+    ApiKey { header: String, value: String },
+}
+
+impl std::fmt::Debug for ProviderAuth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::None => f.write_str("None"),
+            Self::Bearer(_) => f.write_str("Bearer(..)"),
+            Self::ApiKey { header, .. } => write!(f, "ApiKey({header})"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HttpProviderConfig {
+    pub name: String,
+    pub base_url: String,
+    pub auth: ProviderAuth,
+    pub timeout: Duration,
+    /// Attempts after the first one; only timeouts, connection errors, 429
+    /// and 5xx responses are retried.
+    pub max_retries: u32,
+    pub retry_backoff: Duration,
+}
+
+#[derive(Debug)]
+pub struct HttpComplianceProvider {
+    config: HttpProviderConfig,
+    client: Client,
+}
+
+impl HttpComplianceProvider {
+    pub fn new(mut config: HttpProviderConfig) -> Result<Self, String> {
+        config.base_url = config.base_url.trim_end_matches('/').to_string();
+        let client = Client::builder()
+            .timeout(config.timeout)
+            .build()
+            .map_err(|e| format!("failed to build http client: {e}"))?;
+        Ok(Self { config, client })
+    }
+
+    async fn attempt(&self, body: &AssessRequestBody<'_>) -> Result<String, AttemptError> {
+        let mut request = self
+            .client
+            .post(format!("{}/v1/assess", self.config.base_url))
+            .json(body);
+        request = match &self.config.auth {
+            ProviderAuth::None => request,
+            ProviderAuth::Bearer(token) => request.bearer_auth(token),
+            ProviderAuth::ApiKey { header, value } => request.header(header.as_str(), value),
+        };
+        let resp = request.send().await.map_err(|e| {
+            let message = format!("{} request failed: {e}", self.config.name);
+            if e.is_timeout() || e.is_connect() {
+                AttemptError::Retryable(message)
+            } else {
+                AttemptError::Fatal(message)
+            }
+        })?;
+
+        let status = resp.status();
+        if !status.is_success() {
+            let message = format!("{} returned non-success status: {status}", self.config.name);
+            return Err(
+                if status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+                    AttemptError::Retryable(message)
+                } else {
+                    AttemptError::Fatal(message)
+                },
+            );
+        }
+        resp.text().await.map_err(|e| {
+            AttemptError::Retryable(format!("{} response read failed: {e}", self.config.name))
+        })
+    }
+}
+
+enum AttemptError {
+    Retryable(String),
+    Fatal(String),
+}
+
+#[async_trait]
+impl ComplianceProvider for HttpComplianceProvider {
+    fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    async fn assess(
+        &self,
+        request_id: &str,
+        subjects: &[NormalizedSubject],
+    ) -> Result<ProviderAssessment, String> {
+        let body = AssessRequestBody {
+            request_id,
+            subjects: subjects.iter().map(provider_subject).collect(),
+        };
+        let mut attempt = 0;
+        let raw = loop {
+            match self.attempt(&body).await {
+                Ok(raw) => break raw,
+                Err(AttemptError::Retryable(e)) if attempt < self.config.max_retries => {
+                    tokio::time::sleep(self.config.retry_backoff * 2u32.saturating_pow(attempt))
+                        .await;
+                    attempt += 1;
+                    tracing::warn!(error = %e, attempt, "retrying compliance provider");
+                }
+                Err(AttemptError::Retryable(e) | AttemptError::Fatal(e)) => return Err(e),
+            }
+        };
+        parse_assessment(&self.config.name, raw)
+    }
+}
+
+/// Provider selected by `COMPLIANCE_PROVIDER` (`none`, `mock` or `http`).
+/// `mock` is the HTTP provider pointed at the `mock_provider` server, at
+/// `PROVIDER_BASE_URL` or its default address, without auth.
+pub fn provider_from_config(
+    config: &AppConfig,
+) -> Result<Option<Arc<dyn ComplianceProvider>>, String> {
+    match config.compliance_provider.as_deref().map(str::trim) {
+        None | Some("") | Some("none") => Ok(None),
+        Some("mock") => {
+            let base_url = config
+                .provider_base_url
+                .clone()
+                .filter(|u| !u.trim().is_empty())
+                .unwrap_or_else(|| format!("http://{MOCK_PROVIDER_ADDR}"));
+            let provider = HttpComplianceProvider::new(HttpProviderConfig {
+                name: "mock".to_string(),
+                base_url,
+                auth: ProviderAuth::None,
+                timeout: Duration::from_millis(config.provider_timeout_ms.max(1) as u64),
+                max_retries: config.provider_max_retries.clamp(0, 10) as u32,
+                retry_backoff: DEFAULT_RETRY_BACKOFF,
+            })?;
+            Ok(Some(Arc::new(provider)))
+        }
+        Some("http") => {
+            let base_url = config
+                .provider_base_url
+                .clone()
+                .filter(|u| !u.trim().is_empty())
+                .ok_or("PROVIDER_BASE_URL is required for COMPLIANCE_PROVIDER=http")?;
+            let api_key = || {
+                config
+                    .provider_api_key
+                    .clone()
+                    .ok_or("PROVIDER_API_KEY is required for provider auth")
+            };
+            let auth = match config.provider_auth_scheme.as_deref() {
+                None | Some("none") => ProviderAuth::None,
+                Some("bearer") => ProviderAuth::Bearer(api_key()?),
+                Some("api_key") => ProviderAuth::ApiKey {
+                    header: config.provider_api_key_header.clone(),
+                    value: api_key()?,
+                },
+                Some(other) => return Err(format!("unknown PROVIDER_AUTH_SCHEME: {other}")),
+            };
+            let provider = HttpComplianceProvider::new(HttpProviderConfig {
+                name: "http".to_string(),
+                base_url,
+                auth,
+                timeout: Duration::from_millis(config.provider_timeout_ms.max(1) as u64),
+                max_retries: config.provider_max_retries.clamp(0, 10) as u32,
+                retry_backoff: DEFAULT_RETRY_BACKOFF,
+            })?;
+            Ok(Some(Arc::new(provider)))
+        }
+        Some(other) => Err(format!("unknown COMPLIANCE_PROVIDER: {other}")),
+    }
+}
+
+fn parse_assessment(provider: &str, raw: String) -> Result<ProviderAssessment, String> {
+    let body = serde_json::from_str::<AssessResponseBody>(&raw)
+        .map_err(|e| format!("failed to parse {provider} response: {e}"))?;
+    let out_of_range = std::iter::once(body.risk_score)
+        .chain(body.subjects.iter().map(|s| s.risk_score))
+        .any(|score| score > MAX_PROVIDER_RISK_SCORE);
+    if out_of_range {
+        return Err(format!(
+            "{provider} returned a risk score above {MAX_PROVIDER_RISK_SCORE}"
+        ));
+    }
+    Ok(ProviderAssessment {
+        provider: provider.to_string(),
+        reference: body.reference,
+        risk_score: body.risk_score,
+        subjects: body.subjects,
+        raw_response: raw,
+    })
+}
+
+fn provider_subject(subject: &NormalizedSubject) -> ProviderSubject<'_> {
+    ProviderSubject {
+        subject_id: &subject.subject_id,
+        subject_type: match subject.subject_type {
+            SubjectType::Entity => "ENTITY",
+            SubjectType::Counterparty => "COUNTERPARTY",
+        },
+        legal_name: subject.legal_name.as_deref(),
+        jurisdiction: subject.jurisdiction.as_deref(),
+        address: subject.address.as_deref(),
+        wallet_address: subject.wallet_address.as_deref(),
+    }
+}
@@ -2,6 +2,11 @@ use compliance_attestation_adapter::app::{AppState, build_router};
 use compliance_attestation_adapter::config::db::{MongoConfig, RedisConfig};
 use compliance_attestation_adapter::config::environment::AppConfig;
 use compliance_attestation_adapter::infra::init_infra;
+use compliance_attestation_adapter::infra::provider_client::provider_from_config;
+use compliance_attestation_adapter::module::compliance_attestation::crud::spawn_attestation_rescreener;
+use compliance_attestation_adapter::service::attestation_signing_service::AttestationSigner;
+use compliance_attestation_adapter::service::sanctions_store_service::spawn_sanctions_reloader;
+use std::time::Duration;
 use tokio::net::TcpListener;
 use tracing::{error, info, warn};
 
@@ -50,7 +55,60 @@ async fn main() {
         }
     };
 
+    if let Some(key_hex) = config.attestation_signing_key_hex.as_deref()
+        && let Err(e) = AttestationSigner::from_hex(key_hex)
+    {
+        error!(error = %e, "attestation signing key error");
+        std::process::exit(1);
+    }
+
+    if let Err(e) = provider_from_config(&config) {
+        error!(error = %e, "compliance provider config error");
+        std::process::exit(1);
+    }
+
+    let reload_interval_seconds = config.sanctions_reload_interval_seconds;
     let state = AppState::new(config, infra);
+    let sanctions = state.sanctions.status();
+    info!(
+        version = sanctions.version.as_deref().unwrap_or_default(),
+        entry_count = sanctions.entry_count,
+        "sanctions list loaded"
+    );
+    match &state.signer {
+        Some(signer) => info!(key_id = signer.key_id(), "attestations are signed"),
+        None => warn!("ATTESTATION_SIGNING_KEY_HEX not set; attestations are unsigned"),
+    }
+    if let Some(provider) = &state.provider {
+        info!(
+            provider = provider.name(),
+            "external compliance provider enabled"
+        );
+    }
+    if state.policy.is_remote() {
+        info!(
+            url = state
+                .config
+                .policy_snapshot_url
+                .as_deref()
+                .unwrap_or_default(),
+            "resolving active policy from policy-snapshot"
+        );
+    } else {
+        warn!(
+            path = %state.config.policy_snapshot_path,
+            "POLICY_SNAPSHOT_URL not set; reading policy from local snapshot file"
+        );
+    }
+    if reload_interval_seconds > 0 {
+        spawn_sanctions_reloader(
+            state.sanctions.clone(),
+            Duration::from_secs(reload_interval_seconds as u64),
+        );
+        if state.infra.is_some() {
+            spawn_attestation_rescreener(state.clone());
+        }
+    }
     let app = build_router(state);
     if let Err(e) = axum::serve(listener, app).await {
         error!(error = %e, "server runtime error");
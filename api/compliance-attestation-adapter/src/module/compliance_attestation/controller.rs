@@ -1,10 +1,14 @@
 use super::crud;
 use super::error::AppError;
 use super::schema::{
-    ComplianceDecision, HealthResponse, IntakeComplianceRequest, IntakeComplianceResponse,
+    AdjudicateReviewRequest, ComplianceDecision, HealthResponse, IntakeComplianceRequest,
+    IntakeComplianceResponse, ReviewQueueQuery, ReviewQueueResponse, RevokeAttestationRequest,
+    VerifyAttestationRequest, VerifyAttestationResponse,
 };
 use crate::app::AppState;
-use axum::extract::{Path, State};
+use crate::service::attestation_signing_service::JwkSet;
+use axum::extract::{Path, Query, State};
+use axum::http::HeaderMap;
 use axum::{Json, response::IntoResponse};
 use tracing::{error, info};
 
@@ -51,13 +55,155 @@ pub async fn get_attestation(
     }
 }
 
+pub async fn revoke_attestation(
+    State(state): State<AppState>,
+    Path(attestation_id): Path<String>,
+    Json(req): Json<RevokeAttestationRequest>,
+) -> impl IntoResponse {
+    match crud::revoke_attestation(&state, &attestation_id, req).await {
+        Ok(resp) => {
+            info!(
+                attestation_id = %resp.attestation_id,
+                reason = resp
+                    .revocation
+                    .as_ref()
+                    .map(|r| r.reason.as_str())
+                    .unwrap_or_default(),
+                "attestation revoked"
+            );
+            (axum::http::StatusCode::OK, Json(resp))
+        }
+        Err(AppError {
+            status,
+            code,
+            message,
+        }) => {
+            error!(error_code = code, reason = %message, "attestation revocation rejected");
+            (status, Json(error_response(code, message)))
+        }
+    }
+}
+
+pub async fn list_pending_reviews(
+    State(state): State<AppState>,
+    Query(query): Query<ReviewQueueQuery>,
+) -> impl IntoResponse {
+    match crud::list_pending_reviews(&state, query.limit).await {
+        Ok(pending) => (
+            axum::http::StatusCode::OK,
+            Json(ReviewQueueResponse {
+                pending,
+                error_code: None,
+                reason: "ok".to_string(),
+            }),
+        ),
+        Err(AppError {
+            status,
+            code,
+            message,
+        }) => {
+            error!(error_code = code, reason = %message, "list reviews rejected");
+            (
+                status,
+                Json(ReviewQueueResponse {
+                    pending: vec![],
+                    error_code: Some(code.to_string()),
+                    reason: message,
+                }),
+            )
+        }
+    }
+}
+
+pub async fn adjudicate_review(
+    State(state): State<AppState>,
+    Path(attestation_id): Path<String>,
+    headers: HeaderMap,
+    Json(req): Json<AdjudicateReviewRequest>,
+) -> impl IntoResponse {
+    match crud::adjudicate_review(&state, &attestation_id, &headers, req).await {
+        Ok(resp) => {
+            info!(
+                attestation_id = %resp.attestation_id,
+                supersedes = resp.supersedes.as_deref().unwrap_or_default(),
+                decision = %resp.decision.as_str(),
+                "review adjudicated"
+            );
+            (axum::http::StatusCode::OK, Json(resp))
+        }
+        Err(AppError {
+            status,
+            code,
+            message,
+        }) => {
+            error!(error_code = code, reason = %message, "review adjudication rejected");
+            (status, Json(error_response(code, message)))
+        }
+    }
+}
+
+pub async fn verify_attestation(
+    State(state): State<AppState>,
+    Json(req): Json<VerifyAttestationRequest>,
+) -> impl IntoResponse {
+    match crud::verify_attestation(&state, req).await {
+        Ok(resp) => {
+            info!(
+                valid = resp.valid,
+                error_code = resp.error_code.as_deref().unwrap_or_default(),
+                "attestation verified"
+            );
+            (axum::http::StatusCode::OK, Json(resp))
+        }
+        Err(AppError {
+            status,
+            code,
+            message,
+        }) => {
+            error!(error_code = code, reason = %message, "attestation verification rejected");
+            (
+                status,
+                Json(VerifyAttestationResponse {
+                    valid: false,
+                    signature_valid: false,
+                    signer: None,
+                    expired: false,
+                    policy_hash_active: false,
+                    status: None,
+                    error_code: Some(code.to_string()),
+                    reason: message,
+                }),
+            )
+        }
+    }
+}
+
+pub async fn signing_keys(State(state): State<AppState>) -> impl IntoResponse {
+    match crud::signing_keys(&state) {
+        Ok(keys) => (axum::http::StatusCode::OK, Json(keys)),
+        Err(AppError { status, code, .. }) => {
+            error!(error_code = code, "signing keys unavailable");
+            (status, Json(JwkSet { keys: vec![] }))
+        }
+    }
+}
+
 pub async fn health(State(state): State<AppState>) -> impl IntoResponse {
     let persistence_enabled = state.infra.is_some();
+    let sanctions = state.sanctions.status();
+    let status = if sanctions.version.is_some() {
+        "ok"
+    } else {
+        "degraded"
+    };
     (
         axum::http::StatusCode::OK,
         Json(HealthResponse {
-            status: "ok".to_string(),
+            status: status.to_string(),
             persistence_enabled,
+            sanctions_list_version: sanctions.version,
+            sanctions_entry_count: sanctions.entry_count,
+            sanctions_loaded_at: sanctions.loaded_at,
         }),
     )
 }
@@ -74,6 +220,7 @@ fn error_response(code: &str, message: String) -> IntakeComplianceResponse {
         policy_hash: String::new(),
         decision: ComplianceDecision::Fail,
         risk_score: 0,
+        policy_factors: vec![],
         sanctions_hit_count: 0,
         attestation_hash: String::new(),
         issued_at: 0,
@@ -81,5 +228,13 @@ fn error_response(code: &str, message: String) -> IntakeComplianceResponse {
         error_code: Some(code.to_string()),
         reason: message,
         fx_quote: None,
+        review_status: None,
+        supersedes: None,
+        superseded_by: None,
+        signature: None,
+        signing_key_id: None,
+        status: None,
+        revocation: None,
+        provider_refs: vec![],
     }
 }
@@ -1,32 +1,50 @@
 use super::error::AppError;
 use super::model::{
-    AuditEventRecord, ComplianceAttestationRecord, ComplianceRequestRecord, NormalizedSubject,
-    ProviderResponseReference, SubjectType,
+    AdjudicationRecord, AuditEventRecord, ComplianceAttestationRecord, ComplianceRequestRecord,
+    NormalizedSubject, ProviderResponseReference, RevocationRecord, SubjectType,
 };
 use super::schema::{
-    ComplianceDecision, IntakeComplianceRequest, IntakeComplianceResponse, SubjectInput,
+    AdjudicateReviewRequest, AttestationStatus, ComplianceDecision, IntakeComplianceRequest,
+    IntakeComplianceResponse, PendingReview, RevokeAttestationRequest, SubjectInput,
+    VerifyAttestationRequest, VerifyAttestationResponse,
 };
 use crate::app::AppState;
 use crate::infra::mongo::{
     ATTESTATIONS_COLLECTION, AUDIT_EVENTS_COLLECTION, PROVIDER_REFS_COLLECTION, REQUESTS_COLLECTION,
 };
+use crate::infra::provider_client::ProviderAssessment;
 use crate::infra::redis::{IDEMPOTENCY_PREFIX, JOB_STATUS_PREFIX, SCREEN_CACHE_PREFIX};
 use crate::service::attestation_hash_service::{build_attestation_id, compute_attestation_hash};
+use crate::service::attestation_signing_service::{AttestationClaims, JwkSet, recover_signer};
 use crate::service::confidential_http_service::{FxQuote, fetch_fx_quote};
 use crate::service::encryption_service::encrypt_for_storage;
-use crate::service::policy_eval_service::{evaluate_intake_policy, load_policy_snapshot};
+use crate::service::policy_eval_service::{
+    MAX_RISK_SCORE, PolicyEvaluation, evaluate_intake_policy_with_provider,
+};
+use crate::service::policy_source_service::same_hash;
+use crate::service::review_service::{
+    REVIEW_PENDING, initial_review_status, review_signing_payload, supersede_reviewed_attestation,
+};
+use crate::service::revocation_service::{attestation_status, sanctions_revocation};
 use crate::service::sanctions_service::{
-    ScreeningHit, ScreeningResult, load_sanctions_entries, screen_subjects,
+    SanctionsIndex, ScreeningHit, ScreeningResult, screen_subjects,
 };
 use crate::service::signature_service::verify_internal_signature;
+use crate::service::wallet_screening_service::validate_wallet_address;
+use axum::http::HeaderMap;
 use mongodb::Collection;
 use mongodb::bson::doc;
+use mongodb::options::ReturnDocument;
 use redis::AsyncCommands;
 use redis::Script;
 use redis::aio::MultiplexedConnection;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::{info, warn};
+
+const DEFAULT_REVIEW_PAGE_SIZE: i64 = 50;
+const MAX_REVIEW_PAGE_SIZE: i64 = 500;
 
 pub async fn intake_and_normalize(
     state: &AppState,
@@ -37,14 +55,19 @@ pub async fn intake_and_normalize(
 
     let request_hash = compute_request_hash(&req)?;
     let normalized_subjects = normalize_subjects(&req.subjects)?;
-    let policy_snapshot = load_policy_snapshot(&state.config.policy_snapshot_path)
+    let policy_snapshot = state
+        .policy
+        .current()
+        .await
         .map_err(|e| AppError::bad_request("POLICY_SNAPSHOT_ERROR", e))?;
-    if policy_snapshot.active.version != state.config.policy_version {
+    if let Some(pinned) = &state.config.policy_version
+        && policy_snapshot.active.version != *pinned
+    {
         return Err(AppError::bad_request(
             "POLICY_VERSION_NOT_APPROVED",
             format!(
                 "policy version mismatch: configured={} active={}",
-                state.config.policy_version, policy_snapshot.active.version
+                pinned, policy_snapshot.active.version
             ),
         ));
     }
@@ -73,21 +96,46 @@ pub async fn intake_and_normalize(
         .await?;
     }
 
-    let sanctions_entries = load_sanctions_entries(&state.config.sanctions_data_path)
+    let sanctions = state
+        .sanctions
+        .current()
         .map_err(|e| AppError::bad_request("SANCTIONS_DATA_ERROR", e))?;
     let screening = if let Some(infra) = &state.infra {
         get_or_compute_screening(
             infra,
             &normalized_subjects,
-            &sanctions_entries,
+            &sanctions,
             &policy_snapshot.active.hash,
         )
         .await?
     } else {
-        screen_subjects(&normalized_subjects, &sanctions_entries)
+        screen_subjects(&normalized_subjects, &sanctions)
+    };
+    let provider_assessment = match &state.provider {
+        Some(provider) => Some(
+            provider
+                .assess(&req.request_id, &normalized_subjects)
+                .await
+                .inspect_err(
+                    |e| warn!(provider = provider.name(), error = %e, "compliance provider failed"),
+                ),
+        ),
+        None => None,
     };
 
-    let (decision, risk_score) = evaluate_intake_policy(&screening, &policy_snapshot.thresholds);
+    let PolicyEvaluation {
+        decision,
+        risk_score,
+        factors: policy_factors,
+    } = evaluate_intake_policy_with_provider(
+        &normalized_subjects,
+        &screening,
+        provider_assessment
+            .as_ref()
+            .map(|r| r.as_ref().map_err(String::as_str)),
+        &policy_snapshot,
+    );
+    let provider_assessment = provider_assessment.and_then(Result::ok);
     let fx_quote = if state.config.fx_lookup_enabled {
         fetch_fx_quote(
             &state.config.frankfurter_base_url,
@@ -118,9 +166,21 @@ pub async fn intake_and_normalize(
         screening.hits.len(),
         &normalized_subjects,
         &screening.match_digest,
+        provider_assessment.as_ref(),
     );
     let attestation_id = build_attestation_id(&attestation_hash);
-    let response = IntakeComplianceResponse {
+    let signature = sign_attestation(
+        state,
+        &AttestationClaims {
+            attestation_hash: &attestation_hash,
+            policy_hash: &policy_snapshot.active.hash,
+            decision,
+            risk_score,
+            issued_at,
+            expires_at,
+        },
+    )?;
+    let mut response = IntakeComplianceResponse {
         attestation_id: attestation_id.clone(),
         workflow_run_id: req.workflow_run_id.clone(),
         request_id: req.request_id.clone(),
@@ -131,6 +191,7 @@ pub async fn intake_and_normalize(
         policy_hash: policy_snapshot.active.hash.clone(),
         decision,
         risk_score,
+        policy_factors,
         sanctions_hit_count: screening.hits.len(),
         attestation_hash: attestation_hash.clone(),
         issued_at,
@@ -138,9 +199,21 @@ pub async fn intake_and_normalize(
         error_code: None,
         reason: "accepted".to_string(),
         fx_quote,
+        review_status: initial_review_status(decision),
+        supersedes: None,
+        superseded_by: None,
+        signing_key_id: signature.as_ref().and(signing_key_id(state)),
+        signature,
+        status: Some(AttestationStatus::Active),
+        revocation: None,
+        provider_refs: Vec::new(),
     };
 
     if let Some(infra) = &state.infra {
+        response.provider_refs = provider_assessment
+            .iter()
+            .map(|a| provider_ref_id(&attestation_id, &a.provider))
+            .collect();
         persist_records(
             state,
             infra,
@@ -150,6 +223,7 @@ pub async fn intake_and_normalize(
             &request_hash,
             &screening,
             response.fx_quote.as_ref(),
+            provider_assessment.as_ref(),
         )
         .await?;
         finalize_job_keys(
@@ -202,27 +276,378 @@ pub async fn get_attestation_by_id(
         ));
     };
 
-    Ok(IntakeComplianceResponse {
-        attestation_id: record.attestation_id,
-        workflow_run_id: record.workflow_run_id,
-        request_id: record.request_id,
-        accepted: true,
-        normalized_subject_count: record.normalized_subjects.len(),
-        normalized_subjects: record.normalized_subjects,
-        policy_version: record.policy_version,
-        policy_hash: record.policy_hash,
-        decision: decision_from_str(&record.decision),
-        risk_score: record.risk_score,
-        sanctions_hit_count: record.sanctions_hit_count,
-        attestation_hash: record.attestation_hash,
-        issued_at: record.issued_at,
-        expires_at: record.expires_at,
-        error_code: None,
-        reason: "accepted".to_string(),
-        fx_quote: record.fx_quote,
+    Ok(response_from_record(record, now_unix()?))
+}
+
+/// Revokes an attestation that has not been revoked yet. Expired and
+/// superseded attestations can still be revoked so the record shows why they
+/// must not be relied on.
+pub async fn revoke_attestation(
+    state: &AppState,
+    attestation_id: &str,
+    req: RevokeAttestationRequest,
+) -> Result<IntakeComplianceResponse, AppError> {
+    if req.revoked_by.trim().is_empty() {
+        return Err(AppError::bad_request(
+            "INVALID_REVOKED_BY",
+            "revoked_by is required",
+        ));
+    }
+    if req.detail.trim().is_empty() {
+        return Err(AppError::bad_request(
+            "INVALID_REVOCATION_DETAIL",
+            "detail is required",
+        ));
+    }
+    let Some(infra) = &state.infra else {
+        return Err(AppError::bad_request(
+            "PERSISTENCE_DISABLED",
+            "persistence is not configured",
+        ));
+    };
+
+    let now = now_unix()?;
+    let revocation = RevocationRecord {
+        reason: req.reason.as_str().to_string(),
+        detail: req.detail.trim().to_string(),
+        revoked_by: req.revoked_by.trim().to_string(),
+        revoked_at: now,
+        sanctions_version: None,
+    };
+    let record = record_revocation(infra, attestation_id, &revocation).await?;
+    Ok(response_from_record(record, now))
+}
+
+/// Re-screens every active, non-failing attestation against `index` and
+/// revokes those whose subjects the new list now fails. Returns how many were
+/// revoked.
+pub async fn rescreen_active_attestations(
+    state: &AppState,
+    index: &SanctionsIndex,
+) -> Result<usize, AppError> {
+    let Some(infra) = &state.infra else {
+        return Ok(0);
+    };
+    let policy = state
+        .policy
+        .current()
+        .await
+        .map_err(|e| AppError::internal("POLICY_SNAPSHOT_ERROR", e))?;
+    let now = now_unix()?;
+
+    let attestations: Collection<ComplianceAttestationRecord> =
+        infra.mongo_db.collection(ATTESTATIONS_COLLECTION);
+    let mut cursor = attestations
+        .find(doc! {
+            "revocation": null,
+            "superseded_by": null,
+            "expires_at": { "$gt": now },
+            "decision": { "$ne": ComplianceDecision::Fail.as_str() },
+        })
+        .await
+        .map_err(|e| AppError::internal("PERSISTENCE_ERROR", format!("mongo find failed: {e}")))?;
+
+    let mut flagged = Vec::new();
+    while cursor
+        .advance()
+        .await
+        .map_err(|e| AppError::internal("PERSISTENCE_ERROR", format!("mongo cursor failed: {e}")))?
+    {
+        let record = cursor.deserialize_current().map_err(|e| {
+            AppError::internal("PERSISTENCE_ERROR", format!("mongo decode failed: {e}"))
+        })?;
+        if let Some(revocation) = sanctions_revocation(&record, index, &policy, now) {
+            flagged.push((record.attestation_id, revocation));
+        }
+    }
+
+    let mut revoked = 0;
+    for (attestation_id, revocation) in flagged {
+        match record_revocation(infra, &attestation_id, &revocation).await {
+            Ok(_) => revoked += 1,
+            // Revoked by someone else since the scan; nothing left to do.
+            Err(e) if e.code == "ALREADY_REVOKED" => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(revoked)
+}
+
+/// Re-screens stored attestations each time the sanctions store swaps in a
+/// new list.
+pub fn spawn_attestation_rescreener(state: AppState) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut updates = state.sanctions.subscribe();
+        while updates.changed().await.is_ok() {
+            let Ok(index) = state.sanctions.current() else {
+                continue;
+            };
+            match rescreen_active_attestations(&state, &index).await {
+                Ok(revoked) => info!(
+                    version = index.version(),
+                    revoked, "attestations re-screened against new sanctions list"
+                ),
+                Err(e) => warn!(
+                    error_code = e.code,
+                    reason = %e.message,
+                    "attestation re-screening failed"
+                ),
+            }
+        }
     })
 }
 
+async fn record_revocation(
+    infra: &crate::infra::InfraClients,
+    attestation_id: &str,
+    revocation: &RevocationRecord,
+) -> Result<ComplianceAttestationRecord, AppError> {
+    let attestations: Collection<ComplianceAttestationRecord> =
+        infra.mongo_db.collection(ATTESTATIONS_COLLECTION);
+    let revocation_doc = mongodb::bson::to_bson(revocation).map_err(|e| {
+        AppError::internal(
+            "SERIALIZATION_ERROR",
+            format!("revocation encode failed: {e}"),
+        )
+    })?;
+    let updated = attestations
+        .find_one_and_update(
+            doc! { "attestation_id": attestation_id, "revocation": null },
+            doc! { "$set": { "revocation": revocation_doc } },
+        )
+        .return_document(ReturnDocument::After)
+        .await
+        .map_err(|e| {
+            AppError::internal("PERSISTENCE_ERROR", format!("mongo update failed: {e}"))
+        })?;
+    let Some(record) = updated else {
+        let exists = attestations
+            .find_one(doc! { "attestation_id": attestation_id })
+            .await
+            .map_err(|e| {
+                AppError::internal("PERSISTENCE_ERROR", format!("mongo find failed: {e}"))
+            })?
+            .is_some();
+        return Err(if exists {
+            AppError::conflict(
+                "ALREADY_REVOKED",
+                format!("attestation {attestation_id} is already revoked"),
+            )
+        } else {
+            AppError::not_found("ATTESTATION_NOT_FOUND", "attestation not found")
+        });
+    };
+
+    write_audit_event(
+        infra,
+        &record.request_id,
+        Some(record.attestation_id.clone()),
+        "ATTESTATION_REVOKED",
+        "COMPLETED",
+        Some(format!(
+            "reason={} revoked_by={} sanctions_version={} detail={}",
+            revocation.reason,
+            revocation.revoked_by,
+            revocation.sanctions_version.as_deref().unwrap_or("-"),
+            revocation.detail
+        )),
+    )
+    .await?;
+    Ok(record)
+}
+
+/// REVIEW attestations still waiting for an analyst, oldest first.
+pub async fn list_pending_reviews(
+    state: &AppState,
+    limit: Option<i64>,
+) -> Result<Vec<PendingReview>, AppError> {
+    let limit = limit.unwrap_or(DEFAULT_REVIEW_PAGE_SIZE);
+    if !(1..=MAX_REVIEW_PAGE_SIZE).contains(&limit) {
+        return Err(AppError::bad_request(
+            "INVALID_LIMIT",
+            format!("limit must be between 1 and {MAX_REVIEW_PAGE_SIZE}"),
+        ));
+    }
+    let Some(infra) = &state.infra else {
+        return Err(AppError::bad_request(
+            "PERSISTENCE_DISABLED",
+            "persistence is not configured",
+        ));
+    };
+
+    let attestations: Collection<ComplianceAttestationRecord> =
+        infra.mongo_db.collection(ATTESTATIONS_COLLECTION);
+    let mut cursor = attestations
+        .find(doc! { "review_status": REVIEW_PENDING, "superseded_by": null })
+        .sort(doc! { "issued_at": 1 })
+        .limit(limit)
+        .await
+        .map_err(|e| AppError::internal("PERSISTENCE_ERROR", format!("mongo find failed: {e}")))?;
+
+    let mut pending = Vec::new();
+    while cursor
+        .advance()
+        .await
+        .map_err(|e| AppError::internal("PERSISTENCE_ERROR", format!("mongo cursor failed: {e}")))?
+    {
+        let record = cursor.deserialize_current().map_err(|e| {
+            AppError::internal("PERSISTENCE_ERROR", format!("mongo decode failed: {e}"))
+        })?;
+        pending.push(PendingReview {
+            attestation_id: record.attestation_id,
+            request_id: record.request_id,
+            workflow_run_id: record.workflow_run_id,
+            policy_version: record.policy_version,
+            risk_score: record.risk_score,
+            policy_factors: record.policy_factors,
+            sanctions_hit_count: record.sanctions_hit_count,
+            issued_at: record.issued_at,
+            expires_at: record.expires_at,
+        });
+    }
+    Ok(pending)
+}
+
+/// Records an analyst decision on a pending REVIEW and issues the attestation
+/// that supersedes it. The new record is written first and the original is
+/// then claimed with a conditional update, so two analysts racing on the same
+/// review leave exactly one successor.
+pub async fn adjudicate_review(
+    state: &AppState,
+    attestation_id: &str,
+    headers: &HeaderMap,
+    req: AdjudicateReviewRequest,
+) -> Result<IntakeComplianceResponse, AppError> {
+    let reviewer = authenticated_reviewer(state, attestation_id, headers, &req)?;
+    if req.risk_score > MAX_RISK_SCORE {
+        return Err(AppError::bad_request(
+            "INVALID_RISK_SCORE",
+            format!("risk_score must be at most {MAX_RISK_SCORE}"),
+        ));
+    }
+    if req.reason.trim().is_empty() {
+        return Err(AppError::bad_request(
+            "INVALID_REVIEW_REASON",
+            "reason is required",
+        ));
+    }
+    let Some(infra) = &state.infra else {
+        return Err(AppError::bad_request(
+            "PERSISTENCE_DISABLED",
+            "persistence is not configured",
+        ));
+    };
+
+    let attestations: Collection<ComplianceAttestationRecord> =
+        infra.mongo_db.collection(ATTESTATIONS_COLLECTION);
+    let original = attestations
+        .find_one(doc! { "attestation_id": attestation_id })
+        .await
+        .map_err(|e| AppError::internal("PERSISTENCE_ERROR", format!("mongo find failed: {e}")))?
+        .ok_or_else(|| AppError::not_found("ATTESTATION_NOT_FOUND", "attestation not found"))?;
+
+    let adjudication = AdjudicationRecord {
+        outcome: req.outcome.as_str().to_string(),
+        reason: req.reason.trim().to_string(),
+        reviewer,
+        adjudicated_at: now_unix()?,
+        risk_score: Some(req.risk_score),
+        pre_review_risk_score: None,
+    };
+    let mut successor = supersede_reviewed_attestation(
+        &original,
+        adjudication.clone(),
+        state.config.attestation_ttl_seconds,
+    )
+    .map_err(|e| AppError::conflict("REVIEW_NOT_PENDING", e))?;
+    successor.signature = sign_attestation(
+        state,
+        &AttestationClaims {
+            attestation_hash: &successor.attestation_hash,
+            policy_hash: &successor.policy_hash,
+            decision: decision_from_str(&successor.decision),
+            risk_score: successor.risk_score,
+            issued_at: successor.issued_at,
+            expires_at: successor.expires_at,
+        },
+    )?;
+    successor.signing_key_id = successor.signature.as_ref().and(signing_key_id(state));
+
+    attestations.insert_one(&successor).await.map_err(|e| {
+        AppError::internal(
+            "PERSISTENCE_ERROR",
+            format!("insert attestation failed: {e}"),
+        )
+    })?;
+    let adjudication_doc = mongodb::bson::to_bson(&adjudication).map_err(|e| {
+        AppError::internal(
+            "SERIALIZATION_ERROR",
+            format!("adjudication encode failed: {e}"),
+        )
+    })?;
+    let claimed = attestations
+        .update_one(
+            doc! {
+                "attestation_id": attestation_id,
+                "review_status": REVIEW_PENDING,
+                "superseded_by": null,
+            },
+            doc! { "$set": {
+                "review_status": successor.review_status.clone(),
+                "superseded_by": &successor.attestation_id,
+                "adjudication": adjudication_doc,
+            } },
+        )
+        .await
+        .map_err(|e| {
+            AppError::internal("PERSISTENCE_ERROR", format!("mongo update failed: {e}"))
+        })?;
+    if claimed.modified_count == 0 {
+        attestations
+            .delete_one(doc! { "attestation_id": &successor.attestation_id })
+            .await
+            .map_err(|e| {
+                AppError::internal("PERSISTENCE_ERROR", format!("mongo delete failed: {e}"))
+            })?;
+        return Err(AppError::conflict(
+            "REVIEW_NOT_PENDING",
+            format!("attestation {attestation_id} was adjudicated concurrently"),
+        ));
+    }
+
+    write_audit_event(
+        infra,
+        &original.request_id,
+        Some(original.attestation_id.clone()),
+        "REVIEW_ADJUDICATED",
+        "COMPLETED",
+        Some(format!(
+            "outcome={} reviewer={} risk_score={}->{} superseded_by={} reason={}",
+            adjudication.outcome,
+            adjudication.reviewer,
+            original.risk_score,
+            successor.risk_score,
+            successor.attestation_id,
+            adjudication.reason
+        )),
+    )
+    .await?;
+    write_audit_event(
+        infra,
+        &successor.request_id,
+        Some(successor.attestation_id.clone()),
+        "ATTESTATION_REISSUED",
+        "COMPLETED",
+        Some(format!(
+            "decision={} supersedes={}",
+            successor.decision, original.attestation_id
+        )),
+    )
+    .await?;
+
+    Ok(response_from_record(successor, now_unix()?))
+}
+
 fn validate_request(state: &AppState, req: &IntakeComplianceRequest) -> Result<(), AppError> {
     if state.config.attestation_ttl_seconds <= 0 {
         return Err(AppError::bad_request(
@@ -280,6 +705,44 @@ fn validate_request(state: &AppState, req: &IntakeComplianceRequest) -> Result<(
     Ok(())
 }
 
+/// The reviewer named in `x-reviewer-id`. Unlike intake, adjudication always
+/// needs `x-internal-signature` over `review_signing_payload`: the reviewer
+/// is recorded on the attestation, so it has to come from a caller that
+/// holds `INTERNAL_SIGNING_SECRET`, never from an unauthenticated body.
+fn authenticated_reviewer(
+    state: &AppState,
+    attestation_id: &str,
+    headers: &HeaderMap,
+    req: &AdjudicateReviewRequest,
+) -> Result<String, AppError> {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+    };
+    let reviewer = header("x-reviewer-id")
+        .ok_or_else(|| AppError::unauthorized("MISSING_REVIEWER", "x-reviewer-id is required"))?;
+    let signing_secret = state
+        .config
+        .internal_signing_secret
+        .as_deref()
+        .ok_or_else(|| {
+            AppError::internal(
+                "SIGNING_CONFIG_MISSING",
+                "INTERNAL_SIGNING_SECRET is required to adjudicate reviews",
+            )
+        })?;
+    let signature = header("x-internal-signature").ok_or_else(|| {
+        AppError::unauthorized("MISSING_SIGNATURE", "x-internal-signature is required")
+    })?;
+    let payload = review_signing_payload(attestation_id, reviewer, req);
+    verify_internal_signature(&payload, signature, signing_secret)
+        .map_err(|e| AppError::unauthorized("BAD_SIGNATURE", e))?;
+    Ok(reviewer.to_string())
+}
+
 fn maybe_verify_signature(state: &AppState, req: &IntakeComplianceRequest) -> Result<(), AppError> {
     if !state.config.require_internal_signature {
         return Ok(());
@@ -328,6 +791,118 @@ fn compute_request_hash(req: &IntakeComplianceRequest) -> Result<String, AppErro
     Ok(hex::encode(hasher.finalize()))
 }
 
+/// Public half of the attestation signing key, for relying parties that
+/// verify attestations themselves.
+pub fn signing_keys(state: &AppState) -> Result<JwkSet, AppError> {
+    state.signer.as_ref().map(|s| s.jwks()).ok_or_else(|| {
+        AppError::not_found("SIGNING_DISABLED", "attestation signing is not configured")
+    })
+}
+
+/// Checks a presented attestation: the signature must recover to this
+/// service's key, `expires_at` must be in the future and `policy_hash` must
+/// be the policy currently in force. When the attestation is stored here it
+/// must also not be revoked or superseded. Each check is reported separately
+/// so a relying party can tell a stale attestation from a forged one.
+pub async fn verify_attestation(
+    state: &AppState,
+    req: VerifyAttestationRequest,
+) -> Result<VerifyAttestationResponse, AppError> {
+    let Some(signer) = &state.signer else {
+        return Err(AppError::bad_request(
+            "SIGNING_DISABLED",
+            "attestation signing is not configured",
+        ));
+    };
+    let claims = AttestationClaims {
+        attestation_hash: &req.attestation_hash,
+        policy_hash: &req.policy_hash,
+        decision: req.decision,
+        risk_score: req.risk_score,
+        issued_at: req.issued_at,
+        expires_at: req.expires_at,
+    };
+    let recovered = recover_signer(&claims, &req.signature)
+        .map_err(|e| AppError::bad_request("INVALID_SIGNATURE", e))?;
+    let signature_valid = recovered.eq_ignore_ascii_case(signer.key_id());
+    let expired = req.expires_at <= now_unix()?;
+    let policy = state
+        .policy
+        .current()
+        .await
+        .map_err(|e| AppError::internal("POLICY_SNAPSHOT_ERROR", e))?;
+    let policy_hash_active = same_hash(&req.policy_hash, &policy.active.hash);
+    let status = match (&state.infra, signature_valid) {
+        (Some(infra), true) => stored_status(infra, &req.attestation_hash).await?,
+        _ => None,
+    };
+
+    let (error_code, reason) = if !signature_valid {
+        (
+            "SIGNATURE_MISMATCH",
+            "signature was not made by this service's key",
+        )
+    } else if status == Some(AttestationStatus::Revoked) {
+        ("ATTESTATION_REVOKED", "attestation has been revoked")
+    } else if status == Some(AttestationStatus::Superseded) {
+        (
+            "ATTESTATION_SUPERSEDED",
+            "attestation has been superseded by a later one",
+        )
+    } else if expired {
+        ("ATTESTATION_EXPIRED", "attestation has expired")
+    } else if !policy_hash_active {
+        (
+            "POLICY_HASH_INACTIVE",
+            "attestation was issued under a policy that is no longer active",
+        )
+    } else {
+        ("", "valid")
+    };
+    Ok(VerifyAttestationResponse {
+        valid: error_code.is_empty(),
+        signature_valid,
+        signer: Some(recovered),
+        expired,
+        policy_hash_active,
+        status,
+        error_code: (!error_code.is_empty()).then(|| error_code.to_string()),
+        reason: reason.to_string(),
+    })
+}
+
+async fn stored_status(
+    infra: &crate::infra::InfraClients,
+    attestation_hash: &str,
+) -> Result<Option<AttestationStatus>, AppError> {
+    let attestations: Collection<ComplianceAttestationRecord> =
+        infra.mongo_db.collection(ATTESTATIONS_COLLECTION);
+    let found = attestations
+        .find_one(doc! { "attestation_hash": attestation_hash })
+        .await
+        .map_err(|e| AppError::internal("PERSISTENCE_ERROR", format!("mongo find failed: {e}")))?;
+    match found {
+        Some(record) => Ok(Some(attestation_status(&record, now_unix()?))),
+        None => Ok(None),
+    }
+}
+
+fn sign_attestation(
+    state: &AppState,
+    claims: &AttestationClaims<'_>,
+) -> Result<Option<String>, AppError> {
+    state
+        .signer
+        .as_ref()
+        .map(|signer| signer.sign(claims))
+        .transpose()
+        .map_err(|e| AppError::internal("SIGNING_ERROR", e))
+}
+
+fn signing_key_id(state: &AppState) -> Option<String> {
+    state.signer.as_ref().map(|s| s.key_id().to_string())
+}
+
 fn now_unix() -> Result<i64, AppError> {
     let elapsed = SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -354,12 +929,20 @@ fn normalize_subjects(subjects: &[SubjectInput]) -> Result<Vec<NormalizedSubject
                     "counterparty_id is required",
                 ));
             }
+            let wallet_address = c
+                .wallet_address
+                .as_deref()
+                .filter(|w| !w.trim().is_empty())
+                .map(validate_wallet_address)
+                .transpose()
+                .map_err(|e| AppError::bad_request("INVALID_WALLET_ADDRESS", e))?;
             normalized.push(NormalizedSubject {
                 subject_id: c.counterparty_id.clone(),
                 subject_type: SubjectType::Counterparty,
                 jurisdiction: c.country.clone(),
-                address: c.wallet_address.clone(),
+                address: None,
                 legal_name: None,
+                wallet_address,
             });
             continue;
         }
@@ -377,6 +960,7 @@ fn normalize_subjects(subjects: &[SubjectInput]) -> Result<Vec<NormalizedSubject
                 jurisdiction: e.registration_country.clone(),
                 address: None,
                 legal_name: e.legal_name.clone(),
+                wallet_address: None,
             });
         }
     }
@@ -412,7 +996,7 @@ async fn check_idempotent_existing(
     let attestations: Collection<ComplianceAttestationRecord> =
         infra.mongo_db.collection(ATTESTATIONS_COLLECTION);
     let att = attestations
-        .find_one(doc! { "request_id": request_id })
+        .find_one(doc! { "request_id": request_id, "supersedes": null })
         .await
         .map_err(|e| {
             AppError::internal(
@@ -427,25 +1011,7 @@ async fn check_idempotent_existing(
         ));
     };
 
-    Ok(Some(IntakeComplianceResponse {
-        attestation_id: att.attestation_id,
-        workflow_run_id: att.workflow_run_id,
-        request_id: att.request_id,
-        accepted: true,
-        normalized_subject_count: att.normalized_subjects.len(),
-        normalized_subjects: att.normalized_subjects,
-        policy_version: att.policy_version,
-        policy_hash: att.policy_hash,
-        decision: decision_from_str(&att.decision),
-        risk_score: att.risk_score,
-        sanctions_hit_count: att.sanctions_hit_count,
-        attestation_hash: att.attestation_hash,
-        issued_at: att.issued_at,
-        expires_at: att.expires_at,
-        error_code: None,
-        reason: "accepted".to_string(),
-        fx_quote: att.fx_quote,
-    }))
+    Ok(Some(response_from_record(att, now_unix()?)))
 }
 
 async fn reserve_replay_keys(
@@ -500,10 +1066,10 @@ async fn reserve_replay_keys(
 async fn get_or_compute_screening(
     infra: &crate::infra::InfraClients,
     subjects: &[NormalizedSubject],
-    sanctions_entries: &[crate::service::sanctions_service::SanctionsEntry],
+    sanctions: &SanctionsIndex,
     policy_hash: &str,
 ) -> Result<ScreeningResult, AppError> {
-    let cache_key = build_screen_cache_key(subjects, policy_hash);
+    let cache_key = build_screen_cache_key(subjects, policy_hash, sanctions.version());
     let redis_key = format!("{SCREEN_CACHE_PREFIX}{cache_key}");
     let mut conn: MultiplexedConnection = infra
         .redis
@@ -525,7 +1091,7 @@ async fn get_or_compute_screening(
         });
     }
 
-    let screening = screen_subjects(subjects, sanctions_entries);
+    let screening = screen_subjects(subjects, sanctions);
     let payload = CachedScreening {
         hits: screening.hits.clone(),
         match_digest: screening.match_digest.clone(),
@@ -539,13 +1105,24 @@ async fn get_or_compute_screening(
     Ok(screening)
 }
 
-fn build_screen_cache_key(subjects: &[NormalizedSubject], policy_hash: &str) -> String {
+/// Keyed by the sanctions list version too, so a reloaded list is never
+/// answered from a screening of the previous one.
+fn build_screen_cache_key(
+    subjects: &[NormalizedSubject],
+    policy_hash: &str,
+    sanctions_version: &str,
+) -> String {
     let mut stable = subjects
         .iter()
         .map(|s| {
             format!(
-                "{}:{:?}:{:?}:{:?}:{:?}",
-                s.subject_id, s.subject_type, s.jurisdiction, s.address, s.legal_name
+                "{}:{:?}:{:?}:{:?}:{:?}:{:?}",
+                s.subject_id,
+                s.subject_type,
+                s.jurisdiction,
+                s.address,
+                s.legal_name,
+                s.wallet_address
             )
         })
         .collect::<Vec<_>>();
@@ -556,9 +1133,12 @@ fn build_screen_cache_key(subjects: &[NormalizedSubject], policy_hash: &str) ->
         hasher.update(b"|");
     }
     hasher.update(policy_hash.as_bytes());
+    hasher.update(b"|");
+    hasher.update(sanctions_version.as_bytes());
     hex::encode(hasher.finalize())
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn persist_records(
     state: &AppState,
     infra: &crate::infra::InfraClients,
@@ -568,6 +1148,7 @@ async fn persist_records(
     request_hash: &str,
     screening: &ScreeningResult,
     fx_quote: Option<&FxQuote>,
+    provider_assessment: Option<&ProviderAssessment>,
 ) -> Result<(), AppError> {
     let requests: Collection<ComplianceRequestRecord> =
         infra.mongo_db.collection(REQUESTS_COLLECTION);
@@ -611,6 +1192,21 @@ async fn persist_records(
         source: "local_sanctions_dataset+frankfurter".to_string(),
         redacted_payload_ref: encrypted_ref,
         created_at: now_unix()?,
+        provider_reference: None,
+        risk_score: None,
+    };
+    let external_ref = match provider_assessment {
+        Some(assessment) => Some(ProviderResponseReference {
+            request_id: response.request_id.clone(),
+            provider_ref_id: provider_ref_id(&response.attestation_id, &assessment.provider),
+            source: assessment.provider.clone(),
+            redacted_payload_ref: encrypt_for_storage(&assessment.raw_response, encryption_key)
+                .map_err(|e| AppError::internal("ENCRYPTION_ERROR", e))?,
+            created_at: now_unix()?,
+            provider_reference: Some(assessment.reference.clone()),
+            risk_score: Some(assessment.risk_score),
+        }),
+        None => None,
     };
 
     let att_doc = ComplianceAttestationRecord {
@@ -621,23 +1217,34 @@ async fn persist_records(
         policy_hash: response.policy_hash.clone(),
         decision: response.decision.as_str().to_string(),
         risk_score: response.risk_score,
+        policy_factors: response.policy_factors.clone(),
         attestation_hash: response.attestation_hash.clone(),
         issued_at: response.issued_at,
         expires_at: response.expires_at,
         sanctions_hit_count: response.sanctions_hit_count,
         normalized_subjects: response.normalized_subjects.clone(),
         fx_quote: response.fx_quote.clone(),
+        review_status: response.review_status.clone(),
+        supersedes: None,
+        superseded_by: None,
+        adjudication: None,
+        signature: response.signature.clone(),
+        signing_key_id: response.signing_key_id.clone(),
+        revocation: None,
+        provider_refs: response.provider_refs.clone(),
     };
 
     requests.insert_one(request_doc).await.map_err(|e| {
         AppError::internal("PERSISTENCE_ERROR", format!("insert request failed: {e}"))
     })?;
-    provider_refs.insert_one(provider_ref).await.map_err(|e| {
-        AppError::internal(
-            "PERSISTENCE_ERROR",
-            format!("insert provider ref failed: {e}"),
-        )
-    })?;
+    for reference in std::iter::once(provider_ref).chain(external_ref) {
+        provider_refs.insert_one(reference).await.map_err(|e| {
+            AppError::internal(
+                "PERSISTENCE_ERROR",
+                format!("insert provider ref failed: {e}"),
+            )
+        })?;
+    }
     attestations.insert_one(att_doc).await.map_err(|e| {
         AppError::internal(
             "PERSISTENCE_ERROR",
@@ -708,6 +1315,42 @@ async fn write_audit_event(
     Ok(())
 }
 
+fn response_from_record(record: ComplianceAttestationRecord, now: i64) -> IntakeComplianceResponse {
+    let status = attestation_status(&record, now);
+    IntakeComplianceResponse {
+        attestation_id: record.attestation_id,
+        workflow_run_id: record.workflow_run_id,
+        request_id: record.request_id,
+        accepted: true,
+        normalized_subject_count: record.normalized_subjects.len(),
+        normalized_subjects: record.normalized_subjects,
+        policy_version: record.policy_version,
+        policy_hash: record.policy_hash,
+        decision: decision_from_str(&record.decision),
+        risk_score: record.risk_score,
+        policy_factors: record.policy_factors,
+        sanctions_hit_count: record.sanctions_hit_count,
+        attestation_hash: record.attestation_hash,
+        issued_at: record.issued_at,
+        expires_at: record.expires_at,
+        error_code: None,
+        reason: "accepted".to_string(),
+        fx_quote: record.fx_quote,
+        review_status: record.review_status,
+        supersedes: record.supersedes,
+        superseded_by: record.superseded_by,
+        signature: record.signature,
+        signing_key_id: record.signing_key_id,
+        status: Some(status),
+        revocation: record.revocation,
+        provider_refs: record.provider_refs,
+    }
+}
+
+fn provider_ref_id(attestation_id: &str, provider: &str) -> String {
+    format!("prov_{attestation_id}_{provider}")
+}
+
 fn decision_from_str(value: &str) -> ComplianceDecision {
     match value {
         "PASS" => ComplianceDecision::Pass,
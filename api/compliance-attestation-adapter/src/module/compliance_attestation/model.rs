@@ -1,4 +1,5 @@
 use crate::service::confidential_http_service::FxQuote;
+use crate::service::policy_eval_service::PolicyFactor;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -8,6 +9,9 @@ pub struct NormalizedSubject {
     pub jurisdiction: Option<String>,
     pub address: Option<String>,
     pub legal_name: Option<String>,
+    /// Canonical on-chain address (see `canonical_wallet_address`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wallet_address: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -35,6 +39,11 @@ pub struct ProviderResponseReference {
     pub source: String,
     pub redacted_payload_ref: String,
     pub created_at: i64,
+    /// The external provider's own case id, for provider assessments.
+    #[serde(default)]
+    pub provider_reference: Option<String>,
+    #[serde(default)]
+    pub risk_score: Option<u16>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -46,12 +55,58 @@ pub struct ComplianceAttestationRecord {
     pub policy_hash: String,
     pub decision: String,
     pub risk_score: u16,
+    #[serde(default)]
+    pub policy_factors: Vec<PolicyFactor>,
     pub attestation_hash: String,
     pub issued_at: i64,
     pub expires_at: i64,
     pub sanctions_hit_count: usize,
     pub normalized_subjects: Vec<NormalizedSubject>,
     pub fx_quote: Option<FxQuote>,
+    /// `PENDING` for REVIEW decisions until an analyst adjudicates them.
+    #[serde(default)]
+    pub review_status: Option<String>,
+    /// The attestation this one re-issues after adjudication.
+    #[serde(default)]
+    pub supersedes: Option<String>,
+    #[serde(default)]
+    pub superseded_by: Option<String>,
+    #[serde(default)]
+    pub adjudication: Option<AdjudicationRecord>,
+    #[serde(default)]
+    pub signature: Option<String>,
+    #[serde(default)]
+    pub signing_key_id: Option<String>,
+    #[serde(default)]
+    pub revocation: Option<RevocationRecord>,
+    /// `provider_ref_id`s of the external provider responses relied on.
+    #[serde(default)]
+    pub provider_refs: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdjudicationRecord {
+    pub outcome: String,
+    pub reason: String,
+    pub reviewer: String,
+    pub adjudicated_at: i64,
+    /// Score the analyst assigned; the re-issued attestation carries it.
+    #[serde(default)]
+    pub risk_score: Option<u16>,
+    /// Score of the REVIEW attestation, before adjudication.
+    #[serde(default)]
+    pub pre_review_risk_score: Option<u16>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevocationRecord {
+    pub reason: String,
+    pub detail: String,
+    pub revoked_by: String,
+    pub revoked_at: i64,
+    /// Sanctions list version that triggered an automatic revocation.
+    #[serde(default)]
+    pub sanctions_version: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -7,10 +7,27 @@ pub fn register_routes(state: AppState) -> Router {
     Router::new()
         .route("/v1/compliance/intake", post(controller::attest_compliance))
         .route("/v1/compliance/attest", post(controller::attest_compliance))
+        .route(
+            "/v1/compliance/attest/verify",
+            post(controller::verify_attestation),
+        )
+        .route("/v1/compliance/jwks", get(controller::signing_keys))
         .route(
             "/v1/compliance/attest/:attestation_id",
             get(controller::get_attestation),
         )
+        .route(
+            "/v1/compliance/attest/:attestation_id/revoke",
+            post(controller::revoke_attestation),
+        )
+        .route(
+            "/v1/compliance/reviews",
+            get(controller::list_pending_reviews),
+        )
+        .route(
+            "/v1/compliance/reviews/:attestation_id/adjudicate",
+            post(controller::adjudicate_review),
+        )
         .route("/v1/compliance/health", get(controller::health))
         .with_state(state)
 }
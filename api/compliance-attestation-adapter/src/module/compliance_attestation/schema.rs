@@ -1,5 +1,6 @@
-use super::model::NormalizedSubject;
+use super::model::{NormalizedSubject, RevocationRecord};
 use crate::service::confidential_http_service::FxQuote;
+use crate::service::policy_eval_service::PolicyFactor;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -44,6 +45,9 @@ pub struct IntakeComplianceResponse {
     pub policy_hash: String,
     pub decision: ComplianceDecision,
     pub risk_score: u16,
+    /// Everything that moved the decision or risk score, in evaluation order.
+    #[serde(default)]
+    pub policy_factors: Vec<PolicyFactor>,
     pub sanctions_hit_count: usize,
     pub attestation_hash: String,
     pub issued_at: i64,
@@ -51,12 +55,159 @@ pub struct IntakeComplianceResponse {
     pub error_code: Option<String>,
     pub reason: String,
     pub fx_quote: Option<FxQuote>,
+    #[serde(default)]
+    pub review_status: Option<String>,
+    #[serde(default)]
+    pub supersedes: Option<String>,
+    #[serde(default)]
+    pub superseded_by: Option<String>,
+    /// secp256k1 signature over the attestation claims; see
+    /// `attestation_signing_service`.
+    #[serde(default)]
+    pub signature: Option<String>,
+    #[serde(default)]
+    pub signing_key_id: Option<String>,
+    /// Lifecycle status as of the response; see `revocation_service`.
+    #[serde(default)]
+    pub status: Option<AttestationStatus>,
+    #[serde(default)]
+    pub revocation: Option<RevocationRecord>,
+    #[serde(default)]
+    pub provider_refs: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AttestationStatus {
+    Active,
+    Expired,
+    Revoked,
+    Superseded,
+}
+
+impl AttestationStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Active => "active",
+            Self::Expired => "expired",
+            Self::Revoked => "revoked",
+            Self::Superseded => "superseded",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RevocationReason {
+    SanctionsMatch,
+    ComplianceReview,
+    IssuerRequest,
+    KeyCompromise,
+}
+
+impl RevocationReason {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::SanctionsMatch => "SANCTIONS_MATCH",
+            Self::ComplianceReview => "COMPLIANCE_REVIEW",
+            Self::IssuerRequest => "ISSUER_REQUEST",
+            Self::KeyCompromise => "KEY_COMPROMISE",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RevokeAttestationRequest {
+    pub reason: RevocationReason,
+    pub detail: String,
+    pub revoked_by: String,
+}
+
+/// A presented attestation. Extra fields of a full attestation response are
+/// ignored, so callers can post one as-is.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct VerifyAttestationRequest {
+    pub attestation_hash: String,
+    pub policy_hash: String,
+    pub decision: ComplianceDecision,
+    pub risk_score: u16,
+    pub issued_at: i64,
+    pub expires_at: i64,
+    pub signature: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct VerifyAttestationResponse {
+    pub valid: bool,
+    pub signature_valid: bool,
+    pub signer: Option<String>,
+    pub expired: bool,
+    pub policy_hash_active: bool,
+    /// Stored status of the attestation, when this adapter persisted it.
+    #[serde(default)]
+    pub status: Option<AttestationStatus>,
+    pub error_code: Option<String>,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReviewQueueQuery {
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PendingReview {
+    pub attestation_id: String,
+    pub request_id: String,
+    pub workflow_run_id: String,
+    pub policy_version: String,
+    pub risk_score: u16,
+    pub policy_factors: Vec<PolicyFactor>,
+    pub sanctions_hit_count: usize,
+    pub issued_at: i64,
+    pub expires_at: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReviewQueueResponse {
+    pub pending: Vec<PendingReview>,
+    pub error_code: Option<String>,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum ReviewOutcome {
+    Approve,
+    Reject,
+}
+
+impl ReviewOutcome {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Approve => "APPROVE",
+            Self::Reject => "REJECT",
+        }
+    }
+}
+
+/// The reviewer is not part of the body: it comes from the
+/// `x-reviewer-id` header, authenticated by `x-internal-signature`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AdjudicateReviewRequest {
+    pub outcome: ReviewOutcome,
+    pub reason: String,
+    /// The analyst's risk score for the re-issued attestation, 0..=100.
+    pub risk_score: u16,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct HealthResponse {
     pub status: String,
     pub persistence_enabled: bool,
+    pub sanctions_list_version: Option<String>,
+    pub sanctions_entry_count: usize,
+    pub sanctions_loaded_at: Option<i64>,
 }
 
 #[derive(Debug, Clone, Copy, Deserialize, Serialize)]
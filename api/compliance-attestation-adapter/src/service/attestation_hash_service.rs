@@ -1,8 +1,12 @@
-use crate::module::compliance_attestation::model::NormalizedSubject;
+use crate::infra::provider_client::ProviderAssessment;
+use crate::module::compliance_attestation::model::{
+    AdjudicationRecord, ComplianceAttestationRecord, NormalizedSubject,
+};
 use crate::module::compliance_attestation::schema::ComplianceDecision;
 use serde::Serialize;
 use sha2::{Digest, Sha256};
 
+#[allow(clippy::too_many_arguments)]
 pub fn compute_attestation_hash(
     workflow_run_id: &str,
     request_id: &str,
@@ -15,6 +19,7 @@ pub fn compute_attestation_hash(
     sanctions_hit_count: usize,
     subjects: &[NormalizedSubject],
     match_digest: &str,
+    provider: Option<&ProviderAssessment>,
 ) -> String {
     let subjects_digest = digest_subjects(subjects);
     let canonical = CanonicalAttestationHashInput {
@@ -29,6 +34,44 @@ pub fn compute_attestation_hash(
         sanctions_hit_count,
         subjects_digest: &subjects_digest,
         match_digest,
+        provider: provider.map(|a| CanonicalProviderAssessment {
+            provider: &a.provider,
+            reference: &a.reference,
+            risk_score: a.risk_score,
+        }),
+    };
+
+    let encoded = serde_json::to_vec(&canonical).expect("canonical serialization should not fail");
+    let mut hasher = Sha256::new();
+    hasher.update(&encoded);
+    hex::encode(hasher.finalize())
+}
+
+/// Hash of an attestation re-issued after review. It commits to the original
+/// attestation hash and to the adjudication, so the chain can be followed
+/// from either end.
+pub fn compute_superseding_attestation_hash(
+    original: &ComplianceAttestationRecord,
+    decision: ComplianceDecision,
+    issued_at: i64,
+    expires_at: i64,
+    adjudication: &AdjudicationRecord,
+) -> String {
+    let canonical = CanonicalSupersedingHashInput {
+        supersedes_attestation_hash: &original.attestation_hash,
+        workflow_run_id: &original.workflow_run_id,
+        request_id: &original.request_id,
+        policy_version: &original.policy_version,
+        policy_hash: &original.policy_hash,
+        decision: decision.as_str(),
+        risk_score: adjudication.risk_score.unwrap_or(original.risk_score),
+        pre_review_risk_score: original.risk_score,
+        issued_at,
+        expires_at,
+        subjects_digest: &digest_subjects(&original.normalized_subjects),
+        review_outcome: &adjudication.outcome,
+        reviewer: &adjudication.reviewer,
+        review_reason: &adjudication.reason,
     };
 
     let encoded = serde_json::to_vec(&canonical).expect("canonical serialization should not fail");
@@ -56,6 +99,7 @@ fn digest_subjects(subjects: &[NormalizedSubject]) -> String {
             jurisdiction: s.jurisdiction.clone(),
             address: s.address.clone(),
             legal_name: s.legal_name.clone(),
+            wallet_address: s.wallet_address.clone(),
         })
         .collect();
     stable.sort_by(|a, b| {
@@ -83,6 +127,34 @@ struct CanonicalAttestationHashInput<'a> {
     sanctions_hit_count: usize,
     subjects_digest: &'a str,
     match_digest: &'a str,
+    /// Left out entirely without a provider, so those hashes are unchanged.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    provider: Option<CanonicalProviderAssessment<'a>>,
+}
+
+#[derive(Serialize)]
+struct CanonicalProviderAssessment<'a> {
+    provider: &'a str,
+    reference: &'a str,
+    risk_score: u16,
+}
+
+#[derive(Serialize)]
+struct CanonicalSupersedingHashInput<'a> {
+    supersedes_attestation_hash: &'a str,
+    workflow_run_id: &'a str,
+    request_id: &'a str,
+    policy_version: &'a str,
+    policy_hash: &'a str,
+    decision: &'a str,
+    risk_score: u16,
+    pre_review_risk_score: u16,
+    issued_at: i64,
+    expires_at: i64,
+    subjects_digest: &'a str,
+    review_outcome: &'a str,
+    reviewer: &'a str,
+    review_reason: &'a str,
 }
 
 #[derive(Serialize)]
@@ -92,4 +164,6 @@ struct CanonicalSubject {
     jurisdiction: Option<String>,
     address: Option<String>,
     legal_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    wallet_address: Option<String>,
 }
@@ -0,0 +1,177 @@
+use crate::module::compliance_attestation::schema::ComplianceDecision;
+use crate::service::wallet_screening_service::canonical_wallet_address;
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use k256::ecdsa::{RecoveryId, Signature, SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+
+const SIGNATURE_LEN: usize = 65;
+const EIP191_PREFIX: &[u8] = b"\x19Ethereum Signed Message:\n32";
+
+/// The attestation fields a signature commits to. They are packed the way
+/// Solidity's `abi.encodePacked(bytes32, bytes32, uint8, uint16, uint64,
+/// uint64)` would, hashed with keccak256 and wrapped as an EIP-191 personal
+/// message, so a contract can check it with `ecrecover`.
+#[derive(Debug, Clone, Copy)]
+pub struct AttestationClaims<'a> {
+    pub attestation_hash: &'a str,
+    pub policy_hash: &'a str,
+    pub decision: ComplianceDecision,
+    pub risk_score: u16,
+    pub issued_at: i64,
+    pub expires_at: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Jwk {
+    pub kty: String,
+    pub crv: String,
+    pub alg: String,
+    #[serde(rename = "use")]
+    pub key_use: String,
+    pub kid: String,
+    pub x: String,
+    pub y: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JwkSet {
+    pub keys: Vec<Jwk>,
+}
+
+/// The adapter's secp256k1 attestation key. Its key id is the Ethereum
+/// address of the public key, which is also what `ecrecover` returns.
+pub struct AttestationSigner {
+    key: SigningKey,
+    address: String,
+}
+
+impl std::fmt::Debug for AttestationSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AttestationSigner")
+            .field("address", &self.address)
+            .finish()
+    }
+}
+
+impl AttestationSigner {
+    pub fn from_hex(secret_hex: &str) -> Result<Self, String> {
+        let secret = hex::decode(secret_hex.trim().trim_start_matches("0x"))
+            .map_err(|e| format!("invalid ATTESTATION_SIGNING_KEY_HEX: {e}"))?;
+        let key = SigningKey::from_slice(&secret)
+            .map_err(|_| "ATTESTATION_SIGNING_KEY_HEX is not a valid secp256k1 key".to_string())?;
+        let address = evm_address(key.verifying_key());
+        Ok(Self { key, address })
+    }
+
+    pub fn key_id(&self) -> &str {
+        &self.address
+    }
+
+    /// 65-byte `r || s || v` signature, hex with `0x`, `v` in {27, 28}.
+    pub fn sign(&self, claims: &AttestationClaims<'_>) -> Result<String, String> {
+        let digest = signing_digest(claims)?;
+        let (signature, recovery_id) = self
+            .key
+            .sign_prehash_recoverable(&digest)
+            .map_err(|e| format!("attestation signing failed: {e}"))?;
+        let mut out = Vec::with_capacity(SIGNATURE_LEN);
+        out.extend_from_slice(&signature.to_bytes());
+        out.push(27 + recovery_id.to_byte());
+        Ok(format!("0x{}", hex::encode(out)))
+    }
+
+    pub fn jwks(&self) -> JwkSet {
+        let point = self.key.verifying_key().to_encoded_point(false);
+        let coordinate = |c: Option<&k256::FieldBytes>| {
+            URL_SAFE_NO_PAD.encode(c.map(|b| b.as_slice()).unwrap_or_default())
+        };
+        JwkSet {
+            keys: vec![Jwk {
+                kty: "EC".to_string(),
+                crv: "secp256k1".to_string(),
+                alg: "ES256K".to_string(),
+                key_use: "sig".to_string(),
+                kid: self.address.clone(),
+                x: coordinate(point.x()),
+                y: coordinate(point.y()),
+            }],
+        }
+    }
+}
+
+/// Address that produced `signature` over `claims`.
+pub fn recover_signer(
+    claims: &AttestationClaims<'_>,
+    signature_hex: &str,
+) -> Result<String, String> {
+    let raw = hex::decode(signature_hex.trim().trim_start_matches("0x"))
+        .map_err(|e| format!("invalid signature hex: {e}"))?;
+    if raw.len() != SIGNATURE_LEN {
+        return Err(format!("signature must be {SIGNATURE_LEN} bytes"));
+    }
+    let signature =
+        Signature::from_slice(&raw[..64]).map_err(|e| format!("invalid signature: {e}"))?;
+    let v = raw[64];
+    let recovery_id = RecoveryId::from_byte(v.checked_sub(27).unwrap_or(v))
+        .ok_or_else(|| format!("invalid signature recovery byte {v}"))?;
+    let digest = signing_digest(claims)?;
+    let key = VerifyingKey::recover_from_prehash(&digest, &signature, recovery_id)
+        .map_err(|_| "signature does not recover to a public key".to_string())?;
+    Ok(evm_address(&key))
+}
+
+/// EIP-191 digest of the packed claims; what the signature is over.
+pub fn signing_digest(claims: &AttestationClaims<'_>) -> Result<[u8; 32], String> {
+    let mut packed = Vec::with_capacity(32 + 32 + 1 + 2 + 8 + 8);
+    packed.extend_from_slice(&bytes32(claims.attestation_hash, "attestation_hash")?);
+    packed.extend_from_slice(&bytes32(claims.policy_hash, "policy_hash")?);
+    packed.push(decision_code(claims.decision));
+    packed.extend_from_slice(&claims.risk_score.to_be_bytes());
+    packed.extend_from_slice(&timestamp(claims.issued_at, "issued_at")?.to_be_bytes());
+    packed.extend_from_slice(&timestamp(claims.expires_at, "expires_at")?.to_be_bytes());
+
+    let message = Keccak256::digest(&packed);
+    let mut prefixed = Keccak256::new();
+    prefixed.update(EIP191_PREFIX);
+    prefixed.update(message);
+    Ok(prefixed.finalize().into())
+}
+
+/// Onchain decision code; 0 is left for "no attestation".
+pub fn decision_code(decision: ComplianceDecision) -> u8 {
+    match decision {
+        ComplianceDecision::Pass => 1,
+        ComplianceDecision::Review => 2,
+        ComplianceDecision::Fail => 3,
+    }
+}
+
+/// Hex value as a left-padded `bytes32`.
+fn bytes32(value: &str, field: &str) -> Result<[u8; 32], String> {
+    let hex_digits = value.trim().trim_start_matches("0x");
+    let hex_digits = if hex_digits.len() % 2 == 1 {
+        format!("0{hex_digits}")
+    } else {
+        hex_digits.to_string()
+    };
+    let raw = hex::decode(&hex_digits).map_err(|e| format!("{field} is not hex: {e}"))?;
+    if raw.len() > 32 {
+        return Err(format!("{field} is longer than 32 bytes"));
+    }
+    let mut out = [0u8; 32];
+    out[32 - raw.len()..].copy_from_slice(&raw);
+    Ok(out)
+}
+
+fn timestamp(value: i64, field: &str) -> Result<u64, String> {
+    u64::try_from(value).map_err(|_| format!("{field} must not be negative"))
+}
+
+fn evm_address(key: &VerifyingKey) -> String {
+    let point = key.to_encoded_point(false);
+    let hash = Keccak256::digest(&point.as_bytes()[1..]);
+    let address = format!("0x{}", hex::encode(&hash[12..]));
+    canonical_wallet_address(&address).unwrap_or(address)
+}
@@ -1,8 +1,16 @@
 pub mod attestation_hash_service;
+pub mod attestation_signing_service;
 pub mod confidential_http_service;
 pub mod encryption_service;
 pub mod idempotency_service;
 pub mod metrics_service;
+pub mod name_match_service;
 pub mod policy_eval_service;
+pub mod policy_source_service;
+pub mod review_service;
+pub mod revocation_service;
+pub mod sanctions_ingest_service;
 pub mod sanctions_service;
+pub mod sanctions_store_service;
 pub mod signature_service;
+pub mod wallet_screening_service;
@@ -0,0 +1,218 @@
+use deunicode::deunicode;
+use strsim::{jaro_winkler, normalized_levenshtein};
+
+/// Token similarities below this are treated as unrelated words rather than
+/// spelling variants.
+const TOKEN_SIMILARITY_FLOOR: f64 = 0.9;
+/// Similarity given to tokens that differ only in romanisation
+/// (`mohammed`/`muhammad`, `osama`/`usama`).
+const SKELETON_SIMILARITY: f64 = 0.92;
+const SKELETON_MIN_JARO_WINKLER: f64 = 0.75;
+/// Tokens this short (initials, particles, codes) only count when identical.
+const SHORT_TOKEN_LEN: usize = 3;
+const PREFIX_KEY_LEN: usize = 3;
+/// A listed name found inside a longer candidate is capped here, so a
+/// containment alone lands in the review band rather than failing. The
+/// coverage is squared first: a misspelt containment is weaker evidence.
+const CONTAINMENT_WEIGHT: f64 = 0.8;
+
+/// Legal forms and connectives that carry no identifying weight.
+const NOISE_TOKENS: &[&str] = &[
+    "the",
+    "and",
+    "of",
+    "llc",
+    "ltd",
+    "limited",
+    "inc",
+    "incorporated",
+    "co",
+    "corp",
+    "corporation",
+    "company",
+    "sa",
+    "sas",
+    "srl",
+    "spa",
+    "gmbh",
+    "ag",
+    "plc",
+    "bv",
+    "nv",
+    "jsc",
+    "ojsc",
+    "pjsc",
+    "cjsc",
+    "ooo",
+    "zao",
+    "oao",
+];
+
+/// A name reduced to comparable ASCII tokens.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FoldedName {
+    pub tokens: Vec<String>,
+    pub compact: String,
+    skeletons: Vec<String>,
+}
+
+impl FoldedName {
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// Index keys for candidate lookup: every token, and for longer tokens
+    /// their three-letter prefix and skeleton. Token pairs that
+    /// [`name_confidence`] treats as spelling variants share one of these
+    /// keys unless they differ within the first three letters.
+    pub fn blocking_keys(&self) -> Vec<String> {
+        let mut keys = Vec::with_capacity(self.tokens.len() * 3);
+        for (token, skel) in self.tokens.iter().zip(&self.skeletons) {
+            keys.push(format!("t:{token}"));
+            if token.len() > SHORT_TOKEN_LEN {
+                keys.push(format!("p:{}", &token[..PREFIX_KEY_LEN]));
+                if skel.len() >= 3 {
+                    keys.push(format!("s:{skel}"));
+                }
+            }
+        }
+        keys.sort();
+        keys.dedup();
+        keys
+    }
+}
+
+/// Transliterates to ASCII (accents, Cyrillic, Arabic, ...), lowercases, and
+/// splits on punctuation. Periods and apostrophes join rather than split, so
+/// `S.A.` and `O'Neil` stay one token.
+pub fn fold_name(input: &str) -> FoldedName {
+    let ascii = deunicode(input).to_ascii_lowercase();
+    let spaced = ascii
+        .chars()
+        .filter(|c| !matches!(c, '.' | '\'' | '`'))
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { ' ' })
+        .collect::<String>();
+    let all = spaced
+        .split_whitespace()
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    let significant = all
+        .iter()
+        .filter(|t| !NOISE_TOKENS.contains(&t.as_str()))
+        .cloned()
+        .collect::<Vec<_>>();
+    let tokens = if significant.is_empty() {
+        all
+    } else {
+        significant
+    };
+    let compact = tokens.concat();
+    let skeletons = tokens.iter().map(|t| skeleton(t)).collect();
+    FoldedName {
+        tokens,
+        compact,
+        skeletons,
+    }
+}
+
+/// Confidence (0-100) that `candidate` names the same party as `listed`.
+/// Token order is ignored; each listed token is matched to its closest
+/// candidate token and vice versa, weighted by token length, and the two
+/// directions are combined as a harmonic mean so a short candidate cannot
+/// score high against a long listed name.
+pub fn name_confidence(candidate: &FoldedName, listed: &FoldedName) -> u8 {
+    if candidate.is_empty() || listed.is_empty() {
+        return 0;
+    }
+    if candidate.compact == listed.compact {
+        return 100;
+    }
+
+    let coverage = directed_similarity(listed, candidate);
+    let precision = directed_similarity(candidate, listed);
+    let both = if coverage + precision == 0.0 {
+        0.0
+    } else {
+        2.0 * coverage * precision / (coverage + precision)
+    };
+    let token_score = both.max(CONTAINMENT_WEIGHT * coverage * coverage);
+    // Whole-string edit distance only bridges differently split names
+    // (`abdulrahman` / `abdul rahman`); with equal token counts it would let
+    // one differing word hide inside a long name.
+    let compact_score = if candidate.tokens.len() == listed.tokens.len() {
+        0.0
+    } else {
+        normalized_levenshtein(&candidate.compact, &listed.compact)
+    };
+
+    (token_score.max(compact_score) * 100.0).round() as u8
+}
+
+fn directed_similarity(from: &FoldedName, to: &FoldedName) -> f64 {
+    let mut weighted = 0.0;
+    let mut total = 0.0;
+    for (token, skel) in from.tokens.iter().zip(&from.skeletons) {
+        let best = to
+            .tokens
+            .iter()
+            .zip(&to.skeletons)
+            .map(|(other, other_skel)| token_similarity((token, skel), (other, other_skel)))
+            .fold(0.0, f64::max);
+        let weight = token.len() as f64;
+        weighted += best * weight;
+        total += weight;
+    }
+    if total == 0.0 { 0.0 } else { weighted / total }
+}
+
+fn token_similarity((a, skel_a): (&str, &str), (b, skel_b): (&str, &str)) -> f64 {
+    if a == b {
+        return 1.0;
+    }
+    if a.len() <= SHORT_TOKEN_LEN || b.len() <= SHORT_TOKEN_LEN {
+        return 0.0;
+    }
+    // Spelling variants are only looked for between tokens that start alike
+    // or share a skeleton; this also skips most pairs cheaply.
+    let same_skeleton = skel_a.len() >= 3 && skel_a == skel_b;
+    if !same_skeleton && a.as_bytes()[0] != b.as_bytes()[0] {
+        return 0.0;
+    }
+    let mut score = jaro_winkler(a, b);
+    // A shared skeleton alone is too loose for short tokens (`acme` and
+    // `yakima` reduce alike), so the spelling must still be close.
+    if same_skeleton && score >= SKELETON_MIN_JARO_WINKLER {
+        score = score.max(SKELETON_SIMILARITY);
+    }
+    if score < TOKEN_SIMILARITY_FLOOR {
+        0.0
+    } else {
+        score
+    }
+}
+
+/// Consonant skeleton that absorbs common romanisation differences: vowels
+/// after the first letter are dropped, a leading vowel is neutral, a few
+/// digraphs collapse, and doubled letters merge.
+fn skeleton(token: &str) -> String {
+    let mapped = token
+        .replace("kh", "h")
+        .replace("ph", "f")
+        .replace("dh", "d")
+        .replace("th", "t")
+        .replace("ck", "k")
+        .replace(['q', 'c'], "k");
+    let mut out = String::with_capacity(mapped.len());
+    for (i, c) in mapped.chars().enumerate() {
+        let vowel = matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y');
+        let c = match (i, vowel) {
+            (0, true) => 'a',
+            (_, true) => continue,
+            _ => c,
+        };
+        if !out.ends_with(c) {
+            out.push(c);
+        }
+    }
+    out
+}
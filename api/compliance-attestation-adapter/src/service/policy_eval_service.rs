@@ -1,11 +1,19 @@
+use crate::infra::provider_client::ProviderAssessment;
+use crate::module::compliance_attestation::model::{NormalizedSubject, SubjectType};
 use crate::module::compliance_attestation::schema::ComplianceDecision;
-use crate::service::sanctions_service::ScreeningResult;
+use crate::service::sanctions_service::{HitKind, ScreeningResult};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Risk scores are reported on a 0..=100 scale.
+pub const MAX_RISK_SCORE: u16 = 100;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PolicySnapshot {
     pub active: ActivePolicy,
     pub thresholds: PolicyThresholds,
+    #[serde(default)]
+    pub rules: PolicyRules,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,11 +26,77 @@ pub struct ActivePolicy {
 pub struct PolicyThresholds {
     pub review_confidence: u8,
     pub fail_confidence: u8,
+    /// Policy-snapshot bundles carry only the confidences; risk scores fall
+    /// back to the adapter defaults.
+    #[serde(default = "default_pass_risk_score")]
     pub pass_risk_score: u16,
+    #[serde(default = "default_review_risk_score")]
     pub review_risk_score: u16,
+    #[serde(default = "default_fail_risk_score")]
     pub fail_risk_score: u16,
 }
 
+fn default_pass_risk_score() -> u16 {
+    5
+}
+
+fn default_review_risk_score() -> u16 {
+    60
+}
+
+fn default_fail_risk_score() -> u16 {
+    95
+}
+
+/// Jurisdiction and subject-type rules. Every part is optional; an empty
+/// section leaves the decision to sanctions confidence alone.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PolicyRules {
+    /// Allowed jurisdictions (ISO codes). Empty allows any.
+    #[serde(default)]
+    pub countries: Vec<String>,
+    #[serde(default)]
+    pub blocked_countries: Vec<String>,
+    /// Jurisdiction to the risk score added for each subject based there.
+    #[serde(default)]
+    pub high_risk_jurisdictions: BTreeMap<String, u16>,
+    /// Confidence thresholds overriding the global ones per subject type.
+    #[serde(default)]
+    pub subject_thresholds: SubjectThresholds,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SubjectThresholds {
+    #[serde(default)]
+    pub entity: Option<ConfidenceThresholds>,
+    #[serde(default)]
+    pub counterparty: Option<ConfidenceThresholds>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ConfidenceThresholds {
+    pub review_confidence: u8,
+    pub fail_confidence: u8,
+}
+
+/// One reason the decision or risk score moved away from a plain pass.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyFactor {
+    pub code: String,
+    pub subject_id: Option<String>,
+    /// The decision this factor alone calls for.
+    pub outcome: ComplianceDecision,
+    pub risk_uplift: u16,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct PolicyEvaluation {
+    pub decision: ComplianceDecision,
+    pub risk_score: u16,
+    pub factors: Vec<PolicyFactor>,
+}
+
 pub fn load_policy_snapshot(path: &str) -> Result<PolicySnapshot, String> {
     let raw = std::fs::read_to_string(path)
         .map_err(|e| format!("failed to read policy snapshot: {e}"))?;
@@ -30,21 +104,248 @@ pub fn load_policy_snapshot(path: &str) -> Result<PolicySnapshot, String> {
         .map_err(|e| format!("failed to parse policy snapshot: {e}"))
 }
 
+/// Combines sanctions hits and jurisdiction rules into one decision. The
+/// decision is the most severe factor outcome; high-risk jurisdiction uplifts
+/// are added to that decision's risk score and escalate it when the total
+/// reaches the review or fail score. A wallet listed as a sanctioned address
+/// fails regardless of thresholds.
+///
+/// Per subject, jurisdiction outcomes and uplifts match policy-snapshot's
+/// `evaluate_rules` (both run `policy-snapshot/tests/data/jurisdiction_cases.json`).
 pub fn evaluate_intake_policy(
+    subjects: &[NormalizedSubject],
     screening: &ScreeningResult,
-    thresholds: &PolicyThresholds,
-) -> (ComplianceDecision, u16) {
-    let max_conf = screening
-        .hits
+    policy: &PolicySnapshot,
+) -> PolicyEvaluation {
+    evaluate_intake_policy_with_provider(subjects, screening, None, policy)
+}
+
+/// `evaluate_intake_policy` plus an external provider's assessment. The
+/// provider's overall score is a floor for the risk score; a subject it
+/// scores at the review or fail risk score adds a factor calling for that
+/// decision. A provider that could not be reached sends the intake to review
+/// rather than letting it pass on local screening alone.
+pub fn evaluate_intake_policy_with_provider(
+    subjects: &[NormalizedSubject],
+    screening: &ScreeningResult,
+    provider: Option<Result<&ProviderAssessment, &str>>,
+    policy: &PolicySnapshot,
+) -> PolicyEvaluation {
+    let thresholds = &policy.thresholds;
+    let rules = &policy.rules;
+    let mut factors = Vec::new();
+
+    for hit in &screening.hits {
+        let subject_id = Some(hit.subject_id.clone()).filter(|id| !id.is_empty());
+        if hit.kind == HitKind::WalletAddress {
+            factors.push(factor(
+                "SANCTIONED_WALLET_ADDRESS",
+                subject_id,
+                ComplianceDecision::Fail,
+                0,
+                format!(
+                    "wallet {} is listed for {}",
+                    hit.matched_name, hit.entry_name
+                ),
+            ));
+            continue;
+        }
+
+        let subject_type = subjects
+            .iter()
+            .find(|s| s.subject_id == hit.subject_id)
+            .map(|s| &s.subject_type);
+        let limits = confidence_thresholds(thresholds, rules, subject_type);
+        let outcome = if hit.confidence >= limits.fail_confidence {
+            ComplianceDecision::Fail
+        } else if hit.confidence >= limits.review_confidence {
+            ComplianceDecision::Review
+        } else {
+            continue;
+        };
+        factors.push(factor(
+            "SANCTIONS_NAME_MATCH",
+            subject_id,
+            outcome,
+            0,
+            format!(
+                "{} matched {} at confidence {}",
+                hit.matched_name, hit.entry_name, hit.confidence
+            ),
+        ));
+    }
+
+    for subject in subjects {
+        let subject_id = Some(subject.subject_id.clone());
+        let Some(jurisdiction) = subject
+            .jurisdiction
+            .as_deref()
+            .map(country_code)
+            .filter(|code| !code.is_empty())
+        else {
+            if !rules.countries.is_empty() {
+                factors.push(factor(
+                    "JURISDICTION_MISSING",
+                    subject_id,
+                    ComplianceDecision::Review,
+                    0,
+                    "no jurisdiction to check against the allowed countries".to_string(),
+                ));
+            }
+            continue;
+        };
+        if listed(&rules.blocked_countries, &jurisdiction) {
+            factors.push(factor(
+                "JURISDICTION_BLOCKED",
+                subject_id.clone(),
+                ComplianceDecision::Fail,
+                0,
+                format!("{jurisdiction} is a blocked jurisdiction"),
+            ));
+        } else if !rules.countries.is_empty() && !listed(&rules.countries, &jurisdiction) {
+            factors.push(factor(
+                "JURISDICTION_NOT_ALLOWED",
+                subject_id.clone(),
+                ComplianceDecision::Fail,
+                0,
+                format!("{jurisdiction} is not an allowed jurisdiction"),
+            ));
+        }
+        if let Some(uplift) = rules
+            .high_risk_jurisdictions
+            .iter()
+            .find(|(country, _)| country_code(country) == jurisdiction)
+            .map(|(_, uplift)| *uplift)
+        {
+            factors.push(factor(
+                "HIGH_RISK_JURISDICTION",
+                subject_id,
+                ComplianceDecision::Pass,
+                uplift,
+                format!("{jurisdiction} is a high-risk jurisdiction"),
+            ));
+        }
+    }
+
+    let mut provider_floor = 0;
+    match provider {
+        None => {}
+        Some(Err(e)) => factors.push(factor(
+            "PROVIDER_UNAVAILABLE",
+            None,
+            ComplianceDecision::Review,
+            0,
+            e.to_string(),
+        )),
+        Some(Ok(assessment)) => {
+            provider_floor = assessment.risk_score;
+            for subject in &assessment.subjects {
+                let outcome = if subject.risk_score >= thresholds.fail_risk_score {
+                    ComplianceDecision::Fail
+                } else if subject.risk_score >= thresholds.review_risk_score {
+                    ComplianceDecision::Review
+                } else if !subject.flags.is_empty() {
+                    ComplianceDecision::Pass
+                } else {
+                    continue;
+                };
+                let mut detail = format!(
+                    "{} scored {} at {}",
+                    assessment.provider, subject.subject_id, subject.risk_score
+                );
+                if !subject.flags.is_empty() {
+                    detail.push_str(&format!(" ({})", subject.flags.join(", ")));
+                }
+                factors.push(factor(
+                    "PROVIDER_RISK",
+                    Some(subject.subject_id.clone()),
+                    outcome,
+                    0,
+                    detail,
+                ));
+            }
+        }
+    }
+
+    let mut decision = factors
         .iter()
-        .map(|h| h.confidence)
-        .max()
-        .unwrap_or(0);
-    if max_conf >= thresholds.fail_confidence {
-        return (ComplianceDecision::Fail, thresholds.fail_risk_score);
+        .map(|f| f.outcome)
+        .max_by_key(|d| severity(*d))
+        .unwrap_or(ComplianceDecision::Pass);
+    let uplift = factors
+        .iter()
+        .fold(0u16, |sum, f| sum.saturating_add(f.risk_uplift));
+    let risk_score = base_risk_score(thresholds, decision)
+        .saturating_add(uplift)
+        .max(provider_floor)
+        .min(MAX_RISK_SCORE);
+    if risk_score >= thresholds.fail_risk_score {
+        decision = ComplianceDecision::Fail;
+    } else if risk_score >= thresholds.review_risk_score
+        && severity(decision) < severity(ComplianceDecision::Review)
+    {
+        decision = ComplianceDecision::Review;
     }
-    if max_conf >= thresholds.review_confidence {
-        return (ComplianceDecision::Review, thresholds.review_risk_score);
+
+    PolicyEvaluation {
+        decision,
+        risk_score,
+        factors,
     }
-    (ComplianceDecision::Pass, thresholds.pass_risk_score)
+}
+
+fn confidence_thresholds(
+    thresholds: &PolicyThresholds,
+    rules: &PolicyRules,
+    subject_type: Option<&SubjectType>,
+) -> ConfidenceThresholds {
+    let specific = match subject_type {
+        Some(SubjectType::Entity) => rules.subject_thresholds.entity,
+        Some(SubjectType::Counterparty) => rules.subject_thresholds.counterparty,
+        None => None,
+    };
+    specific.unwrap_or(ConfidenceThresholds {
+        review_confidence: thresholds.review_confidence,
+        fail_confidence: thresholds.fail_confidence,
+    })
+}
+
+fn base_risk_score(thresholds: &PolicyThresholds, decision: ComplianceDecision) -> u16 {
+    match decision {
+        ComplianceDecision::Pass => thresholds.pass_risk_score,
+        ComplianceDecision::Review => thresholds.review_risk_score,
+        ComplianceDecision::Fail => thresholds.fail_risk_score,
+    }
+}
+
+fn severity(decision: ComplianceDecision) -> u8 {
+    match decision {
+        ComplianceDecision::Pass => 0,
+        ComplianceDecision::Review => 1,
+        ComplianceDecision::Fail => 2,
+    }
+}
+
+fn factor(
+    code: &str,
+    subject_id: Option<String>,
+    outcome: ComplianceDecision,
+    risk_uplift: u16,
+    detail: String,
+) -> PolicyFactor {
+    PolicyFactor {
+        code: code.to_string(),
+        subject_id,
+        outcome,
+        risk_uplift,
+        detail,
+    }
+}
+
+fn country_code(value: &str) -> String {
+    value.trim().to_ascii_uppercase()
+}
+
+fn listed(countries: &[String], jurisdiction: &str) -> bool {
+    countries.iter().any(|c| country_code(c) == jurisdiction)
 }
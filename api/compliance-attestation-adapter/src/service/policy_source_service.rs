@@ -0,0 +1,202 @@
+use crate::service::policy_eval_service::{
+    ActivePolicy, PolicyRules, PolicySnapshot, PolicyThresholds, load_policy_snapshot,
+};
+use reqwest::Client;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+/// Where the adapter gets its effective policy. With a policy-snapshot URL
+/// the active policy is fetched from `/v1/policy/active`, verified against
+/// its `policy_hash` and cached for `cache_ttl`; without one the local
+/// snapshot file is read on every request (offline runs and tests).
+#[derive(Debug)]
+pub struct PolicySource {
+    remote: Option<RemotePolicy>,
+    file_path: String,
+    cache_ttl: Duration,
+    cached: Mutex<Option<CachedPolicy>>,
+}
+
+#[derive(Debug)]
+struct RemotePolicy {
+    base_url: String,
+    client: Client,
+}
+
+#[derive(Debug, Clone)]
+struct CachedPolicy {
+    snapshot: Arc<PolicySnapshot>,
+    fetched_at: Instant,
+}
+
+#[derive(Debug, Deserialize)]
+struct ActivePolicyResponse {
+    found: bool,
+    active_mapping: Option<ActiveMapping>,
+    snapshot: Option<SnapshotRecord>,
+    reason: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ActiveMapping {
+    policy_version: String,
+    policy_hash: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SnapshotRecord {
+    policy_version: String,
+    policy_hash: String,
+    canonical_rules_json: String,
+}
+
+/// The parts of a policy-snapshot rules bundle the adapter evaluates.
+#[derive(Debug, Deserialize)]
+struct RulesBundle {
+    thresholds: PolicyThresholds,
+    #[serde(flatten)]
+    rules: PolicyRules,
+}
+
+impl PolicySource {
+    pub fn new(
+        base_url: Option<&str>,
+        file_path: &str,
+        cache_ttl: Duration,
+    ) -> Result<Self, String> {
+        let remote = match base_url.map(str::trim).filter(|u| !u.is_empty()) {
+            Some(url) => Some(RemotePolicy {
+                base_url: url.trim_end_matches('/').to_string(),
+                client: Client::builder()
+                    .timeout(Duration::from_secs(5))
+                    .build()
+                    .map_err(|e| format!("failed to build http client: {e}"))?,
+            }),
+            None => None,
+        };
+        Ok(Self {
+            remote,
+            file_path: file_path.to_string(),
+            cache_ttl,
+            cached: Mutex::new(None),
+        })
+    }
+
+    pub fn is_remote(&self) -> bool {
+        self.remote.is_some()
+    }
+
+    /// The policy to evaluate the next request under. A fetch that fails
+    /// falls back to the last verified policy; a policy whose rules do not
+    /// hash to its `policy_hash` is never used.
+    pub async fn current(&self) -> Result<Arc<PolicySnapshot>, String> {
+        let Some(remote) = &self.remote else {
+            return load_policy_snapshot(&self.file_path).map(Arc::new);
+        };
+
+        let cached = self
+            .cached
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        if let Some(cached) = &cached
+            && cached.fetched_at.elapsed() < self.cache_ttl
+        {
+            return Ok(cached.snapshot.clone());
+        }
+
+        match fetch_active_policy(remote).await {
+            Ok(snapshot) => {
+                let snapshot = Arc::new(snapshot);
+                *self.cached.lock().unwrap_or_else(|e| e.into_inner()) = Some(CachedPolicy {
+                    snapshot: snapshot.clone(),
+                    fetched_at: Instant::now(),
+                });
+                Ok(snapshot)
+            }
+            Err(e) => match cached {
+                Some(cached) => {
+                    warn!(
+                        error = %e,
+                        version = %cached.snapshot.active.version,
+                        "policy fetch failed; serving last verified policy"
+                    );
+                    Ok(cached.snapshot)
+                }
+                None => Err(e),
+            },
+        }
+    }
+}
+
+async fn fetch_active_policy(remote: &RemotePolicy) -> Result<PolicySnapshot, String> {
+    let resp = remote
+        .client
+        .get(format!("{}/v1/policy/active", remote.base_url))
+        .send()
+        .await
+        .map_err(|e| format!("policy-snapshot request failed: {e}"))?;
+    if !resp.status().is_success() {
+        return Err(format!(
+            "policy-snapshot returned non-success status: {}",
+            resp.status()
+        ));
+    }
+    let payload = resp
+        .json::<ActivePolicyResponse>()
+        .await
+        .map_err(|e| format!("failed to parse policy-snapshot payload: {e}"))?;
+    verified_snapshot(payload)
+}
+
+fn verified_snapshot(payload: ActivePolicyResponse) -> Result<PolicySnapshot, String> {
+    let (true, Some(active), Some(snapshot)) =
+        (payload.found, payload.active_mapping, payload.snapshot)
+    else {
+        return Err(format!("no active policy: {}", payload.reason));
+    };
+    if active.policy_version != snapshot.policy_version
+        || !same_hash(&active.policy_hash, &snapshot.policy_hash)
+    {
+        return Err(format!(
+            "active mapping {}@{} does not match snapshot {}@{}",
+            active.policy_version,
+            active.policy_hash,
+            snapshot.policy_version,
+            snapshot.policy_hash
+        ));
+    }
+
+    let computed = hex::encode(Sha256::digest(snapshot.canonical_rules_json.as_bytes()));
+    if !same_hash(&computed, &snapshot.policy_hash) {
+        return Err(format!(
+            "policy {} rules hash to {computed}, expected {}",
+            snapshot.policy_version, snapshot.policy_hash
+        ));
+    }
+
+    let bundle =
+        serde_json::from_str::<RulesBundle>(&snapshot.canonical_rules_json).map_err(|e| {
+            format!(
+                "failed to parse policy {} rules: {e}",
+                snapshot.policy_version
+            )
+        })?;
+    Ok(PolicySnapshot {
+        active: ActivePolicy {
+            version: snapshot.policy_version,
+            hash: snapshot.policy_hash,
+        },
+        thresholds: bundle.thresholds,
+        rules: bundle.rules,
+    })
+}
+
+/// Hash equality ignoring a `0x` prefix and hex case.
+pub fn same_hash(a: &str, b: &str) -> bool {
+    let strip = |h: &str| h.trim().trim_start_matches("0x").to_ascii_lowercase();
+    strip(a) == strip(b)
+}
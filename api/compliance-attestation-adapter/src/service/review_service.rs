@@ -0,0 +1,131 @@
+use crate::module::compliance_attestation::model::{
+    AdjudicationRecord, ComplianceAttestationRecord,
+};
+use crate::module::compliance_attestation::schema::{AdjudicateReviewRequest, ComplianceDecision};
+use crate::service::attestation_hash_service::{
+    build_attestation_id, compute_superseding_attestation_hash,
+};
+use crate::service::policy_eval_service::PolicyFactor;
+use serde::Serialize;
+
+pub const REVIEW_PENDING: &str = "PENDING";
+pub const REVIEW_APPROVED: &str = "APPROVED";
+pub const REVIEW_REJECTED: &str = "REJECTED";
+
+/// Review status a freshly issued attestation starts with.
+pub fn initial_review_status(decision: ComplianceDecision) -> Option<String> {
+    matches!(decision, ComplianceDecision::Review).then(|| REVIEW_PENDING.to_string())
+}
+
+/// Canonical payload an internal caller signs with `INTERNAL_SIGNING_SECRET`
+/// to adjudicate a review as `reviewer`, sent as `x-internal-signature`.
+pub fn review_signing_payload(
+    attestation_id: &str,
+    reviewer: &str,
+    req: &AdjudicateReviewRequest,
+) -> String {
+    #[derive(Serialize)]
+    struct Payload<'a> {
+        attestation_id: &'a str,
+        reviewer: &'a str,
+        outcome: &'a str,
+        reason: &'a str,
+        risk_score: u16,
+    }
+    serde_json::to_string(&Payload {
+        attestation_id,
+        reviewer,
+        outcome: req.outcome.as_str(),
+        reason: &req.reason,
+        risk_score: req.risk_score,
+    })
+    .expect("review payload serialization should not fail")
+}
+
+/// Builds the attestation that replaces a pending REVIEW once an analyst has
+/// decided it: PASS on approval, FAIL on rejection. Subjects and policy carry
+/// over, the risk score becomes the analyst's, the decision is appended as a
+/// policy factor and the validity window restarts at adjudication time.
+pub fn supersede_reviewed_attestation(
+    original: &ComplianceAttestationRecord,
+    adjudication: AdjudicationRecord,
+    ttl_seconds: i64,
+) -> Result<ComplianceAttestationRecord, String> {
+    if original.review_status.as_deref() != Some(REVIEW_PENDING) {
+        return Err(format!(
+            "attestation {} is not pending review",
+            original.attestation_id
+        ));
+    }
+    if let Some(revocation) = &original.revocation {
+        return Err(format!(
+            "attestation {} was revoked: {}",
+            original.attestation_id, revocation.reason
+        ));
+    }
+    if let Some(next) = &original.superseded_by {
+        return Err(format!(
+            "attestation {} is already superseded by {next}",
+            original.attestation_id
+        ));
+    }
+
+    let (decision, review_status) = match adjudication.outcome.as_str() {
+        "APPROVE" => (ComplianceDecision::Pass, REVIEW_APPROVED),
+        "REJECT" => (ComplianceDecision::Fail, REVIEW_REJECTED),
+        other => return Err(format!("unknown review outcome {other}")),
+    };
+    let risk_score = adjudication
+        .risk_score
+        .ok_or_else(|| "adjudicated risk score is required".to_string())?;
+    let mut adjudication = adjudication;
+    adjudication.pre_review_risk_score = Some(original.risk_score);
+    let issued_at = adjudication.adjudicated_at;
+    let expires_at = issued_at
+        .checked_add(ttl_seconds)
+        .ok_or_else(|| "invalid expires_at value".to_string())?;
+    let attestation_hash = compute_superseding_attestation_hash(
+        original,
+        decision,
+        issued_at,
+        expires_at,
+        &adjudication,
+    );
+
+    let mut policy_factors = original.policy_factors.clone();
+    policy_factors.push(PolicyFactor {
+        code: format!("ANALYST_{review_status}"),
+        subject_id: None,
+        outcome: decision,
+        risk_uplift: 0,
+        detail: format!(
+            "{}: {} (risk {} -> {risk_score})",
+            adjudication.reviewer, adjudication.reason, original.risk_score
+        ),
+    });
+
+    Ok(ComplianceAttestationRecord {
+        attestation_id: build_attestation_id(&attestation_hash),
+        request_id: original.request_id.clone(),
+        workflow_run_id: original.workflow_run_id.clone(),
+        policy_version: original.policy_version.clone(),
+        policy_hash: original.policy_hash.clone(),
+        decision: decision.as_str().to_string(),
+        risk_score,
+        policy_factors,
+        attestation_hash,
+        issued_at,
+        expires_at,
+        sanctions_hit_count: original.sanctions_hit_count,
+        normalized_subjects: original.normalized_subjects.clone(),
+        fx_quote: original.fx_quote.clone(),
+        review_status: Some(review_status.to_string()),
+        supersedes: Some(original.attestation_id.clone()),
+        superseded_by: None,
+        adjudication: Some(adjudication),
+        signature: None,
+        signing_key_id: None,
+        revocation: None,
+        provider_refs: original.provider_refs.clone(),
+    })
+}
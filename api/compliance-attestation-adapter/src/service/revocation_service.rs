@@ -0,0 +1,74 @@
+use crate::module::compliance_attestation::model::{ComplianceAttestationRecord, RevocationRecord};
+use crate::module::compliance_attestation::schema::{
+    AttestationStatus, ComplianceDecision, RevocationReason,
+};
+use crate::service::policy_eval_service::{PolicyFactor, PolicySnapshot, evaluate_intake_policy};
+use crate::service::sanctions_service::{SanctionsIndex, screen_subjects};
+
+/// `revoked_by` for revocations raised by re-screening after a list update.
+pub const SANCTIONS_RESCREEN_ACTOR: &str = "sanctions-rescreen";
+
+const SANCTIONS_FACTOR_CODES: [&str; 2] = ["SANCTIONED_WALLET_ADDRESS", "SANCTIONS_NAME_MATCH"];
+
+/// Status of a stored attestation at `now`. Revocation wins over
+/// supersession, which wins over expiry: a revoked attestation stays revoked
+/// after it would have expired.
+pub fn attestation_status(record: &ComplianceAttestationRecord, now: i64) -> AttestationStatus {
+    if record.revocation.is_some() {
+        AttestationStatus::Revoked
+    } else if record.superseded_by.is_some() {
+        AttestationStatus::Superseded
+    } else if record.expires_at <= now {
+        AttestationStatus::Expired
+    } else {
+        AttestationStatus::Active
+    }
+}
+
+/// Re-screens an active, non-failing attestation's subjects against a newer
+/// sanctions list. Returns the revocation to record when the list now calls
+/// for a FAIL on a subject the attestation did not already fail on; name
+/// matches use the thresholds of `policy`, normally the one in force.
+pub fn sanctions_revocation(
+    record: &ComplianceAttestationRecord,
+    index: &SanctionsIndex,
+    policy: &PolicySnapshot,
+    now: i64,
+) -> Option<RevocationRecord> {
+    if attestation_status(record, now) != AttestationStatus::Active
+        || record.decision == ComplianceDecision::Fail.as_str()
+    {
+        return None;
+    }
+
+    let screening = screen_subjects(&record.normalized_subjects, index);
+    let evaluation = evaluate_intake_policy(&record.normalized_subjects, &screening, policy);
+    let new_failures = evaluation
+        .factors
+        .iter()
+        .filter(|f| is_sanctions_failure(f))
+        .filter(|f| {
+            !record
+                .policy_factors
+                .iter()
+                .any(|known| is_sanctions_failure(known) && known.subject_id == f.subject_id)
+        })
+        .map(|f| f.detail.as_str())
+        .collect::<Vec<_>>();
+    if new_failures.is_empty() {
+        return None;
+    }
+
+    Some(RevocationRecord {
+        reason: RevocationReason::SanctionsMatch.as_str().to_string(),
+        detail: new_failures.join("; "),
+        revoked_by: SANCTIONS_RESCREEN_ACTOR.to_string(),
+        revoked_at: now,
+        sanctions_version: Some(index.version().to_string()),
+    })
+}
+
+fn is_sanctions_failure(factor: &PolicyFactor) -> bool {
+    SANCTIONS_FACTOR_CODES.contains(&factor.code.as_str())
+        && matches!(factor.outcome, ComplianceDecision::Fail)
+}
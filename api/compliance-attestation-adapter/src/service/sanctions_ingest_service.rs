@@ -0,0 +1,491 @@
+use crate::service::name_match_service::fold_name;
+use crate::service::sanctions_service::{DigitalCurrencyAddress, SanctionsEntry};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// Reads one published sanctions list into [`SanctionsEntry`] values, one per
+/// listed party with its aliases folded in.
+pub trait SanctionsListParser {
+    fn source(&self) -> &'static str;
+    fn parse(&self) -> Result<Vec<SanctionsEntry>, String>;
+}
+
+/// OFAC SDN list as the legacy CSV trio: `sdn.csv` (names and programs),
+/// `alt.csv` (aliases) and `add.csv` (addresses). The CSVs carry no date,
+/// so the SDN file's mtime stands in (`curl -R` keeps the server's).
+pub struct OfacParser {
+    pub sdn_path: PathBuf,
+    pub alt_path: Option<PathBuf>,
+    pub add_path: Option<PathBuf>,
+}
+
+/// EU consolidated financial sanctions list, XML export format 1.1.
+pub struct EuConsolidatedParser {
+    pub path: PathBuf,
+}
+
+/// UN Security Council consolidated list XML.
+pub struct UnConsolidatedParser {
+    pub path: PathBuf,
+}
+
+/// UK HM Treasury (OFSI) consolidated list CSV, 2022 format: one row per
+/// name variant, grouped by `Group ID`.
+pub struct UkHmtParser {
+    pub path: PathBuf,
+}
+
+impl SanctionsListParser for OfacParser {
+    fn source(&self) -> &'static str {
+        "OFAC"
+    }
+
+    fn parse(&self) -> Result<Vec<SanctionsEntry>, String> {
+        let published_at = file_date(&self.sdn_path);
+        let mut aliases = match &self.alt_path {
+            Some(path) => ofac_aliases(path)?,
+            None => HashMap::new(),
+        };
+        let mut addresses = match &self.add_path {
+            Some(path) => ofac_addresses(path)?,
+            None => HashMap::new(),
+        };
+
+        let mut entries = Vec::new();
+        for rec in read_csv_rows(&self.sdn_path)? {
+            let (Some(ent_num), Some(name)) = (ofac_field(&rec, 0), ofac_field(&rec, 1)) else {
+                continue;
+            };
+            let (jurisdiction, address) = addresses.remove(&ent_num).unwrap_or_default();
+            entries.push(SanctionsEntry {
+                source: self.source().to_string(),
+                program: ofac_field(&rec, 3).unwrap_or_else(|| "SDN".to_string()),
+                name,
+                jurisdiction,
+                address,
+                aliases: aliases.remove(&ent_num).unwrap_or_default(),
+                source_id: Some(ent_num),
+                published_at: published_at.clone(),
+                digital_currency_addresses: ofac_field(&rec, 11)
+                    .map(|remarks| ofac_digital_currency_addresses(&remarks))
+                    .unwrap_or_default(),
+            });
+        }
+        Ok(entries)
+    }
+}
+
+impl SanctionsListParser for EuConsolidatedParser {
+    fn source(&self) -> &'static str {
+        "EU"
+    }
+
+    fn parse(&self) -> Result<Vec<SanctionsEntry>, String> {
+        let raw = read_text(&self.path)?;
+        let doc = roxmltree::Document::parse(&raw)
+            .map_err(|e| format!("failed to parse EU list xml: {e}"))?;
+        let published_at = doc.root_element().attribute("generationDate").map(iso_date);
+
+        let mut entries = Vec::new();
+        for entity in doc
+            .descendants()
+            .filter(|n| n.has_tag_name("sanctionEntity"))
+        {
+            let mut names = children(entity, "nameAlias")
+                .filter_map(|n| non_empty(n.attribute("wholeName")))
+                .collect::<Vec<_>>();
+            if names.is_empty() {
+                continue;
+            }
+            let name = names.remove(0);
+            let address = children(entity, "address").next();
+            let jurisdiction = address
+                .and_then(|a| non_empty(a.attribute("countryIso2Code")))
+                .or_else(|| {
+                    children(entity, "citizenship")
+                        .find_map(|c| non_empty(c.attribute("countryIso2Code")))
+                });
+            entries.push(SanctionsEntry {
+                source: self.source().to_string(),
+                program: children(entity, "regulation")
+                    .find_map(|r| non_empty(r.attribute("programme")))
+                    .unwrap_or_else(|| "EU".to_string()),
+                name,
+                jurisdiction,
+                address: address.and_then(|a| {
+                    join_parts([a.attribute("street"), a.attribute("city")].into_iter())
+                }),
+                aliases: names,
+                source_id: non_empty(entity.attribute("logicalId")),
+                published_at: published_at.clone(),
+                digital_currency_addresses: Vec::new(),
+            });
+        }
+        Ok(entries)
+    }
+}
+
+impl SanctionsListParser for UnConsolidatedParser {
+    fn source(&self) -> &'static str {
+        "UN"
+    }
+
+    fn parse(&self) -> Result<Vec<SanctionsEntry>, String> {
+        let raw = read_text(&self.path)?;
+        let doc = roxmltree::Document::parse(&raw)
+            .map_err(|e| format!("failed to parse UN list xml: {e}"))?;
+        let published_at = doc.root_element().attribute("dateGenerated").map(iso_date);
+
+        let mut entries = Vec::new();
+        for (tag, alias_tag, address_tag) in [
+            ("INDIVIDUAL", "INDIVIDUAL_ALIAS", "INDIVIDUAL_ADDRESS"),
+            ("ENTITY", "ENTITY_ALIAS", "ENTITY_ADDRESS"),
+        ] {
+            for party in doc.descendants().filter(|n| n.has_tag_name(tag)) {
+                let name_parts = ["FIRST_NAME", "SECOND_NAME", "THIRD_NAME", "FOURTH_NAME"]
+                    .into_iter()
+                    .map(|part| child_text(party, part));
+                let Some(name) = join_words(name_parts) else {
+                    continue;
+                };
+                let mut aliases = children(party, alias_tag)
+                    .filter_map(|a| child_text(a, "ALIAS_NAME"))
+                    .collect::<Vec<_>>();
+                aliases.extend(child_text(party, "NAME_ORIGINAL_SCRIPT"));
+                let address = children(party, address_tag).next();
+                let jurisdiction = address.and_then(|a| child_text(a, "COUNTRY")).or_else(|| {
+                    children(party, "NATIONALITY").find_map(|n| child_text(n, "VALUE"))
+                });
+                entries.push(SanctionsEntry {
+                    source: self.source().to_string(),
+                    program: child_text(party, "UN_LIST_TYPE").unwrap_or_else(|| "UN".to_string()),
+                    name,
+                    jurisdiction,
+                    address: address.and_then(|a| {
+                        join_parts(
+                            ["STREET", "CITY"]
+                                .into_iter()
+                                .map(|part| child_text(a, part)),
+                        )
+                    }),
+                    aliases,
+                    source_id: child_text(party, "REFERENCE_NUMBER")
+                        .or_else(|| child_text(party, "DATAID")),
+                    published_at: published_at.clone(),
+                    digital_currency_addresses: Vec::new(),
+                });
+            }
+        }
+        Ok(entries)
+    }
+}
+
+impl SanctionsListParser for UkHmtParser {
+    fn source(&self) -> &'static str {
+        "UK_HMT"
+    }
+
+    fn parse(&self) -> Result<Vec<SanctionsEntry>, String> {
+        let mut rows = read_csv_rows(&self.path)?.into_iter();
+        let published_at = rows
+            .next()
+            .filter(|rec| rec.get(0).is_some_and(|c| c.trim() == "Last Updated"))
+            .and_then(|rec| rec.get(1).and_then(uk_date))
+            .ok_or("UK HMT list is missing its `Last Updated` line")?;
+        let header = rows.next().ok_or("UK HMT list is missing its header row")?;
+        let column = |name: &str| {
+            header
+                .iter()
+                .position(|h| h.trim() == name)
+                .ok_or_else(|| format!("UK HMT list has no `{name}` column"))
+        };
+        let given = ["Name 1", "Name 2", "Name 3", "Name 4", "Name 5", "Name 6"]
+            .into_iter()
+            .map(column)
+            .collect::<Result<Vec<_>, _>>()?;
+        let (group_id, alias_type, regime, country) = (
+            column("Group ID")?,
+            column("Alias Type")?,
+            column("Regime")?,
+            column("Country")?,
+        );
+        let address_cols = ["Address 1", "Address 2", "Address 3", "Address 4"]
+            .into_iter()
+            .filter_map(|name| column(name).ok())
+            .collect::<Vec<_>>();
+
+        let mut by_group: Vec<SanctionsEntry> = Vec::new();
+        let mut positions: HashMap<String, usize> = HashMap::new();
+        for rec in rows {
+            let cell = |i: usize| non_empty(rec.get(i));
+            let (Some(id), Some(name)) =
+                (cell(group_id), join_words(given.iter().map(|i| cell(*i))))
+            else {
+                continue;
+            };
+            let primary = cell(alias_type).is_some_and(|t| t.eq_ignore_ascii_case("Primary name"));
+            let Some(&pos) = positions.get(&id) else {
+                positions.insert(id.clone(), by_group.len());
+                by_group.push(SanctionsEntry {
+                    source: self.source().to_string(),
+                    program: cell(regime).unwrap_or_else(|| "UK".to_string()),
+                    name,
+                    jurisdiction: cell(country),
+                    address: join_parts(address_cols.iter().map(|i| cell(*i))),
+                    aliases: Vec::new(),
+                    source_id: Some(id),
+                    published_at: Some(published_at.clone()),
+                    digital_currency_addresses: Vec::new(),
+                });
+                continue;
+            };
+            let entry = &mut by_group[pos];
+            if primary {
+                let previous = std::mem::replace(&mut entry.name, name);
+                entry.aliases.push(previous);
+                entry.jurisdiction = cell(country).or(entry.jurisdiction.take());
+                entry.address =
+                    join_parts(address_cols.iter().map(|i| cell(*i))).or(entry.address.take());
+            } else {
+                entry.aliases.push(name);
+            }
+        }
+        Ok(by_group)
+    }
+}
+
+/// Combines parsed lists into one. Rows sharing a source and source id (or,
+/// without an id, a folded primary name) collapse into one entry with the
+/// union of their aliases. The same party on two different lists stays two
+/// entries, since each carries its own program and publication date.
+pub fn merge_lists(lists: Vec<Vec<SanctionsEntry>>) -> Vec<SanctionsEntry> {
+    let mut merged: Vec<SanctionsEntry> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    for entry in lists.into_iter().flatten() {
+        let key = entry_key(&entry);
+        match positions.get(&key) {
+            Some(&pos) => absorb(&mut merged[pos], entry),
+            None => {
+                positions.insert(key, merged.len());
+                let mut entry = entry;
+                entry.aliases = dedup_aliases(&entry.name, std::mem::take(&mut entry.aliases));
+                merged.push(entry);
+            }
+        }
+    }
+    merged
+}
+
+pub fn entry_key(entry: &SanctionsEntry) -> String {
+    match &entry.source_id {
+        Some(id) => format!("{}:{id}", entry.source),
+        None => format!("{}:~{}", entry.source, fold_name(&entry.name).compact),
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DiffEntry {
+    pub key: String,
+    pub source: String,
+    pub name: String,
+}
+
+/// What changed between two refreshes, keyed by [`entry_key`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SanctionsDiffReport {
+    pub previous_count: usize,
+    pub current_count: usize,
+    pub added: Vec<DiffEntry>,
+    pub removed: Vec<DiffEntry>,
+    pub current_count_by_source: BTreeMap<String, usize>,
+}
+
+pub fn diff_lists(previous: &[SanctionsEntry], current: &[SanctionsEntry]) -> SanctionsDiffReport {
+    let (before, after) = (keyed(previous), keyed(current));
+    let describe = |(key, entry): (&String, &&SanctionsEntry)| DiffEntry {
+        key: key.clone(),
+        source: entry.source.clone(),
+        name: entry.name.clone(),
+    };
+    let mut current_count_by_source = BTreeMap::new();
+    for entry in current {
+        *current_count_by_source
+            .entry(entry.source.clone())
+            .or_insert(0) += 1;
+    }
+    SanctionsDiffReport {
+        previous_count: previous.len(),
+        current_count: current.len(),
+        added: after
+            .iter()
+            .filter(|(key, _)| !before.contains_key(*key))
+            .map(describe)
+            .collect(),
+        removed: before
+            .iter()
+            .filter(|(key, _)| !after.contains_key(*key))
+            .map(describe)
+            .collect(),
+        current_count_by_source,
+    }
+}
+
+fn keyed(entries: &[SanctionsEntry]) -> BTreeMap<String, &SanctionsEntry> {
+    entries.iter().map(|e| (entry_key(e), e)).collect()
+}
+
+fn absorb(target: &mut SanctionsEntry, other: SanctionsEntry) {
+    let mut aliases = std::mem::take(&mut target.aliases);
+    aliases.push(other.name);
+    aliases.extend(other.aliases);
+    target.aliases = dedup_aliases(&target.name, aliases);
+    if !target.program.split("; ").any(|p| p == other.program) {
+        target.program = format!("{}; {}", target.program, other.program);
+    }
+    target.jurisdiction = target.jurisdiction.take().or(other.jurisdiction);
+    target.address = target.address.take().or(other.address);
+    target.published_at = target.published_at.take().or(other.published_at);
+    for listed in other.digital_currency_addresses {
+        if !target.digital_currency_addresses.contains(&listed) {
+            target.digital_currency_addresses.push(listed);
+        }
+    }
+}
+
+/// Drops aliases that fold to the primary name or to an earlier alias.
+fn dedup_aliases(primary: &str, aliases: Vec<String>) -> Vec<String> {
+    let mut seen = HashSet::from([fold_name(primary).compact]);
+    aliases
+        .into_iter()
+        .filter(|alias| seen.insert(fold_name(alias).compact))
+        .collect()
+}
+
+fn ofac_aliases(path: &Path) -> Result<HashMap<String, Vec<String>>, String> {
+    let mut aliases: HashMap<String, Vec<String>> = HashMap::new();
+    for rec in read_csv_rows(path)? {
+        if let (Some(ent_num), Some(alt_name)) = (ofac_field(&rec, 0), ofac_field(&rec, 3)) {
+            aliases.entry(ent_num).or_default().push(alt_name);
+        }
+    }
+    Ok(aliases)
+}
+
+/// `(country, street + city)` of an OFAC entry.
+type OfacAddress = (Option<String>, Option<String>);
+
+/// First address with a country per entry.
+fn ofac_addresses(path: &Path) -> Result<HashMap<String, OfacAddress>, String> {
+    let mut addresses = HashMap::new();
+    for rec in read_csv_rows(path)? {
+        let (Some(ent_num), Some(country)) = (ofac_field(&rec, 0), ofac_field(&rec, 4)) else {
+            continue;
+        };
+        let address = join_parts([ofac_field(&rec, 2), ofac_field(&rec, 3)].into_iter());
+        addresses.entry(ent_num).or_insert((Some(country), address));
+    }
+    Ok(addresses)
+}
+
+/// OFAC marks empty fields with `-0-`.
+/// SDN remarks carry listed wallets as `Digital Currency Address - XBT <addr>;`
+/// (or `alt. Digital Currency Address - ...`) among other `;`-separated notes.
+fn ofac_digital_currency_addresses(remarks: &str) -> Vec<DigitalCurrencyAddress> {
+    remarks
+        .split(';')
+        .filter_map(|part| {
+            let part = part.trim();
+            let part = part.strip_prefix("alt. ").unwrap_or(part);
+            let rest = part.strip_prefix("Digital Currency Address - ")?;
+            let (currency, address) = rest.trim().split_once(' ')?;
+            let address = address.trim().trim_end_matches('.');
+            (!address.is_empty()).then(|| DigitalCurrencyAddress {
+                currency: currency.to_string(),
+                address: address.to_string(),
+            })
+        })
+        .collect()
+}
+
+fn ofac_field(rec: &csv::StringRecord, idx: usize) -> Option<String> {
+    non_empty(rec.get(idx)).filter(|v| v != "-0-")
+}
+
+fn read_csv_rows(path: &Path) -> Result<Vec<csv::StringRecord>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_path(path)
+        .map_err(|e| format!("failed to open `{}`: {e}", path.display()))?;
+    Ok(reader.records().filter_map(Result::ok).collect())
+}
+
+fn read_text(path: &Path) -> Result<String, String> {
+    std::fs::read_to_string(path).map_err(|e| format!("failed to read `{}`: {e}", path.display()))
+}
+
+fn children<'a, 'input>(
+    node: roxmltree::Node<'a, 'input>,
+    tag: &'static str,
+) -> impl Iterator<Item = roxmltree::Node<'a, 'input>> {
+    node.children().filter(move |n| n.has_tag_name(tag))
+}
+
+fn child_text(node: roxmltree::Node<'_, '_>, tag: &'static str) -> Option<String> {
+    children(node, tag).find_map(|n| non_empty(n.text()))
+}
+
+fn non_empty(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+fn join_words(parts: impl Iterator<Item = Option<String>>) -> Option<String> {
+    let words = parts.flatten().collect::<Vec<_>>();
+    (!words.is_empty()).then(|| words.join(" "))
+}
+
+fn join_parts<S: AsRef<str>>(parts: impl Iterator<Item = Option<S>>) -> Option<String> {
+    let parts = parts
+        .flatten()
+        .filter_map(|p| non_empty(Some(p.as_ref())))
+        .collect::<Vec<_>>();
+    (!parts.is_empty()).then(|| parts.join(", "))
+}
+
+/// `2024-05-01T10:00:00` (or a bare date) to `2024-05-01`.
+fn iso_date(value: &str) -> String {
+    value.trim().chars().take(10).collect()
+}
+
+/// `dd/mm/yyyy` to `yyyy-mm-dd`.
+fn uk_date(value: &str) -> Option<String> {
+    let mut parts = value.trim().split('/');
+    let (day, month, year) = (parts.next()?, parts.next()?, parts.next()?);
+    let valid = |s: &str, len: usize| s.len() == len && s.chars().all(|c| c.is_ascii_digit());
+    (valid(day, 2) && valid(month, 2) && valid(year, 4)).then(|| format!("{year}-{month}-{day}"))
+}
+
+fn file_date(path: &Path) -> Option<String> {
+    let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+    let days = modified.duration_since(UNIX_EPOCH).ok()?.as_secs() / 86_400;
+    Some(civil_date(days as i64))
+}
+
+/// Days since 1970-01-01 to a proleptic Gregorian `YYYY-MM-DD`.
+fn civil_date(days: i64) -> String {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
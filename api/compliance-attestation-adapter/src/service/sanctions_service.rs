@@ -1,6 +1,12 @@
 use crate::module::compliance_attestation::model::NormalizedSubject;
+use crate::service::name_match_service::{FoldedName, fold_name, name_confidence};
+use crate::service::wallet_screening_service::canonical_wallet_address;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+/// Weaker name similarities are not reported as hits at all.
+pub const MIN_HIT_CONFIDENCE: u8 = 60;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SanctionsEntry {
@@ -9,12 +15,51 @@ pub struct SanctionsEntry {
     pub name: String,
     pub jurisdiction: Option<String>,
     pub address: Option<String>,
+    /// Alternate spellings and AKAs published with the entry.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    /// The entry's reference in its source list (OFAC `ent_num`, EU
+    /// logical id, UN reference number, UK group id).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_id: Option<String>,
+    /// Publication date (`YYYY-MM-DD`) of the list the entry was read from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub published_at: Option<String>,
+    /// Listed crypto addresses (OFAC "Digital Currency Address" remarks).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub digital_currency_addresses: Vec<DigitalCurrencyAddress>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DigitalCurrencyAddress {
+    /// Ticker as published, e.g. `XBT`, `ETH`, `USDT`.
+    pub currency: String,
+    pub address: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScreeningHit {
+    /// The screened subject the hit belongs to.
+    #[serde(default)]
+    pub subject_id: String,
     pub entry_name: String,
+    /// The primary name or alias that produced the confidence, or the listed
+    /// address for a wallet hit.
+    #[serde(default)]
+    pub matched_name: String,
     pub confidence: u8,
+    #[serde(default)]
+    pub kind: HitKind,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum HitKind {
+    /// Fuzzy name match; graded by confidence.
+    #[default]
+    Name,
+    /// Exact match of a subject's wallet against a listed address.
+    WalletAddress,
 }
 
 #[derive(Debug, Clone)]
@@ -26,42 +71,167 @@ pub struct ScreeningResult {
 pub fn load_sanctions_entries(path: &str) -> Result<Vec<SanctionsEntry>, String> {
     let raw =
         std::fs::read_to_string(path).map_err(|e| format!("failed to read sanctions file: {e}"))?;
-    serde_json::from_str::<Vec<SanctionsEntry>>(&raw)
+    parse_sanctions_entries(raw.as_bytes())
+}
+
+pub fn parse_sanctions_entries(raw: &[u8]) -> Result<Vec<SanctionsEntry>, String> {
+    serde_json::from_slice::<Vec<SanctionsEntry>>(raw)
         .map_err(|e| format!("failed to parse sanctions file: {e}"))
 }
 
-pub fn screen_subjects(
-    subjects: &[NormalizedSubject],
-    entries: &[SanctionsEntry],
-) -> ScreeningResult {
-    let mut hits = Vec::new();
+/// Sanctions entries with every primary name and alias folded once and
+/// looked up through an inverted index of their blocking keys.
+pub struct SanctionsIndex {
+    version: String,
+    entries: Vec<SanctionsEntry>,
+    /// Folded names of entry `i` are `names[name_ranges[i].clone()]`.
+    names: Vec<(usize, FoldedName)>,
+    name_ranges: Vec<std::ops::Range<usize>>,
+    postings: HashMap<String, Vec<u32>>,
+    /// Canonical listed address to the entries listing it.
+    wallets: HashMap<String, Vec<u32>>,
+}
 
-    for subject in subjects {
-        let candidates = candidate_strings(subject);
-        for entry in entries {
-            let entry_name = normalize(&entry.name);
-            let mut best = 0u8;
-
-            for candidate in &candidates {
-                if candidate.is_empty() {
-                    continue;
+impl SanctionsIndex {
+    pub fn build(entries: Vec<SanctionsEntry>, version: impl Into<String>) -> Self {
+        let mut names = Vec::new();
+        let mut name_ranges = Vec::with_capacity(entries.len());
+        let mut postings: HashMap<String, Vec<u32>> = HashMap::new();
+        let mut wallets: HashMap<String, Vec<u32>> = HashMap::new();
+        for (entry_idx, entry) in entries.iter().enumerate() {
+            let start = names.len();
+            let all_names = std::iter::once(&entry.name).chain(&entry.aliases);
+            for (name_idx, name) in all_names.enumerate() {
+                let folded = fold_name(name);
+                for key in folded.blocking_keys() {
+                    let posting = postings.entry(key).or_default();
+                    if posting.last() != Some(&(entry_idx as u32)) {
+                        posting.push(entry_idx as u32);
+                    }
                 }
-                if *candidate == entry_name {
-                    best = best.max(100);
-                    continue;
-                }
-                if candidate.contains(&entry_name) || entry_name.contains(candidate) {
-                    best = best.max(80);
+                names.push((name_idx, folded));
+            }
+            name_ranges.push(start..names.len());
+            for listed in &entry.digital_currency_addresses {
+                if let Some(canonical) = canonical_wallet_address(&listed.address) {
+                    let posting = wallets.entry(canonical).or_default();
+                    if posting.last() != Some(&(entry_idx as u32)) {
+                        posting.push(entry_idx as u32);
+                    }
                 }
             }
+        }
+        Self {
+            version: version.into(),
+            entries,
+            names,
+            name_ranges,
+            postings,
+            wallets,
+        }
+    }
+
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    pub fn entry_count(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn entries(&self) -> &[SanctionsEntry] {
+        &self.entries
+    }
+
+    /// Entries sharing at least one blocking key with any candidate, in list
+    /// order.
+    fn candidate_entries(&self, candidates: &[FoldedName]) -> Vec<usize> {
+        let mut found = candidates
+            .iter()
+            .flat_map(FoldedName::blocking_keys)
+            .filter_map(|key| self.postings.get(&key))
+            .flatten()
+            .map(|idx| *idx as usize)
+            .collect::<Vec<_>>();
+        found.sort_unstable();
+        found.dedup();
+        found
+    }
+
+    fn listed_name(&self, entry_idx: usize, name_idx: usize) -> &str {
+        let entry = &self.entries[entry_idx];
+        match name_idx {
+            0 => &entry.name,
+            n => &entry.aliases[n - 1],
+        }
+    }
+
+    /// Exact hits for an already canonical wallet address.
+    fn wallet_hits(&self, subject_id: &str, wallet: &str) -> Vec<ScreeningHit> {
+        let Some(posting) = self.wallets.get(wallet) else {
+            return Vec::new();
+        };
+        posting
+            .iter()
+            .map(|&entry_idx| ScreeningHit {
+                subject_id: subject_id.to_string(),
+                entry_name: self.entries[entry_idx as usize].name.clone(),
+                matched_name: wallet.to_string(),
+                confidence: 100,
+                kind: HitKind::WalletAddress,
+            })
+            .collect()
+    }
+}
+
+impl std::fmt::Debug for SanctionsIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SanctionsIndex")
+            .field("version", &self.version)
+            .field("entry_count", &self.entries.len())
+            .field("name_count", &self.names.len())
+            .finish()
+    }
+}
+
+/// Scores every subject name against the listed names and aliases that share
+/// a blocking key with it, keeping the best match per entry when it reaches
+/// [`MIN_HIT_CONFIDENCE`]. A subject wallet equal to a listed digital currency
+/// address is an exact [`HitKind::WalletAddress`] hit.
+pub fn screen_subjects(subjects: &[NormalizedSubject], index: &SanctionsIndex) -> ScreeningResult {
+    let mut hits = Vec::new();
+
+    for subject in subjects {
+        let candidates = candidate_names(subject);
+        for entry_idx in index.candidate_entries(&candidates) {
+            let best = index.names[index.name_ranges[entry_idx].clone()]
+                .iter()
+                .map(|(name_idx, folded)| {
+                    let confidence = candidates
+                        .iter()
+                        .map(|candidate| name_confidence(candidate, folded))
+                        .max()
+                        .unwrap_or(0);
+                    (confidence, *name_idx)
+                })
+                .max_by_key(|(confidence, _)| *confidence);
 
-            if best > 0 {
+            if let Some((confidence, name_idx)) = best
+                && confidence >= MIN_HIT_CONFIDENCE
+            {
                 hits.push(ScreeningHit {
-                    entry_name: entry.name.clone(),
-                    confidence: best,
+                    subject_id: subject.subject_id.clone(),
+                    entry_name: index.entries[entry_idx].name.clone(),
+                    matched_name: index.listed_name(entry_idx, name_idx).to_string(),
+                    confidence,
+                    kind: HitKind::Name,
                 });
             }
         }
+
+        if let Some(wallet) = subject.wallet_address.as_deref() {
+            hits.extend(index.wallet_hits(&subject.subject_id, wallet));
+        }
     }
 
     let match_digest = compute_match_digest(&hits);
@@ -71,7 +241,10 @@ pub fn screen_subjects(
 fn compute_match_digest(hits: &[ScreeningHit]) -> String {
     let mut stable = hits
         .iter()
-        .map(|h| format!("{}:{}", h.entry_name, h.confidence))
+        .map(|h| match h.kind {
+            HitKind::Name => format!("{}:{}", h.entry_name, h.confidence),
+            HitKind::WalletAddress => format!("{}:wallet:{}", h.entry_name, h.matched_name),
+        })
         .collect::<Vec<_>>();
     stable.sort();
 
@@ -83,21 +256,16 @@ fn compute_match_digest(hits: &[ScreeningHit]) -> String {
     hex::encode(hasher.finalize())
 }
 
-fn candidate_strings(subject: &NormalizedSubject) -> Vec<String> {
-    vec![
-        normalize(&subject.subject_id),
-        normalize(subject.legal_name.as_deref().unwrap_or_default()),
-        normalize(subject.address.as_deref().unwrap_or_default()),
+/// Names the subject is known by. The address is not screened: fuzzy
+/// matching street words against party names only produces noise.
+fn candidate_names(subject: &NormalizedSubject) -> Vec<FoldedName> {
+    [
+        Some(subject.subject_id.as_str()),
+        subject.legal_name.as_deref(),
     ]
-}
-
-fn normalize(input: &str) -> String {
-    input
-        .to_ascii_lowercase()
-        .chars()
-        .filter(|c| c.is_ascii_alphanumeric() || c.is_ascii_whitespace())
-        .collect::<String>()
-        .split_whitespace()
-        .collect::<Vec<_>>()
-        .join(" ")
+    .into_iter()
+    .flatten()
+    .map(fold_name)
+    .filter(|name| !name.is_empty())
+    .collect()
 }
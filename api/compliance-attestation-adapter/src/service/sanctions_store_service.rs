@@ -0,0 +1,176 @@
+use crate::service::sanctions_service::{SanctionsIndex, parse_sanctions_entries};
+use sha2::{Digest, Sha256};
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::watch;
+use tracing::{info, warn};
+
+/// Size and mtime of the data file when it was last read. A change in either
+/// triggers a re-read; the content hash then decides whether to rebuild.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileStamp {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+#[derive(Debug, Default)]
+struct StoreState {
+    index: Option<Arc<SanctionsIndex>>,
+    loaded_at: Option<i64>,
+    stamp: Option<FileStamp>,
+    last_error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SanctionsStatus {
+    pub version: Option<String>,
+    pub entry_count: usize,
+    pub loaded_at: Option<i64>,
+    pub last_error: Option<String>,
+}
+
+/// In-memory sanctions index for one data file. Readers take an `Arc` to the
+/// current index, so a reload swaps the whole list at once and in-flight
+/// screenings finish against the list they started with. A failed reload
+/// keeps serving the previous list. Each swap is published to subscribers
+/// as the new list version.
+#[derive(Debug)]
+pub struct SanctionsStore {
+    path: PathBuf,
+    state: RwLock<StoreState>,
+    updates: watch::Sender<Option<String>>,
+}
+
+impl SanctionsStore {
+    /// Loads the file right away; a load error is kept in the status and
+    /// surfaces on screening until a later reload succeeds.
+    pub fn open(path: impl Into<PathBuf>) -> Self {
+        let store = Self {
+            path: path.into(),
+            state: RwLock::new(StoreState::default()),
+            updates: watch::Sender::new(None),
+        };
+        if let Err(e) = store.reload() {
+            warn!(error = %e, path = %store.path.display(), "sanctions list not loaded");
+        }
+        store
+    }
+
+    pub fn current(&self) -> Result<Arc<SanctionsIndex>, String> {
+        let state = self.read_state();
+        state.index.clone().ok_or_else(|| {
+            state
+                .last_error
+                .clone()
+                .unwrap_or_else(|| "sanctions list not loaded".to_string())
+        })
+    }
+
+    /// Re-reads the data file if it changed since the last load. Returns
+    /// whether a new list was swapped in.
+    pub fn reload(&self) -> Result<bool, String> {
+        let result = self.reload_inner();
+        if let Err(e) = &result {
+            self.write_state().last_error = Some(e.clone());
+        }
+        result
+    }
+
+    /// Version of every list swapped in after subscribing; the value seen
+    /// on subscription is already marked as read.
+    pub fn subscribe(&self) -> watch::Receiver<Option<String>> {
+        self.updates.subscribe()
+    }
+
+    pub fn status(&self) -> SanctionsStatus {
+        let state = self.read_state();
+        SanctionsStatus {
+            version: state.index.as_ref().map(|i| i.version().to_string()),
+            entry_count: state.index.as_ref().map_or(0, |i| i.entry_count()),
+            loaded_at: state.loaded_at,
+            last_error: state.last_error.clone(),
+        }
+    }
+
+    fn reload_inner(&self) -> Result<bool, String> {
+        let metadata = std::fs::metadata(&self.path)
+            .map_err(|e| format!("failed to read sanctions file: {e}"))?;
+        let stamp = FileStamp {
+            modified: metadata.modified().ok(),
+            len: metadata.len(),
+        };
+        let current_version = {
+            let state = self.read_state();
+            if state.index.is_some() && state.stamp == Some(stamp) {
+                return Ok(false);
+            }
+            state.index.as_ref().map(|i| i.version().to_string())
+        };
+
+        let raw =
+            std::fs::read(&self.path).map_err(|e| format!("failed to read sanctions file: {e}"))?;
+        let version = hex::encode(Sha256::digest(&raw));
+        if current_version.as_deref() == Some(version.as_str()) {
+            let mut state = self.write_state();
+            state.stamp = Some(stamp);
+            state.last_error = None;
+            return Ok(false);
+        }
+
+        let index = SanctionsIndex::build(parse_sanctions_entries(&raw)?, version.clone());
+        {
+            let mut state = self.write_state();
+            state.index = Some(Arc::new(index));
+            state.loaded_at = Some(now_unix());
+            state.stamp = Some(stamp);
+            state.last_error = None;
+        }
+        self.updates.send_replace(Some(version));
+        Ok(true)
+    }
+
+    fn read_state(&self) -> std::sync::RwLockReadGuard<'_, StoreState> {
+        self.state.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write_state(&self) -> std::sync::RwLockWriteGuard<'_, StoreState> {
+        self.state.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Polls the data file every `interval` and swaps in a rebuilt index when it
+/// changes. Rebuilding runs on the blocking pool.
+pub fn spawn_sanctions_reloader(
+    store: Arc<SanctionsStore>,
+    interval: Duration,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.tick().await;
+        loop {
+            ticker.tick().await;
+            let worker = store.clone();
+            match tokio::task::spawn_blocking(move || worker.reload()).await {
+                Ok(Ok(true)) => {
+                    let status = store.status();
+                    info!(
+                        version = status.version.as_deref().unwrap_or_default(),
+                        entry_count = status.entry_count,
+                        "sanctions list reloaded"
+                    );
+                }
+                Ok(Ok(false)) => {}
+                Ok(Err(e)) => warn!(error = %e, "sanctions reload failed; keeping previous list"),
+                Err(e) => warn!(error = %e, "sanctions reload task failed"),
+            }
+        }
+    })
+}
+
+fn now_unix() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default()
+}
@@ -0,0 +1,69 @@
+use sha3::{Digest, Keccak256};
+
+/// Bech32 prefixes whose addresses are case-insensitive (BTC, LTC, testnets).
+const BECH32_PREFIXES: &[&str] = &["bc1", "tb1", "bcrt1", "ltc1", "tltc1"];
+const CASHADDR_PREFIX: &str = "bitcoincash:";
+
+/// Form used to compare a wallet address with listed ones. EVM addresses
+/// become their EIP-55 checksum spelling and case-insensitive encodings are
+/// lowercased; anything else (base58 and friends) is case-sensitive and only
+/// trimmed. `None` when the value cannot be an address at all.
+pub fn canonical_wallet_address(raw: &str) -> Option<String> {
+    let trimmed = raw.trim();
+    if let Some(hex) = evm_hex(trimmed) {
+        return Some(eip55_checksum(&hex.to_ascii_lowercase()));
+    }
+    let lower = trimmed.to_ascii_lowercase();
+    if let Some(rest) = lower.strip_prefix(CASHADDR_PREFIX) {
+        return is_alphanumeric(rest).then(|| rest.to_string());
+    }
+    if BECH32_PREFIXES.iter().any(|p| lower.starts_with(p)) && is_alphanumeric(&lower) {
+        return Some(lower);
+    }
+    (trimmed.len() >= 20 && is_alphanumeric(trimmed)).then(|| trimmed.to_string())
+}
+
+/// Canonical form of a caller-supplied address. Stricter than
+/// [`canonical_wallet_address`]: a mixed-case EVM address must carry a valid
+/// EIP-55 checksum, since a mismatch means the address was mistyped.
+pub fn validate_wallet_address(raw: &str) -> Result<String, String> {
+    let canonical = canonical_wallet_address(raw)
+        .ok_or_else(|| "wallet_address is not a recognised address format".to_string())?;
+    if let Some(hex) = evm_hex(raw.trim()) {
+        let mixed_case = hex.chars().any(|c| c.is_ascii_uppercase())
+            && hex.chars().any(|c| c.is_ascii_lowercase());
+        if mixed_case && canonical[2..] != *hex {
+            return Err("wallet_address fails its EIP-55 checksum".to_string());
+        }
+    }
+    Ok(canonical)
+}
+
+/// The 40 hex digits of a `0x`-prefixed EVM address.
+fn evm_hex(value: &str) -> Option<&str> {
+    let hex = value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))?;
+    (hex.len() == 40 && hex.chars().all(|c| c.is_ascii_hexdigit())).then_some(hex)
+}
+
+/// EIP-55: a hex letter is uppercased when the matching nibble of
+/// `keccak256(lowercase hex)` is 8 or more.
+fn eip55_checksum(lower_hex: &str) -> String {
+    let hash = Keccak256::digest(lower_hex.as_bytes());
+    let mut out = String::with_capacity(42);
+    out.push_str("0x");
+    for (i, c) in lower_hex.chars().enumerate() {
+        let nibble = (hash[i / 2] >> (if i % 2 == 0 { 4 } else { 0 })) & 0x0f;
+        out.push(if nibble >= 8 {
+            c.to_ascii_uppercase()
+        } else {
+            c
+        });
+    }
+    out
+}
+
+fn is_alphanumeric(value: &str) -> bool {
+    !value.is_empty() && value.chars().all(|c| c.is_ascii_alphanumeric())
+}
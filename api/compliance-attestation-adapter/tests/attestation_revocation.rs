@@ -0,0 +1,290 @@
+mod common;
+
+use axum::body::{Body, to_bytes};
+use compliance_attestation_adapter::app::{AppState, build_router};
+use compliance_attestation_adapter::config::environment::AppConfig;
+use compliance_attestation_adapter::module::compliance_attestation::model::{
+    ComplianceAttestationRecord, NormalizedSubject, RevocationRecord, SubjectType,
+};
+use compliance_attestation_adapter::module::compliance_attestation::schema::{
+    AttestationStatus, ComplianceDecision, EntityInput, IntakeComplianceRequest,
+    IntakeComplianceResponse, SubjectInput,
+};
+use compliance_attestation_adapter::service::policy_eval_service::{
+    PolicyFactor, load_policy_snapshot,
+};
+use compliance_attestation_adapter::service::revocation_service::{
+    SANCTIONS_RESCREEN_ACTOR, attestation_status, sanctions_revocation,
+};
+use compliance_attestation_adapter::service::sanctions_service::{
+    SanctionsIndex, parse_sanctions_entries,
+};
+use compliance_attestation_adapter::service::sanctions_store_service::SanctionsStore;
+use http::Request;
+use serde_json::json;
+use std::fs;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+const NOW: i64 = 1_700_001_000;
+const WALLET: &str = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+
+fn config() -> AppConfig {
+    common::config(common::CIMEX_SANCTIONS)
+}
+
+fn passed_record() -> ComplianceAttestationRecord {
+    ComplianceAttestationRecord {
+        attestation_id: "attn_0123456789abcdef01234567".to_string(),
+        request_id: "req-1".to_string(),
+        workflow_run_id: "run-1".to_string(),
+        policy_version: "policy-v1".to_string(),
+        policy_hash: "0x01".to_string(),
+        decision: "PASS".to_string(),
+        risk_score: 5,
+        policy_factors: Vec::new(),
+        attestation_hash: "0123456789abcdef0123456789abcdef".to_string(),
+        issued_at: 1_700_000_000,
+        expires_at: 1_700_003_600,
+        sanctions_hit_count: 0,
+        normalized_subjects: vec![
+            NormalizedSubject {
+                subject_id: "ent-001".to_string(),
+                subject_type: SubjectType::Entity,
+                jurisdiction: Some("GB".to_string()),
+                address: None,
+                legal_name: Some("Harbour Freight Partners".to_string()),
+                wallet_address: None,
+            },
+            NormalizedSubject {
+                subject_id: "cp-001".to_string(),
+                subject_type: SubjectType::Counterparty,
+                jurisdiction: Some("GB".to_string()),
+                address: None,
+                legal_name: None,
+                wallet_address: Some(WALLET.to_string()),
+            },
+        ],
+        fx_quote: None,
+        review_status: None,
+        supersedes: None,
+        superseded_by: None,
+        adjudication: None,
+        signature: None,
+        signing_key_id: None,
+        revocation: None,
+        provider_refs: Vec::new(),
+    }
+}
+
+fn index(entries: serde_json::Value, version: &str) -> SanctionsIndex {
+    SanctionsIndex::build(
+        parse_sanctions_entries(entries.to_string().as_bytes()).expect("entries"),
+        version,
+    )
+}
+
+#[test]
+fn revocation_outranks_supersession_and_expiry() {
+    let mut record = passed_record();
+    assert_eq!(attestation_status(&record, NOW), AttestationStatus::Active);
+    assert_eq!(
+        attestation_status(&record, record.expires_at),
+        AttestationStatus::Expired
+    );
+
+    record.superseded_by = Some("attn_next".to_string());
+    assert_eq!(
+        attestation_status(&record, NOW),
+        AttestationStatus::Superseded
+    );
+
+    record.revocation = Some(RevocationRecord {
+        reason: "ISSUER_REQUEST".to_string(),
+        detail: "withdrawn by the issuer".to_string(),
+        revoked_by: "ops".to_string(),
+        revoked_at: NOW,
+        sanctions_version: None,
+    });
+    assert_eq!(
+        attestation_status(&record, record.expires_at + 1),
+        AttestationStatus::Revoked
+    );
+}
+
+#[test]
+fn a_new_listing_revokes_active_attestations() {
+    let policy = load_policy_snapshot("config/policy_snapshot.json").expect("policy");
+    let record = passed_record();
+
+    let unrelated = index(
+        json!([{"source": "TEST", "program": "DEMO", "name": "CIMEX IBERICA"}]),
+        "v1",
+    );
+    assert!(sanctions_revocation(&record, &unrelated, &policy, NOW).is_none());
+
+    let listed = index(
+        json!([
+            {"source": "TEST", "program": "DEMO", "name": "Harbour Freight Partners"},
+            {
+                "source": "OFAC",
+                "program": "CYBER2",
+                "name": "Garantex Europe OU",
+                "digital_currency_addresses": [{"currency": "ETH", "address": WALLET}]
+            }
+        ]),
+        "v2",
+    );
+    let revocation =
+        sanctions_revocation(&record, &listed, &policy, NOW).expect("newly listed subjects");
+    assert_eq!(revocation.reason, "SANCTIONS_MATCH");
+    assert_eq!(revocation.revoked_by, SANCTIONS_RESCREEN_ACTOR);
+    assert_eq!(revocation.sanctions_version.as_deref(), Some("v2"));
+    assert_eq!(revocation.revoked_at, NOW);
+    assert!(revocation.detail.contains("Harbour Freight Partners"));
+    assert!(revocation.detail.contains(WALLET), "{}", revocation.detail);
+
+    let mut expired = passed_record();
+    expired.expires_at = NOW;
+    assert!(sanctions_revocation(&expired, &listed, &policy, NOW).is_none());
+
+    let mut failed = passed_record();
+    failed.decision = "FAIL".to_string();
+    assert!(sanctions_revocation(&failed, &listed, &policy, NOW).is_none());
+}
+
+#[test]
+fn failures_already_on_the_attestation_do_not_revoke_it() {
+    let policy = load_policy_snapshot("config/policy_snapshot.json").expect("policy");
+    let mut record = passed_record();
+    record.decision = "REVIEW".to_string();
+    record.normalized_subjects.truncate(1);
+    record.policy_factors.push(PolicyFactor {
+        code: "SANCTIONS_NAME_MATCH".to_string(),
+        subject_id: Some("ent-001".to_string()),
+        outcome: ComplianceDecision::Fail,
+        risk_uplift: 0,
+        detail: "Harbour Freight Partners matched HARBOUR FREIGHT PARTNERS".to_string(),
+    });
+    let listed = index(
+        json!([{"source": "TEST", "program": "DEMO", "name": "Harbour Freight Partners"}]),
+        "v2",
+    );
+    assert!(sanctions_revocation(&record, &listed, &policy, NOW).is_none());
+}
+
+#[test]
+fn store_publishes_each_new_list_version() {
+    let path = std::env::temp_dir().join(format!("zkclear-sanctions-{}.json", Uuid::now_v7()));
+    fs::write(
+        &path,
+        r#"[{"source":"TEST","program":"DEMO","name":"CIMEX IBERICA"}]"#,
+    )
+    .expect("write v1");
+    let store = SanctionsStore::open(&path);
+    let mut updates = store.subscribe();
+    assert!(!updates.has_changed().expect("sender alive"));
+
+    fs::write(
+        &path,
+        r#"[{"source":"TEST","program":"DEMO","name":"CIMEX IBERICA"},{"source":"TEST","program":"DEMO","name":"Harbour Freight Partners"}]"#,
+    )
+    .expect("write v2");
+    assert!(store.reload().expect("reload"));
+    assert!(updates.has_changed().expect("sender alive"));
+    assert_eq!(
+        updates.borrow_and_update().clone(),
+        store.status().version,
+        "subscribers see the version that was swapped in"
+    );
+
+    assert!(!store.reload().expect("unchanged"));
+    assert!(!updates.has_changed().expect("sender alive"));
+}
+
+#[tokio::test]
+async fn revocation_endpoint_validates_before_touching_storage() {
+    let app = build_router(AppState::new(config(), None));
+    let revoke = |body: serde_json::Value| {
+        Request::builder()
+            .method("POST")
+            .uri("/v1/compliance/attest/attn_0123/revoke")
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .expect("build request")
+    };
+
+    for (body, code) in [
+        (
+            json!({"reason": "ISSUER_REQUEST", "detail": "withdrawn", "revoked_by": ""}),
+            "INVALID_REVOKED_BY",
+        ),
+        (
+            json!({"reason": "SANCTIONS_MATCH", "detail": " ", "revoked_by": "ops"}),
+            "INVALID_REVOCATION_DETAIL",
+        ),
+        (
+            json!({"reason": "KEY_COMPROMISE", "detail": "key rotated", "revoked_by": "ops"}),
+            "PERSISTENCE_DISABLED",
+        ),
+    ] {
+        let resp = app.clone().oneshot(revoke(body)).await.expect("response");
+        assert_eq!(resp.status(), http::StatusCode::BAD_REQUEST);
+        let body = to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .expect("read body");
+        let parsed: IntakeComplianceResponse = serde_json::from_slice(&body).expect("parse body");
+        assert_eq!(parsed.error_code.as_deref(), Some(code));
+    }
+
+    let resp = app
+        .clone()
+        .oneshot(revoke(
+            json!({"reason": "NO_LONGER_LIKED", "detail": "x", "revoked_by": "ops"}),
+        ))
+        .await
+        .expect("response");
+    assert!(resp.status().is_client_error());
+}
+
+#[tokio::test]
+async fn fresh_attestations_are_active() {
+    let app = build_router(AppState::new(config(), None));
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("clock")
+        .as_secs() as i64;
+    let request = IntakeComplianceRequest {
+        workflow_run_id: "run-1".to_string(),
+        request_id: "req-1".to_string(),
+        nonce: "nonce-1".to_string(),
+        timestamp: now,
+        internal_signature: None,
+        subjects: vec![SubjectInput {
+            counterparty: None,
+            entity: Some(EntityInput {
+                entity_id: "ent-001".to_string(),
+                registration_country: Some("GB".to_string()),
+                legal_name: Some("Harbour Freight Partners".to_string()),
+            }),
+        }],
+    };
+    let resp = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/compliance/intake")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_vec(&request).expect("serialize")))
+                .expect("build request"),
+        )
+        .await
+        .expect("response");
+    let body = to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .expect("read body");
+    let parsed: IntakeComplianceResponse = serde_json::from_slice(&body).expect("parse body");
+    assert_eq!(parsed.status, Some(AttestationStatus::Active));
+    assert!(parsed.revocation.is_none());
+}
@@ -0,0 +1,226 @@
+mod common;
+
+use axum::Router;
+use axum::body::{Body, to_bytes};
+use compliance_attestation_adapter::app::{AppState, build_router};
+use compliance_attestation_adapter::config::environment::AppConfig;
+use compliance_attestation_adapter::module::compliance_attestation::schema::{
+    ComplianceDecision, EntityInput, IntakeComplianceRequest, IntakeComplianceResponse,
+    SubjectInput, VerifyAttestationResponse,
+};
+use compliance_attestation_adapter::service::attestation_signing_service::{
+    AttestationClaims, AttestationSigner, JwkSet, recover_signer,
+};
+use http::Request;
+use serde_json::{Value, json};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tower::util::ServiceExt;
+
+/// Secret key 1; its address is a well-known test vector.
+const SIGNING_KEY_HEX: &str = "0000000000000000000000000000000000000000000000000000000000000001";
+const SIGNING_ADDRESS: &str = "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf";
+
+fn config(signing_key: Option<&str>) -> AppConfig {
+    AppConfig {
+        attestation_signing_key_hex: signing_key.map(str::to_string),
+        ..common::config(common::ACME_SANCTIONS)
+    }
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("clock")
+        .as_secs() as i64
+}
+
+fn claims(expires_at: i64) -> AttestationClaims<'static> {
+    AttestationClaims {
+        attestation_hash: "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08",
+        policy_hash: "0x60303ae22b998861bce3b28f33eec1be758a213c86c93c076dbe9f558c11c752",
+        decision: ComplianceDecision::Pass,
+        risk_score: 5,
+        issued_at: 1_700_000_000,
+        expires_at,
+    }
+}
+
+async fn send(app: &Router, request: Request<Body>) -> (http::StatusCode, Vec<u8>) {
+    let resp = app.clone().oneshot(request).await.expect("response");
+    let status = resp.status();
+    let body = to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .expect("read body");
+    (status, body.to_vec())
+}
+
+async fn verify(app: &Router, attestation: Value) -> (http::StatusCode, VerifyAttestationResponse) {
+    let (status, body) = send(
+        app,
+        Request::builder()
+            .method("POST")
+            .uri("/v1/compliance/attest/verify")
+            .header("content-type", "application/json")
+            .body(Body::from(attestation.to_string()))
+            .expect("build request"),
+    )
+    .await;
+    (status, serde_json::from_slice(&body).expect("parse body"))
+}
+
+#[test]
+fn signatures_recover_to_the_service_address() {
+    let signer = AttestationSigner::from_hex(SIGNING_KEY_HEX).expect("signer");
+    assert_eq!(signer.key_id(), SIGNING_ADDRESS);
+
+    let signature = signer.sign(&claims(1_700_003_600)).expect("sign");
+    assert_eq!(signature.len(), 2 + 65 * 2);
+    assert!(signature.ends_with("1b") || signature.ends_with("1c"));
+    assert_eq!(
+        recover_signer(&claims(1_700_003_600), &signature).expect("recover"),
+        SIGNING_ADDRESS
+    );
+
+    let mut tampered = claims(1_700_003_600);
+    tampered.decision = ComplianceDecision::Review;
+    assert_ne!(
+        recover_signer(&tampered, &signature).unwrap_or_default(),
+        SIGNING_ADDRESS
+    );
+    assert!(recover_signer(&claims(1_700_003_600), "0x1234").is_err());
+    assert!(AttestationSigner::from_hex(&"0".repeat(64)).is_err());
+}
+
+#[test]
+fn jwks_publishes_the_public_key() {
+    let signer = AttestationSigner::from_hex(SIGNING_KEY_HEX).expect("signer");
+    let jwks = serde_json::to_value(signer.jwks()).expect("serialize");
+    let key = &jwks["keys"][0];
+    assert_eq!(key["kty"], "EC");
+    assert_eq!(key["crv"], "secp256k1");
+    assert_eq!(key["alg"], "ES256K");
+    assert_eq!(key["use"], "sig");
+    assert_eq!(key["kid"], SIGNING_ADDRESS);
+    // Generator point of secp256k1, base64url without padding.
+    assert_eq!(key["x"], "eb5mfvncu6xVoGKVzocLBwKb_NstzijZWfKBWxb4F5g");
+    assert_eq!(key["y"], "SDradyajxGVdpPv8DhEIqP0XtEimhVQZnEfQj_sQ1Lg");
+}
+
+#[tokio::test]
+async fn intake_attestations_are_signed_and_verifiable() {
+    let app = build_router(AppState::new(config(Some(SIGNING_KEY_HEX)), None));
+    let request = IntakeComplianceRequest {
+        workflow_run_id: "run-1".to_string(),
+        request_id: "req-1".to_string(),
+        nonce: "nonce-1".to_string(),
+        timestamp: now(),
+        internal_signature: None,
+        subjects: vec![SubjectInput {
+            counterparty: None,
+            entity: Some(EntityInput {
+                entity_id: "ent-001".to_string(),
+                registration_country: Some("GB".to_string()),
+                legal_name: Some("Harbour Freight Partners".to_string()),
+            }),
+        }],
+    };
+    let (status, body) = send(
+        &app,
+        Request::builder()
+            .method("POST")
+            .uri("/v1/compliance/intake")
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::to_vec(&request).expect("serialize")))
+            .expect("build request"),
+    )
+    .await;
+    assert_eq!(status, http::StatusCode::OK);
+    let attestation: IntakeComplianceResponse = serde_json::from_slice(&body).expect("parse");
+    assert!(attestation.signature.is_some());
+    assert_eq!(attestation.signing_key_id.as_deref(), Some(SIGNING_ADDRESS));
+
+    let presented = serde_json::to_value(&attestation).expect("serialize");
+    let (status, verdict) = verify(&app, presented.clone()).await;
+    assert_eq!(status, http::StatusCode::OK);
+    assert!(verdict.valid, "{}", verdict.reason);
+    assert_eq!(verdict.signer.as_deref(), Some(SIGNING_ADDRESS));
+
+    let mut tampered = presented;
+    tampered["risk_score"] = json!(attestation.risk_score + 1);
+    let (_, verdict) = verify(&app, tampered).await;
+    assert!(!verdict.valid);
+    assert!(!verdict.signature_valid);
+    assert_eq!(verdict.error_code.as_deref(), Some("SIGNATURE_MISMATCH"));
+
+    let (status, body) = send(
+        &app,
+        Request::builder()
+            .uri("/v1/compliance/jwks")
+            .body(Body::empty())
+            .expect("build request"),
+    )
+    .await;
+    assert_eq!(status, http::StatusCode::OK);
+    let jwks: JwkSet = serde_json::from_slice(&body).expect("parse jwks");
+    assert_eq!(jwks.keys[0].kid, SIGNING_ADDRESS);
+}
+
+#[tokio::test]
+async fn verification_rejects_expired_and_inactive_policy_attestations() {
+    let app = build_router(AppState::new(config(Some(SIGNING_KEY_HEX)), None));
+    let signer = AttestationSigner::from_hex(SIGNING_KEY_HEX).expect("signer");
+    let presented = |claims: AttestationClaims<'_>| {
+        json!({
+            "attestation_hash": claims.attestation_hash,
+            "policy_hash": claims.policy_hash,
+            "decision": claims.decision,
+            "risk_score": claims.risk_score,
+            "issued_at": claims.issued_at,
+            "expires_at": claims.expires_at,
+            "signature": signer.sign(&claims).expect("sign"),
+        })
+    };
+
+    let (_, verdict) = verify(&app, presented(claims(now() - 1))).await;
+    assert!(verdict.signature_valid);
+    assert!(verdict.expired);
+    assert_eq!(verdict.error_code.as_deref(), Some("ATTESTATION_EXPIRED"));
+
+    let mut stale_policy = claims(now() + 3600);
+    stale_policy.policy_hash = "0x01";
+    let (_, verdict) = verify(&app, presented(stale_policy)).await;
+    assert!(verdict.signature_valid);
+    assert!(!verdict.expired);
+    assert!(!verdict.policy_hash_active);
+    assert_eq!(verdict.error_code.as_deref(), Some("POLICY_HASH_INACTIVE"));
+}
+
+#[tokio::test]
+async fn without_a_key_attestations_are_unsigned() {
+    let app = build_router(AppState::new(config(None), None));
+    let (status, _) = send(
+        &app,
+        Request::builder()
+            .uri("/v1/compliance/jwks")
+            .body(Body::empty())
+            .expect("build request"),
+    )
+    .await;
+    assert_eq!(status, http::StatusCode::NOT_FOUND);
+
+    let (status, verdict) = verify(
+        &app,
+        json!({
+            "attestation_hash": "00",
+            "policy_hash": "00",
+            "decision": "PASS",
+            "risk_score": 5,
+            "issued_at": 1,
+            "expires_at": 2,
+            "signature": "0x00",
+        }),
+    )
+    .await;
+    assert_eq!(status, http::StatusCode::BAD_REQUEST);
+    assert_eq!(verdict.error_code.as_deref(), Some("SIGNING_DISABLED"));
+}
@@ -1 +1,61 @@
+#![allow(dead_code)]
+
+use compliance_attestation_adapter::config::environment::AppConfig;
+use std::fs;
+use std::path::Path;
+use uuid::Uuid;
+
 pub fn setup() {}
+
+/// Sanctions list with a single `CIMEX IBERICA` entry.
+pub const CIMEX_SANCTIONS: &str = r#"[{"source":"TEST","program":"DEMO","name":"CIMEX IBERICA","jurisdiction":null,"address":null}]"#;
+
+/// Sanctions list with a single `Acme Restricted Trading LLC` entry in GB.
+pub const ACME_SANCTIONS: &str = r#"[{"source":"TEST","program":"DEMO","name":"Acme Restricted Trading LLC","jurisdiction":"GB","address":null}]"#;
+
+/// Writes `sanctions_json` to a fresh temp file and returns a test config
+/// that loads it. Tests override only the fields they exercise:
+/// `AppConfig { compliance_provider: .., ..common::config(CIMEX_SANCTIONS) }`.
+pub fn config(sanctions_json: &str) -> AppConfig {
+    let sanctions_path =
+        std::env::temp_dir().join(format!("zkclear-sanctions-{}.json", Uuid::now_v7()));
+    fs::write(&sanctions_path, sanctions_json).expect("write sanctions fixture");
+    config_with_sanctions_path(&sanctions_path)
+}
+
+/// Test config: no persistence, no signing, no provider, no FX lookup.
+pub fn config_with_sanctions_path(sanctions_path: &Path) -> AppConfig {
+    AppConfig {
+        rust_env: "test".to_string(),
+        api_host: "127.0.0.1".to_string(),
+        api_port: 0,
+        mongodb_url: None,
+        mongodb_database: None,
+        redis_url: None,
+        intake_max_age_seconds: 300,
+        intake_max_future_skew_seconds: 30,
+        sanctions_data_path: sanctions_path.to_string_lossy().to_string(),
+        sanctions_reload_interval_seconds: 0,
+        policy_snapshot_url: None,
+        policy_cache_ttl_seconds: 30,
+        policy_snapshot_path: "config/policy_snapshot.json".to_string(),
+        policy_version: Some("policy-v1".to_string()),
+        attestation_ttl_seconds: 3600,
+        replay_ttl_seconds: 86400,
+        require_internal_signature: false,
+        internal_signing_secret: None,
+        encryption_key_hex: None,
+        attestation_signing_key_hex: None,
+        compliance_provider: None,
+        provider_base_url: None,
+        provider_auth_scheme: None,
+        provider_api_key: None,
+        provider_api_key_header: "X-API-Key".to_string(),
+        provider_timeout_ms: 5000,
+        provider_max_retries: 2,
+        frankfurter_base_url: "https://api.frankfurter.dev/v1".to_string(),
+        fx_base_currency: "EUR".to_string(),
+        fx_quote_currency: "USD".to_string(),
+        fx_lookup_enabled: false,
+    }
+}
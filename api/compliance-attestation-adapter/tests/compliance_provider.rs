@@ -0,0 +1,315 @@
+mod common;
+
+use axum::Router;
+use axum::body::{Body, to_bytes};
+use axum::extract::State;
+use axum::http::HeaderMap;
+use axum::routing::post;
+use compliance_attestation_adapter::app::{AppState, build_router};
+use compliance_attestation_adapter::config::environment::AppConfig;
+use compliance_attestation_adapter::infra::mock_provider::MockProvider;
+use compliance_attestation_adapter::infra::provider_client::{
+    ComplianceProvider, HttpComplianceProvider, HttpProviderConfig, ProviderAuth,
+    provider_from_config,
+};
+use compliance_attestation_adapter::module::compliance_attestation::model::{
+    NormalizedSubject, SubjectType,
+};
+use compliance_attestation_adapter::module::compliance_attestation::schema::{
+    ComplianceDecision, EntityInput, IntakeComplianceRequest, IntakeComplianceResponse,
+    SubjectInput,
+};
+use compliance_attestation_adapter::service::policy_eval_service::{
+    evaluate_intake_policy_with_provider, load_policy_snapshot,
+};
+use compliance_attestation_adapter::service::sanctions_service::ScreeningResult;
+use http::Request;
+use serde_json::{Value, json};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::net::TcpListener;
+use tower::util::ServiceExt;
+
+/// Stands in for a provider's `/v1/assess`: replies with the queued
+/// responses in order, repeating the last one.
+#[derive(Clone, Default)]
+struct Upstream {
+    replies: Arc<Mutex<Vec<(http::StatusCode, Value)>>>,
+    hits: Arc<AtomicUsize>,
+    api_keys: Arc<Mutex<Vec<Option<String>>>>,
+}
+
+async fn assess(
+    State(upstream): State<Upstream>,
+    headers: HeaderMap,
+) -> (http::StatusCode, String) {
+    let hit = upstream.hits.fetch_add(1, Ordering::SeqCst);
+    upstream.api_keys.lock().unwrap().push(
+        headers
+            .get("x-api-key")
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string),
+    );
+    let replies = upstream.replies.lock().unwrap();
+    let (status, body) = replies[hit.min(replies.len() - 1)].clone();
+    (status, body.to_string())
+}
+
+async fn start_upstream(replies: Vec<(http::StatusCode, Value)>) -> (Upstream, String) {
+    let upstream = Upstream {
+        replies: Arc::new(Mutex::new(replies)),
+        ..Upstream::default()
+    };
+    let app = Router::new()
+        .route("/v1/assess", post(assess))
+        .with_state(upstream.clone());
+    let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+    let addr = listener.local_addr().expect("addr");
+    tokio::spawn(async move { axum::serve(listener, app).await });
+    (upstream, format!("http://{addr}"))
+}
+
+fn http_provider(base_url: String) -> HttpComplianceProvider {
+    HttpComplianceProvider::new(HttpProviderConfig {
+        name: "acme-kyt".to_string(),
+        base_url,
+        auth: ProviderAuth::ApiKey {
+            header: "X-API-Key".to_string(),
+            value: "secret-key".to_string(),
+        },
+        timeout: Duration::from_secs(2),
+        max_retries: 2,
+        retry_backoff: Duration::from_millis(1),
+    })
+    .expect("provider")
+}
+
+fn subjects() -> Vec<NormalizedSubject> {
+    vec![
+        NormalizedSubject {
+            subject_id: "ent-001".to_string(),
+            subject_type: SubjectType::Entity,
+            jurisdiction: Some("GB".to_string()),
+            address: None,
+            legal_name: Some("Harbour Freight Partners".to_string()),
+            wallet_address: None,
+        },
+        NormalizedSubject {
+            subject_id: "cp-001".to_string(),
+            subject_type: SubjectType::Counterparty,
+            jurisdiction: Some("GB".to_string()),
+            address: None,
+            legal_name: None,
+            wallet_address: Some("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed".to_string()),
+        },
+    ]
+}
+
+fn assessment(risk_score: u16) -> Value {
+    json!({
+        "reference": "case-7781",
+        "risk_score": risk_score,
+        "subjects": [
+            {"subject_id": "ent-001", "risk_score": 12},
+            {"subject_id": "cp-001", "risk_score": risk_score, "flags": ["MIXER_EXPOSURE"]}
+        ]
+    })
+}
+
+fn config(provider: Option<&str>) -> AppConfig {
+    AppConfig {
+        compliance_provider: provider.map(str::to_string),
+        ..common::config(common::CIMEX_SANCTIONS)
+    }
+}
+
+#[tokio::test]
+async fn http_provider_retries_transient_failures_and_keeps_the_raw_response() {
+    let (upstream, url) = start_upstream(vec![
+        (http::StatusCode::SERVICE_UNAVAILABLE, json!({})),
+        (http::StatusCode::TOO_MANY_REQUESTS, json!({})),
+        (http::StatusCode::OK, assessment(72)),
+    ])
+    .await;
+    let provider = http_provider(url);
+
+    let result = provider.assess("req-1", &subjects()).await.expect("assess");
+    assert_eq!(upstream.hits.load(Ordering::SeqCst), 3);
+    assert_eq!(result.provider, "acme-kyt");
+    assert_eq!(result.reference, "case-7781");
+    assert_eq!(result.risk_score, 72);
+    assert_eq!(result.subjects[1].flags, ["MIXER_EXPOSURE"]);
+    assert_eq!(
+        serde_json::from_str::<Value>(&result.raw_response).expect("raw json"),
+        assessment(72)
+    );
+    assert!(
+        upstream
+            .api_keys
+            .lock()
+            .unwrap()
+            .iter()
+            .all(|k| k.as_deref() == Some("secret-key"))
+    );
+}
+
+#[tokio::test]
+async fn http_provider_gives_up_on_client_errors_and_bad_scores() {
+    let (upstream, url) = start_upstream(vec![(http::StatusCode::UNAUTHORIZED, json!({}))]).await;
+    let err = http_provider(url)
+        .assess("req-1", &subjects())
+        .await
+        .expect_err("unauthorized");
+    assert!(err.contains("401"), "{err}");
+    assert_eq!(
+        upstream.hits.load(Ordering::SeqCst),
+        1,
+        "4xx is not retried"
+    );
+
+    let (upstream, url) = start_upstream(vec![(http::StatusCode::BAD_GATEWAY, json!({}))]).await;
+    assert!(
+        http_provider(url)
+            .assess("req-1", &subjects())
+            .await
+            .is_err()
+    );
+    assert_eq!(
+        upstream.hits.load(Ordering::SeqCst),
+        3,
+        "first try plus retries"
+    );
+
+    let (_, url) = start_upstream(vec![(http::StatusCode::OK, assessment(140))]).await;
+    let err = http_provider(url)
+        .assess("req-1", &subjects())
+        .await
+        .expect_err("out of range");
+    assert!(err.contains("above 100"), "{err}");
+}
+
+#[tokio::test]
+async fn provider_scores_combine_with_local_screening() {
+    let policy = load_policy_snapshot("config/policy_snapshot.json").expect("policy");
+    let screening = ScreeningResult {
+        hits: Vec::new(),
+        match_digest: String::new(),
+    };
+
+    let quiet_url = MockProvider::new(20).spawn().await.expect("mock");
+    let quiet = http_provider(quiet_url)
+        .assess("req-1", &subjects())
+        .await
+        .expect("assess");
+    let evaluation =
+        evaluate_intake_policy_with_provider(&subjects(), &screening, Some(Ok(&quiet)), &policy);
+    assert_eq!(evaluation.decision.as_str(), "PASS");
+    assert_eq!(evaluation.risk_score, 20, "provider score is a floor");
+    assert!(evaluation.factors.is_empty());
+
+    let risky_url = MockProvider::new(10)
+        .with_subject("cp-001", 72, &["MIXER_EXPOSURE"])
+        .spawn()
+        .await
+        .expect("mock");
+    let risky = http_provider(risky_url)
+        .assess("req-1", &subjects())
+        .await
+        .expect("assess");
+    assert_eq!(risky.risk_score, 72);
+    let evaluation =
+        evaluate_intake_policy_with_provider(&subjects(), &screening, Some(Ok(&risky)), &policy);
+    assert_eq!(evaluation.decision.as_str(), "REVIEW");
+    let factor = &evaluation.factors[0];
+    assert_eq!(factor.code, "PROVIDER_RISK");
+    assert_eq!(factor.subject_id.as_deref(), Some("cp-001"));
+    assert!(factor.detail.contains("MIXER_EXPOSURE"));
+
+    let evaluation = evaluate_intake_policy_with_provider(
+        &subjects(),
+        &screening,
+        Some(Err("acme-kyt request failed: timed out")),
+        &policy,
+    );
+    assert!(matches!(evaluation.decision, ComplianceDecision::Review));
+    assert_eq!(evaluation.factors[0].code, "PROVIDER_UNAVAILABLE");
+}
+
+#[tokio::test]
+async fn intake_uses_the_configured_provider() {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("clock")
+        .as_secs() as i64;
+    let request = IntakeComplianceRequest {
+        workflow_run_id: "run-1".to_string(),
+        request_id: "req-1".to_string(),
+        nonce: "nonce-1".to_string(),
+        timestamp: now,
+        internal_signature: None,
+        subjects: vec![SubjectInput {
+            counterparty: None,
+            entity: Some(EntityInput {
+                entity_id: "ent-001".to_string(),
+                registration_country: Some("GB".to_string()),
+                legal_name: Some("Harbour Freight Partners".to_string()),
+            }),
+        }],
+    };
+    let mock_url = MockProvider::new(10).spawn().await.expect("mock");
+    let mut attestations = Vec::new();
+    for provider in [None, Some("mock")] {
+        let config = AppConfig {
+            provider_base_url: Some(mock_url.clone()),
+            ..config(provider)
+        };
+        let app = build_router(AppState::new(config, None));
+        let resp = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/v1/compliance/intake")
+                    .header("content-type", "application/json")
+                    .body(Body::from(serde_json::to_vec(&request).expect("serialize")))
+                    .expect("build request"),
+            )
+            .await
+            .expect("response");
+        let body = to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .expect("read body");
+        let parsed: IntakeComplianceResponse = serde_json::from_slice(&body).expect("parse body");
+        attestations.push(parsed);
+    }
+
+    assert_eq!(attestations[0].risk_score, 5);
+    assert_eq!(attestations[1].decision.as_str(), "PASS");
+    assert_eq!(attestations[1].risk_score, 10, "mock provider base score");
+    assert_ne!(
+        attestations[0].attestation_hash, attestations[1].attestation_hash,
+        "the attestation commits to the provider assessment"
+    );
+}
+
+#[test]
+fn provider_is_selected_from_config() {
+    assert!(provider_from_config(&config(None)).expect("none").is_none());
+    assert_eq!(
+        provider_from_config(&config(Some("mock")))
+            .expect("mock")
+            .expect("provider")
+            .name(),
+        "mock"
+    );
+
+    let mut http = config(Some("http"));
+    assert!(provider_from_config(&http).is_err(), "base url required");
+    http.provider_base_url = Some("https://kyt.example".to_string());
+    http.provider_auth_scheme = Some("bearer".to_string());
+    assert!(provider_from_config(&http).is_err(), "api key required");
+    http.provider_api_key = Some("token".to_string());
+    assert!(provider_from_config(&http).expect("http").is_some());
+
+    assert!(provider_from_config(&config(Some("carrier-pigeon"))).is_err());
+}
@@ -1,48 +1,19 @@
+mod common;
+
 use axum::body::{Body, to_bytes};
 use compliance_attestation_adapter::app::{AppState, build_router};
-use compliance_attestation_adapter::config::environment::AppConfig;
 use compliance_attestation_adapter::module::compliance_attestation::schema::{
     ComplianceDecision, EntityInput, IntakeComplianceRequest, IntakeComplianceResponse,
     SubjectInput,
 };
 use http::Request;
-use std::fs;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tower::util::ServiceExt;
-use uuid::Uuid;
 
 #[tokio::test]
 async fn intake_normalizes_and_flags_sanctions_hit() {
-    let sanctions_path =
-        std::env::temp_dir().join(format!("zkclear-sanctions-{}.json", Uuid::now_v7()));
-    fs::write(
-        &sanctions_path,
-        r#"[{"source":"TEST","program":"DEMO","name":"Acme Restricted Trading LLC","jurisdiction":"GB","address":null}]"#,
-    )
-    .expect("write sanctions fixture");
+    let config = common::config(common::ACME_SANCTIONS);
 
-    let config = AppConfig {
-        rust_env: "test".to_string(),
-        api_host: "127.0.0.1".to_string(),
-        api_port: 0,
-        mongodb_url: None,
-        mongodb_database: None,
-        redis_url: None,
-        intake_max_age_seconds: 300,
-        intake_max_future_skew_seconds: 30,
-        sanctions_data_path: sanctions_path.to_string_lossy().to_string(),
-        policy_snapshot_path: "config/policy_snapshot.json".to_string(),
-        policy_version: "policy-v1".to_string(),
-        attestation_ttl_seconds: 3600,
-        replay_ttl_seconds: 86400,
-        require_internal_signature: false,
-        internal_signing_secret: None,
-        encryption_key_hex: None,
-        frankfurter_base_url: "https://api.frankfurter.dev/v1".to_string(),
-        fx_base_currency: "EUR".to_string(),
-        fx_quote_currency: "USD".to_string(),
-        fx_lookup_enabled: false,
-    };
     let app = build_router(AppState::new(config.clone(), None));
 
     let now = SystemTime::now()
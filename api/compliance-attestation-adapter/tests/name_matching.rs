@@ -0,0 +1,124 @@
+use compliance_attestation_adapter::module::compliance_attestation::model::{
+    NormalizedSubject, SubjectType,
+};
+use compliance_attestation_adapter::module::compliance_attestation::schema::ComplianceDecision;
+use compliance_attestation_adapter::service::name_match_service::{fold_name, name_confidence};
+use compliance_attestation_adapter::service::policy_eval_service::{
+    evaluate_intake_policy, load_policy_snapshot,
+};
+use compliance_attestation_adapter::service::sanctions_service::{
+    MIN_HIT_CONFIDENCE, SanctionsEntry, SanctionsIndex, screen_subjects,
+};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct CorpusCase {
+    query: String,
+    listed: String,
+    #[serde(default)]
+    aliases: Vec<String>,
+    expect: String,
+}
+
+fn subject(legal_name: &str) -> NormalizedSubject {
+    NormalizedSubject {
+        subject_id: "ent-001".to_string(),
+        subject_type: SubjectType::Entity,
+        jurisdiction: None,
+        address: None,
+        legal_name: Some(legal_name.to_string()),
+        wallet_address: None,
+    }
+}
+
+fn index(name: &str, aliases: &[String]) -> SanctionsIndex {
+    SanctionsIndex::build(vec![entry(name, aliases)], "test")
+}
+
+fn entry(name: &str, aliases: &[String]) -> SanctionsEntry {
+    SanctionsEntry {
+        source: "TEST".to_string(),
+        program: "DEMO".to_string(),
+        name: name.to_string(),
+        jurisdiction: None,
+        address: None,
+        aliases: aliases.to_vec(),
+        source_id: None,
+        published_at: None,
+        digital_currency_addresses: Vec::new(),
+    }
+}
+
+#[test]
+fn regression_corpus_lands_in_expected_decision_band() {
+    let raw = std::fs::read_to_string("tests/data/name_match_corpus.json").expect("corpus");
+    let corpus: Vec<CorpusCase> = serde_json::from_str(&raw).expect("parse corpus");
+    let policy = load_policy_snapshot("config/policy_snapshot.json").expect("policy snapshot");
+
+    let mut failures = Vec::new();
+    for case in &corpus {
+        let subjects = [subject(&case.query)];
+        let screening = screen_subjects(&subjects, &index(&case.listed, &case.aliases));
+        let decision = evaluate_intake_policy(&subjects, &screening, &policy).decision;
+        let expected = match case.expect.as_str() {
+            "fail" => ComplianceDecision::Fail,
+            "review" => ComplianceDecision::Review,
+            "miss" => ComplianceDecision::Pass,
+            other => panic!("unknown expectation {other}"),
+        };
+        if decision.as_str() != expected.as_str() {
+            let confidence = screening.hits.iter().map(|h| h.confidence).max();
+            failures.push(format!(
+                "{:?} vs {:?}: expected {}, got {} ({confidence:?})",
+                case.query,
+                case.listed,
+                expected.as_str(),
+                decision.as_str()
+            ));
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "corpus mismatches:\n{}",
+        failures.join("\n")
+    );
+}
+
+#[test]
+fn alias_match_reports_the_matched_name() {
+    let screening = screen_subjects(
+        &[subject("Muammar Gaddafi")],
+        &index("QADHAFI, Muammar", &["GADDAFI, Muammar".to_string()]),
+    );
+    assert_eq!(screening.hits.len(), 1);
+    assert_eq!(screening.hits[0].entry_name, "QADHAFI, Muammar");
+    assert_eq!(screening.hits[0].matched_name, "GADDAFI, Muammar");
+    assert_eq!(screening.hits[0].confidence, 100);
+}
+
+#[test]
+fn folding_transliterates_and_drops_legal_forms() {
+    assert_eq!(
+        fold_name("Société Générale S.A.").tokens,
+        ["societe", "generale"]
+    );
+    assert_eq!(fold_name("ООО Газпром").tokens, ["gazprom"]);
+    assert!(fold_name(" - ").is_empty());
+    assert_eq!(
+        name_confidence(&fold_name("Ltd"), &fold_name("LTD.")),
+        100,
+        "a name made only of noise words still compares"
+    );
+}
+
+#[test]
+fn confidences_are_graded() {
+    let listed = fold_name("MOHAMMED AL RASHID");
+    let exact = name_confidence(&fold_name("Mohammed Al Rashid"), &listed);
+    let variant = name_confidence(&fold_name("Muhammad Al Rashid"), &listed);
+    let partial = name_confidence(&fold_name("Rashid"), &listed);
+    let unrelated = name_confidence(&fold_name("Peter Novak"), &listed);
+    assert_eq!(exact, 100);
+    assert!(exact > variant && variant > partial && partial > unrelated);
+    assert!(unrelated < MIN_HIT_CONFIDENCE);
+}
@@ -0,0 +1,214 @@
+use compliance_attestation_adapter::module::compliance_attestation::model::{
+    NormalizedSubject, SubjectType,
+};
+use compliance_attestation_adapter::module::compliance_attestation::schema::ComplianceDecision;
+use compliance_attestation_adapter::service::policy_eval_service::{
+    PolicyEvaluation, PolicySnapshot, evaluate_intake_policy, load_policy_snapshot,
+};
+use compliance_attestation_adapter::service::sanctions_service::{
+    SanctionsEntry, SanctionsIndex, ScreeningResult, screen_subjects,
+};
+use serde_json::json;
+
+fn policy(rules: serde_json::Value) -> PolicySnapshot {
+    serde_json::from_value(json!({
+        "active": {"version": "policy-v1", "hash": "0x01"},
+        "thresholds": {
+            "review_confidence": 70,
+            "fail_confidence": 90,
+            "pass_risk_score": 5,
+            "review_risk_score": 60,
+            "fail_risk_score": 95
+        },
+        "rules": rules
+    }))
+    .expect("policy")
+}
+
+fn subject(id: &str, subject_type: SubjectType, jurisdiction: Option<&str>) -> NormalizedSubject {
+    NormalizedSubject {
+        subject_id: id.to_string(),
+        subject_type,
+        jurisdiction: jurisdiction.map(str::to_string),
+        address: None,
+        legal_name: Some("Northwind Logistics".to_string()),
+        wallet_address: None,
+    }
+}
+
+fn no_hits() -> ScreeningResult {
+    ScreeningResult {
+        hits: Vec::new(),
+        match_digest: String::new(),
+    }
+}
+
+fn codes(evaluation: &PolicyEvaluation) -> Vec<&str> {
+    evaluation.factors.iter().map(|f| f.code.as_str()).collect()
+}
+
+/// The same cases run against policy-snapshot's `evaluate_rules`.
+#[test]
+fn jurisdiction_rules_match_the_shared_cases() {
+    let fixture: serde_json::Value = serde_json::from_str(include_str!(
+        "../../policy-snapshot/tests/data/jurisdiction_cases.json"
+    ))
+    .expect("jurisdiction cases");
+    let policy = policy(fixture["rules"].clone());
+
+    for case in fixture["cases"].as_array().expect("cases") {
+        let evaluation = evaluate_intake_policy(
+            &[subject(
+                "ent-001",
+                SubjectType::Entity,
+                case["jurisdiction"].as_str(),
+            )],
+            &no_hits(),
+            &policy,
+        );
+        let outcome = evaluation
+            .factors
+            .iter()
+            .find(|f| f.code.starts_with("JURISDICTION_"))
+            .map_or("PASS", |f| f.outcome.as_str());
+        let uplift: u16 = evaluation
+            .factors
+            .iter()
+            .filter(|f| f.code == "HIGH_RISK_JURISDICTION")
+            .map(|f| f.risk_uplift)
+            .sum();
+        assert_eq!(
+            outcome,
+            case["outcome"].as_str().expect("outcome"),
+            "{case}"
+        );
+        assert_eq!(
+            u64::from(uplift),
+            case["uplift"].as_u64().expect("uplift"),
+            "{case}"
+        );
+    }
+}
+
+#[test]
+fn snapshot_without_rules_still_loads() {
+    let snapshot = load_policy_snapshot("config/policy_snapshot.json").expect("policy snapshot");
+    assert!(snapshot.rules.countries.is_empty());
+    let evaluation = evaluate_intake_policy(
+        &[subject("ent-001", SubjectType::Entity, Some("GB"))],
+        &no_hits(),
+        &snapshot,
+    );
+    assert_eq!(evaluation.decision.as_str(), "PASS");
+    assert_eq!(evaluation.risk_score, 5);
+    assert!(evaluation.factors.is_empty());
+}
+
+#[test]
+fn blocked_and_unlisted_countries_fail() {
+    let policy = policy(json!({
+        "countries": ["US", "GB", "IR"],
+        "blocked_countries": ["IR"]
+    }));
+
+    let blocked = evaluate_intake_policy(
+        &[subject("ent-001", SubjectType::Entity, Some("ir"))],
+        &no_hits(),
+        &policy,
+    );
+    assert_eq!(blocked.decision.as_str(), "FAIL");
+    assert_eq!(codes(&blocked), ["JURISDICTION_BLOCKED"]);
+    assert_eq!(blocked.factors[0].subject_id.as_deref(), Some("ent-001"));
+
+    let unlisted = evaluate_intake_policy(
+        &[
+            subject("ent-001", SubjectType::Entity, Some("GB")),
+            subject("cp-001", SubjectType::Counterparty, Some("FR")),
+        ],
+        &no_hits(),
+        &policy,
+    );
+    assert_eq!(unlisted.decision.as_str(), "FAIL");
+    assert_eq!(codes(&unlisted), ["JURISDICTION_NOT_ALLOWED"]);
+    assert_eq!(unlisted.factors[0].subject_id.as_deref(), Some("cp-001"));
+
+    let missing = evaluate_intake_policy(
+        &[subject("cp-001", SubjectType::Counterparty, None)],
+        &no_hits(),
+        &policy,
+    );
+    assert_eq!(missing.decision.as_str(), "REVIEW");
+    assert_eq!(codes(&missing), ["JURISDICTION_MISSING"]);
+}
+
+#[test]
+fn high_risk_uplift_raises_score_and_escalates() {
+    let policy = policy(json!({
+        "high_risk_jurisdictions": {"AE": 20, "PA": 60}
+    }));
+
+    let uplifted = evaluate_intake_policy(
+        &[subject("ent-001", SubjectType::Entity, Some("AE"))],
+        &no_hits(),
+        &policy,
+    );
+    assert_eq!(uplifted.decision.as_str(), "PASS");
+    assert_eq!(uplifted.risk_score, 25);
+    assert_eq!(uplifted.factors[0].risk_uplift, 20);
+
+    let escalated = evaluate_intake_policy(
+        &[
+            subject("ent-001", SubjectType::Entity, Some("AE")),
+            subject("cp-001", SubjectType::Counterparty, Some("PA")),
+        ],
+        &no_hits(),
+        &policy,
+    );
+    assert_eq!(escalated.risk_score, 85);
+    assert_eq!(escalated.decision.as_str(), "REVIEW");
+    assert_eq!(
+        codes(&escalated),
+        ["HIGH_RISK_JURISDICTION", "HIGH_RISK_JURISDICTION"]
+    );
+}
+
+#[test]
+fn subject_type_thresholds_override_global_ones() {
+    let index = SanctionsIndex::build(
+        vec![SanctionsEntry {
+            source: "TEST".to_string(),
+            program: "DEMO".to_string(),
+            name: "Northwind Logistiks".to_string(),
+            jurisdiction: None,
+            address: None,
+            aliases: Vec::new(),
+            source_id: None,
+            published_at: None,
+            digital_currency_addresses: Vec::new(),
+        }],
+        "v1",
+    );
+    let subjects = [
+        subject("ent-001", SubjectType::Entity, Some("GB")),
+        subject("cp-001", SubjectType::Counterparty, Some("GB")),
+    ];
+    let screening = screen_subjects(&subjects, &index);
+    assert_eq!(screening.hits.len(), 2);
+    let confidence = screening.hits[0].confidence;
+    assert!(confidence < 100, "confidence {confidence}");
+
+    let policy = policy(json!({
+        "subject_thresholds": {
+            "counterparty": {"review_confidence": 50, "fail_confidence": confidence},
+            "entity": {"review_confidence": confidence + 1, "fail_confidence": 100}
+        }
+    }));
+    let evaluation = evaluate_intake_policy(&subjects, &screening, &policy);
+    assert_eq!(
+        evaluation.decision.as_str(),
+        ComplianceDecision::Fail.as_str()
+    );
+    assert_eq!(evaluation.risk_score, 95);
+    assert_eq!(codes(&evaluation), ["SANCTIONS_NAME_MATCH"]);
+    assert_eq!(evaluation.factors[0].subject_id.as_deref(), Some("cp-001"));
+}
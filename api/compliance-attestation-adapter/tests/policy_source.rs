@@ -0,0 +1,167 @@
+use axum::Router;
+use axum::extract::State;
+use axum::routing::get;
+use compliance_attestation_adapter::service::policy_source_service::PolicySource;
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::net::TcpListener;
+
+/// Stands in for policy-snapshot's `/v1/policy/active`, counting requests.
+#[derive(Clone, Default)]
+struct Upstream {
+    body: Arc<Mutex<Option<Value>>>,
+    hits: Arc<AtomicUsize>,
+}
+
+impl Upstream {
+    fn serve(&self, body: Option<Value>) {
+        *self.body.lock().unwrap() = body;
+    }
+
+    fn hits(&self) -> usize {
+        self.hits.load(Ordering::SeqCst)
+    }
+}
+
+async fn active(State(upstream): State<Upstream>) -> (http::StatusCode, String) {
+    upstream.hits.fetch_add(1, Ordering::SeqCst);
+    match upstream.body.lock().unwrap().clone() {
+        Some(body) => (http::StatusCode::OK, body.to_string()),
+        None => (http::StatusCode::SERVICE_UNAVAILABLE, String::new()),
+    }
+}
+
+async fn start_upstream() -> (Upstream, String) {
+    let upstream = Upstream::default();
+    let app = Router::new()
+        .route("/v1/policy/active", get(active))
+        .with_state(upstream.clone());
+    let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+    let addr = listener.local_addr().expect("addr");
+    tokio::spawn(async move { axum::serve(listener, app).await });
+    (upstream, format!("http://{addr}"))
+}
+
+/// Mirrors policy-snapshot: rules are canonicalized (sorted keys) and the
+/// hash is sha256 over the canonical JSON.
+fn active_response(version: &str, rules: Value) -> Value {
+    let canonical_rules_json = serde_json::to_string(&rules).expect("canonical");
+    let policy_hash = hex::encode(Sha256::digest(canonical_rules_json.as_bytes()));
+    json!({
+        "found": true,
+        "active_mapping": {
+            "onchain_policy_version": "1",
+            "policy_version": version,
+            "policy_hash": policy_hash,
+            "activated_at": 1_700_000_000,
+            "deactivated_at": null
+        },
+        "snapshot": {
+            "policy_version": version,
+            "policy_hash": policy_hash,
+            "canonical_rules": rules,
+            "canonical_rules_json": canonical_rules_json,
+            "metadata": null,
+            "created_at": 1_700_000_000
+        },
+        "error_code": null,
+        "reason": "active policy found"
+    })
+}
+
+fn rules(fail_confidence: u8) -> Value {
+    json!({
+        "limits": {"max_notional": 1000000, "min_notional": 100},
+        "countries": ["US", "GB"],
+        "blocked_countries": ["KP"],
+        "thresholds": {"fail_confidence": fail_confidence, "review_confidence": 70}
+    })
+}
+
+#[tokio::test]
+async fn remote_policy_is_verified_and_cached() {
+    let (upstream, url) = start_upstream().await;
+    upstream.serve(Some(active_response("policy-v2", rules(92))));
+    let source =
+        PolicySource::new(Some(&url), "missing.json", Duration::from_secs(60)).expect("source");
+
+    let policy = source.current().await.expect("policy");
+    assert_eq!(policy.active.version, "policy-v2");
+    assert_eq!(policy.thresholds.fail_confidence, 92);
+    assert_eq!(
+        policy.thresholds.fail_risk_score, 95,
+        "risk scores default when the bundle omits them"
+    );
+    assert_eq!(policy.rules.countries, ["US", "GB"]);
+    assert_eq!(policy.rules.blocked_countries, ["KP"]);
+
+    upstream.serve(Some(active_response("policy-v3", rules(95))));
+    assert_eq!(
+        source.current().await.expect("cached").active.version,
+        "policy-v2"
+    );
+    assert_eq!(upstream.hits(), 1);
+}
+
+#[tokio::test]
+async fn activation_is_picked_up_after_the_cache_expires() {
+    let (upstream, url) = start_upstream().await;
+    upstream.serve(Some(active_response("policy-v2", rules(92))));
+    let source = PolicySource::new(Some(&url), "missing.json", Duration::ZERO).expect("source");
+    assert_eq!(
+        source.current().await.expect("v2").active.version,
+        "policy-v2"
+    );
+
+    upstream.serve(Some(active_response("policy-v3", rules(95))));
+    let policy = source.current().await.expect("v3");
+    assert_eq!(policy.active.version, "policy-v3");
+    assert_eq!(policy.thresholds.fail_confidence, 95);
+
+    upstream.serve(None);
+    assert_eq!(
+        source
+            .current()
+            .await
+            .expect("last verified")
+            .active
+            .version,
+        "policy-v3",
+        "an outage keeps serving the last verified policy"
+    );
+}
+
+#[tokio::test]
+async fn tampered_rules_are_rejected() {
+    let (upstream, url) = start_upstream().await;
+    let mut body = active_response("policy-v2", rules(92));
+    body["snapshot"]["canonical_rules_json"] =
+        Value::String(serde_json::to_string(&rules(100)).expect("json"));
+    upstream.serve(Some(body));
+    let source = PolicySource::new(Some(&url), "missing.json", Duration::ZERO).expect("source");
+
+    let err = source.current().await.expect_err("hash mismatch");
+    assert!(err.contains("rules hash to"), "{err}");
+
+    upstream.serve(Some(json!({
+        "found": false,
+        "active_mapping": null,
+        "snapshot": null,
+        "error_code": null,
+        "reason": "no active policy"
+    })));
+    assert!(source.current().await.is_err());
+}
+
+#[tokio::test]
+async fn without_a_url_the_local_file_is_used() {
+    let source = PolicySource::new(None, "config/policy_snapshot.json", Duration::from_secs(30))
+        .expect("source");
+    assert!(!source.is_remote());
+    let policy = source.current().await.expect("file policy");
+    assert_eq!(policy.active.version, "policy-v1");
+    assert_eq!(policy.thresholds.fail_confidence, 90);
+}
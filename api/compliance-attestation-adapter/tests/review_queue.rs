@@ -0,0 +1,303 @@
+mod common;
+
+use axum::body::{Body, to_bytes};
+use compliance_attestation_adapter::app::{AppState, build_router};
+use compliance_attestation_adapter::config::environment::AppConfig;
+use compliance_attestation_adapter::module::compliance_attestation::model::{
+    AdjudicationRecord, ComplianceAttestationRecord,
+};
+use compliance_attestation_adapter::module::compliance_attestation::schema::{
+    AdjudicateReviewRequest, EntityInput, IntakeComplianceRequest, IntakeComplianceResponse,
+    ReviewQueueResponse, SubjectInput,
+};
+use compliance_attestation_adapter::service::review_service::{
+    REVIEW_APPROVED, REVIEW_PENDING, REVIEW_REJECTED, review_signing_payload,
+    supersede_reviewed_attestation,
+};
+use hmac::{Hmac, Mac};
+use http::Request;
+use serde_json::json;
+use sha2::Sha256;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tower::util::ServiceExt;
+
+fn config() -> AppConfig {
+    common::config(common::CIMEX_SANCTIONS)
+}
+
+fn pending_record() -> ComplianceAttestationRecord {
+    ComplianceAttestationRecord {
+        attestation_id: "attn_0123456789abcdef01234567".to_string(),
+        request_id: "req-1".to_string(),
+        workflow_run_id: "run-1".to_string(),
+        policy_version: "policy-v1".to_string(),
+        policy_hash: "0x01".to_string(),
+        decision: "REVIEW".to_string(),
+        risk_score: 60,
+        policy_factors: Vec::new(),
+        attestation_hash: "0123456789abcdef0123456789abcdef".to_string(),
+        issued_at: 1_700_000_000,
+        expires_at: 1_700_003_600,
+        sanctions_hit_count: 1,
+        normalized_subjects: Vec::new(),
+        fx_quote: None,
+        review_status: Some(REVIEW_PENDING.to_string()),
+        supersedes: None,
+        superseded_by: None,
+        adjudication: None,
+        signature: None,
+        signing_key_id: None,
+        revocation: None,
+        provider_refs: Vec::new(),
+    }
+}
+
+fn adjudication(outcome: &str) -> AdjudicationRecord {
+    AdjudicationRecord {
+        outcome: outcome.to_string(),
+        reason: "name collision with an unrelated branch office".to_string(),
+        reviewer: "analyst@zkclear".to_string(),
+        adjudicated_at: 1_700_000_500,
+        risk_score: Some(if outcome == "APPROVE" { 15 } else { 90 }),
+        pre_review_risk_score: None,
+    }
+}
+
+#[test]
+fn approval_reissues_a_pass_that_references_the_original() {
+    let original = pending_record();
+    let approved =
+        supersede_reviewed_attestation(&original, adjudication("APPROVE"), 3600).expect("approve");
+
+    assert_eq!(approved.decision, "PASS");
+    assert_eq!(approved.review_status.as_deref(), Some(REVIEW_APPROVED));
+    assert_eq!(
+        approved.supersedes.as_deref(),
+        Some(original.attestation_id.as_str())
+    );
+    assert_ne!(approved.attestation_hash, original.attestation_hash);
+    assert!(approved.attestation_id.starts_with("attn_"));
+    assert_eq!(approved.issued_at, 1_700_000_500);
+    assert_eq!(approved.expires_at, 1_700_004_100);
+    assert_eq!(approved.risk_score, 15);
+    let recorded = approved.adjudication.as_ref().expect("adjudication");
+    assert_eq!(recorded.risk_score, Some(15));
+    assert_eq!(recorded.pre_review_risk_score, Some(original.risk_score));
+    let factor = approved.policy_factors.last().expect("analyst factor");
+    assert_eq!(factor.code, "ANALYST_APPROVED");
+    assert!(factor.detail.starts_with("analyst@zkclear: "));
+    assert!(
+        factor.detail.ends_with("(risk 60 -> 15)"),
+        "{}",
+        factor.detail
+    );
+
+    let mut unscored = adjudication("APPROVE");
+    unscored.risk_score = None;
+    assert!(supersede_reviewed_attestation(&original, unscored, 3600).is_err());
+
+    let again =
+        supersede_reviewed_attestation(&original, adjudication("APPROVE"), 3600).expect("approve");
+    assert_eq!(again.attestation_hash, approved.attestation_hash);
+
+    let rejected =
+        supersede_reviewed_attestation(&original, adjudication("REJECT"), 3600).expect("reject");
+    assert_eq!(rejected.decision, "FAIL");
+    assert_eq!(rejected.review_status.as_deref(), Some(REVIEW_REJECTED));
+    assert_eq!(rejected.risk_score, 90);
+    assert_ne!(rejected.attestation_hash, approved.attestation_hash);
+}
+
+#[test]
+fn only_pending_reviews_can_be_adjudicated() {
+    let mut passed = pending_record();
+    passed.decision = "PASS".to_string();
+    passed.review_status = None;
+    assert!(supersede_reviewed_attestation(&passed, adjudication("APPROVE"), 3600).is_err());
+
+    let mut superseded = pending_record();
+    superseded.superseded_by = Some("attn_next".to_string());
+    let err = supersede_reviewed_attestation(&superseded, adjudication("APPROVE"), 3600)
+        .expect_err("already superseded");
+    assert!(err.contains("attn_next"), "{err}");
+}
+
+#[tokio::test]
+async fn review_decisions_start_pending() {
+    let app = build_router(AppState::new(config(), None));
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("clock")
+        .as_secs() as i64;
+    let request = IntakeComplianceRequest {
+        workflow_run_id: "run-1".to_string(),
+        request_id: "req-1".to_string(),
+        nonce: "nonce-1".to_string(),
+        timestamp: now,
+        internal_signature: None,
+        subjects: vec![SubjectInput {
+            counterparty: None,
+            entity: Some(EntityInput {
+                entity_id: "ent-001".to_string(),
+                registration_country: Some("ES".to_string()),
+                legal_name: Some("Cimex Iberica Havana Branch".to_string()),
+            }),
+        }],
+    };
+    let resp = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/compliance/intake")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_vec(&request).expect("serialize")))
+                .expect("build request"),
+        )
+        .await
+        .expect("response");
+    let body = to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .expect("read body");
+    let parsed: IntakeComplianceResponse = serde_json::from_slice(&body).expect("parse body");
+    assert_eq!(parsed.decision.as_str(), "REVIEW");
+    assert_eq!(parsed.review_status.as_deref(), Some(REVIEW_PENDING));
+    assert!(parsed.supersedes.is_none());
+}
+
+fn sign_review(secret: &str, payload: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("hmac key");
+    mac.update(payload.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+#[tokio::test]
+async fn review_endpoints_validate_before_touching_storage() {
+    let secret = "review-secret";
+    let app = build_router(AppState::new(
+        AppConfig {
+            internal_signing_secret: Some(secret.to_string()),
+            ..config()
+        },
+        None,
+    ));
+
+    let adjudicate = |body: serde_json::Value, reviewer: Option<&str>, signer: Option<&str>| {
+        let mut builder = Request::builder()
+            .method("POST")
+            .uri("/v1/compliance/reviews/attn_0123/adjudicate")
+            .header("content-type", "application/json");
+        if let Some(reviewer) = reviewer {
+            builder = builder.header("x-reviewer-id", reviewer);
+            if let Some(signer) = signer {
+                let req: AdjudicateReviewRequest =
+                    serde_json::from_value(body.clone()).expect("request");
+                let payload = review_signing_payload("attn_0123", reviewer, &req);
+                builder = builder.header("x-internal-signature", sign_review(signer, &payload));
+            }
+        }
+        builder
+            .body(Body::from(body.to_string()))
+            .expect("build request")
+    };
+    let approve = json!({"outcome": "APPROVE", "reason": "ok", "risk_score": 10});
+
+    for (request, code) in [
+        (adjudicate(approve.clone(), None, None), "MISSING_REVIEWER"),
+        (
+            adjudicate(approve.clone(), Some("analyst"), None),
+            "MISSING_SIGNATURE",
+        ),
+        (
+            adjudicate(approve.clone(), Some("analyst"), Some("wrong-secret")),
+            "BAD_SIGNATURE",
+        ),
+    ] {
+        let resp = app.clone().oneshot(request).await.expect("response");
+        assert_eq!(resp.status(), http::StatusCode::UNAUTHORIZED);
+        let body = to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .expect("read body");
+        let parsed: IntakeComplianceResponse = serde_json::from_slice(&body).expect("parse body");
+        assert_eq!(parsed.error_code.as_deref(), Some(code));
+    }
+
+    // A reviewer named in the body is ignored; the signed header decides.
+    let mut spoofed = approve.clone();
+    spoofed["reviewer"] = json!("someone-else");
+    let signed_for_analyst = {
+        let req: AdjudicateReviewRequest =
+            serde_json::from_value(approve.clone()).expect("request");
+        sign_review(
+            secret,
+            &review_signing_payload("attn_0123", "analyst", &req),
+        )
+    };
+    let resp = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/compliance/reviews/attn_0123/adjudicate")
+                .header("content-type", "application/json")
+                .header("x-reviewer-id", "someone-else")
+                .header("x-internal-signature", signed_for_analyst)
+                .body(Body::from(spoofed.to_string()))
+                .expect("build request"),
+        )
+        .await
+        .expect("response");
+    assert_eq!(resp.status(), http::StatusCode::UNAUTHORIZED);
+
+    let resp = app
+        .clone()
+        .oneshot(adjudicate(
+            json!({"outcome": "APPROVE", "reason": "ok", "risk_score": 101}),
+            Some("analyst"),
+            Some(secret),
+        ))
+        .await
+        .expect("response");
+    assert_eq!(resp.status(), http::StatusCode::BAD_REQUEST);
+    let body = to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .expect("read body");
+    let parsed: IntakeComplianceResponse = serde_json::from_slice(&body).expect("parse body");
+    assert_eq!(parsed.error_code.as_deref(), Some("INVALID_RISK_SCORE"));
+
+    let resp = app
+        .clone()
+        .oneshot(adjudicate(
+            json!({"outcome": "REJECT", "reason": "confirmed match", "risk_score": 95}),
+            Some("analyst"),
+            Some(secret),
+        ))
+        .await
+        .expect("response");
+    let body = to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .expect("read body");
+    let parsed: IntakeComplianceResponse = serde_json::from_slice(&body).expect("parse body");
+    assert_eq!(parsed.error_code.as_deref(), Some("PERSISTENCE_DISABLED"));
+
+    for (uri, code) in [
+        ("/v1/compliance/reviews?limit=0", "INVALID_LIMIT"),
+        ("/v1/compliance/reviews", "PERSISTENCE_DISABLED"),
+    ] {
+        let resp = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri(uri)
+                    .body(Body::empty())
+                    .expect("build request"),
+            )
+            .await
+            .expect("response");
+        assert_eq!(resp.status(), http::StatusCode::BAD_REQUEST);
+        let body = to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .expect("read body");
+        let parsed: ReviewQueueResponse = serde_json::from_slice(&body).expect("parse body");
+        assert_eq!(parsed.error_code.as_deref(), Some(code));
+    }
+}
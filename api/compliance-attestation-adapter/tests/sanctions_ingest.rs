@@ -0,0 +1,187 @@
+use compliance_attestation_adapter::service::sanctions_ingest_service::{
+    EuConsolidatedParser, OfacParser, SanctionsListParser, UkHmtParser, UnConsolidatedParser,
+    diff_lists, entry_key, merge_lists,
+};
+use compliance_attestation_adapter::service::sanctions_service::SanctionsEntry;
+use std::path::PathBuf;
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from("tests/data/lists").join(name)
+}
+
+fn find<'a>(entries: &'a [SanctionsEntry], source_id: &str) -> &'a SanctionsEntry {
+    entries
+        .iter()
+        .find(|e| e.source_id.as_deref() == Some(source_id))
+        .unwrap_or_else(|| panic!("entry {source_id} missing"))
+}
+
+fn ofac() -> Vec<SanctionsEntry> {
+    OfacParser {
+        sdn_path: fixture("ofac_sdn.csv"),
+        alt_path: Some(fixture("ofac_alt.csv")),
+        add_path: Some(fixture("ofac_add.csv")),
+    }
+    .parse()
+    .expect("ofac")
+}
+
+#[test]
+fn ofac_joins_aliases_and_addresses_by_entity_number() {
+    let entries = ofac();
+    assert_eq!(entries.len(), 5, "the first row is data, not a header");
+
+    let trust = find(&entries, "2674");
+    assert_eq!(trust.source, "OFAC");
+    assert_eq!(trust.program, "SDGT");
+    assert_eq!(trust.aliases, ["AL-RASHID TRUST", "AL RASHEED TRUST"]);
+    assert_eq!(trust.jurisdiction.as_deref(), Some("Pakistan"));
+    assert_eq!(trust.address.as_deref(), Some("Kitab Ghar, Karachi"));
+    assert_eq!(
+        trust.published_at.as_deref().map(str::len),
+        Some("YYYY-MM-DD".len())
+    );
+
+    let anglo = find(&entries, "173");
+    assert_eq!(anglo.jurisdiction.as_deref(), Some("United Kingdom"));
+    assert_eq!(find(&entries, "36").address.as_deref(), Some("Havana"));
+}
+
+#[test]
+fn eu_reads_name_aliases_programme_and_generation_date() {
+    let entries = EuConsolidatedParser {
+        path: fixture("eu_consolidated.xml"),
+    }
+    .parse()
+    .expect("eu");
+    assert_eq!(entries.len(), 2, "entities without a name are skipped");
+
+    let saddam = find(&entries, "13");
+    assert_eq!(saddam.source, "EU");
+    assert_eq!(saddam.name, "Saddam Hussein Al-Tikriti");
+    assert_eq!(saddam.aliases, ["Abu Ali", "صدام حسين"]);
+    assert_eq!(saddam.program, "IRQ");
+    assert_eq!(saddam.jurisdiction.as_deref(), Some("IQ"));
+    assert_eq!(saddam.published_at.as_deref(), Some("2024-05-02"));
+
+    let trust = find(&entries, "118");
+    assert_eq!(trust.jurisdiction.as_deref(), Some("PK"));
+    assert_eq!(trust.address.as_deref(), Some("Kitab Ghar, Karachi"));
+}
+
+#[test]
+fn un_reads_individuals_and_entities() {
+    let entries = UnConsolidatedParser {
+        path: fixture("un_consolidated.xml"),
+    }
+    .parse()
+    .expect("un");
+    assert_eq!(entries.len(), 2);
+
+    let ri = find(&entries, "KPi.001");
+    assert_eq!(ri.name, "RI WON HO");
+    assert_eq!(ri.program, "DPRK");
+    assert_eq!(ri.aliases, ["Ri Won-ho", "리원호"]);
+    assert_eq!(
+        ri.jurisdiction.as_deref(),
+        Some("Democratic People's Republic of Korea")
+    );
+    assert_eq!(ri.published_at.as_deref(), Some("2024-05-03"));
+
+    let trust = find(&entries, "QDe.005");
+    assert_eq!(trust.program, "Al-Qaida");
+    assert_eq!(trust.jurisdiction.as_deref(), Some("Pakistan"));
+}
+
+#[test]
+fn uk_hmt_groups_name_rows_under_the_primary_name() {
+    let entries = UkHmtParser {
+        path: fixture("uk_hmt.csv"),
+    }
+    .parse()
+    .expect("uk");
+    assert_eq!(entries.len(), 2);
+
+    let saddam = find(&entries, "7234");
+    assert_eq!(saddam.source, "UK_HMT");
+    assert_eq!(saddam.name, "Saddam HUSSEIN AL-TIKRITI");
+    assert!(saddam.aliases.contains(&"ABU ALI".to_string()));
+    assert_eq!(saddam.program, "Iraq");
+    assert_eq!(saddam.published_at.as_deref(), Some("2024-05-01"));
+
+    let trust = find(&entries, "6896");
+    assert_eq!(
+        trust.name, "AL RASHID TRUST",
+        "primary row listed after an AKA"
+    );
+    assert_eq!(trust.aliases, ["AL RASHEED TRUST"]);
+    assert_eq!(trust.jurisdiction.as_deref(), Some("Pakistan"));
+}
+
+#[test]
+fn merge_dedups_within_a_list_and_keeps_lists_apart() {
+    let uk = UkHmtParser {
+        path: fixture("uk_hmt.csv"),
+    }
+    .parse()
+    .expect("uk");
+    let merged = merge_lists(vec![ofac(), uk]);
+    assert_eq!(merged.len(), 7);
+
+    let saddam = find(&merged, "7234");
+    assert_eq!(
+        saddam.aliases,
+        ["ABU ALI"],
+        "AKAs repeating the primary name or each other collapse"
+    );
+    assert_eq!(
+        merged
+            .iter()
+            .filter(|e| e.name.eq_ignore_ascii_case("AL RASHID TRUST"))
+            .map(|e| e.source.as_str())
+            .collect::<Vec<_>>(),
+        ["OFAC", "UK_HMT"]
+    );
+
+    let mut duplicate = find(&merged, "2674").clone();
+    duplicate.program = "SDGT2".to_string();
+    duplicate.aliases = vec!["AL RASHID TRUST".to_string(), "RASHID TRUST".to_string()];
+    let remerged = merge_lists(vec![merged.clone(), vec![duplicate]]);
+    let trust = find(&remerged, "2674");
+    assert_eq!(remerged.len(), merged.len());
+    assert_eq!(trust.program, "SDGT; SDGT2");
+    assert_eq!(trust.aliases, ["AL RASHEED TRUST", "RASHID TRUST"]);
+}
+
+#[test]
+fn diff_reports_added_and_removed_entries() {
+    let previous = ofac();
+    let mut current = previous.clone();
+    let removed = current.remove(0);
+    current.extend(
+        UnConsolidatedParser {
+            path: fixture("un_consolidated.xml"),
+        }
+        .parse()
+        .expect("un"),
+    );
+
+    let report = diff_lists(&previous, &current);
+    assert_eq!(report.previous_count, 5);
+    assert_eq!(report.current_count, 6);
+    assert_eq!(
+        report.removed.iter().map(|e| &e.key).collect::<Vec<_>>(),
+        [&entry_key(&removed)]
+    );
+    assert_eq!(
+        report
+            .added
+            .iter()
+            .map(|e| e.key.as_str())
+            .collect::<Vec<_>>(),
+        ["UN:KPi.001", "UN:QDe.005"]
+    );
+    assert_eq!(report.current_count_by_source["OFAC"], 4);
+    assert_eq!(report.current_count_by_source["UN"], 2);
+    assert!(diff_lists(&current, &current).added.is_empty());
+}
@@ -0,0 +1,160 @@
+mod common;
+
+use axum::body::{Body, to_bytes};
+use compliance_attestation_adapter::app::{AppState, build_router};
+use compliance_attestation_adapter::module::compliance_attestation::model::{
+    NormalizedSubject, SubjectType,
+};
+use compliance_attestation_adapter::module::compliance_attestation::schema::HealthResponse;
+use compliance_attestation_adapter::service::name_match_service::{fold_name, name_confidence};
+use compliance_attestation_adapter::service::sanctions_service::{
+    MIN_HIT_CONFIDENCE, SanctionsIndex, load_sanctions_entries, screen_subjects,
+};
+use compliance_attestation_adapter::service::sanctions_store_service::SanctionsStore;
+use http::Request;
+use std::fs;
+use std::path::PathBuf;
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+fn sanctions_file(contents: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("zkclear-sanctions-{}.json", Uuid::now_v7()));
+    fs::write(&path, contents).expect("write sanctions fixture");
+    path
+}
+
+fn entry_json(name: &str) -> String {
+    format!(
+        r#"{{"source":"TEST","program":"DEMO","name":"{name}","jurisdiction":null,"address":null}}"#
+    )
+}
+
+fn subject(legal_name: &str) -> NormalizedSubject {
+    NormalizedSubject {
+        subject_id: "ent-001".to_string(),
+        subject_type: SubjectType::Entity,
+        jurisdiction: None,
+        address: None,
+        legal_name: Some(legal_name.to_string()),
+        wallet_address: None,
+    }
+}
+
+#[test]
+fn index_finds_what_a_full_scan_finds() {
+    let mut entries = load_sanctions_entries("data/sanctions.json").expect("dataset");
+    entries.truncate(3000);
+    let folded = entries
+        .iter()
+        .map(|e| fold_name(&e.name))
+        .collect::<Vec<_>>();
+    let index = SanctionsIndex::build(entries.clone(), "sample");
+
+    for query in [
+        "Banco Nacional de Cuba",
+        "Cimex Iberica",
+        "Mohammed Al Rashid",
+        "Anglo Caribbean Company",
+        "Havana Trading",
+    ] {
+        let candidates = [fold_name("ent-001"), fold_name(query)];
+        let mut expected = entries
+            .iter()
+            .zip(&folded)
+            .filter_map(|(entry, listed)| {
+                let confidence = candidates
+                    .iter()
+                    .map(|c| name_confidence(c, listed))
+                    .max()
+                    .unwrap_or(0);
+                (confidence >= MIN_HIT_CONFIDENCE).then(|| (entry.name.clone(), confidence))
+            })
+            .collect::<Vec<_>>();
+        let mut actual = screen_subjects(&[subject(query)], &index)
+            .hits
+            .into_iter()
+            .map(|h| (h.entry_name, h.confidence))
+            .collect::<Vec<_>>();
+        expected.sort();
+        actual.sort();
+        assert_eq!(actual, expected, "query {query:?}");
+    }
+}
+
+#[test]
+fn store_swaps_the_whole_list_when_the_file_changes() {
+    let path = sanctions_file(&format!("[{}]", entry_json("ALPHA TRADING")));
+    let store = SanctionsStore::open(&path);
+    let first = store.current().expect("loaded");
+    let first_status = store.status();
+    assert_eq!(first_status.entry_count, 1);
+    assert_eq!(first_status.version.as_deref(), Some(first.version()));
+    assert!(!store.reload().expect("unchanged"));
+
+    fs::write(
+        &path,
+        format!(
+            "[{},{}]",
+            entry_json("ALPHA TRADING"),
+            entry_json("BRAVO SHIPPING")
+        ),
+    )
+    .expect("rewrite");
+    assert!(store.reload().expect("changed"));
+    let second = store.current().expect("reloaded");
+    assert_eq!(second.entry_count(), 2);
+    assert_ne!(second.version(), first.version());
+    // Screenings holding the previous index keep a consistent view.
+    assert_eq!(first.entry_count(), 1);
+
+    fs::write(&path, "not json").expect("corrupt");
+    assert!(store.reload().is_err());
+    let status = store.status();
+    assert_eq!(status.version.as_deref(), Some(second.version()));
+    assert_eq!(status.entry_count, 2);
+    assert!(status.last_error.is_some());
+}
+
+#[test]
+fn store_reports_a_missing_file_instead_of_panicking() {
+    let store = SanctionsStore::open(std::env::temp_dir().join("zkclear-missing-sanctions.json"));
+    assert!(store.current().is_err());
+    let status = store.status();
+    assert_eq!(status.version, None);
+    assert_eq!(status.entry_count, 0);
+    assert!(status.last_error.is_some());
+}
+
+#[tokio::test]
+async fn health_reports_loaded_list_version_and_size() {
+    let path = sanctions_file(&format!(
+        "[{},{}]",
+        entry_json("ALPHA TRADING"),
+        entry_json("BRAVO SHIPPING")
+    ));
+    let config = common::config_with_sanctions_path(&path);
+
+    let state = AppState::new(config, None);
+    let expected_version = state.sanctions.status().version;
+    let app = build_router(state);
+
+    let resp = app
+        .oneshot(
+            Request::builder()
+                .uri("/v1/compliance/health")
+                .body(Body::empty())
+                .expect("build request"),
+        )
+        .await
+        .expect("response");
+    assert_eq!(resp.status(), http::StatusCode::OK);
+    let body = to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .expect("read body");
+    let health: HealthResponse = serde_json::from_slice(&body).expect("parse body");
+    assert_eq!(health.status, "ok");
+    assert_eq!(health.sanctions_entry_count, 2);
+    assert!(health.sanctions_list_version.is_some());
+    assert_eq!(health.sanctions_list_version, expected_version);
+    assert!(health.sanctions_loaded_at.is_some());
+}
@@ -0,0 +1,235 @@
+mod common;
+
+use axum::body::{Body, to_bytes};
+use compliance_attestation_adapter::app::{AppState, build_router};
+use compliance_attestation_adapter::module::compliance_attestation::model::{
+    NormalizedSubject, SubjectType,
+};
+use compliance_attestation_adapter::module::compliance_attestation::schema::{
+    ComplianceDecision, CounterpartyInput, IntakeComplianceRequest, IntakeComplianceResponse,
+    SubjectInput,
+};
+use compliance_attestation_adapter::service::policy_eval_service::{
+    ActivePolicy, PolicySnapshot, PolicyThresholds, evaluate_intake_policy,
+};
+use compliance_attestation_adapter::service::sanctions_ingest_service::{
+    OfacParser, SanctionsListParser,
+};
+use compliance_attestation_adapter::service::sanctions_service::{
+    DigitalCurrencyAddress, HitKind, SanctionsEntry, SanctionsIndex, screen_subjects,
+};
+use compliance_attestation_adapter::service::wallet_screening_service::{
+    canonical_wallet_address, validate_wallet_address,
+};
+use http::Request;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+// EIP-55 reference vectors.
+const CHECKSUMMED: &str = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+const CHECKSUMMED_2: &str = "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359";
+
+fn listed_entry(address: &str) -> SanctionsEntry {
+    SanctionsEntry {
+        source: "OFAC".to_string(),
+        program: "CYBER2".to_string(),
+        name: "Garantex Europe OU".to_string(),
+        jurisdiction: None,
+        address: None,
+        aliases: Vec::new(),
+        source_id: Some("1001".to_string()),
+        published_at: None,
+        digital_currency_addresses: vec![DigitalCurrencyAddress {
+            currency: "ETH".to_string(),
+            address: address.to_string(),
+        }],
+    }
+}
+
+fn counterparty(wallet: &str) -> NormalizedSubject {
+    NormalizedSubject {
+        subject_id: "cp-001".to_string(),
+        subject_type: SubjectType::Counterparty,
+        jurisdiction: None,
+        address: None,
+        legal_name: None,
+        wallet_address: Some(validate_wallet_address(wallet).expect("valid wallet")),
+    }
+}
+
+#[test]
+fn evm_addresses_canonicalize_to_their_checksum() {
+    for vector in [CHECKSUMMED, CHECKSUMMED_2] {
+        assert_eq!(
+            canonical_wallet_address(&vector.to_lowercase()).as_deref(),
+            Some(vector)
+        );
+        assert_eq!(
+            canonical_wallet_address(&vector.to_uppercase().replacen("0X", "0x", 1)).as_deref(),
+            Some(vector)
+        );
+        assert_eq!(validate_wallet_address(vector).as_deref(), Ok(vector));
+    }
+
+    let mistyped = CHECKSUMMED.replacen("aA", "Aa", 1);
+    assert!(validate_wallet_address(&mistyped).is_err());
+    assert!(validate_wallet_address("not a wallet").is_err());
+    assert!(validate_wallet_address("0x1234").is_err());
+}
+
+#[test]
+fn bech32_is_case_insensitive_and_base58_is_not() {
+    assert_eq!(
+        canonical_wallet_address("BC1QAR0SRRR7XFKVY5L643LYDNW9RE59GTZZWF5MDQ").as_deref(),
+        Some("bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq")
+    );
+    assert_eq!(
+        canonical_wallet_address(" 12HQDsicffSBaYdJ6BhnE22sfjTESmmzKx ").as_deref(),
+        Some("12HQDsicffSBaYdJ6BhnE22sfjTESmmzKx")
+    );
+    assert_ne!(
+        canonical_wallet_address("12hqdsicffsbaydj6bhne22sfjtesmmzkx"),
+        canonical_wallet_address("12HQDsicffSBaYdJ6BhnE22sfjTESmmzKx")
+    );
+}
+
+#[test]
+fn listed_wallet_fails_regardless_of_thresholds() {
+    let index = SanctionsIndex::build(vec![listed_entry(&CHECKSUMMED.to_lowercase())], "v1");
+    let subjects = [counterparty(CHECKSUMMED)];
+    let result = screen_subjects(&subjects, &index);
+    assert_eq!(result.hits.len(), 1);
+    assert_eq!(result.hits[0].kind, HitKind::WalletAddress);
+    assert_eq!(result.hits[0].matched_name, CHECKSUMMED);
+
+    let lenient = PolicySnapshot {
+        active: ActivePolicy {
+            version: "policy-v1".to_string(),
+            hash: "0x00".to_string(),
+        },
+        thresholds: PolicyThresholds {
+            review_confidence: 255,
+            fail_confidence: 255,
+            pass_risk_score: 0,
+            review_risk_score: 50,
+            fail_risk_score: 100,
+        },
+        rules: Default::default(),
+    };
+    let evaluation = evaluate_intake_policy(&subjects, &result, &lenient);
+    assert_eq!(
+        evaluation.decision.as_str(),
+        ComplianceDecision::Fail.as_str()
+    );
+    assert_eq!(evaluation.risk_score, 100);
+    assert_eq!(evaluation.factors[0].code, "SANCTIONED_WALLET_ADDRESS");
+
+    let clean = screen_subjects(&[counterparty(CHECKSUMMED_2)], &index);
+    assert!(clean.hits.is_empty());
+}
+
+#[test]
+fn ofac_remarks_yield_digital_currency_addresses() {
+    let fixture = PathBuf::from("tests/data/lists");
+    let entries = OfacParser {
+        sdn_path: fixture.join("ofac_sdn.csv"),
+        alt_path: None,
+        add_path: None,
+    }
+    .parse()
+    .expect("ofac");
+
+    let suex = entries
+        .iter()
+        .find(|e| e.source_id.as_deref() == Some("39750"))
+        .expect("SUEX row");
+    assert_eq!(
+        suex.digital_currency_addresses
+            .iter()
+            .map(|a| (a.currency.as_str(), a.address.as_str()))
+            .collect::<Vec<_>>(),
+        [
+            ("XBT", "12HQDsicffSBaYdJ6BhnE22sfjTESmmzKx"),
+            ("ETH", "0x2f389ce8bd8ff92de3402ffce4691d17fc4f6535"),
+            ("USDT", "0x19aa5fe80d33a56d56c78e82ea5e50e5d80b4dff"),
+        ]
+    );
+    assert!(
+        entries
+            .iter()
+            .filter(|e| e.source_id.as_deref() != Some("39750"))
+            .all(|e| e.digital_currency_addresses.is_empty())
+    );
+}
+
+#[tokio::test]
+async fn intake_screens_wallets_and_rejects_bad_checksums() {
+    let sanctions_path =
+        std::env::temp_dir().join(format!("zkclear-sanctions-{}.json", Uuid::now_v7()));
+    fs::write(
+        &sanctions_path,
+        serde_json::to_vec(&[listed_entry(&CHECKSUMMED.to_lowercase())]).expect("serialize"),
+    )
+    .expect("write sanctions fixture");
+
+    let config = common::config_with_sanctions_path(&sanctions_path);
+
+    let app = build_router(AppState::new(config, None));
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("clock")
+        .as_secs() as i64;
+
+    let intake = |request_id: &str, wallet: &str| {
+        let request = IntakeComplianceRequest {
+            workflow_run_id: "run-1".to_string(),
+            request_id: request_id.to_string(),
+            nonce: format!("nonce-{request_id}"),
+            timestamp: now,
+            internal_signature: None,
+            subjects: vec![SubjectInput {
+                counterparty: Some(CounterpartyInput {
+                    counterparty_id: "cp-001".to_string(),
+                    country: None,
+                    wallet_address: Some(wallet.to_string()),
+                }),
+                entity: None,
+            }],
+        };
+        Request::builder()
+            .method("POST")
+            .uri("/v1/compliance/intake")
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::to_vec(&request).expect("serialize")))
+            .expect("build request")
+    };
+
+    let resp = app
+        .clone()
+        .oneshot(intake(
+            "req-1",
+            &CHECKSUMMED.to_uppercase().replacen("0X", "0x", 1),
+        ))
+        .await
+        .expect("response");
+    assert_eq!(resp.status(), http::StatusCode::OK);
+    let body = to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .expect("read body");
+    let parsed: IntakeComplianceResponse = serde_json::from_slice(&body).expect("parse body");
+    assert_eq!(parsed.decision.as_str(), ComplianceDecision::Fail.as_str());
+    assert_eq!(parsed.sanctions_hit_count, 1);
+
+    let resp = app
+        .oneshot(intake("req-2", &CHECKSUMMED.replacen("aA", "Aa", 1)))
+        .await
+        .expect("response");
+    assert_eq!(resp.status(), http::StatusCode::BAD_REQUEST);
+    let body = to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .expect("read body");
+    assert!(String::from_utf8_lossy(&body).contains("INVALID_WALLET_ADDRESS"));
+}
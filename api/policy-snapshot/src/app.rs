@@ -1,7 +1,9 @@
 use crate::config::environment::AppConfig;
 use crate::infra::InfraClients;
+use crate::infra::policy_manager_client::{PolicyManagerClient, policy_manager_from_config};
 use crate::module::policy_snapshot::crud::PolicyStore;
 use crate::module::policy_snapshot::route::register_routes;
+use crate::service::evidence_signing_service::EvidenceKeyRing;
 use axum::Router;
 use std::sync::Arc;
 
@@ -10,15 +12,26 @@ pub struct AppState {
     pub config: AppConfig,
     pub store: Arc<PolicyStore>,
     pub infra: Option<InfraClients>,
+    pub policy_manager: Option<Arc<PolicyManagerClient>>,
+    pub evidence_keys: Arc<EvidenceKeyRing>,
 }
 
 impl AppState {
-    pub fn new(config: AppConfig, infra: Option<InfraClients>) -> Self {
-        Self {
+    /// Fails on a PolicyManager or evidence key config that cannot be used,
+    /// rather than silently running without onchain checks or signatures.
+    pub fn new(config: AppConfig, infra: Option<InfraClients>) -> Result<Self, String> {
+        let policy_manager = policy_manager_from_config(&config)?.map(Arc::new);
+        let evidence_keys = EvidenceKeyRing::new(
+            config.evidence_signing_key_hex.as_deref(),
+            &config.evidence_retired_public_keys,
+        )?;
+        Ok(Self {
             config,
             store: Arc::new(PolicyStore::default()),
             infra,
-        }
+            policy_manager,
+            evidence_keys: Arc::new(evidence_keys),
+        })
     }
 }
 
@@ -1,34 +1,91 @@
+use ethers::types::Address;
+use ethers::utils::to_checksum;
+use std::collections::BTreeMap;
 use std::env;
+use std::str::FromStr;
 
 #[derive(Debug, Clone)]
 pub struct AppConfig {
     pub rust_env: String,
     pub api_host: String,
     pub api_port: u16,
-    pub audit_signing_secret: Option<String>,
+    /// Hex secp256k1 key that signs run evidence.
+    pub evidence_signing_key_hex: Option<String>,
+    /// SEC1 hex public keys of rotated-out evidence keys, kept for verification.
+    pub evidence_retired_public_keys: Vec<String>,
     pub mongodb_url: Option<String>,
     pub mongodb_database: Option<String>,
     pub redis_url: Option<String>,
     pub require_internal_auth: bool,
     pub internal_auth_secret: Option<String>,
+    /// Approver id to the checksummed address of the secp256k1 key it signs
+    /// activation approvals with; the keys stay with the approvers. When any
+    /// are configured, activations go through proposals only.
+    pub activation_approvers: BTreeMap<String, String>,
+    pub activation_required_approvals: usize,
+    /// PolicyManager to check activations against; both must be set to enable
+    /// onchain checks and reconciliation.
+    pub policy_manager_rpc_url: Option<String>,
+    pub policy_manager_address: Option<String>,
+    /// First block scanned for PolicyManager events.
+    pub policy_manager_from_block: u64,
 }
 
 impl AppConfig {
     pub fn from_env() -> Result<Self, String> {
         load_dotenv_layers();
-        Ok(Self {
+        Self {
             rust_env: read_var("RUST_ENV")?,
             api_host: read_var("API_HOST")?,
             api_port: read_var("API_PORT")?
                 .parse::<u16>()
                 .map_err(|e| format!("invalid API_PORT: {e}"))?,
-            audit_signing_secret: env::var("AUDIT_SIGNING_SECRET").ok(),
+            evidence_signing_key_hex: env::var("EVIDENCE_SIGNING_KEY_HEX")
+                .ok()
+                .filter(|v| !v.trim().is_empty()),
+            evidence_retired_public_keys: read_list("EVIDENCE_RETIRED_PUBLIC_KEYS"),
             mongodb_url: env::var("MONGODB_URL").ok(),
             mongodb_database: env::var("MONGODB_DATABASE").ok(),
             redis_url: env::var("REDIS_URL").ok(),
             require_internal_auth: read_optional_bool("REQUIRE_INTERNAL_AUTH", false),
             internal_auth_secret: env::var("INTERNAL_AUTH_SECRET").ok(),
-        })
+            activation_approvers: read_approvers("ACTIVATION_APPROVERS")?,
+            activation_required_approvals: env::var("ACTIVATION_REQUIRED_APPROVALS")
+                .ok()
+                .map(|v| v.parse::<usize>())
+                .transpose()
+                .map_err(|e| format!("invalid ACTIVATION_REQUIRED_APPROVALS: {e}"))?
+                .unwrap_or(2),
+            policy_manager_rpc_url: env::var("POLICY_MANAGER_RPC_URL")
+                .ok()
+                .filter(|v| !v.trim().is_empty()),
+            policy_manager_address: env::var("POLICY_MANAGER_ADDRESS")
+                .ok()
+                .filter(|v| !v.trim().is_empty()),
+            policy_manager_from_block: env::var("POLICY_MANAGER_FROM_BLOCK")
+                .ok()
+                .map(|v| v.parse::<u64>())
+                .transpose()
+                .map_err(|e| format!("invalid POLICY_MANAGER_FROM_BLOCK: {e}"))?
+                .unwrap_or(0),
+        }
+        .validated()
+    }
+
+    fn validated(self) -> Result<Self, String> {
+        let approvers = self.activation_approvers.len();
+        if approvers > 0 && !(1..=approvers).contains(&self.activation_required_approvals) {
+            return Err(format!(
+                "ACTIVATION_REQUIRED_APPROVALS must be between 1 and {approvers} configured approvers"
+            ));
+        }
+        if self.policy_manager_rpc_url.is_some() != self.policy_manager_address.is_some() {
+            return Err(
+                "POLICY_MANAGER_RPC_URL and POLICY_MANAGER_ADDRESS must be set together"
+                    .to_string(),
+            );
+        }
+        Ok(self)
     }
 }
 
@@ -36,6 +93,41 @@ fn read_var(key: &str) -> Result<String, String> {
     env::var(key).map_err(|_| format!("missing required env var: {key}"))
 }
 
+fn read_list(key: &str) -> Vec<String> {
+    env::var(key)
+        .map(|raw| {
+            raw.split(',')
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Parses `id:0xaddress` pairs separated by commas.
+fn read_approvers(key: &str) -> Result<BTreeMap<String, String>, String> {
+    let Ok(raw) = env::var(key) else {
+        return Ok(BTreeMap::new());
+    };
+    let mut approvers = BTreeMap::new();
+    for entry in raw.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        let (id, address) = entry
+            .split_once(':')
+            .filter(|(id, _)| !id.trim().is_empty())
+            .ok_or_else(|| format!("invalid {key} entry: expected approver_id:0xaddress"))?;
+        let address = Address::from_str(address.trim())
+            .map_err(|e| format!("invalid {key} address for {}: {e}", id.trim()))?;
+        if approvers
+            .insert(id.trim().to_string(), to_checksum(&address, None))
+            .is_some()
+        {
+            return Err(format!("duplicate approver in {key}: {}", id.trim()));
+        }
+    }
+    Ok(approvers)
+}
+
 fn load_dotenv_layers() {
     for path in [".env", "../.env", "../../.env"] {
         let _ = dotenvy::from_path(path);
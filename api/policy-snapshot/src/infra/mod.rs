@@ -1,3 +1,5 @@
+pub mod policy_manager_client;
+
 use crate::config::environment::AppConfig;
 use mongodb::Client as MongoClient;
 use mongodb::Database;
@@ -7,6 +9,8 @@ pub const SNAPSHOTS_COLLECTION: &str = "policy_snapshots";
 pub const ACTIVATION_HISTORY_COLLECTION: &str = "policy_activation_history";
 pub const RUN_EVIDENCE_COLLECTION: &str = "policy_run_evidence";
 pub const AUDIT_LOG_COLLECTION: &str = "policy_audit_log";
+pub const RUN_EVALUATIONS_COLLECTION: &str = "policy_run_evaluations";
+pub const ACTIVATION_PROPOSALS_COLLECTION: &str = "policy_activation_proposals";
 
 #[derive(Debug, Clone)]
 pub struct InfraClients {
@@ -30,6 +34,7 @@ pub async fn init_infra(config: &AppConfig) -> Result<Option<InfraClients>, Stri
         .map_err(|e| format!("mongodb client init failed: {e}"))?;
     let mongo_db = mongo_client.database(mongo_db_name);
 
-    let redis = RedisClient::open(redis_url.clone()).map_err(|e| format!("redis client init failed: {e}"))?;
+    let redis = RedisClient::open(redis_url.clone())
+        .map_err(|e| format!("redis client init failed: {e}"))?;
     Ok(Some(InfraClients { mongo_db, redis }))
 }
@@ -0,0 +1,210 @@
+use crate::config::environment::AppConfig;
+use crate::module::policy_snapshot::schema::{
+    OnchainActivePolicy, OnchainEventKind, OnchainPolicyEvent,
+};
+use ethers::abi::{Abi, parse_abi};
+use ethers::contract::Contract;
+use ethers::providers::{Http, Middleware, Provider};
+use ethers::types::{Address, BlockNumber, Filter, H256, Log, U256};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+
+const POLICY_MANAGER_ABI: &[&str] = &[
+    "function policyHashOf(uint64 version) external view returns (bytes32)",
+    "function getActivePolicy() external view returns (uint64 version, bytes32 policyHash, bytes32 metadataHash)",
+    "event PolicyCommitted(uint64 indexed version, bytes32 indexed policyHash, bytes32 metadataHash)",
+    "event PolicyActivated(uint64 indexed version)",
+    "event PolicyDeactivated(uint64 indexed version)",
+];
+
+/// Read-only view of the onchain `PolicyManager` contract.
+#[derive(Debug, Clone)]
+pub struct PolicyManagerClient {
+    contract: Contract<Provider<Http>>,
+    from_block: u64,
+}
+
+/// Builds the client when `POLICY_MANAGER_RPC_URL` and `POLICY_MANAGER_ADDRESS`
+/// are set; onchain checks are skipped otherwise.
+pub fn policy_manager_from_config(
+    config: &AppConfig,
+) -> Result<Option<PolicyManagerClient>, String> {
+    let (Some(rpc_url), Some(address)) = (
+        &config.policy_manager_rpc_url,
+        &config.policy_manager_address,
+    ) else {
+        return Ok(None);
+    };
+    PolicyManagerClient::new(rpc_url, address, config.policy_manager_from_block).map(Some)
+}
+
+impl PolicyManagerClient {
+    pub fn new(rpc_url: &str, address: &str, from_block: u64) -> Result<Self, String> {
+        let provider = Provider::<Http>::try_from(rpc_url)
+            .map_err(|e| format!("invalid POLICY_MANAGER_RPC_URL: {e}"))?;
+        let address = Address::from_str(address)
+            .map_err(|e| format!("invalid POLICY_MANAGER_ADDRESS: {e}"))?;
+        let abi = parse_abi(POLICY_MANAGER_ABI).map_err(|e| format!("policy manager abi: {e}"))?;
+        Ok(Self {
+            contract: Contract::new(address, abi, Arc::new(provider)),
+            from_block,
+        })
+    }
+
+    /// The hash committed for `version`, or `None` when it was never committed.
+    pub async fn policy_hash_of(&self, version: u64) -> Result<Option<String>, String> {
+        let call = self
+            .contract
+            .method::<_, [u8; 32]>("policyHashOf", version)
+            .map_err(|e| format!("policyHashOf encode failed: {e}"))?;
+        match call.call().await {
+            Ok(hash) => Ok(Some(hex::encode(hash))),
+            // The only revert is PolicyNotFound.
+            Err(e) if e.is_revert() => Ok(None),
+            Err(e) => Err(format!("policyHashOf({version}) failed: {e}")),
+        }
+    }
+
+    pub async fn active_policy(&self) -> Result<Option<OnchainActivePolicy>, String> {
+        let (version, policy_hash, _metadata_hash) = self
+            .contract
+            .method::<_, (u64, [u8; 32], [u8; 32])>("getActivePolicy", ())
+            .map_err(|e| format!("getActivePolicy encode failed: {e}"))?
+            .call()
+            .await
+            .map_err(|e| format!("getActivePolicy failed: {e}"))?;
+        Ok((version != 0).then(|| OnchainActivePolicy {
+            version,
+            policy_hash: hex::encode(policy_hash),
+        }))
+    }
+
+    /// Commit, activation and deactivation events since `POLICY_MANAGER_FROM_BLOCK`,
+    /// in chain order, with their block timestamps.
+    pub async fn policy_events(&self) -> Result<Vec<OnchainPolicyEvent>, String> {
+        let abi = self.contract.abi();
+        let kinds = [
+            (
+                event_topic(abi, "PolicyCommitted")?,
+                OnchainEventKind::Committed,
+            ),
+            (
+                event_topic(abi, "PolicyActivated")?,
+                OnchainEventKind::Activated,
+            ),
+            (
+                event_topic(abi, "PolicyDeactivated")?,
+                OnchainEventKind::Deactivated,
+            ),
+        ];
+        let filter = Filter::new()
+            .address(self.contract.address())
+            .from_block(self.from_block)
+            .to_block(BlockNumber::Latest)
+            .topic0(kinds.iter().map(|(topic, _)| *topic).collect::<Vec<_>>());
+        let provider = self.contract.client();
+        let mut logs = provider
+            .get_logs(&filter)
+            .await
+            .map_err(|e| format!("PolicyManager logs failed: {e}"))?;
+        logs.sort_by_key(|log| (log.block_number, log.log_index));
+
+        let mut block_timestamps = HashMap::new();
+        let mut events = Vec::with_capacity(logs.len());
+        for log in logs {
+            let Some(kind) = log
+                .topics
+                .first()
+                .and_then(|t| kinds.iter().find(|(topic, _)| topic == t))
+                .map(|(_, kind)| *kind)
+            else {
+                continue;
+            };
+            let block_number = log.block_number.map(|n| n.as_u64());
+            let block_timestamp = match block_number {
+                Some(number) => match block_timestamps.get(&number) {
+                    Some(ts) => Some(*ts),
+                    None => {
+                        let ts = provider
+                            .get_block(number)
+                            .await
+                            .map_err(|e| format!("block {number} lookup failed: {e}"))?
+                            .map(|block| block.timestamp.as_u64() as i64);
+                        if let Some(ts) = ts {
+                            block_timestamps.insert(number, ts);
+                        }
+                        ts
+                    }
+                },
+                None => None,
+            };
+            events.push(decode_event(kind, &log, block_number, block_timestamp)?);
+        }
+        Ok(events)
+    }
+}
+
+/// Onchain versions are `uint64`. Offchain mappings carry either the plain
+/// number or `onchain-<n>`; anything else (`v1.2`, `onchain-1b`) is rejected
+/// rather than guessed at.
+pub fn parse_onchain_policy_version(v: &str) -> Result<u64, String> {
+    let trimmed = v.trim();
+    let digits = trimmed.strip_prefix("onchain-").unwrap_or(trimmed);
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(format!(
+            "onchain_policy_version `{v}` must be an integer or onchain-<integer>"
+        ));
+    }
+    digits
+        .parse::<u64>()
+        .map_err(|e| format!("invalid onchain_policy_version `{v}`: {e}"))
+}
+
+/// Lowercase hex without `0x`, the form snapshot hashes are stored in.
+pub fn normalize_policy_hash(hash: &str) -> String {
+    let hash = hash.trim();
+    hash.strip_prefix("0x")
+        .or_else(|| hash.strip_prefix("0X"))
+        .unwrap_or(hash)
+        .to_ascii_lowercase()
+}
+
+fn event_topic(abi: &Abi, name: &str) -> Result<H256, String> {
+    abi.event(name)
+        .map(|event| event.signature())
+        .map_err(|e| format!("policy manager abi: {e}"))
+}
+
+fn decode_event(
+    kind: OnchainEventKind,
+    log: &Log,
+    block_number: Option<u64>,
+    block_timestamp: Option<i64>,
+) -> Result<OnchainPolicyEvent, String> {
+    let version_topic = log
+        .topics
+        .get(1)
+        .ok_or_else(|| format!("{kind:?} log without version topic"))?;
+    let version = U256::from_big_endian(version_topic.as_bytes());
+    if version > U256::from(u64::MAX) {
+        return Err(format!("{kind:?} log version out of range"));
+    }
+    let policy_hash = match kind {
+        OnchainEventKind::Committed => Some(hex::encode(
+            log.topics
+                .get(2)
+                .ok_or_else(|| "PolicyCommitted log without policyHash topic".to_string())?
+                .as_bytes(),
+        )),
+        OnchainEventKind::Activated | OnchainEventKind::Deactivated => None,
+    };
+    Ok(OnchainPolicyEvent {
+        kind,
+        version: version.as_u64(),
+        policy_hash,
+        block_number,
+        block_timestamp,
+        tx_hash: log.transaction_hash.map(|h| format!("{h:#x}")),
+    })
+}
@@ -40,7 +40,21 @@ async fn main() {
         }
     };
 
-    let state = AppState::new(config, infra);
+    let state = match AppState::new(config, infra) {
+        Ok(s) => s,
+        Err(e) => {
+            error!(error = %e, "app state error");
+            std::process::exit(1);
+        }
+    };
+    match state.evidence_keys.signer() {
+        Some(signer) => info!(key_id = signer.key_id(), "run evidence is signed"),
+        None => warn!("EVIDENCE_SIGNING_KEY_HEX not set; run evidence is unsigned"),
+    }
+    match &state.config.policy_manager_address {
+        Some(address) => info!(address = %address, "activations are checked against PolicyManager"),
+        None => warn!("POLICY_MANAGER_ADDRESS not set; activations are not checked onchain"),
+    }
     let app = build_router(state);
     if let Err(e) = axum::serve(listener, app).await {
         error!(error = %e, "server runtime error");
@@ -1,10 +1,14 @@
 use super::crud;
 use super::error::AppError;
 use super::schema::{
-    ActivatePolicyRequest, ActivatePolicyResponse, ActivePolicyResponse, CreateSnapshotRequest,
-    CreateSnapshotResponse, EffectivePolicyQuery, EffectivePolicyResponse, SnapshotLookupResponse,
+    ActivatePolicyRequest, ActivatePolicyResponse, ActivePolicyResponse, ApproveProposalRequest,
+    CancelProposalRequest, CreateSnapshotRequest, CreateSnapshotResponse, EffectivePolicyQuery,
+    EffectivePolicyResponse, EvaluatePolicyRequest, EvaluatePolicyResponse, PolicyDiffQuery,
+    PolicyDiffResponse, ProposalResponse, ProposeActivationRequest, ReconciliationResponse,
+    SnapshotLookupResponse,
 };
 use crate::app::AppState;
+use crate::service::evidence_signing_service::JwkSet;
 use axum::extract::{Path, Query, State};
 use axum::http::HeaderMap;
 use axum::{Json, response::IntoResponse};
@@ -32,6 +36,51 @@ pub async fn activate_policy(
     }
 }
 
+pub async fn propose_activation(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<ProposeActivationRequest>,
+) -> impl IntoResponse {
+    match crud::propose_activation(&state, headers, req).await {
+        Ok(resp) => (axum::http::StatusCode::OK, Json(resp)),
+        Err(err) => error_proposal(err),
+    }
+}
+
+pub async fn approve_proposal(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(proposal_id): Path<String>,
+    Json(req): Json<ApproveProposalRequest>,
+) -> impl IntoResponse {
+    match crud::approve_proposal(&state, headers, &proposal_id, req).await {
+        Ok(resp) => (axum::http::StatusCode::OK, Json(resp)),
+        Err(err) => error_proposal(err),
+    }
+}
+
+pub async fn cancel_proposal(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(proposal_id): Path<String>,
+    Json(req): Json<CancelProposalRequest>,
+) -> impl IntoResponse {
+    match crud::cancel_proposal(&state, headers, &proposal_id, req).await {
+        Ok(resp) => (axum::http::StatusCode::OK, Json(resp)),
+        Err(err) => error_proposal(err),
+    }
+}
+
+pub async fn get_proposal(
+    State(state): State<AppState>,
+    Path(proposal_id): Path<String>,
+) -> impl IntoResponse {
+    match crud::get_proposal(&state, &proposal_id).await {
+        Ok(resp) => (axum::http::StatusCode::OK, Json(resp)),
+        Err(err) => error_proposal(err),
+    }
+}
+
 pub async fn get_snapshot_by_version(
     State(state): State<AppState>,
     Path(policy_version): Path<String>,
@@ -75,7 +124,7 @@ pub async fn get_effective_policy_for_run(
     Query(query): Query<EffectivePolicyQuery>,
 ) -> impl IntoResponse {
     match crud::get_effective_policy_for_run(
-        &state.config,
+        &state.evidence_keys,
         &state.store,
         state.infra.as_ref(),
         &run_id,
@@ -89,6 +138,44 @@ pub async fn get_effective_policy_for_run(
     }
 }
 
+pub async fn evidence_signing_keys(State(state): State<AppState>) -> impl IntoResponse {
+    match crud::evidence_signing_keys(&state) {
+        Ok(keys) => (axum::http::StatusCode::OK, Json(keys)),
+        Err(AppError { status, code, .. }) => {
+            error!(error_code = code, "evidence signing keys unavailable");
+            (status, Json(JwkSet { keys: vec![] }))
+        }
+    }
+}
+
+pub async fn evaluate_policy(
+    State(state): State<AppState>,
+    Json(req): Json<EvaluatePolicyRequest>,
+) -> impl IntoResponse {
+    match crud::evaluate_policy(&state, req).await {
+        Ok(resp) => (axum::http::StatusCode::OK, Json(resp)),
+        Err(err) => error_evaluate(err),
+    }
+}
+
+pub async fn diff_policies(
+    State(state): State<AppState>,
+    Path((from, to)): Path<(String, String)>,
+    Query(query): Query<PolicyDiffQuery>,
+) -> impl IntoResponse {
+    match crud::diff_policies(&state, &from, &to, query).await {
+        Ok(resp) => (axum::http::StatusCode::OK, Json(resp)),
+        Err(err) => error_diff(err, from, to),
+    }
+}
+
+pub async fn reconcile_onchain(State(state): State<AppState>) -> impl IntoResponse {
+    match crud::reconcile_onchain(&state).await {
+        Ok(resp) => (axum::http::StatusCode::OK, Json(resp)),
+        Err(err) => error_reconcile(err),
+    }
+}
+
 fn error_snapshot(err: AppError) -> (axum::http::StatusCode, Json<CreateSnapshotResponse>) {
     error!(error_code = err.code, reason = %err.message, "create snapshot rejected");
     (
@@ -124,6 +211,22 @@ fn error_activate(err: AppError) -> (axum::http::StatusCode, Json<ActivatePolicy
     )
 }
 
+fn error_proposal(err: AppError) -> (axum::http::StatusCode, Json<ProposalResponse>) {
+    error!(error_code = err.code, reason = %err.message, "activation proposal rejected");
+    (
+        err.status,
+        Json(ProposalResponse {
+            accepted: false,
+            proposal: None,
+            approvals_remaining: 0,
+            approval_payload: None,
+            approval_hash: None,
+            error_code: Some(err.code.to_string()),
+            reason: err.message,
+        }),
+    )
+}
+
 fn error_lookup(err: AppError) -> (axum::http::StatusCode, Json<SnapshotLookupResponse>) {
     error!(error_code = err.code, reason = %err.message, "lookup policy rejected");
     (
@@ -170,3 +273,63 @@ fn error_effective(
         }),
     )
 }
+
+fn error_evaluate(err: AppError) -> (axum::http::StatusCode, Json<EvaluatePolicyResponse>) {
+    error!(error_code = err.code, reason = %err.message, "policy evaluation rejected");
+    (
+        err.status,
+        Json(EvaluatePolicyResponse {
+            evaluated: false,
+            policy_version: String::new(),
+            policy_hash: String::new(),
+            schema_version: 0,
+            decision: None,
+            risk_score: 0,
+            results: Vec::new(),
+            error_code: Some(err.code.to_string()),
+            reason: err.message,
+        }),
+    )
+}
+
+fn error_diff(
+    err: AppError,
+    from_version: String,
+    to_version: String,
+) -> (axum::http::StatusCode, Json<PolicyDiffResponse>) {
+    error!(error_code = err.code, reason = %err.message, "policy diff rejected");
+    (
+        err.status,
+        Json(PolicyDiffResponse {
+            found: false,
+            from_version,
+            from_hash: String::new(),
+            to_version,
+            to_hash: String::new(),
+            identical: false,
+            changes: Vec::new(),
+            summary: Vec::new(),
+            dry_run: None,
+            error_code: Some(err.code.to_string()),
+            reason: err.message,
+        }),
+    )
+}
+
+fn error_reconcile(err: AppError) -> (axum::http::StatusCode, Json<ReconciliationResponse>) {
+    error!(error_code = err.code, reason = %err.message, "onchain reconciliation failed");
+    (
+        err.status,
+        Json(ReconciliationResponse {
+            reconciled: false,
+            in_sync: false,
+            onchain_active: None,
+            offchain_active: None,
+            offchain_activations: Vec::new(),
+            onchain_events: Vec::new(),
+            drift: Vec::new(),
+            error_code: Some(err.code.to_string()),
+            reason: err.message,
+        }),
+    )
+}
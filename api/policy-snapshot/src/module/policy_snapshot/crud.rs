@@ -1,29 +1,50 @@
 use super::error::AppError;
-use super::model::{ActivePolicyRecord, AuditLogRecord, PolicySnapshotRecord, RunPolicyEvidenceRecord};
+use super::model::{
+    ActivationProposalRecord, ActivePolicyRecord, AuditLogRecord, PolicySnapshotRecord,
+    ProposalApprovalRecord, ProposalStatus, RunEvaluationRecord, RunPolicyEvidenceRecord,
+};
 use super::schema::{
-    ActivatePolicyRequest, ActivatePolicyResponse, ActivePolicyResponse, CreateSnapshotRequest,
-    CreateSnapshotResponse, EffectivePolicyResponse, SnapshotLookupResponse,
+    ActivatePolicyRequest, ActivatePolicyResponse, ActivePolicyResponse, ApproveProposalRequest,
+    CancelProposalRequest, CreateSnapshotRequest, CreateSnapshotResponse, EffectivePolicyResponse,
+    EvaluatePolicyRequest, EvaluatePolicyResponse, PolicyDiffQuery, PolicyDiffResponse,
+    ProposalResponse, ProposeActivationRequest, ReconciliationResponse, SnapshotLookupResponse,
 };
 use crate::app::AppState;
 use crate::config::environment::AppConfig;
+use crate::infra::policy_manager_client::{normalize_policy_hash, parse_onchain_policy_version};
 use crate::infra::{
-    ACTIVATION_HISTORY_COLLECTION, AUDIT_LOG_COLLECTION, InfraClients, RUN_EVIDENCE_COLLECTION,
-    SNAPSHOTS_COLLECTION,
+    ACTIVATION_HISTORY_COLLECTION, ACTIVATION_PROPOSALS_COLLECTION, AUDIT_LOG_COLLECTION,
+    InfraClients, RUN_EVALUATIONS_COLLECTION, RUN_EVIDENCE_COLLECTION, SNAPSHOTS_COLLECTION,
+};
+use crate::service::activation_approval_service::{
+    approval_hash, approval_payload, verify_approval, verify_cancellation,
 };
 use crate::service::canonical_json_service::{canonical_string, canonicalize};
-use crate::service::hash_service::{hmac_sha256_hex, sha256_hex};
+use crate::service::evidence_signing_service::{
+    EvidenceKeyRing, JwkSet, compute_run_evidence_hash,
+};
+use crate::service::hash_service::sha256_hex;
 use crate::service::internal_auth_service::verify_signature;
+use crate::service::policy_diff_service::{diff_rules, dry_run, summarize_changes};
+use crate::service::policy_evaluation_service::evaluate_rules;
+use crate::service::policy_reconciliation_service::{OnchainView, reconcile_activations};
 use crate::service::rules_validation_service::validate_rules_bundle;
 use axum::http::HeaderMap;
 use mongodb::Collection;
-use mongodb::bson::doc;
+use mongodb::bson::{Bson, doc, to_bson};
+use mongodb::options::ReturnDocument;
 use redis::AsyncCommands;
 use redis::aio::MultiplexedConnection;
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
 use std::sync::{Mutex, MutexGuard};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+/// Run evaluations kept in memory for dry-runs when there is no Mongo.
+const MAX_RETAINED_RUN_EVALUATIONS: usize = 1000;
+const DEFAULT_DRY_RUN_LIMIT: usize = 100;
+const MAX_DRY_RUN_LIMIT: usize = 1000;
+
 #[derive(Debug, Default)]
 pub struct PolicyStore {
     inner: Mutex<PolicyStoreInner>,
@@ -36,6 +57,8 @@ struct PolicyStoreInner {
     activation_history: Vec<ActivePolicyRecord>,
     active_idx: Option<usize>,
     run_evidence_by_run: HashMap<String, RunPolicyEvidenceRecord>,
+    run_evaluations: VecDeque<RunEvaluationRecord>,
+    proposals: HashMap<String, ActivationProposalRecord>,
 }
 
 pub async fn create_snapshot(
@@ -47,8 +70,10 @@ pub async fn create_snapshot(
     let resp = create_snapshot_in_memory(&state.store, req.clone())?;
 
     if let Some(infra) = &state.infra {
-        let snapshot = snapshot_by_version(&state.store, &resp.policy_version)?
-            .ok_or_else(|| AppError::internal("SNAPSHOT_NOT_FOUND", "snapshot missing after create"))?;
+        let snapshot =
+            snapshot_by_version(&state.store, &resp.policy_version)?.ok_or_else(|| {
+                AppError::internal("SNAPSHOT_NOT_FOUND", "snapshot missing after create")
+            })?;
         persist_snapshot(infra, &snapshot).await?;
         cache_snapshot(infra, &snapshot).await?;
         append_audit(
@@ -74,29 +99,316 @@ pub async fn activate_policy(
     req: ActivatePolicyRequest,
 ) -> Result<ActivatePolicyResponse, AppError> {
     maybe_verify_internal_auth(&state.config, &headers, &req)?;
+    if !state.config.activation_approvers.is_empty() {
+        return Err(AppError::bad_request(
+            "APPROVAL_REQUIRED",
+            "activations require an approved proposal",
+        ));
+    }
+    if state.policy_manager.is_some()
+        && let Some(snapshot) = get_snapshot_by_version(state, &req.policy_version)
+            .await?
+            .snapshot
+    {
+        ensure_onchain_hash_matches(state, &req.onchain_policy_version, &snapshot.policy_hash)
+            .await?;
+    }
     let resp = activate_policy_in_memory(&state.store, req)?;
 
     if let Some(infra) = &state.infra {
-        persist_activation(infra, &resp.active_mapping).await?;
-        cache_active_mapping(infra, &resp.active_mapping).await?;
+        record_activation_in_infra(infra, &resp.active_mapping, None).await?;
+    }
+
+    Ok(resp)
+}
+
+pub async fn propose_activation(
+    state: &AppState,
+    headers: HeaderMap,
+    req: ProposeActivationRequest,
+) -> Result<ProposalResponse, AppError> {
+    maybe_verify_internal_auth(&state.config, &headers, &req)?;
+    if state.config.activation_approvers.is_empty() {
+        return Err(AppError::bad_request(
+            "APPROVALS_DISABLED",
+            "ACTIVATION_APPROVERS is not configured",
+        ));
+    }
+    parse_onchain_policy_version(&req.onchain_policy_version)
+        .map_err(|e| AppError::bad_request("INVALID_ONCHAIN_POLICY_VERSION", e))?;
+    if req.policy_version.trim().is_empty() {
+        return Err(AppError::bad_request(
+            "INVALID_POLICY_VERSION",
+            "policy_version is required",
+        ));
+    }
+    if req.proposed_by.trim().is_empty() {
+        return Err(AppError::bad_request(
+            "INVALID_PROPOSED_BY",
+            "proposed_by is required",
+        ));
+    }
+    let now = now_unix()?;
+    if req.effective_from.is_some_and(|ts| ts < now) {
+        return Err(AppError::bad_request(
+            "INVALID_EFFECTIVE_FROM",
+            "effective_from must not be in the past",
+        ));
+    }
+    let snapshot = get_snapshot_by_version(state, &req.policy_version)
+        .await?
+        .snapshot
+        .ok_or_else(|| {
+            AppError::not_found("POLICY_VERSION_NOT_FOUND", "cannot propose unknown policy")
+        })?;
+    ensure_onchain_hash_matches(state, &req.onchain_policy_version, &snapshot.policy_hash).await?;
+
+    let proposal_id = format!(
+        "prop_{}",
+        &sha256_hex(&format!(
+            "{}:{}:{}:{:?}:{}",
+            req.proposed_by,
+            req.onchain_policy_version,
+            snapshot.policy_hash,
+            req.effective_from,
+            now_nanos()?
+        ))[..24]
+    );
+    let proposal = ActivationProposalRecord {
+        proposal_id: proposal_id.clone(),
+        onchain_policy_version: req.onchain_policy_version,
+        policy_version: snapshot.policy_version,
+        policy_hash: snapshot.policy_hash,
+        effective_from: req.effective_from,
+        proposed_by: req.proposed_by,
+        proposed_at: now,
+        required_approvals: state.config.activation_required_approvals,
+        approvals: Vec::new(),
+        status: ProposalStatus::Pending,
+        activation: None,
+        cancelled_by: None,
+        cancelled_at: None,
+        cancel_reason: None,
+        cancel_signature: None,
+    };
+    lock_store(&state.store)?
+        .proposals
+        .insert(proposal_id, proposal.clone());
+
+    if let Some(infra) = &state.infra {
+        upsert_proposal(infra, &proposal).await?;
         append_audit(
             infra,
-            AuditLogRecord {
-                event_type: "POLICY_ACTIVATED".to_string(),
-                policy_version: Some(resp.active_mapping.policy_version.clone()),
-                policy_hash: Some(resp.active_mapping.policy_hash.clone()),
-                run_id: None,
-                timestamp: now_unix()?,
-                details: Some(format!(
-                    "onchain_policy_version={}",
-                    resp.active_mapping.onchain_policy_version
-                )),
-            },
+            proposal_audit(
+                "ACTIVATION_PROPOSED",
+                &proposal,
+                format!(
+                    "proposed_by={} effective_from={:?}",
+                    proposal.proposed_by, proposal.effective_from
+                ),
+            )?,
         )
         .await?;
     }
 
-    Ok(resp)
+    proposal_response(proposal, "activation proposed")
+}
+
+pub async fn approve_proposal(
+    state: &AppState,
+    headers: HeaderMap,
+    proposal_id: &str,
+    req: ApproveProposalRequest,
+) -> Result<ProposalResponse, AppError> {
+    maybe_verify_internal_auth(&state.config, &headers, &req)?;
+    if req.approver_id.trim().is_empty() {
+        return Err(AppError::bad_request(
+            "INVALID_APPROVER_ID",
+            "approver_id is required",
+        ));
+    }
+    if req.signature.trim().is_empty() {
+        return Err(AppError::bad_request(
+            "MISSING_SIGNATURE",
+            "signature is required",
+        ));
+    }
+    let proposal = load_proposal(state, proposal_id).await?;
+    // Make sure the snapshot is in memory before the final approval needs it.
+    get_snapshot_by_version(state, &proposal.policy_version).await?;
+    // The chain may have moved since the proposal was made.
+    if proposal.status == ProposalStatus::Pending
+        && proposal.approvals.len() + 1 >= proposal.required_approvals
+    {
+        ensure_onchain_hash_matches(
+            state,
+            &proposal.onchain_policy_version,
+            &proposal.policy_hash,
+        )
+        .await?;
+    }
+
+    let (proposal, activation) = match &state.infra {
+        Some(infra) => approve_proposal_in_infra(state, infra, proposal, &req).await?,
+        None => approve_proposal_in_memory(state, proposal_id, &req)?,
+    };
+
+    if let Some(infra) = &state.infra {
+        append_audit(
+            infra,
+            proposal_audit(
+                "ACTIVATION_APPROVED",
+                &proposal,
+                format!(
+                    "approver_id={} signature={}",
+                    req.approver_id, req.signature
+                ),
+            )?,
+        )
+        .await?;
+        if let Some(active) = &activation {
+            record_activation_in_infra(infra, active, Some(&proposal.proposal_id)).await?;
+        }
+    }
+
+    let reason = match &activation {
+        Some(active) if active.activated_at > now_unix()? => "approved; activation scheduled",
+        Some(_) => "approved; policy activated",
+        None => "approval recorded",
+    };
+    proposal_response(proposal, reason)
+}
+
+pub async fn cancel_proposal(
+    state: &AppState,
+    headers: HeaderMap,
+    proposal_id: &str,
+    req: CancelProposalRequest,
+) -> Result<ProposalResponse, AppError> {
+    maybe_verify_internal_auth(&state.config, &headers, &req)?;
+    if req.cancelled_by.trim().is_empty() {
+        return Err(AppError::bad_request(
+            "INVALID_CANCELLED_BY",
+            "cancelled_by is required",
+        ));
+    }
+    if req.reason.trim().is_empty() {
+        return Err(AppError::bad_request(
+            "INVALID_CANCEL_REASON",
+            "reason is required",
+        ));
+    }
+    if req.signature.trim().is_empty() {
+        return Err(AppError::bad_request(
+            "MISSING_SIGNATURE",
+            "signature is required",
+        ));
+    }
+    let mut proposal = load_proposal(state, proposal_id).await?;
+    check_cancellation(&state.config, &proposal, &req)?;
+    let now = now_unix()?;
+
+    match &state.infra {
+        // Mongo decides whether the cancel wins; memory follows only once it has.
+        Some(infra) => {
+            mark_cancelled(&mut proposal, &req, now);
+            proposal = cancel_proposal_in_infra(state, infra, &proposal).await?;
+            append_audit(
+                infra,
+                proposal_audit(
+                    "ACTIVATION_PROPOSAL_CANCELLED",
+                    &proposal,
+                    format!(
+                        "cancelled_by={} reason={} signature={}",
+                        req.cancelled_by, req.reason, req.signature
+                    ),
+                )?,
+            )
+            .await?;
+        }
+        None => {
+            let mut inner = lock_store(&state.store)?;
+            let current = inner
+                .proposals
+                .get_mut(proposal_id)
+                .ok_or_else(|| AppError::not_found("PROPOSAL_NOT_FOUND", "proposal not found"))?;
+            if current.status != ProposalStatus::Pending {
+                return Err(AppError::conflict(
+                    "PROPOSAL_NOT_PENDING",
+                    "only pending proposals can be cancelled",
+                ));
+            }
+            mark_cancelled(current, &req, now);
+            proposal = current.clone();
+        }
+    }
+
+    proposal_response(proposal, "proposal cancelled")
+}
+
+pub async fn get_proposal(
+    state: &AppState,
+    proposal_id: &str,
+) -> Result<ProposalResponse, AppError> {
+    let proposal = load_proposal(state, proposal_id).await?;
+    proposal_response(proposal, "proposal found")
+}
+
+pub async fn reconcile_onchain(state: &AppState) -> Result<ReconciliationResponse, AppError> {
+    let policy_manager = state.policy_manager.as_ref().ok_or_else(|| {
+        AppError::bad_request(
+            "ONCHAIN_SYNC_DISABLED",
+            "POLICY_MANAGER_ADDRESS is not configured",
+        )
+    })?;
+    let history = match &state.infra {
+        Some(infra) => read_activation_history(infra).await?,
+        None => lock_store(&state.store)?.activation_history.clone(),
+    };
+    let now = now_unix()?;
+    let offchain_active = history
+        .iter()
+        .filter(|rec| rec.activated_at <= now && rec.deactivated_at.is_none_or(|d| now < d))
+        .max_by_key(|rec| rec.activated_at)
+        .cloned();
+
+    let onchain_read = |e: String| AppError::bad_gateway("ONCHAIN_READ_FAILED", e);
+    let versions = history
+        .iter()
+        .filter_map(|rec| parse_onchain_policy_version(&rec.onchain_policy_version).ok())
+        .collect::<BTreeSet<_>>();
+    let mut committed_hashes = BTreeMap::new();
+    for version in versions {
+        committed_hashes.insert(
+            version,
+            policy_manager
+                .policy_hash_of(version)
+                .await
+                .map_err(onchain_read)?,
+        );
+    }
+    let onchain = OnchainView {
+        active: policy_manager.active_policy().await.map_err(onchain_read)?,
+        committed_hashes,
+        events: policy_manager.policy_events().await.map_err(onchain_read)?,
+    };
+    let drift = reconcile_activations(&history, offchain_active.as_ref(), &onchain, now);
+
+    Ok(ReconciliationResponse {
+        reconciled: true,
+        in_sync: drift.is_empty(),
+        onchain_active: onchain.active,
+        offchain_active,
+        offchain_activations: history,
+        onchain_events: onchain.events,
+        reason: if drift.is_empty() {
+            "offchain and onchain activations agree".to_string()
+        } else {
+            format!("{} drift item(s) found", drift.len())
+        },
+        drift,
+        error_code: None,
+    })
 }
 
 pub async fn get_snapshot_by_version(
@@ -206,7 +518,7 @@ pub async fn get_active_policy_at_timestamp(
 }
 
 pub async fn get_effective_policy_for_run(
-    config: &AppConfig,
+    evidence_keys: &EvidenceKeyRing,
     store: &PolicyStore,
     infra: Option<&InfraClients>,
     run_id: &str,
@@ -214,7 +526,10 @@ pub async fn get_effective_policy_for_run(
     version_hint: Option<String>,
 ) -> Result<EffectivePolicyResponse, AppError> {
     if run_id.trim().is_empty() {
-        return Err(AppError::bad_request("INVALID_RUN_ID", "run_id is required"));
+        return Err(AppError::bad_request(
+            "INVALID_RUN_ID",
+            "run_id is required",
+        ));
     }
     if run_timestamp <= 0 {
         return Err(AppError::bad_request(
@@ -243,37 +558,42 @@ pub async fn get_effective_policy_for_run(
         });
     }
 
-    if let Some(infra) = infra {
-        if let Some(existing) = read_run_evidence(infra, run_id).await? {
-            let same = existing.run_timestamp == run_timestamp && existing.version_hint == version_hint;
-            if !same {
-                return Err(AppError::conflict(
-                    "RUN_EVIDENCE_CONFLICT",
-                    "run_id already resolved with different metadata",
-                ));
-            }
-            warm_run_evidence_in_memory(store, &existing)?;
-            let snapshot = snapshot_by_version(store, &existing.policy_version)?;
-            return Ok(EffectivePolicyResponse {
-                found: snapshot.is_some(),
-                run_id: run_id.to_string(),
-                snapshot,
-                activation: Some(active_record_from_evidence(&existing)),
-                evidence: Some(existing),
-                error_code: None,
-                reason: "effective policy found".to_string(),
-            });
+    if let Some(infra) = infra
+        && let Some(existing) = read_run_evidence(infra, run_id).await?
+    {
+        let same = existing.run_timestamp == run_timestamp && existing.version_hint == version_hint;
+        if !same {
+            return Err(AppError::conflict(
+                "RUN_EVIDENCE_CONFLICT",
+                "run_id already resolved with different metadata",
+            ));
         }
+        warm_run_evidence_in_memory(store, &existing)?;
+        let snapshot = snapshot_by_version(store, &existing.policy_version)?;
+        return Ok(EffectivePolicyResponse {
+            found: snapshot.is_some(),
+            run_id: run_id.to_string(),
+            snapshot,
+            activation: Some(active_record_from_evidence(&existing)),
+            evidence: Some(existing),
+            error_code: None,
+            reason: "effective policy found".to_string(),
+        });
     }
 
     let activation = match &version_hint {
         Some(version) => active_for_version_at_timestamp(store, version, run_timestamp)?,
-        None => active_at_timestamp(store, run_timestamp)?
-            .ok_or_else(|| AppError::not_found("ACTIVE_POLICY_NOT_FOUND_AT_TIMESTAMP", "no active policy"))?,
+        None => active_at_timestamp(store, run_timestamp)?.ok_or_else(|| {
+            AppError::not_found("ACTIVE_POLICY_NOT_FOUND_AT_TIMESTAMP", "no active policy")
+        })?,
     };
 
-    let snapshot = snapshot_by_version(store, &activation.policy_version)?
-        .ok_or_else(|| AppError::internal("SNAPSHOT_MISSING", "active mapping references missing snapshot"))?;
+    let snapshot = snapshot_by_version(store, &activation.policy_version)?.ok_or_else(|| {
+        AppError::internal(
+            "SNAPSHOT_MISSING",
+            "active mapping references missing snapshot",
+        )
+    })?;
 
     let evidence_hash = compute_run_evidence_hash(
         run_id,
@@ -283,13 +603,18 @@ pub async fn get_effective_policy_for_run(
         &snapshot.policy_hash,
         activation.activated_at,
         activation.deactivated_at,
-    )?;
-    let evidence_signature = match &config.audit_signing_secret {
-        Some(secret) => Some(
-            hmac_sha256_hex(&evidence_hash, secret)
-                .map_err(|e| AppError::internal("AUDIT_SIGNATURE_ERROR", e))?,
+    )
+    .map_err(|e| AppError::internal("SERIALIZATION_ERROR", e))?;
+    let (evidence_signature, evidence_signing_key_id) = match evidence_keys.signer() {
+        Some(signer) => (
+            Some(
+                signer
+                    .sign(&evidence_hash)
+                    .map_err(|e| AppError::internal("EVIDENCE_SIGNATURE_ERROR", e))?,
+            ),
+            Some(signer.key_id().to_string()),
         ),
-        None => None,
+        None => (None, None),
     };
     let evidence = RunPolicyEvidenceRecord {
         run_id: run_id.to_string(),
@@ -301,6 +626,7 @@ pub async fn get_effective_policy_for_run(
         deactivated_at: activation.deactivated_at,
         evidence_hash,
         evidence_signature,
+        evidence_signing_key_id,
         created_at: now_unix()?,
     };
     warm_run_evidence_in_memory(store, &evidence)?;
@@ -332,6 +658,192 @@ pub async fn get_effective_policy_for_run(
     })
 }
 
+/// Public keys run evidence is verified with, including retired ones.
+pub fn evidence_signing_keys(state: &AppState) -> Result<JwkSet, AppError> {
+    let keys = state.evidence_keys.jwks();
+    if keys.keys.is_empty() {
+        return Err(AppError::not_found(
+            "EVIDENCE_SIGNING_DISABLED",
+            "evidence signing is not configured",
+        ));
+    }
+    Ok(keys)
+}
+
+pub async fn evaluate_policy(
+    state: &AppState,
+    req: EvaluatePolicyRequest,
+) -> Result<EvaluatePolicyResponse, AppError> {
+    if req.trade.is_none() && req.subjects.is_empty() {
+        return Err(AppError::bad_request(
+            "EMPTY_CANDIDATE",
+            "a trade or at least one subject is required",
+        ));
+    }
+    if let Some(trade) = &req.trade {
+        if trade.asset_pair.trim().is_empty() {
+            return Err(AppError::bad_request(
+                "INVALID_ASSET_PAIR",
+                "trade.asset_pair is required",
+            ));
+        }
+        if trade.notional.is_zero() {
+            return Err(AppError::bad_request(
+                "INVALID_NOTIONAL",
+                "trade.notional must be positive",
+            ));
+        }
+    }
+    if req.subjects.iter().any(|s| s.subject_id.trim().is_empty()) {
+        return Err(AppError::bad_request(
+            "INVALID_SUBJECT_ID",
+            "subject_id is required",
+        ));
+    }
+    if req.run_id.as_deref().is_some_and(|r| r.trim().is_empty()) {
+        return Err(AppError::bad_request(
+            "INVALID_RUN_ID",
+            "run_id must not be empty",
+        ));
+    }
+
+    let snapshot =
+        resolve_snapshot_for_evaluation(state, req.policy_version.as_deref(), req.timestamp)
+            .await?;
+    let rules = validate_rules_bundle(&snapshot.canonical_rules)
+        .map_err(|e| AppError::internal("UNSUPPORTED_RULES_BUNDLE", e))?;
+    let evaluation = evaluate_rules(&rules, req.trade.as_ref(), &req.subjects);
+
+    if let Some(run_id) = req.run_id {
+        let record = RunEvaluationRecord {
+            run_id,
+            policy_version: snapshot.policy_version.clone(),
+            policy_hash: snapshot.policy_hash.clone(),
+            trade: req.trade,
+            subjects: req.subjects,
+            decision: evaluation.decision,
+            risk_score: evaluation.risk_score,
+            evaluated_at: now_unix()?,
+        };
+        retain_run_evaluation_in_memory(&state.store, &record)?;
+        if let Some(infra) = &state.infra {
+            persist_run_evaluation(infra, &record).await?;
+        }
+    }
+
+    Ok(EvaluatePolicyResponse {
+        evaluated: true,
+        policy_version: snapshot.policy_version,
+        policy_hash: snapshot.policy_hash,
+        schema_version: rules.schema_version,
+        decision: Some(evaluation.decision),
+        risk_score: evaluation.risk_score,
+        results: evaluation.results,
+        error_code: None,
+        reason: "policy evaluated".to_string(),
+    })
+}
+
+async fn resolve_snapshot_for_evaluation(
+    state: &AppState,
+    policy_version: Option<&str>,
+    timestamp: Option<i64>,
+) -> Result<PolicySnapshotRecord, AppError> {
+    if let Some(version) = policy_version {
+        if version.trim().is_empty() {
+            return Err(AppError::bad_request(
+                "INVALID_POLICY_VERSION",
+                "policy_version must not be empty",
+            ));
+        }
+        return get_snapshot_by_version(state, version)
+            .await?
+            .snapshot
+            .ok_or_else(|| AppError::not_found("POLICY_VERSION_NOT_FOUND", "snapshot not found"));
+    }
+    let resp = match timestamp {
+        Some(ts) if ts <= 0 => {
+            return Err(AppError::bad_request(
+                "INVALID_TIMESTAMP",
+                "timestamp must be positive unix epoch",
+            ));
+        }
+        Some(ts) => get_active_policy_at_timestamp(state, ts).await?,
+        None => get_active_policy(state).await?,
+    };
+    resp.snapshot.ok_or_else(|| {
+        AppError::not_found(
+            if timestamp.is_some() {
+                "ACTIVE_POLICY_NOT_FOUND_AT_TIMESTAMP"
+            } else {
+                "ACTIVE_POLICY_NOT_SET"
+            },
+            "no active policy to evaluate against",
+        )
+    })
+}
+
+pub async fn diff_policies(
+    state: &AppState,
+    from: &str,
+    to: &str,
+    query: PolicyDiffQuery,
+) -> Result<PolicyDiffResponse, AppError> {
+    let from_snapshot = get_snapshot_by_version(state, from)
+        .await?
+        .snapshot
+        .ok_or_else(|| {
+            AppError::not_found(
+                "FROM_VERSION_NOT_FOUND",
+                format!("snapshot {from} not found"),
+            )
+        })?;
+    let to_snapshot = get_snapshot_by_version(state, to)
+        .await?
+        .snapshot
+        .ok_or_else(|| {
+            AppError::not_found("TO_VERSION_NOT_FOUND", format!("snapshot {to} not found"))
+        })?;
+
+    let changes = diff_rules(&from_snapshot.canonical_rules, &to_snapshot.canonical_rules);
+    let summary = summarize_changes(&changes);
+
+    let dry_run_report = if query.dry_run {
+        let limit = query
+            .limit
+            .unwrap_or(DEFAULT_DRY_RUN_LIMIT)
+            .clamp(1, MAX_DRY_RUN_LIMIT);
+        let rules = validate_rules_bundle(&to_snapshot.canonical_rules)
+            .map_err(|e| AppError::internal("UNSUPPORTED_RULES_BUNDLE", e))?;
+        let records = match &state.infra {
+            Some(infra) => read_recent_run_evaluations(infra, limit).await?,
+            None => recent_run_evaluations_in_memory(&state.store, limit)?,
+        };
+        Some(dry_run(&to_snapshot.policy_version, &rules, &records))
+    } else {
+        None
+    };
+
+    let identical = from_snapshot.policy_hash == to_snapshot.policy_hash;
+    Ok(PolicyDiffResponse {
+        found: true,
+        from_version: from_snapshot.policy_version,
+        from_hash: from_snapshot.policy_hash,
+        to_version: to_snapshot.policy_version,
+        to_hash: to_snapshot.policy_hash,
+        identical,
+        reason: if changes.is_empty() {
+            "no rule changes".to_string()
+        } else {
+            format!("{} rule changes", changes.len())
+        },
+        changes,
+        summary,
+        dry_run: dry_run_report,
+        error_code: None,
+    })
+}
+
 fn create_snapshot_in_memory(
     store: &PolicyStore,
     req: CreateSnapshotRequest,
@@ -342,23 +854,26 @@ fn create_snapshot_in_memory(
             "policy_version is required",
         ));
     }
-    validate_rules_bundle(&req.rules).map_err(|e| AppError::bad_request("INVALID_RULES_BUNDLE", e))?;
+    validate_rules_bundle(&req.rules)
+        .map_err(|e| AppError::bad_request("INVALID_RULES_BUNDLE", e))?;
 
     let canonical_rules = canonicalize(&req.rules);
     let canonical_rules_json = canonical_string(&canonical_rules)?;
     let computed_hash = sha256_hex(&canonical_rules_json);
-    if let Some(provided) = &req.policy_hash {
-        if provided != &computed_hash {
-            return Err(AppError::bad_request(
-                "POLICY_HASH_MISMATCH",
-                "provided policy_hash does not match canonical rule bundle hash",
-            ));
-        }
+    if let Some(provided) = &req.policy_hash
+        && provided != &computed_hash
+    {
+        return Err(AppError::bad_request(
+            "POLICY_HASH_MISMATCH",
+            "provided policy_hash does not match canonical rule bundle hash",
+        ));
     }
 
     let mut inner = lock_store(store)?;
     if let Some(existing) = inner.snapshots_by_version.get(&req.policy_version) {
-        if existing.policy_hash == computed_hash && existing.canonical_rules_json == canonical_rules_json {
+        if existing.policy_hash == computed_hash
+            && existing.canonical_rules_json == canonical_rules_json
+        {
             return Ok(CreateSnapshotResponse {
                 accepted: true,
                 idempotent: true,
@@ -389,8 +904,12 @@ fn create_snapshot_in_memory(
         metadata: req.metadata,
         created_at: now_unix()?,
     };
-    inner.snapshots_by_version.insert(req.policy_version.clone(), record);
-    inner.version_by_hash.insert(computed_hash.clone(), req.policy_version.clone());
+    inner
+        .snapshots_by_version
+        .insert(req.policy_version.clone(), record);
+    inner
+        .version_by_hash
+        .insert(computed_hash.clone(), req.policy_version.clone());
 
     Ok(CreateSnapshotResponse {
         accepted: true,
@@ -407,12 +926,8 @@ fn activate_policy_in_memory(
     store: &PolicyStore,
     req: ActivatePolicyRequest,
 ) -> Result<ActivatePolicyResponse, AppError> {
-    if req.onchain_policy_version.trim().is_empty() {
-        return Err(AppError::bad_request(
-            "INVALID_ONCHAIN_POLICY_VERSION",
-            "onchain_policy_version is required",
-        ));
-    }
+    parse_onchain_policy_version(&req.onchain_policy_version)
+        .map_err(|e| AppError::bad_request("INVALID_ONCHAIN_POLICY_VERSION", e))?;
     if req.policy_version.trim().is_empty() {
         return Err(AppError::bad_request(
             "INVALID_POLICY_VERSION",
@@ -424,30 +939,501 @@ fn activate_policy_in_memory(
     let snapshot = inner
         .snapshots_by_version
         .get(&req.policy_version)
-        .ok_or_else(|| AppError::not_found("POLICY_VERSION_NOT_FOUND", "cannot activate unknown policy"))?
+        .ok_or_else(|| {
+            AppError::not_found("POLICY_VERSION_NOT_FOUND", "cannot activate unknown policy")
+        })?
         .clone();
 
     let now = now_unix()?;
-    if let Some(idx) = inner.active_idx {
-        if let Some(prev) = inner.activation_history.get_mut(idx) {
-            prev.deactivated_at = Some(now);
-        }
+    let activated_at = req.effective_from.map_or(now, |ts| ts.max(now));
+    let active = record_activation_locked(
+        &mut inner,
+        req.onchain_policy_version,
+        &snapshot,
+        activated_at,
+    )?;
+
+    Ok(ActivatePolicyResponse {
+        accepted: true,
+        active_mapping: active,
+        error_code: None,
+        reason: if activated_at > now {
+            "policy activation scheduled".to_string()
+        } else {
+            "policy activated".to_string()
+        },
+    })
+}
+
+/// Appends an activation starting at `activated_at` and closes the window of
+/// the one before it. Activations are kept in start order, so a new one may
+/// not start before an already scheduled one.
+fn record_activation_locked(
+    inner: &mut PolicyStoreInner,
+    onchain_policy_version: String,
+    snapshot: &PolicySnapshotRecord,
+    activated_at: i64,
+) -> Result<ActivePolicyRecord, AppError> {
+    if inner
+        .activation_history
+        .last()
+        .is_some_and(|last| activated_at < last.activated_at)
+    {
+        return Err(AppError::conflict(
+            "ACTIVATION_ORDER_CONFLICT",
+            "another activation is scheduled after the requested time",
+        ));
+    }
+    if let Some(idx) = inner.active_idx
+        && let Some(prev) = inner.activation_history.get_mut(idx)
+    {
+        prev.deactivated_at = Some(activated_at);
     }
     let active = ActivePolicyRecord {
-        onchain_policy_version: req.onchain_policy_version,
-        policy_version: snapshot.policy_version,
-        policy_hash: snapshot.policy_hash,
-        activated_at: now,
+        onchain_policy_version,
+        policy_version: snapshot.policy_version.clone(),
+        policy_hash: snapshot.policy_hash.clone(),
+        activated_at,
         deactivated_at: None,
     };
     inner.activation_history.push(active.clone());
     inner.active_idx = Some(inner.activation_history.len() - 1);
+    Ok(active)
+}
 
-    Ok(ActivatePolicyResponse {
+/// Single-process approvals: the store lock makes check, push and execute
+/// atomic.
+fn approve_proposal_in_memory(
+    state: &AppState,
+    proposal_id: &str,
+    req: &ApproveProposalRequest,
+) -> Result<(ActivationProposalRecord, Option<ActivePolicyRecord>), AppError> {
+    let now = now_unix()?;
+    let mut inner = lock_store(&state.store)?;
+    let mut proposal = inner
+        .proposals
+        .get(proposal_id)
+        .cloned()
+        .ok_or_else(|| AppError::not_found("PROPOSAL_NOT_FOUND", "proposal not found"))?;
+    check_approval(&state.config, &proposal, req)?;
+
+    proposal.approvals.push(approval_record(req, now));
+    let mut activation = None;
+    if proposal.approvals.len() >= proposal.required_approvals {
+        activation = Some(execute_proposal_locked(&mut inner, &mut proposal, now)?);
+    }
+    inner
+        .proposals
+        .insert(proposal_id.to_string(), proposal.clone());
+    Ok((proposal, activation))
+}
+
+/// Everything a cancellation must satisfy before it is recorded. Only
+/// configured approvers hold a key the service can check, so a proposer who
+/// is not one cannot cancel.
+fn check_cancellation(
+    config: &AppConfig,
+    proposal: &ActivationProposalRecord,
+    req: &CancelProposalRequest,
+) -> Result<(), AppError> {
+    if proposal.status != ProposalStatus::Pending {
+        return Err(AppError::conflict(
+            "PROPOSAL_NOT_PENDING",
+            "only pending proposals can be cancelled",
+        ));
+    }
+    if !config.activation_approvers.contains_key(&req.cancelled_by) {
+        return Err(AppError::bad_request(
+            "UNKNOWN_CANCELLER",
+            "only a configured approver can cancel",
+        ));
+    }
+    verify_cancellation(
+        &config.activation_approvers,
+        &proposal.proposal_id,
+        &req.reason,
+        &req.cancelled_by,
+        &req.signature,
+    )
+    .map_err(|e| AppError::bad_request("BAD_CANCEL_SIGNATURE", e))
+}
+
+fn mark_cancelled(proposal: &mut ActivationProposalRecord, req: &CancelProposalRequest, now: i64) {
+    proposal.status = ProposalStatus::Cancelled;
+    proposal.cancelled_by = Some(req.cancelled_by.clone());
+    proposal.cancelled_at = Some(now);
+    proposal.cancel_reason = Some(req.reason.clone());
+    proposal.cancel_signature = Some(req.signature.clone());
+}
+
+/// Approvals with Mongo, where other instances approve the same proposal
+/// concurrently. The approval is a conditional `$push` that only matches a
+/// pending proposal this approver has not approved yet, and only the request
+/// that flips the stored status from pending executes the activation, so
+/// neither a duplicate approval nor a lost one can slip through.
+async fn approve_proposal_in_infra(
+    state: &AppState,
+    infra: &InfraClients,
+    proposal: ActivationProposalRecord,
+    req: &ApproveProposalRequest,
+) -> Result<(ActivationProposalRecord, Option<ActivePolicyRecord>), AppError> {
+    check_approval(&state.config, &proposal, req)?;
+    let now = now_unix()?;
+    let persistence = |e: mongodb::error::Error| {
+        AppError::internal("PERSISTENCE_ERROR", format!("approve proposal failed: {e}"))
+    };
+    let coll: Collection<ActivationProposalRecord> =
+        infra.mongo_db.collection(ACTIVATION_PROPOSALS_COLLECTION);
+    let pending = status_bson(ProposalStatus::Pending)?;
+
+    let approval = to_bson(&approval_record(req, now)).map_err(|e| {
+        AppError::internal("SERIALIZATION_ERROR", format!("approval bson failed: {e}"))
+    })?;
+    let pushed = coll
+        .find_one_and_update(
+            doc! {
+                "proposal_id": &proposal.proposal_id,
+                "status": pending.clone(),
+                "approvals.approver_id": { "$ne": &req.approver_id },
+            },
+            doc! { "$push": { "approvals": approval } },
+        )
+        .return_document(ReturnDocument::After)
+        .await
+        .map_err(persistence)?;
+    let Some(mut proposal) = pushed else {
+        // Someone else got there first; report what the stored proposal says.
+        let current = refresh_proposal(state, infra, &proposal.proposal_id).await?;
+        check_approval(&state.config, &current, req)?;
+        return Err(AppError::conflict(
+            "APPROVAL_CONFLICT",
+            "proposal changed while approving; retry",
+        ));
+    };
+
+    let mut activation = None;
+    if proposal.approvals.len() >= proposal.required_approvals {
+        let claimed = coll
+            .update_one(
+                doc! { "proposal_id": &proposal.proposal_id, "status": pending },
+                doc! { "$set": { "status": status_bson(ProposalStatus::Executed)? } },
+            )
+            .await
+            .map_err(persistence)?
+            .matched_count
+            == 1;
+        if claimed {
+            let executed = {
+                let mut inner = lock_store(&state.store)?;
+                let executed = execute_proposal_locked(&mut inner, &mut proposal, now);
+                if executed.is_ok() {
+                    inner
+                        .proposals
+                        .insert(proposal.proposal_id.clone(), proposal.clone());
+                }
+                executed
+            };
+            let active = match executed {
+                Ok(active) => active,
+                Err(err) => {
+                    release_claim(state, infra, &proposal.proposal_id, &req.approver_id).await?;
+                    return Err(err);
+                }
+            };
+            let activation_bson = to_bson(&active).map_err(|e| {
+                AppError::internal(
+                    "SERIALIZATION_ERROR",
+                    format!("activation bson failed: {e}"),
+                )
+            })?;
+            coll.update_one(
+                doc! { "proposal_id": &proposal.proposal_id },
+                doc! { "$set": { "activation": activation_bson } },
+            )
+            .await
+            .map_err(persistence)?;
+            activation = Some(active);
+        } else {
+            proposal = refresh_proposal(state, infra, &proposal.proposal_id).await?;
+        }
+    }
+    if activation.is_none() {
+        lock_store(&state.store)?
+            .proposals
+            .insert(proposal.proposal_id.clone(), proposal.clone());
+    }
+    Ok((proposal, activation))
+}
+
+/// Hands back a proposal this request claimed but could not activate (the
+/// snapshot is gone or the activation would reorder the timeline): it goes
+/// back to pending without the claiming approval, as the in-memory path
+/// leaves it, so it can still be approved again or cancelled.
+async fn release_claim(
+    state: &AppState,
+    infra: &InfraClients,
+    proposal_id: &str,
+    approver_id: &str,
+) -> Result<(), AppError> {
+    let coll: Collection<ActivationProposalRecord> =
+        infra.mongo_db.collection(ACTIVATION_PROPOSALS_COLLECTION);
+    coll.update_one(
+        doc! {
+            "proposal_id": proposal_id,
+            "status": status_bson(ProposalStatus::Executed)?,
+            "activation": Bson::Null,
+        },
+        doc! {
+            "$set": { "status": status_bson(ProposalStatus::Pending)? },
+            "$pull": { "approvals": { "approver_id": approver_id } },
+        },
+    )
+    .await
+    .map_err(|e| {
+        AppError::internal("PERSISTENCE_ERROR", format!("release proposal failed: {e}"))
+    })?;
+    refresh_proposal(state, infra, proposal_id).await?;
+    Ok(())
+}
+
+/// Everything an approval must satisfy before it is recorded.
+fn check_approval(
+    config: &AppConfig,
+    proposal: &ActivationProposalRecord,
+    req: &ApproveProposalRequest,
+) -> Result<(), AppError> {
+    if proposal.status != ProposalStatus::Pending {
+        return Err(AppError::conflict(
+            "PROPOSAL_NOT_PENDING",
+            "proposal is no longer pending",
+        ));
+    }
+    if req.approver_id == proposal.proposed_by {
+        return Err(AppError::bad_request(
+            "SELF_APPROVAL",
+            "proposers cannot approve their own proposal",
+        ));
+    }
+    if proposal
+        .approvals
+        .iter()
+        .any(|a| a.approver_id == req.approver_id)
+    {
+        return Err(AppError::conflict(
+            "DUPLICATE_APPROVAL",
+            "approver already approved this proposal",
+        ));
+    }
+    if !config.activation_approvers.contains_key(&req.approver_id) {
+        return Err(AppError::bad_request(
+            "UNKNOWN_APPROVER",
+            "approver is not configured",
+        ));
+    }
+    verify_approval(
+        &config.activation_approvers,
+        proposal,
+        &req.approver_id,
+        &req.signature,
+    )
+    .map_err(|e| AppError::bad_request("BAD_APPROVAL_SIGNATURE", e))
+}
+
+fn approval_record(req: &ApproveProposalRequest, now: i64) -> ProposalApprovalRecord {
+    ProposalApprovalRecord {
+        approver_id: req.approver_id.clone(),
+        signature: req.signature.clone(),
+        approved_at: now,
+    }
+}
+
+/// Writes the activation of a fully approved proposal and marks it executed.
+fn execute_proposal_locked(
+    inner: &mut PolicyStoreInner,
+    proposal: &mut ActivationProposalRecord,
+    now: i64,
+) -> Result<ActivePolicyRecord, AppError> {
+    let snapshot = inner
+        .snapshots_by_version
+        .get(&proposal.policy_version)
+        .cloned()
+        .ok_or_else(|| {
+            AppError::internal("SNAPSHOT_MISSING", "proposal references missing snapshot")
+        })?;
+    let activated_at = proposal.effective_from.map_or(now, |ts| ts.max(now));
+    let active = record_activation_locked(
+        inner,
+        proposal.onchain_policy_version.clone(),
+        &snapshot,
+        activated_at,
+    )?;
+    proposal.status = ProposalStatus::Executed;
+    proposal.activation = Some(active.clone());
+    Ok(active)
+}
+
+/// Cancels only a proposal that is still pending in Mongo, so a cancel cannot
+/// overwrite approvals or an execution that landed in the meantime. Memory is
+/// only updated from what Mongo stored.
+async fn cancel_proposal_in_infra(
+    state: &AppState,
+    infra: &InfraClients,
+    proposal: &ActivationProposalRecord,
+) -> Result<ActivationProposalRecord, AppError> {
+    let coll: Collection<ActivationProposalRecord> =
+        infra.mongo_db.collection(ACTIVATION_PROPOSALS_COLLECTION);
+    let cancelled = coll
+        .find_one_and_update(
+            doc! { "proposal_id": &proposal.proposal_id, "status": status_bson(ProposalStatus::Pending)? },
+            doc! { "$set": {
+                "status": status_bson(ProposalStatus::Cancelled)?,
+                "cancelled_by": proposal.cancelled_by.as_deref(),
+                "cancelled_at": proposal.cancelled_at,
+                "cancel_reason": proposal.cancel_reason.as_deref(),
+                "cancel_signature": proposal.cancel_signature.as_deref(),
+            } },
+        )
+        .return_document(ReturnDocument::After)
+        .await
+        .map_err(|e| AppError::internal("PERSISTENCE_ERROR", format!("cancel proposal failed: {e}")))?;
+    let Some(cancelled) = cancelled else {
+        refresh_proposal(state, infra, &proposal.proposal_id).await?;
+        return Err(AppError::conflict(
+            "PROPOSAL_NOT_PENDING",
+            "only pending proposals can be cancelled",
+        ));
+    };
+    lock_store(&state.store)?
+        .proposals
+        .insert(cancelled.proposal_id.clone(), cancelled.clone());
+    Ok(cancelled)
+}
+
+/// Re-reads a proposal from Mongo and replaces the in-memory copy with it.
+async fn refresh_proposal(
+    state: &AppState,
+    infra: &InfraClients,
+    proposal_id: &str,
+) -> Result<ActivationProposalRecord, AppError> {
+    let coll: Collection<ActivationProposalRecord> =
+        infra.mongo_db.collection(ACTIVATION_PROPOSALS_COLLECTION);
+    let proposal = coll
+        .find_one(doc! { "proposal_id": proposal_id })
+        .await
+        .map_err(|e| AppError::internal("PERSISTENCE_ERROR", format!("read proposal failed: {e}")))?
+        .ok_or_else(|| AppError::not_found("PROPOSAL_NOT_FOUND", "proposal not found"))?;
+    lock_store(&state.store)?
+        .proposals
+        .insert(proposal_id.to_string(), proposal.clone());
+    Ok(proposal)
+}
+
+fn status_bson(status: ProposalStatus) -> Result<Bson, AppError> {
+    to_bson(&status)
+        .map_err(|e| AppError::internal("SERIALIZATION_ERROR", format!("status bson failed: {e}")))
+}
+
+async fn load_proposal(
+    state: &AppState,
+    proposal_id: &str,
+) -> Result<ActivationProposalRecord, AppError> {
+    if let Some(proposal) = lock_store(&state.store)?
+        .proposals
+        .get(proposal_id)
+        .cloned()
+    {
+        return Ok(proposal);
+    }
+    let Some(infra) = &state.infra else {
+        return Err(AppError::not_found(
+            "PROPOSAL_NOT_FOUND",
+            "proposal not found",
+        ));
+    };
+    let coll: Collection<ActivationProposalRecord> =
+        infra.mongo_db.collection(ACTIVATION_PROPOSALS_COLLECTION);
+    let proposal = coll
+        .find_one(doc! { "proposal_id": proposal_id })
+        .await
+        .map_err(|e| AppError::internal("PERSISTENCE_ERROR", format!("read proposal failed: {e}")))?
+        .ok_or_else(|| AppError::not_found("PROPOSAL_NOT_FOUND", "proposal not found"))?;
+    lock_store(&state.store)?
+        .proposals
+        .entry(proposal_id.to_string())
+        .or_insert_with(|| proposal.clone());
+    Ok(proposal)
+}
+
+/// Refuses to map `onchain_policy_version` to a snapshot whose hash differs
+/// from what PolicyManager committed for that version. A no-op when no
+/// PolicyManager is configured.
+async fn ensure_onchain_hash_matches(
+    state: &AppState,
+    onchain_policy_version: &str,
+    policy_hash: &str,
+) -> Result<(), AppError> {
+    let Some(policy_manager) = &state.policy_manager else {
+        return Ok(());
+    };
+    let version = parse_onchain_policy_version(onchain_policy_version)
+        .map_err(|e| AppError::bad_request("INVALID_ONCHAIN_POLICY_VERSION", e))?;
+    let onchain_hash = policy_manager
+        .policy_hash_of(version)
+        .await
+        .map_err(|e| AppError::bad_gateway("ONCHAIN_READ_FAILED", e))?
+        .ok_or_else(|| {
+            AppError::conflict(
+                "ONCHAIN_POLICY_NOT_COMMITTED",
+                format!("onchain policy version {version} is not committed"),
+            )
+        })?;
+    if onchain_hash != normalize_policy_hash(policy_hash) {
+        return Err(AppError::conflict(
+            "ONCHAIN_POLICY_HASH_MISMATCH",
+            format!(
+                "onchain policy version {version} commits 0x{onchain_hash}, snapshot hash is {policy_hash}"
+            ),
+        ));
+    }
+    Ok(())
+}
+
+fn proposal_response(
+    proposal: ActivationProposalRecord,
+    reason: &str,
+) -> Result<ProposalResponse, AppError> {
+    let approval_payload =
+        approval_payload(&proposal).map_err(|e| AppError::internal("SERIALIZATION_ERROR", e))?;
+    let approval_hash =
+        approval_hash(&proposal).map_err(|e| AppError::internal("SERIALIZATION_ERROR", e))?;
+    Ok(ProposalResponse {
         accepted: true,
-        active_mapping: active,
+        approvals_remaining: match proposal.status {
+            ProposalStatus::Pending => proposal
+                .required_approvals
+                .saturating_sub(proposal.approvals.len()),
+            ProposalStatus::Executed | ProposalStatus::Cancelled => 0,
+        },
+        approval_payload: Some(approval_payload),
+        approval_hash: Some(approval_hash),
+        proposal: Some(proposal),
         error_code: None,
-        reason: "policy activated".to_string(),
+        reason: reason.to_string(),
+    })
+}
+
+fn proposal_audit(
+    event_type: &str,
+    proposal: &ActivationProposalRecord,
+    details: String,
+) -> Result<AuditLogRecord, AppError> {
+    Ok(AuditLogRecord {
+        event_type: event_type.to_string(),
+        policy_version: Some(proposal.policy_version.clone()),
+        policy_hash: Some(proposal.policy_hash.clone()),
+        run_id: None,
+        timestamp: now_unix()?,
+        details: Some(format!("proposal_id={} {details}", proposal.proposal_id)),
     })
 }
 
@@ -510,7 +1496,10 @@ fn get_active_policy_in_memory(store: &PolicyStore) -> Result<ActivePolicyRespon
             reason: "no active policy".to_string(),
         });
     };
-    let snapshot = inner.snapshots_by_version.get(&active.policy_version).cloned();
+    let snapshot = inner
+        .snapshots_by_version
+        .get(&active.policy_version)
+        .cloned();
     Ok(ActivePolicyResponse {
         found: snapshot.is_some(),
         active_mapping: Some(active),
@@ -534,7 +1523,10 @@ fn get_active_policy_at_timestamp_in_memory(
             reason: "no active policy at timestamp".to_string(),
         });
     };
-    let snapshot = inner.snapshots_by_version.get(&active.policy_version).cloned();
+    let snapshot = inner
+        .snapshots_by_version
+        .get(&active.policy_version)
+        .cloned();
     Ok(ActivePolicyResponse {
         found: snapshot.is_some(),
         active_mapping: Some(active),
@@ -565,18 +1557,25 @@ fn run_evidence_in_memory(
     Ok(inner.run_evidence_by_run.get(run_id).cloned())
 }
 
-fn warm_snapshot_in_memory(store: &PolicyStore, snapshot: &PolicySnapshotRecord) -> Result<(), AppError> {
+fn warm_snapshot_in_memory(
+    store: &PolicyStore,
+    snapshot: &PolicySnapshotRecord,
+) -> Result<(), AppError> {
     let mut inner = lock_store(store)?;
-    inner
-        .version_by_hash
-        .insert(snapshot.policy_hash.clone(), snapshot.policy_version.clone());
+    inner.version_by_hash.insert(
+        snapshot.policy_hash.clone(),
+        snapshot.policy_version.clone(),
+    );
     inner
         .snapshots_by_version
         .insert(snapshot.policy_version.clone(), snapshot.clone());
     Ok(())
 }
 
-fn warm_activation_in_memory(store: &PolicyStore, active: &ActivePolicyRecord) -> Result<(), AppError> {
+fn warm_activation_in_memory(
+    store: &PolicyStore,
+    active: &ActivePolicyRecord,
+) -> Result<(), AppError> {
     let mut inner = lock_store(store)?;
     inner.activation_history.push(active.clone());
     inner.active_idx = Some(inner.activation_history.len() - 1);
@@ -594,6 +1593,32 @@ fn warm_run_evidence_in_memory(
     Ok(())
 }
 
+fn retain_run_evaluation_in_memory(
+    store: &PolicyStore,
+    record: &RunEvaluationRecord,
+) -> Result<(), AppError> {
+    let mut inner = lock_store(store)?;
+    if inner.run_evaluations.len() == MAX_RETAINED_RUN_EVALUATIONS {
+        inner.run_evaluations.pop_front();
+    }
+    inner.run_evaluations.push_back(record.clone());
+    Ok(())
+}
+
+fn recent_run_evaluations_in_memory(
+    store: &PolicyStore,
+    limit: usize,
+) -> Result<Vec<RunEvaluationRecord>, AppError> {
+    let inner = lock_store(store)?;
+    Ok(inner
+        .run_evaluations
+        .iter()
+        .rev()
+        .take(limit)
+        .cloned()
+        .collect())
+}
+
 fn active_at_timestamp(
     store: &PolicyStore,
     timestamp: i64,
@@ -633,33 +1658,45 @@ fn maybe_verify_internal_auth<T: serde::Serialize>(
     if !config.require_internal_auth {
         return Ok(());
     }
-    let secret = config
-        .internal_auth_secret
-        .as_deref()
-        .ok_or_else(|| AppError::internal("AUTH_CONFIG_MISSING", "INTERNAL_AUTH_SECRET is required"))?;
+    let secret = config.internal_auth_secret.as_deref().ok_or_else(|| {
+        AppError::internal("AUTH_CONFIG_MISSING", "INTERNAL_AUTH_SECRET is required")
+    })?;
     let signature = headers
         .get("x-internal-signature")
         .and_then(|v| v.to_str().ok())
-        .ok_or_else(|| AppError::bad_request("MISSING_SIGNATURE", "x-internal-signature required"))?;
-    let payload = serde_json::to_string(request)
-        .map_err(|e| AppError::internal("SERIALIZATION_ERROR", format!("auth payload failed: {e}")))?;
+        .ok_or_else(|| {
+            AppError::bad_request("MISSING_SIGNATURE", "x-internal-signature required")
+        })?;
+    let payload = serde_json::to_string(request).map_err(|e| {
+        AppError::internal("SERIALIZATION_ERROR", format!("auth payload failed: {e}"))
+    })?;
     verify_signature(&payload, signature, secret)
         .map_err(|e| AppError::bad_request("BAD_SIGNATURE", e))
 }
 
-async fn persist_snapshot(infra: &InfraClients, snapshot: &PolicySnapshotRecord) -> Result<(), AppError> {
+async fn persist_snapshot(
+    infra: &InfraClients,
+    snapshot: &PolicySnapshotRecord,
+) -> Result<(), AppError> {
     let coll: Collection<PolicySnapshotRecord> = infra.mongo_db.collection(SNAPSHOTS_COLLECTION);
-    coll.insert_one(snapshot)
-        .await
-        .map_err(|e| AppError::internal("PERSISTENCE_ERROR", format!("insert snapshot failed: {e}")))?;
+    coll.insert_one(snapshot).await.map_err(|e| {
+        AppError::internal("PERSISTENCE_ERROR", format!("insert snapshot failed: {e}"))
+    })?;
     Ok(())
 }
 
-async fn persist_activation(infra: &InfraClients, active: &ActivePolicyRecord) -> Result<(), AppError> {
-    let coll: Collection<ActivePolicyRecord> = infra.mongo_db.collection(ACTIVATION_HISTORY_COLLECTION);
-    coll.insert_one(active)
-        .await
-        .map_err(|e| AppError::internal("PERSISTENCE_ERROR", format!("insert activation failed: {e}")))?;
+async fn persist_activation(
+    infra: &InfraClients,
+    active: &ActivePolicyRecord,
+) -> Result<(), AppError> {
+    let coll: Collection<ActivePolicyRecord> =
+        infra.mongo_db.collection(ACTIVATION_HISTORY_COLLECTION);
+    coll.insert_one(active).await.map_err(|e| {
+        AppError::internal(
+            "PERSISTENCE_ERROR",
+            format!("insert activation failed: {e}"),
+        )
+    })?;
     Ok(())
 }
 
@@ -667,22 +1704,91 @@ async fn persist_run_evidence(
     infra: &InfraClients,
     evidence: &RunPolicyEvidenceRecord,
 ) -> Result<(), AppError> {
-    let coll: Collection<RunPolicyEvidenceRecord> = infra.mongo_db.collection(RUN_EVIDENCE_COLLECTION);
-    coll.insert_one(evidence)
+    let coll: Collection<RunPolicyEvidenceRecord> =
+        infra.mongo_db.collection(RUN_EVIDENCE_COLLECTION);
+    coll.insert_one(evidence).await.map_err(|e| {
+        AppError::internal("PERSISTENCE_ERROR", format!("insert evidence failed: {e}"))
+    })?;
+    Ok(())
+}
+
+/// Persists an activation, keeps the Redis active mapping accurate and
+/// audits it. A scheduled activation leaves the cached mapping in place but
+/// expires it when the new one takes effect.
+async fn record_activation_in_infra(
+    infra: &InfraClients,
+    active: &ActivePolicyRecord,
+    proposal_id: Option<&str>,
+) -> Result<(), AppError> {
+    persist_activation(infra, active).await?;
+    if active.activated_at > now_unix()? {
+        expire_active_mapping_at(infra, active.activated_at).await?;
+    } else {
+        cache_active_mapping(infra, active).await?;
+    }
+    let mut details = format!(
+        "onchain_policy_version={} activated_at={}",
+        active.onchain_policy_version, active.activated_at
+    );
+    if let Some(proposal_id) = proposal_id {
+        details.push_str(&format!(" proposal_id={proposal_id}"));
+    }
+    append_audit(
+        infra,
+        AuditLogRecord {
+            event_type: "POLICY_ACTIVATED".to_string(),
+            policy_version: Some(active.policy_version.clone()),
+            policy_hash: Some(active.policy_hash.clone()),
+            run_id: None,
+            timestamp: now_unix()?,
+            details: Some(details),
+        },
+    )
+    .await
+}
+
+async fn upsert_proposal(
+    infra: &InfraClients,
+    proposal: &ActivationProposalRecord,
+) -> Result<(), AppError> {
+    let coll: Collection<ActivationProposalRecord> =
+        infra.mongo_db.collection(ACTIVATION_PROPOSALS_COLLECTION);
+    coll.replace_one(doc! { "proposal_id": &proposal.proposal_id }, proposal)
+        .upsert(true)
         .await
-        .map_err(|e| AppError::internal("PERSISTENCE_ERROR", format!("insert evidence failed: {e}")))?;
+        .map_err(|e| {
+            AppError::internal("PERSISTENCE_ERROR", format!("upsert proposal failed: {e}"))
+        })?;
+    Ok(())
+}
+
+async fn persist_run_evaluation(
+    infra: &InfraClients,
+    record: &RunEvaluationRecord,
+) -> Result<(), AppError> {
+    let coll: Collection<RunEvaluationRecord> =
+        infra.mongo_db.collection(RUN_EVALUATIONS_COLLECTION);
+    coll.insert_one(record).await.map_err(|e| {
+        AppError::internal(
+            "PERSISTENCE_ERROR",
+            format!("insert run evaluation failed: {e}"),
+        )
+    })?;
     Ok(())
 }
 
 async fn append_audit(infra: &InfraClients, record: AuditLogRecord) -> Result<(), AppError> {
     let coll: Collection<AuditLogRecord> = infra.mongo_db.collection(AUDIT_LOG_COLLECTION);
-    coll.insert_one(record)
-        .await
-        .map_err(|e| AppError::internal("PERSISTENCE_ERROR", format!("insert audit failed: {e}")))?;
+    coll.insert_one(record).await.map_err(|e| {
+        AppError::internal("PERSISTENCE_ERROR", format!("insert audit failed: {e}"))
+    })?;
     Ok(())
 }
 
-async fn cache_snapshot(infra: &InfraClients, snapshot: &PolicySnapshotRecord) -> Result<(), AppError> {
+async fn cache_snapshot(
+    infra: &InfraClients,
+    snapshot: &PolicySnapshotRecord,
+) -> Result<(), AppError> {
     let mut conn: MultiplexedConnection = infra
         .redis
         .get_multiplexed_async_connection()
@@ -690,8 +1796,12 @@ async fn cache_snapshot(infra: &InfraClients, snapshot: &PolicySnapshotRecord) -
         .map_err(|e| AppError::internal("REDIS_ERROR", format!("redis connect failed: {e}")))?;
     let by_version_key = format!("policy:snapshot:version:{}", snapshot.policy_version);
     let by_hash_key = format!("policy:snapshot:hash:{}", snapshot.policy_hash);
-    let payload = serde_json::to_string(snapshot)
-        .map_err(|e| AppError::internal("SERIALIZATION_ERROR", format!("snapshot cache encode failed: {e}")))?;
+    let payload = serde_json::to_string(snapshot).map_err(|e| {
+        AppError::internal(
+            "SERIALIZATION_ERROR",
+            format!("snapshot cache encode failed: {e}"),
+        )
+    })?;
     let _: () = conn
         .set_ex(by_version_key, payload, 3600)
         .await
@@ -703,14 +1813,21 @@ async fn cache_snapshot(infra: &InfraClients, snapshot: &PolicySnapshotRecord) -
     Ok(())
 }
 
-async fn cache_active_mapping(infra: &InfraClients, active: &ActivePolicyRecord) -> Result<(), AppError> {
+async fn cache_active_mapping(
+    infra: &InfraClients,
+    active: &ActivePolicyRecord,
+) -> Result<(), AppError> {
     let mut conn: MultiplexedConnection = infra
         .redis
         .get_multiplexed_async_connection()
         .await
         .map_err(|e| AppError::internal("REDIS_ERROR", format!("redis connect failed: {e}")))?;
-    let payload = serde_json::to_string(active)
-        .map_err(|e| AppError::internal("SERIALIZATION_ERROR", format!("active cache encode failed: {e}")))?;
+    let payload = serde_json::to_string(active).map_err(|e| {
+        AppError::internal(
+            "SERIALIZATION_ERROR",
+            format!("active cache encode failed: {e}"),
+        )
+    })?;
     let _: () = conn
         .set_ex("policy:active", payload, 3600)
         .await
@@ -718,6 +1835,21 @@ async fn cache_active_mapping(infra: &InfraClients, active: &ActivePolicyRecord)
     Ok(())
 }
 
+async fn expire_active_mapping_at(infra: &InfraClients, timestamp: i64) -> Result<(), AppError> {
+    let mut conn: MultiplexedConnection = infra
+        .redis
+        .get_multiplexed_async_connection()
+        .await
+        .map_err(|e| AppError::internal("REDIS_ERROR", format!("redis connect failed: {e}")))?;
+    let _: () = conn
+        .expire_at("policy:active", timestamp)
+        .await
+        .map_err(|e| {
+            AppError::internal("REDIS_ERROR", format!("expire active cache failed: {e}"))
+        })?;
+    Ok(())
+}
+
 async fn read_snapshot_by_version(
     infra: &InfraClients,
     policy_version: &str,
@@ -728,13 +1860,13 @@ async fn read_snapshot_by_version(
         .await
         .map_err(|e| AppError::internal("REDIS_ERROR", format!("redis connect failed: {e}")))?;
     let cache_key = format!("policy:snapshot:version:{policy_version}");
-    let cached: Option<String> = conn
-        .get(&cache_key)
-        .await
-        .map_err(|e| AppError::internal("REDIS_ERROR", format!("redis read snapshot failed: {e}")))?;
+    let cached: Option<String> = conn.get(&cache_key).await.map_err(|e| {
+        AppError::internal("REDIS_ERROR", format!("redis read snapshot failed: {e}"))
+    })?;
     if let Some(raw) = cached {
-        let parsed = serde_json::from_str::<PolicySnapshotRecord>(&raw)
-            .map_err(|e| AppError::internal("REDIS_ERROR", format!("cached snapshot parse failed: {e}")))?;
+        let parsed = serde_json::from_str::<PolicySnapshotRecord>(&raw).map_err(|e| {
+            AppError::internal("REDIS_ERROR", format!("cached snapshot parse failed: {e}"))
+        })?;
         return Ok(Some(parsed));
     }
 
@@ -754,17 +1886,21 @@ async fn read_snapshot_by_hash(
         .await
         .map_err(|e| AppError::internal("REDIS_ERROR", format!("redis connect failed: {e}")))?;
     let hash_key = format!("policy:snapshot:hash:{policy_hash}");
-    let mapped_version: Option<String> = conn
-        .get(&hash_key)
-        .await
-        .map_err(|e| AppError::internal("REDIS_ERROR", format!("redis read hash map failed: {e}")))?;
+    let mapped_version: Option<String> = conn.get(&hash_key).await.map_err(|e| {
+        AppError::internal("REDIS_ERROR", format!("redis read hash map failed: {e}"))
+    })?;
     if let Some(version) = mapped_version {
         return read_snapshot_by_version(infra, &version).await;
     }
     let coll: Collection<PolicySnapshotRecord> = infra.mongo_db.collection(SNAPSHOTS_COLLECTION);
     coll.find_one(doc! { "policy_hash": policy_hash })
         .await
-        .map_err(|e| AppError::internal("PERSISTENCE_ERROR", format!("read snapshot by hash failed: {e}")))
+        .map_err(|e| {
+            AppError::internal(
+                "PERSISTENCE_ERROR",
+                format!("read snapshot by hash failed: {e}"),
+            )
+        })
 }
 
 async fn read_active_mapping(infra: &InfraClients) -> Result<Option<ActivePolicyRecord>, AppError> {
@@ -778,22 +1914,22 @@ async fn read_active_mapping(infra: &InfraClients) -> Result<Option<ActivePolicy
         .await
         .map_err(|e| AppError::internal("REDIS_ERROR", format!("redis read active failed: {e}")))?;
     if let Some(raw) = cached {
-        let parsed = serde_json::from_str::<ActivePolicyRecord>(&raw)
-            .map_err(|e| AppError::internal("REDIS_ERROR", format!("cached active parse failed: {e}")))?;
+        let parsed = serde_json::from_str::<ActivePolicyRecord>(&raw).map_err(|e| {
+            AppError::internal("REDIS_ERROR", format!("cached active parse failed: {e}"))
+        })?;
         return Ok(Some(parsed));
     }
-    let coll: Collection<ActivePolicyRecord> = infra.mongo_db.collection(ACTIVATION_HISTORY_COLLECTION);
-    coll.find_one(doc! {})
-        .sort(doc! {"activated_at": -1})
-        .await
-        .map_err(|e| AppError::internal("PERSISTENCE_ERROR", format!("read active mapping failed: {e}")))
+    // Scheduled activations are stored ahead of time; only one already in
+    // effect counts as active.
+    read_active_mapping_at_timestamp(infra, now_unix()?).await
 }
 
 async fn read_active_mapping_at_timestamp(
     infra: &InfraClients,
     timestamp: i64,
 ) -> Result<Option<ActivePolicyRecord>, AppError> {
-    let coll: Collection<ActivePolicyRecord> = infra.mongo_db.collection(ACTIVATION_HISTORY_COLLECTION);
+    let coll: Collection<ActivePolicyRecord> =
+        infra.mongo_db.collection(ACTIVATION_HISTORY_COLLECTION);
     coll.find_one(doc! {
         "activated_at": { "$lte": timestamp },
         "$or": [
@@ -804,17 +1940,90 @@ async fn read_active_mapping_at_timestamp(
     })
     .sort(doc! { "activated_at": -1 })
     .await
-    .map_err(|e| AppError::internal("PERSISTENCE_ERROR", format!("read active at timestamp failed: {e}")))
+    .map_err(|e| {
+        AppError::internal(
+            "PERSISTENCE_ERROR",
+            format!("read active at timestamp failed: {e}"),
+        )
+    })
+}
+
+async fn read_activation_history(
+    infra: &InfraClients,
+) -> Result<Vec<ActivePolicyRecord>, AppError> {
+    let coll: Collection<ActivePolicyRecord> =
+        infra.mongo_db.collection(ACTIVATION_HISTORY_COLLECTION);
+    let mut cursor = coll
+        .find(doc! {})
+        .sort(doc! { "activated_at": 1 })
+        .await
+        .map_err(|e| {
+            AppError::internal(
+                "PERSISTENCE_ERROR",
+                format!("read activation history failed: {e}"),
+            )
+        })?;
+    let mut records = Vec::new();
+    while cursor
+        .advance()
+        .await
+        .map_err(|e| AppError::internal("PERSISTENCE_ERROR", format!("mongo cursor failed: {e}")))?
+    {
+        records.push(cursor.deserialize_current().map_err(|e| {
+            AppError::internal(
+                "PERSISTENCE_ERROR",
+                format!("decode activation failed: {e}"),
+            )
+        })?);
+    }
+    Ok(records)
 }
 
 async fn read_run_evidence(
     infra: &InfraClients,
     run_id: &str,
 ) -> Result<Option<RunPolicyEvidenceRecord>, AppError> {
-    let coll: Collection<RunPolicyEvidenceRecord> = infra.mongo_db.collection(RUN_EVIDENCE_COLLECTION);
-    coll.find_one(doc! { "run_id": run_id })
+    let coll: Collection<RunPolicyEvidenceRecord> =
+        infra.mongo_db.collection(RUN_EVIDENCE_COLLECTION);
+    coll.find_one(doc! { "run_id": run_id }).await.map_err(|e| {
+        AppError::internal(
+            "PERSISTENCE_ERROR",
+            format!("read run evidence failed: {e}"),
+        )
+    })
+}
+
+async fn read_recent_run_evaluations(
+    infra: &InfraClients,
+    limit: usize,
+) -> Result<Vec<RunEvaluationRecord>, AppError> {
+    let coll: Collection<RunEvaluationRecord> =
+        infra.mongo_db.collection(RUN_EVALUATIONS_COLLECTION);
+    let mut cursor = coll
+        .find(doc! {})
+        .sort(doc! { "evaluated_at": -1 })
+        .limit(limit as i64)
+        .await
+        .map_err(|e| {
+            AppError::internal(
+                "PERSISTENCE_ERROR",
+                format!("read run evaluations failed: {e}"),
+            )
+        })?;
+    let mut records = Vec::new();
+    while cursor
+        .advance()
         .await
-        .map_err(|e| AppError::internal("PERSISTENCE_ERROR", format!("read run evidence failed: {e}")))
+        .map_err(|e| AppError::internal("PERSISTENCE_ERROR", format!("mongo cursor failed: {e}")))?
+    {
+        records.push(cursor.deserialize_current().map_err(|e| {
+            AppError::internal(
+                "PERSISTENCE_ERROR",
+                format!("decode run evaluation failed: {e}"),
+            )
+        })?);
+    }
+    Ok(records)
 }
 
 fn lock_store(store: &PolicyStore) -> Result<MutexGuard<'_, PolicyStoreInner>, AppError> {
@@ -831,53 +2040,34 @@ fn now_unix() -> Result<i64, AppError> {
     Ok(elapsed.as_secs() as i64)
 }
 
+fn now_nanos() -> Result<u128, AppError> {
+    let elapsed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| AppError::internal("CLOCK_ERROR", format!("clock error: {e}")))?;
+    Ok(elapsed.as_nanos())
+}
+
+/// The activation in effect now; a scheduled activation stays at the end of
+/// the history without being current until its start time.
 fn current_active(inner: &PolicyStoreInner) -> Option<ActivePolicyRecord> {
-    inner
-        .active_idx
-        .and_then(|idx| inner.activation_history.get(idx).cloned())
+    let now = now_unix().ok()?;
+    active_at_timestamp_locked(inner, now)
 }
 
-fn active_at_timestamp_locked(inner: &PolicyStoreInner, timestamp: i64) -> Option<ActivePolicyRecord> {
+fn active_at_timestamp_locked(
+    inner: &PolicyStoreInner,
+    timestamp: i64,
+) -> Option<ActivePolicyRecord> {
     inner
         .activation_history
         .iter()
-        .filter(|rec| rec.activated_at <= timestamp && rec.deactivated_at.is_none_or(|d| timestamp < d))
+        .filter(|rec| {
+            rec.activated_at <= timestamp && rec.deactivated_at.is_none_or(|d| timestamp < d)
+        })
         .max_by_key(|rec| rec.activated_at)
         .cloned()
 }
 
-fn compute_run_evidence_hash(
-    run_id: &str,
-    run_timestamp: i64,
-    version_hint: Option<&str>,
-    policy_version: &str,
-    policy_hash: &str,
-    activated_at: i64,
-    deactivated_at: Option<i64>,
-) -> Result<String, AppError> {
-    #[derive(serde::Serialize)]
-    struct Payload<'a> {
-        run_id: &'a str,
-        run_timestamp: i64,
-        version_hint: Option<&'a str>,
-        policy_version: &'a str,
-        policy_hash: &'a str,
-        activated_at: i64,
-        deactivated_at: Option<i64>,
-    }
-    let payload = serde_json::to_string(&Payload {
-        run_id,
-        run_timestamp,
-        version_hint,
-        policy_version,
-        policy_hash,
-        activated_at,
-        deactivated_at,
-    })
-    .map_err(|e| AppError::internal("SERIALIZATION_ERROR", format!("evidence payload failed: {e}")))?;
-    Ok(sha256_hex(&payload))
-}
-
 fn active_record_from_evidence(e: &RunPolicyEvidenceRecord) -> ActivePolicyRecord {
     ActivePolicyRecord {
         onchain_policy_version: String::new(),
@@ -39,4 +39,12 @@ impl AppError {
             message: message.into(),
         }
     }
+
+    pub fn bad_gateway(code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::BAD_GATEWAY,
+            code,
+            message: message.into(),
+        }
+    }
 }
@@ -1,3 +1,4 @@
+use super::schema::{RuleOutcome, SubjectCandidate, TradeCandidate};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -30,7 +31,11 @@ pub struct RunPolicyEvidenceRecord {
     pub activated_at: i64,
     pub deactivated_at: Option<i64>,
     pub evidence_hash: String,
+    /// Recoverable secp256k1 signature over `evidence_hash`; see
+    /// `evidence_signing_service::verify_run_evidence`.
     pub evidence_signature: Option<String>,
+    #[serde(default)]
+    pub evidence_signing_key_id: Option<String>,
     pub created_at: i64,
 }
 
@@ -43,3 +48,50 @@ pub struct AuditLogRecord {
     pub timestamp: i64,
     pub details: Option<String>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunEvaluationRecord {
+    pub run_id: String,
+    pub policy_version: String,
+    pub policy_hash: String,
+    pub trade: Option<TradeCandidate>,
+    pub subjects: Vec<SubjectCandidate>,
+    pub decision: RuleOutcome,
+    pub risk_score: i64,
+    pub evaluated_at: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ProposalStatus {
+    Pending,
+    Executed,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProposalApprovalRecord {
+    pub approver_id: String,
+    pub signature: String,
+    pub approved_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivationProposalRecord {
+    pub proposal_id: String,
+    pub onchain_policy_version: String,
+    pub policy_version: String,
+    pub policy_hash: String,
+    pub effective_from: Option<i64>,
+    pub proposed_by: String,
+    pub proposed_at: i64,
+    pub required_approvals: usize,
+    pub approvals: Vec<ProposalApprovalRecord>,
+    pub status: ProposalStatus,
+    /// The activation written once the last required approval arrived.
+    pub activation: Option<ActivePolicyRecord>,
+    pub cancelled_by: Option<String>,
+    pub cancelled_at: Option<i64>,
+    pub cancel_reason: Option<String>,
+    pub cancel_signature: Option<String>,
+}
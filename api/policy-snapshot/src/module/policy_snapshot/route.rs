@@ -1,12 +1,25 @@
 use crate::app::AppState;
 use crate::module::policy_snapshot::controller;
-use axum::routing::{get, post};
 use axum::Router;
+use axum::routing::{get, post};
 
 pub fn register_routes(state: AppState) -> Router {
     Router::new()
         .route("/v1/policy/snapshots", post(controller::create_snapshot))
         .route("/v1/policy/activate", post(controller::activate_policy))
+        .route("/v1/policy/proposals", post(controller::propose_activation))
+        .route(
+            "/v1/policy/proposals/:proposal_id",
+            get(controller::get_proposal),
+        )
+        .route(
+            "/v1/policy/proposals/:proposal_id/approve",
+            post(controller::approve_proposal),
+        )
+        .route(
+            "/v1/policy/proposals/:proposal_id/cancel",
+            post(controller::cancel_proposal),
+        )
         .route(
             "/v1/policy/snapshots/:policy_version",
             get(controller::get_snapshot_by_version),
@@ -24,5 +37,15 @@ pub fn register_routes(state: AppState) -> Router {
             "/v1/policy/effective/:run_id",
             get(controller::get_effective_policy_for_run),
         )
+        .route(
+            "/v1/policy/evidence/jwks",
+            get(controller::evidence_signing_keys),
+        )
+        .route("/v1/policy/evaluate", post(controller::evaluate_policy))
+        .route("/v1/policy/diff/:from/:to", get(controller::diff_policies))
+        .route(
+            "/v1/policy/onchain/reconciliation",
+            get(controller::reconcile_onchain),
+        )
         .with_state(state)
 }
@@ -1,4 +1,7 @@
-use super::model::{ActivePolicyRecord, PolicySnapshotRecord, RunPolicyEvidenceRecord};
+use super::model::{
+    ActivationProposalRecord, ActivePolicyRecord, PolicySnapshotRecord, RunPolicyEvidenceRecord,
+};
+use fixed_amount::Amount;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -25,6 +28,9 @@ pub struct CreateSnapshotResponse {
 pub struct ActivatePolicyRequest {
     pub onchain_policy_version: String,
     pub policy_version: String,
+    /// Schedules the activation; absent or past means now.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effective_from: Option<i64>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -69,3 +75,230 @@ pub struct EffectivePolicyResponse {
     pub error_code: Option<String>,
     pub reason: String,
 }
+
+/// Scores a candidate against `policy_version` when given, otherwise against
+/// the policy active at `timestamp`, otherwise the currently active policy.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EvaluatePolicyRequest {
+    pub policy_version: Option<String>,
+    pub timestamp: Option<i64>,
+    /// When set, the candidate and its outcome are recorded for the run so
+    /// later policy dry-runs can replay it.
+    #[serde(default)]
+    pub run_id: Option<String>,
+    pub trade: Option<TradeCandidate>,
+    #[serde(default)]
+    pub subjects: Vec<SubjectCandidate>,
+}
+
+/// Amounts are decimal strings (`"3010.25"`), compared exactly against the
+/// rules; JSON numbers are rejected.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TradeCandidate {
+    /// `BASE/QUOTE`, e.g. `ETH/USDC`.
+    pub asset_pair: String,
+    /// In quote-asset units, like the rules' notional limits.
+    pub notional: Amount,
+    pub price: Option<Amount>,
+    pub reference_price: Option<Amount>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SubjectCandidate {
+    pub subject_id: String,
+    pub jurisdiction: Option<String>,
+    pub counterparty_tier: Option<String>,
+    /// Upstream risk score on a 0..=100 scale.
+    pub risk_score: Option<i64>,
+    /// Best sanctions match confidence on a 0..=100 scale.
+    pub sanctions_confidence: Option<i64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RuleOutcome {
+    Pass,
+    Review,
+    Fail,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RuleResult {
+    pub rule: String,
+    pub subject_id: Option<String>,
+    pub outcome: RuleOutcome,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EvaluatePolicyResponse {
+    pub evaluated: bool,
+    pub policy_version: String,
+    pub policy_hash: String,
+    pub schema_version: u32,
+    pub decision: Option<RuleOutcome>,
+    pub risk_score: i64,
+    pub results: Vec<RuleResult>,
+    pub error_code: Option<String>,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct PolicyDiffQuery {
+    /// Replay recently evaluated runs against the `to` rules.
+    #[serde(default)]
+    pub dry_run: bool,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RuleChange {
+    /// Dotted path into the canonical rules; set-like string arrays report
+    /// each added or removed element at the array's path.
+    pub path: String,
+    pub kind: ChangeKind,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DryRunResult {
+    pub run_id: String,
+    pub recorded_policy_version: String,
+    pub recorded_decision: RuleOutcome,
+    pub replayed_decision: RuleOutcome,
+    pub failed_rules: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DryRunReport {
+    pub policy_version: String,
+    pub replayed: usize,
+    /// Runs that were not rejected when recorded but would be now.
+    pub newly_rejected: usize,
+    /// Runs whose decision would change, most recent first.
+    pub changed: Vec<DryRunResult>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PolicyDiffResponse {
+    pub found: bool,
+    pub from_version: String,
+    pub from_hash: String,
+    pub to_version: String,
+    pub to_hash: String,
+    pub identical: bool,
+    pub changes: Vec<RuleChange>,
+    pub summary: Vec<String>,
+    pub dry_run: Option<DryRunReport>,
+    pub error_code: Option<String>,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProposeActivationRequest {
+    pub onchain_policy_version: String,
+    pub policy_version: String,
+    pub effective_from: Option<i64>,
+    pub proposed_by: String,
+}
+
+/// `signature` is the approver's EIP-191 signature over the proposal's
+/// `approval_hash`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ApproveProposalRequest {
+    pub approver_id: String,
+    pub signature: String,
+}
+
+/// `signature` is `cancelled_by`'s EIP-191 signature over the sha256 of
+/// `{"proposal_id":..,"reason":..}`, signed like an approval.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CancelProposalRequest {
+    pub cancelled_by: String,
+    pub reason: String,
+    pub signature: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProposalResponse {
+    pub accepted: bool,
+    pub proposal: Option<ActivationProposalRecord>,
+    pub approvals_remaining: usize,
+    /// What an approval commits to.
+    pub approval_payload: Option<String>,
+    /// sha256 of `approval_payload`; what approvers sign (EIP-191).
+    pub approval_hash: Option<String>,
+    pub error_code: Option<String>,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OnchainActivePolicy {
+    pub version: u64,
+    pub policy_hash: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OnchainEventKind {
+    Committed,
+    Activated,
+    Deactivated,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OnchainPolicyEvent {
+    pub kind: OnchainEventKind,
+    pub version: u64,
+    /// Only set on commits.
+    pub policy_hash: Option<String>,
+    pub block_number: Option<u64>,
+    pub block_timestamp: Option<i64>,
+    pub tx_hash: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DriftKind {
+    /// The offchain mapping's onchain version is not a number.
+    UnparseableVersion,
+    NotCommittedOnchain,
+    PolicyHashMismatch,
+    /// Activated offchain (and already in effect) but never activated onchain.
+    NotActivatedOnchain,
+    /// Activated onchain but never mapped offchain.
+    NotActivatedOffchain,
+    ActiveVersionMismatch,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ActivationDrift {
+    pub kind: DriftKind,
+    pub onchain_policy_version: Option<u64>,
+    pub policy_version: Option<String>,
+    pub offchain_hash: Option<String>,
+    pub onchain_hash: Option<String>,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReconciliationResponse {
+    pub reconciled: bool,
+    pub in_sync: bool,
+    pub onchain_active: Option<OnchainActivePolicy>,
+    pub offchain_active: Option<ActivePolicyRecord>,
+    pub offchain_activations: Vec<ActivePolicyRecord>,
+    pub onchain_events: Vec<OnchainPolicyEvent>,
+    pub drift: Vec<ActivationDrift>,
+    pub error_code: Option<String>,
+    pub reason: String,
+}
@@ -0,0 +1,94 @@
+use crate::module::policy_snapshot::model::ActivationProposalRecord;
+use crate::service::evidence_signing_service::recover_signer;
+use crate::service::hash_service::sha256_hex;
+use std::collections::BTreeMap;
+
+/// The exact string an approver signs: everything that decides what gets
+/// activated and when, bound to the proposal id.
+pub fn approval_payload(proposal: &ActivationProposalRecord) -> Result<String, String> {
+    #[derive(serde::Serialize)]
+    struct Payload<'a> {
+        proposal_id: &'a str,
+        onchain_policy_version: &'a str,
+        policy_version: &'a str,
+        policy_hash: &'a str,
+        effective_from: Option<i64>,
+    }
+    serde_json::to_string(&Payload {
+        proposal_id: &proposal.proposal_id,
+        onchain_policy_version: &proposal.onchain_policy_version,
+        policy_version: &proposal.policy_version,
+        policy_hash: &proposal.policy_hash,
+        effective_from: proposal.effective_from,
+    })
+    .map_err(|e| format!("approval payload failed: {e}"))
+}
+
+/// sha256 of `approval_payload`. Approvers sign it like run evidence: an
+/// EIP-191 (`personal_sign`) signature over the 32 bytes.
+pub fn approval_hash(proposal: &ActivationProposalRecord) -> Result<String, String> {
+    Ok(sha256_hex(&approval_payload(proposal)?))
+}
+
+/// The exact string a canceller signs: the proposal and the stated reason.
+pub fn cancel_payload(proposal_id: &str, reason: &str) -> Result<String, String> {
+    #[derive(serde::Serialize)]
+    struct Payload<'a> {
+        proposal_id: &'a str,
+        reason: &'a str,
+    }
+    serde_json::to_string(&Payload {
+        proposal_id,
+        reason,
+    })
+    .map_err(|e| format!("cancel payload failed: {e}"))
+}
+
+/// sha256 of `cancel_payload`, signed the same way as `approval_hash`.
+pub fn cancel_hash(proposal_id: &str, reason: &str) -> Result<String, String> {
+    Ok(sha256_hex(&cancel_payload(proposal_id, reason)?))
+}
+
+/// Checks that `signature` recovers to the address configured for
+/// `approver_id`. Only addresses are configured, so the service holds nothing
+/// that could forge an approval.
+pub fn verify_approval(
+    approvers: &BTreeMap<String, String>,
+    proposal: &ActivationProposalRecord,
+    approver_id: &str,
+    signature: &str,
+) -> Result<(), String> {
+    verify_signed_by(approvers, approver_id, &approval_hash(proposal)?, signature)
+}
+
+/// Checks a cancellation signature the same way as an approval.
+pub fn verify_cancellation(
+    approvers: &BTreeMap<String, String>,
+    proposal_id: &str,
+    reason: &str,
+    cancelled_by: &str,
+    signature: &str,
+) -> Result<(), String> {
+    verify_signed_by(
+        approvers,
+        cancelled_by,
+        &cancel_hash(proposal_id, reason)?,
+        signature,
+    )
+}
+
+fn verify_signed_by(
+    approvers: &BTreeMap<String, String>,
+    approver_id: &str,
+    hash: &str,
+    signature: &str,
+) -> Result<(), String> {
+    let address = approvers
+        .get(approver_id)
+        .ok_or_else(|| format!("{approver_id} is not a configured approver"))?;
+    let signer = recover_signer(hash, signature)?;
+    if !signer.eq_ignore_ascii_case(address) {
+        return Err(format!("signature is from {signer}, not {approver_id}"));
+    }
+    Ok(())
+}
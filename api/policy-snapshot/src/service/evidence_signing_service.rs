@@ -0,0 +1,276 @@
+use crate::module::policy_snapshot::model::RunPolicyEvidenceRecord;
+use crate::service::hash_service::sha256_hex;
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use ethers::types::Address;
+use ethers::utils::{keccak256, to_checksum};
+use k256::ecdsa::{RecoveryId, Signature, SigningKey, VerifyingKey};
+use k256::elliptic_curve::sec1::EncodedPoint;
+use serde::{Deserialize, Serialize};
+
+const SIGNATURE_LEN: usize = 65;
+const EIP191_PREFIX: &[u8] = b"\x19Ethereum Signed Message:\n32";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Jwk {
+    pub kty: String,
+    pub crv: String,
+    pub alg: String,
+    #[serde(rename = "use")]
+    pub key_use: String,
+    pub kid: String,
+    pub x: String,
+    pub y: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JwkSet {
+    pub keys: Vec<Jwk>,
+}
+
+/// The secp256k1 key run evidence is signed with. Its key id is the checksummed
+/// Ethereum address of the public key, which is what signature recovery yields.
+pub struct EvidenceSigner {
+    key: SigningKey,
+    key_id: String,
+}
+
+impl std::fmt::Debug for EvidenceSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EvidenceSigner")
+            .field("key_id", &self.key_id)
+            .finish()
+    }
+}
+
+impl EvidenceSigner {
+    pub fn from_hex(secret_hex: &str) -> Result<Self, String> {
+        let secret = hex::decode(secret_hex.trim().trim_start_matches("0x"))
+            .map_err(|e| format!("invalid EVIDENCE_SIGNING_KEY_HEX: {e}"))?;
+        let key = SigningKey::from_slice(&secret)
+            .map_err(|_| "EVIDENCE_SIGNING_KEY_HEX is not a valid secp256k1 key".to_string())?;
+        let key_id = evidence_key_id(key.verifying_key());
+        Ok(Self { key, key_id })
+    }
+
+    pub fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    /// 65-byte `r || s || v` signature over the evidence hash, hex with `0x`.
+    pub fn sign(&self, evidence_hash: &str) -> Result<String, String> {
+        let digest = signing_digest(evidence_hash)?;
+        let (signature, recovery_id) = self
+            .key
+            .sign_prehash_recoverable(&digest)
+            .map_err(|e| format!("evidence signing failed: {e}"))?;
+        let mut out = Vec::with_capacity(SIGNATURE_LEN);
+        out.extend_from_slice(&signature.to_bytes());
+        out.push(27 + recovery_id.to_byte());
+        Ok(format!("0x{}", hex::encode(out)))
+    }
+}
+
+/// The current signing key plus retired public keys. Rotating means moving the
+/// old key's public half to `EVIDENCE_RETIRED_PUBLIC_KEYS` so evidence it
+/// signed stays verifiable.
+#[derive(Debug)]
+pub struct EvidenceKeyRing {
+    signer: Option<EvidenceSigner>,
+    retired: Vec<VerifyingKey>,
+}
+
+impl EvidenceKeyRing {
+    pub fn new(
+        signing_key_hex: Option<&str>,
+        retired_public_keys: &[String],
+    ) -> Result<Self, String> {
+        let signer = signing_key_hex.map(EvidenceSigner::from_hex).transpose()?;
+        let mut retired = Vec::with_capacity(retired_public_keys.len());
+        for public_key in retired_public_keys {
+            let raw = hex::decode(public_key.trim().trim_start_matches("0x"))
+                .map_err(|e| format!("invalid retired evidence key {public_key}: {e}"))?;
+            let key = VerifyingKey::from_sec1_bytes(&raw).map_err(|_| {
+                format!("retired evidence key {public_key} is not a secp256k1 public key")
+            })?;
+            if signer
+                .as_ref()
+                .is_some_and(|s| s.key.verifying_key() == &key)
+            {
+                return Err("the active evidence key is also listed as retired".to_string());
+            }
+            retired.push(key);
+        }
+        Ok(Self { signer, retired })
+    }
+
+    pub fn signer(&self) -> Option<&EvidenceSigner> {
+        self.signer.as_ref()
+    }
+
+    /// Every key evidence may have been signed with, the active one first.
+    pub fn jwks(&self) -> JwkSet {
+        JwkSet {
+            keys: self
+                .signer
+                .iter()
+                .map(|s| s.key.verifying_key())
+                .chain(&self.retired)
+                .map(jwk)
+                .collect(),
+        }
+    }
+}
+
+/// Offline check of a run evidence record against a trusted key set, e.g. the
+/// JWKS fetched from `/v1/policy/evidence/jwks` and pinned by the auditor.
+/// Recomputes the evidence hash from the record's fields, recovers the signer
+/// and requires it to be the embedded key id and one of `trusted_keys`.
+/// Returns the key id that signed.
+pub fn verify_run_evidence(
+    evidence: &RunPolicyEvidenceRecord,
+    trusted_keys: &JwkSet,
+) -> Result<String, String> {
+    let expected_hash = compute_run_evidence_hash(
+        &evidence.run_id,
+        evidence.run_timestamp,
+        evidence.version_hint.as_deref(),
+        &evidence.policy_version,
+        &evidence.policy_hash,
+        evidence.activated_at,
+        evidence.deactivated_at,
+    )?;
+    if expected_hash != evidence.evidence_hash {
+        return Err("evidence_hash does not match the record's fields".to_string());
+    }
+    let signature = evidence
+        .evidence_signature
+        .as_deref()
+        .ok_or_else(|| "evidence is not signed".to_string())?;
+    let key_id = evidence
+        .evidence_signing_key_id
+        .as_deref()
+        .ok_or_else(|| "evidence has no signing key id".to_string())?;
+    let signer = recover_signer(&evidence.evidence_hash, signature)?;
+    if !signer.eq_ignore_ascii_case(key_id) {
+        return Err(format!("signature was made by {signer}, not {key_id}"));
+    }
+    let trusted = trusted_keys.keys.iter().any(|k| {
+        k.kid.eq_ignore_ascii_case(key_id)
+            && jwk_key_id(k).is_ok_and(|derived| derived.eq_ignore_ascii_case(key_id))
+    });
+    if !trusted {
+        return Err(format!("{key_id} is not a trusted evidence key"));
+    }
+    Ok(signer)
+}
+
+/// Key id of whoever produced `signature_hex` over `evidence_hash`.
+pub fn recover_signer(evidence_hash: &str, signature_hex: &str) -> Result<String, String> {
+    let raw = hex::decode(signature_hex.trim().trim_start_matches("0x"))
+        .map_err(|e| format!("invalid signature hex: {e}"))?;
+    if raw.len() != SIGNATURE_LEN {
+        return Err(format!("signature must be {SIGNATURE_LEN} bytes"));
+    }
+    let signature =
+        Signature::from_slice(&raw[..64]).map_err(|e| format!("invalid signature: {e}"))?;
+    let v = raw[64];
+    let recovery_id = RecoveryId::from_byte(v.checked_sub(27).unwrap_or(v))
+        .ok_or_else(|| format!("invalid signature recovery byte {v}"))?;
+    let digest = signing_digest(evidence_hash)?;
+    let key = VerifyingKey::recover_from_prehash(&digest, &signature, recovery_id)
+        .map_err(|_| "signature does not recover to a public key".to_string())?;
+    Ok(evidence_key_id(&key))
+}
+
+/// EIP-191 digest of the 32-byte evidence hash, so a contract can check the
+/// signature with `ecrecover` as well.
+pub fn signing_digest(evidence_hash: &str) -> Result<[u8; 32], String> {
+    let raw = hex::decode(evidence_hash.trim().trim_start_matches("0x"))
+        .map_err(|e| format!("evidence_hash is not hex: {e}"))?;
+    if raw.len() != 32 {
+        return Err("evidence_hash must be 32 bytes".to_string());
+    }
+    let mut prefixed = Vec::with_capacity(EIP191_PREFIX.len() + 32);
+    prefixed.extend_from_slice(EIP191_PREFIX);
+    prefixed.extend_from_slice(&raw);
+    Ok(keccak256(prefixed))
+}
+
+/// sha256 over the JSON of the fields that pin a run to its policy.
+pub fn compute_run_evidence_hash(
+    run_id: &str,
+    run_timestamp: i64,
+    version_hint: Option<&str>,
+    policy_version: &str,
+    policy_hash: &str,
+    activated_at: i64,
+    deactivated_at: Option<i64>,
+) -> Result<String, String> {
+    #[derive(serde::Serialize)]
+    struct Payload<'a> {
+        run_id: &'a str,
+        run_timestamp: i64,
+        version_hint: Option<&'a str>,
+        policy_version: &'a str,
+        policy_hash: &'a str,
+        activated_at: i64,
+        deactivated_at: Option<i64>,
+    }
+    let payload = serde_json::to_string(&Payload {
+        run_id,
+        run_timestamp,
+        version_hint,
+        policy_version,
+        policy_hash,
+        activated_at,
+        deactivated_at,
+    })
+    .map_err(|e| format!("evidence payload failed: {e}"))?;
+    Ok(sha256_hex(&payload))
+}
+
+fn jwk(key: &VerifyingKey) -> Jwk {
+    let point = key.to_encoded_point(false);
+    let coordinate = |c: Option<&k256::FieldBytes>| {
+        URL_SAFE_NO_PAD.encode(c.map(|b| b.as_slice()).unwrap_or_default())
+    };
+    Jwk {
+        kty: "EC".to_string(),
+        crv: "secp256k1".to_string(),
+        alg: "ES256K".to_string(),
+        key_use: "sig".to_string(),
+        kid: evidence_key_id(key),
+        x: coordinate(point.x()),
+        y: coordinate(point.y()),
+    }
+}
+
+/// Key id derived from a JWK's coordinates rather than its `kid`.
+fn jwk_key_id(jwk: &Jwk) -> Result<String, String> {
+    if jwk.kty != "EC" || jwk.crv != "secp256k1" {
+        return Err(format!("unsupported key type {} {}", jwk.kty, jwk.crv));
+    }
+    let coordinate = |c: &str| {
+        URL_SAFE_NO_PAD
+            .decode(c)
+            .ok()
+            .filter(|b| b.len() == 32)
+            .map(|b| *k256::FieldBytes::from_slice(&b))
+            .ok_or_else(|| format!("invalid coordinate in key {}", jwk.kid))
+    };
+    let point = EncodedPoint::<k256::Secp256k1>::from_affine_coordinates(
+        &coordinate(&jwk.x)?,
+        &coordinate(&jwk.y)?,
+        false,
+    );
+    let key = VerifyingKey::from_encoded_point(&point)
+        .map_err(|_| format!("key {} is not on the curve", jwk.kid))?;
+    Ok(evidence_key_id(&key))
+}
+
+fn evidence_key_id(key: &VerifyingKey) -> String {
+    let point = key.to_encoded_point(false);
+    let hash = keccak256(&point.as_bytes()[1..]);
+    to_checksum(&Address::from_slice(&hash[12..]), None)
+}
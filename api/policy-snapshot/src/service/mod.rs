@@ -1,4 +1,9 @@
+pub mod activation_approval_service;
 pub mod canonical_json_service;
+pub mod evidence_signing_service;
 pub mod hash_service;
 pub mod internal_auth_service;
+pub mod policy_diff_service;
+pub mod policy_evaluation_service;
+pub mod policy_reconciliation_service;
 pub mod rules_validation_service;
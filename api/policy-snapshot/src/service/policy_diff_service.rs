@@ -0,0 +1,162 @@
+use crate::module::policy_snapshot::model::RunEvaluationRecord;
+use crate::module::policy_snapshot::schema::{
+    ChangeKind, DryRunReport, DryRunResult, RuleChange, RuleOutcome,
+};
+use crate::service::policy_evaluation_service::evaluate_rules;
+use crate::service::rules_validation_service::RulesBundle;
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+/// Structural diff between two rule bundles. Objects are compared key by key
+/// and arrays of scalars as sets, so reordering `countries` is not a change;
+/// any other differing value is reported as a single change at its path.
+pub fn diff_rules(from: &Value, to: &Value) -> Vec<RuleChange> {
+    let mut changes = Vec::new();
+    diff_at("", from, to, &mut changes);
+    changes
+}
+
+/// One human-readable line per change, e.g. `limits.max_notional lowered
+/// from 2000000 to 1000000`.
+pub fn summarize_changes(changes: &[RuleChange]) -> Vec<String> {
+    changes
+        .iter()
+        .map(|c| match (c.kind, &c.before, &c.after) {
+            (ChangeKind::Changed, Some(Value::Number(before)), Some(Value::Number(after))) => {
+                let direction = match (before.as_f64(), after.as_f64()) {
+                    (Some(b), Some(a)) if a < b => "lowered",
+                    (Some(b), Some(a)) if a > b => "raised",
+                    _ => "changed",
+                };
+                format!("{} {direction} from {before} to {after}", c.path)
+            }
+            (ChangeKind::Changed, before, after) => format!(
+                "{} changed from {} to {}",
+                c.path,
+                display(before.as_ref()),
+                display(after.as_ref())
+            ),
+            (ChangeKind::Added, _, after) => {
+                format!("{}: added {}", c.path, display(after.as_ref()))
+            }
+            (ChangeKind::Removed, before, _) => {
+                format!("{}: removed {}", c.path, display(before.as_ref()))
+            }
+        })
+        .collect()
+}
+
+/// Replays recorded run evaluations against `rules`. Only runs whose decision
+/// would change are listed.
+pub fn dry_run(
+    policy_version: &str,
+    rules: &RulesBundle,
+    records: &[RunEvaluationRecord],
+) -> DryRunReport {
+    let mut changed = Vec::new();
+    let mut newly_rejected = 0;
+    for record in records {
+        let replay = evaluate_rules(rules, record.trade.as_ref(), &record.subjects);
+        if replay.decision == record.decision {
+            continue;
+        }
+        if replay.decision == RuleOutcome::Fail {
+            newly_rejected += 1;
+        }
+        changed.push(DryRunResult {
+            run_id: record.run_id.clone(),
+            recorded_policy_version: record.policy_version.clone(),
+            recorded_decision: record.decision,
+            replayed_decision: replay.decision,
+            failed_rules: replay
+                .results
+                .iter()
+                .filter(|r| r.outcome == RuleOutcome::Fail)
+                .map(|r| match &r.subject_id {
+                    Some(subject) => format!("{}[{subject}]", r.rule),
+                    None => r.rule.clone(),
+                })
+                .collect(),
+        });
+    }
+    DryRunReport {
+        policy_version: policy_version.to_string(),
+        replayed: records.len(),
+        newly_rejected,
+        changed,
+    }
+}
+
+fn diff_at(path: &str, from: &Value, to: &Value, changes: &mut Vec<RuleChange>) {
+    if from == to {
+        return;
+    }
+    match (from, to) {
+        (Value::Object(a), Value::Object(b)) => {
+            let keys = a.keys().chain(b.keys()).collect::<BTreeSet<_>>();
+            for key in keys {
+                let child = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                match (a.get(key), b.get(key)) {
+                    (Some(x), Some(y)) => diff_at(&child, x, y, changes),
+                    (Some(x), None) => {
+                        changes.push(change(child, ChangeKind::Removed, Some(x), None))
+                    }
+                    (None, Some(y)) => {
+                        changes.push(change(child, ChangeKind::Added, None, Some(y)))
+                    }
+                    (None, None) => {}
+                }
+            }
+        }
+        (Value::Array(a), Value::Array(b)) if is_scalar_set(a) && is_scalar_set(b) => {
+            for x in a.iter().filter(|x| !b.contains(x)) {
+                changes.push(change(path.to_string(), ChangeKind::Removed, Some(x), None));
+            }
+            for y in b.iter().filter(|y| !a.contains(y)) {
+                changes.push(change(path.to_string(), ChangeKind::Added, None, Some(y)));
+            }
+        }
+        _ => changes.push(change(
+            path.to_string(),
+            ChangeKind::Changed,
+            Some(from),
+            Some(to),
+        )),
+    }
+}
+
+fn change(
+    path: String,
+    kind: ChangeKind,
+    before: Option<&Value>,
+    after: Option<&Value>,
+) -> RuleChange {
+    RuleChange {
+        path,
+        kind,
+        before: before.cloned(),
+        after: after.cloned(),
+    }
+}
+
+/// Arrays of distinct scalars; anything else is compared as a whole so order
+/// and duplicates still register as a change.
+fn is_scalar_set(values: &[Value]) -> bool {
+    values.iter().all(|v| !v.is_array() && !v.is_object())
+        && values
+            .iter()
+            .enumerate()
+            .all(|(i, v)| !values[..i].contains(v))
+}
+
+fn display(value: Option<&Value>) -> String {
+    match value {
+        Some(Value::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+        None => "nothing".to_string(),
+    }
+}
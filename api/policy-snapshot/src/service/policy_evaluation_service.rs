@@ -0,0 +1,305 @@
+use crate::module::policy_snapshot::schema::{
+    RuleOutcome, RuleResult, SubjectCandidate, TradeCandidate,
+};
+use crate::service::rules_validation_service::{NotionalLimits, RulesBundle};
+use fixed_amount::Amount;
+use std::collections::BTreeMap;
+
+const MAX_RISK_SCORE: i64 = 100;
+
+#[derive(Debug, Clone)]
+pub struct PolicyEvaluation {
+    pub decision: RuleOutcome,
+    pub risk_score: i64,
+    pub results: Vec<RuleResult>,
+}
+
+/// Scores a candidate trade and its subjects against a rule bundle. Every rule
+/// that applies reports its own outcome; the decision is the worst of them and
+/// the risk score the highest subject score after jurisdiction uplifts.
+///
+/// Jurisdiction rules follow the compliance adapter's `evaluate_intake_policy`
+/// subject by subject (same outcome, same uplift, also for blocked or
+/// unlisted jurisdictions; `tests/data/jurisdiction_cases.json` is checked
+/// against both). Only the aggregation differs: the adapter adds every
+/// subject's uplift to one intake score, while here each subject keeps its
+/// own upstream score plus its uplift.
+pub fn evaluate_rules(
+    rules: &RulesBundle,
+    trade: Option<&TradeCandidate>,
+    subjects: &[SubjectCandidate],
+) -> PolicyEvaluation {
+    let mut results = Vec::new();
+    if let Some(trade) = trade {
+        evaluate_trade(rules, trade, &mut results);
+    }
+
+    let mut risk_score = 0;
+    for subject in subjects {
+        let score = evaluate_subject(rules, trade, subject, &mut results);
+        risk_score = risk_score.max(score);
+    }
+
+    let decision = results
+        .iter()
+        .map(|r| r.outcome)
+        .max()
+        .unwrap_or(RuleOutcome::Pass);
+    PolicyEvaluation {
+        decision,
+        risk_score,
+        results,
+    }
+}
+
+fn evaluate_trade(rules: &RulesBundle, trade: &TradeCandidate, results: &mut Vec<RuleResult>) {
+    let pair = trade.asset_pair.trim();
+    results.push(limit_result(
+        "NOTIONAL_LIMIT",
+        "policy",
+        &rules.limits,
+        trade.notional,
+    ));
+
+    if !rules.asset_pair_limits.is_empty() {
+        results.push(match lookup_pair(&rules.asset_pair_limits, pair) {
+            Some(limits) => limit_result("ASSET_PAIR_LIMIT", pair, limits, trade.notional),
+            None => trade_result(
+                "ASSET_PAIR_LIMIT",
+                RuleOutcome::Fail,
+                format!("asset pair {pair} is not permitted by the policy"),
+            ),
+        });
+    }
+
+    if let Some(band) = lookup_pair(&rules.pricing_bands, pair) {
+        let max_bps = band.max_deviation_bps;
+        let result = match (trade.price, trade.reference_price) {
+            (Some(price), Some(reference)) if !price.is_zero() && !reference.is_zero() => {
+                match price.within_bps_of(&reference, max_bps) {
+                    Ok(within) => trade_result(
+                        "PRICING_BAND",
+                        if within {
+                            RuleOutcome::Pass
+                        } else {
+                            RuleOutcome::Fail
+                        },
+                        format!(
+                            "price {price} {} {max_bps} bps of reference {reference}",
+                            if within { "within" } else { "outside" }
+                        ),
+                    ),
+                    Err(e) => trade_result(
+                        "PRICING_BAND",
+                        RuleOutcome::Review,
+                        format!("price {price} cannot be compared to reference {reference}: {e}"),
+                    ),
+                }
+            }
+            _ => trade_result(
+                "PRICING_BAND",
+                RuleOutcome::Review,
+                format!(
+                    "positive price and reference_price are required for the {pair} pricing band"
+                ),
+            ),
+        };
+        results.push(result);
+    }
+}
+
+/// Adds the subject's rule results and returns its risk score.
+fn evaluate_subject(
+    rules: &RulesBundle,
+    trade: Option<&TradeCandidate>,
+    subject: &SubjectCandidate,
+    results: &mut Vec<RuleResult>,
+) -> i64 {
+    let mut push = |rule: &str, outcome: RuleOutcome, detail: String| {
+        results.push(RuleResult {
+            rule: rule.to_string(),
+            subject_id: Some(subject.subject_id.clone()),
+            outcome,
+            detail,
+        });
+    };
+
+    let mut uplift = 0;
+    match subject
+        .jurisdiction
+        .as_deref()
+        .map(country_code)
+        .filter(|code| !code.is_empty())
+    {
+        None if rules.countries.is_empty() => push(
+            "JURISDICTION",
+            RuleOutcome::Pass,
+            "jurisdiction is missing; no allowed countries to check".to_string(),
+        ),
+        None => push(
+            "JURISDICTION",
+            RuleOutcome::Review,
+            "jurisdiction is missing".to_string(),
+        ),
+        Some(code) => {
+            uplift = rules
+                .high_risk_jurisdictions
+                .iter()
+                .find(|(k, _)| country_code(k) == code)
+                .map_or(0, |(_, v)| *v);
+            let high_risk = if uplift > 0 {
+                format!(" and high risk (+{uplift})")
+            } else {
+                String::new()
+            };
+            if contains_code(&rules.blocked_countries, &code) {
+                push(
+                    "JURISDICTION",
+                    RuleOutcome::Fail,
+                    format!("jurisdiction {code} is blocked{high_risk}"),
+                );
+            } else if !rules.countries.is_empty() && !contains_code(&rules.countries, &code) {
+                push(
+                    "JURISDICTION",
+                    RuleOutcome::Fail,
+                    format!("jurisdiction {code} is not an allowed country{high_risk}"),
+                );
+            } else {
+                push(
+                    "JURISDICTION",
+                    RuleOutcome::Pass,
+                    format!("jurisdiction {code} is allowed{high_risk}"),
+                );
+            }
+        }
+    }
+    let risk_score = (subject.risk_score.unwrap_or(0).max(0) + uplift).min(MAX_RISK_SCORE);
+
+    if !rules.counterparty_tiers.is_empty() {
+        match subject.counterparty_tier.as_deref().map(str::trim) {
+            None | Some("") => push(
+                "COUNTERPARTY_TIER",
+                RuleOutcome::Review,
+                "counterparty tier is missing".to_string(),
+            ),
+            Some(tier) => match rules.counterparty_tiers.get(tier) {
+                None => push(
+                    "COUNTERPARTY_TIER",
+                    RuleOutcome::Fail,
+                    format!("counterparty tier {tier} is not defined by the policy"),
+                ),
+                Some(rule) => {
+                    if let Some(notional) = trade
+                        .map(|t| t.notional)
+                        .filter(|n| *n > whole_units(rule.max_notional))
+                    {
+                        push(
+                            "COUNTERPARTY_TIER",
+                            RuleOutcome::Fail,
+                            format!(
+                                "notional {notional} exceeds tier {tier} max {}",
+                                rule.max_notional
+                            ),
+                        );
+                    } else if let Some(max) = rule.max_risk_score
+                        && risk_score > max
+                    {
+                        push(
+                            "COUNTERPARTY_TIER",
+                            RuleOutcome::Fail,
+                            format!("risk score {risk_score} exceeds tier {tier} max {max}"),
+                        );
+                    } else {
+                        push(
+                            "COUNTERPARTY_TIER",
+                            RuleOutcome::Pass,
+                            format!("within tier {tier} limits"),
+                        );
+                    }
+                }
+            },
+        }
+    }
+
+    if let Some(confidence) = subject.sanctions_confidence {
+        let t = &rules.thresholds;
+        push(
+            "SANCTIONS_CONFIDENCE",
+            threshold_outcome(confidence, t.review_confidence, t.fail_confidence),
+            format!(
+                "match confidence {confidence} (review {}, fail {})",
+                t.review_confidence, t.fail_confidence
+            ),
+        );
+    }
+
+    let t = &rules.thresholds;
+    push(
+        "RISK_SCORE",
+        threshold_outcome(risk_score, t.review_risk_score, t.fail_risk_score),
+        format!(
+            "risk score {risk_score} (review {}, fail {})",
+            t.review_risk_score, t.fail_risk_score
+        ),
+    );
+    risk_score
+}
+
+fn threshold_outcome(value: i64, review: i64, fail: i64) -> RuleOutcome {
+    if value >= fail {
+        RuleOutcome::Fail
+    } else if value >= review {
+        RuleOutcome::Review
+    } else {
+        RuleOutcome::Pass
+    }
+}
+
+fn limit_result(rule: &str, scope: &str, limits: &NotionalLimits, notional: Amount) -> RuleResult {
+    let within =
+        (whole_units(limits.min_notional)..=whole_units(limits.max_notional)).contains(&notional);
+    trade_result(
+        rule,
+        if within {
+            RuleOutcome::Pass
+        } else {
+            RuleOutcome::Fail
+        },
+        format!(
+            "notional {notional} {} {scope} range {}..={}",
+            if within { "within" } else { "outside" },
+            limits.min_notional,
+            limits.max_notional
+        ),
+    )
+}
+
+fn trade_result(rule: &str, outcome: RuleOutcome, detail: String) -> RuleResult {
+    RuleResult {
+        rule: rule.to_string(),
+        subject_id: None,
+        outcome,
+        detail,
+    }
+}
+
+fn lookup_pair<'a, T>(by_pair: &'a BTreeMap<String, T>, pair: &str) -> Option<&'a T> {
+    by_pair
+        .iter()
+        .find(|(k, _)| k.trim().eq_ignore_ascii_case(pair))
+        .map(|(_, v)| v)
+}
+
+/// Rule limits are whole quote units; validation keeps them positive.
+fn whole_units(value: i64) -> Amount {
+    Amount::from(u64::try_from(value).unwrap_or(0))
+}
+
+/// Same normalisation as the adapter: trimmed, upper-case ISO codes.
+fn country_code(value: &str) -> String {
+    value.trim().to_ascii_uppercase()
+}
+
+fn contains_code(codes: &[String], code: &str) -> bool {
+    codes.iter().any(|c| country_code(c) == code)
+}
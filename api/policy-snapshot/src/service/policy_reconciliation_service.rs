@@ -0,0 +1,143 @@
+use crate::infra::policy_manager_client::{normalize_policy_hash, parse_onchain_policy_version};
+use crate::module::policy_snapshot::model::ActivePolicyRecord;
+use crate::module::policy_snapshot::schema::{
+    ActivationDrift, DriftKind, OnchainActivePolicy, OnchainEventKind, OnchainPolicyEvent,
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// What the chain says, as read for one reconciliation.
+#[derive(Debug, Clone, Default)]
+pub struct OnchainView {
+    pub active: Option<OnchainActivePolicy>,
+    /// `policyHashOf` for every version the offchain history maps to; `None`
+    /// when the version was never committed.
+    pub committed_hashes: BTreeMap<u64, Option<String>>,
+    pub events: Vec<OnchainPolicyEvent>,
+}
+
+/// Compares the offchain activation history with PolicyManager. Each distinct
+/// mapping is checked once; activations scheduled after `now` are only checked
+/// for their hash, since the chain is not expected to have caught up yet.
+pub fn reconcile_activations(
+    history: &[ActivePolicyRecord],
+    offchain_active: Option<&ActivePolicyRecord>,
+    onchain: &OnchainView,
+    now: i64,
+) -> Vec<ActivationDrift> {
+    let activated_onchain = onchain
+        .events
+        .iter()
+        .filter(|e| e.kind == OnchainEventKind::Activated)
+        .map(|e| e.version)
+        .collect::<BTreeSet<_>>();
+    let mut mapped_versions = BTreeSet::new();
+    let mut seen = BTreeSet::new();
+    let mut drift = Vec::new();
+
+    for rec in history {
+        let version = match parse_onchain_policy_version(&rec.onchain_policy_version) {
+            Ok(v) => v,
+            Err(e) => {
+                if seen.insert((rec.onchain_policy_version.clone(), rec.policy_hash.clone())) {
+                    drift.push(offchain_drift(
+                        DriftKind::UnparseableVersion,
+                        None,
+                        rec,
+                        None,
+                        e,
+                    ));
+                }
+                continue;
+            }
+        };
+        mapped_versions.insert(version);
+        if !seen.insert((rec.onchain_policy_version.clone(), rec.policy_hash.clone())) {
+            continue;
+        }
+        match onchain.committed_hashes.get(&version).cloned().flatten() {
+            None => drift.push(offchain_drift(
+                DriftKind::NotCommittedOnchain,
+                Some(version),
+                rec,
+                None,
+                format!("onchain version {version} is not committed"),
+            )),
+            Some(onchain_hash) if onchain_hash != normalize_policy_hash(&rec.policy_hash) => drift
+                .push(offchain_drift(
+                    DriftKind::PolicyHashMismatch,
+                    Some(version),
+                    rec,
+                    Some(onchain_hash),
+                    format!("onchain version {version} commits a different policy hash"),
+                )),
+            Some(_) => {}
+        }
+        if rec.activated_at <= now && !activated_onchain.contains(&version) {
+            drift.push(offchain_drift(
+                DriftKind::NotActivatedOnchain,
+                Some(version),
+                rec,
+                None,
+                format!("no PolicyActivated event for version {version}"),
+            ));
+        }
+    }
+
+    for version in activated_onchain.difference(&mapped_versions) {
+        drift.push(ActivationDrift {
+            kind: DriftKind::NotActivatedOffchain,
+            onchain_policy_version: Some(*version),
+            policy_version: None,
+            offchain_hash: None,
+            onchain_hash: committed_hash_from_events(&onchain.events, *version),
+            detail: format!("onchain version {version} was activated without an offchain mapping"),
+        });
+    }
+
+    let offchain_version =
+        offchain_active.and_then(|a| parse_onchain_policy_version(&a.onchain_policy_version).ok());
+    let onchain_version = onchain.active.as_ref().map(|a| a.version);
+    if offchain_version != onchain_version {
+        drift.push(ActivationDrift {
+            kind: DriftKind::ActiveVersionMismatch,
+            onchain_policy_version: onchain_version,
+            policy_version: offchain_active.map(|a| a.policy_version.clone()),
+            offchain_hash: offchain_active.map(|a| a.policy_hash.clone()),
+            onchain_hash: onchain.active.as_ref().map(|a| a.policy_hash.clone()),
+            detail: format!(
+                "offchain active version is {}, onchain is {}",
+                describe(offchain_version),
+                describe(onchain_version)
+            ),
+        });
+    }
+    drift
+}
+
+fn offchain_drift(
+    kind: DriftKind,
+    version: Option<u64>,
+    rec: &ActivePolicyRecord,
+    onchain_hash: Option<String>,
+    detail: String,
+) -> ActivationDrift {
+    ActivationDrift {
+        kind,
+        onchain_policy_version: version,
+        policy_version: Some(rec.policy_version.clone()),
+        offchain_hash: Some(rec.policy_hash.clone()),
+        onchain_hash,
+        detail,
+    }
+}
+
+fn committed_hash_from_events(events: &[OnchainPolicyEvent], version: u64) -> Option<String> {
+    events
+        .iter()
+        .find(|e| e.kind == OnchainEventKind::Committed && e.version == version)
+        .and_then(|e| e.policy_hash.clone())
+}
+
+fn describe(version: Option<u64>) -> String {
+    version.map_or_else(|| "none".to_string(), |v| v.to_string())
+}
@@ -1,52 +1,173 @@
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::BTreeMap;
 
-pub fn validate_rules_bundle(rules: &Value) -> Result<(), String> {
-    let obj = rules
-        .as_object()
-        .ok_or_else(|| "rules must be a JSON object".to_string())?;
-
-    let limits = obj
-        .get("limits")
-        .and_then(Value::as_object)
-        .ok_or_else(|| "rules.limits object is required".to_string())?;
-    let max_notional = limits
-        .get("max_notional")
-        .and_then(Value::as_i64)
-        .ok_or_else(|| "rules.limits.max_notional integer is required".to_string())?;
-    let min_notional = limits
-        .get("min_notional")
-        .and_then(Value::as_i64)
-        .ok_or_else(|| "rules.limits.min_notional integer is required".to_string())?;
-    if min_notional <= 0 || max_notional <= 0 || min_notional > max_notional {
-        return Err("rules.limits values are invalid".to_string());
+/// Rules schema version this service understands. Bundles without a
+/// `schema_version` are treated as version 1.
+pub const RULES_SCHEMA_VERSION: u32 = 1;
+
+const MAX_SCORE: i64 = 100;
+const MAX_DEVIATION_BPS: u32 = 10_000;
+/// confidential-match's `PricingRule` names.
+const PRICING_RULES: [&str; 4] = ["midpoint", "maker_price", "taker_price", "reference_price"];
+
+/// Typed view of a policy rule bundle. The stored bundle and its hash are
+/// always taken from the submitted JSON, so optional sections left out stay
+/// out of the canonical form. Keys this model does not know about are kept
+/// for downstream consumers and ignored here.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RulesBundle {
+    #[serde(default = "default_schema_version")]
+    pub schema_version: u32,
+    /// Notional bounds applied to every trade.
+    pub limits: NotionalLimits,
+    /// Allowed jurisdictions (ISO codes).
+    pub countries: Vec<String>,
+    #[serde(default)]
+    pub blocked_countries: Vec<String>,
+    /// Jurisdiction to the risk score added for each subject based there.
+    #[serde(default)]
+    pub high_risk_jurisdictions: BTreeMap<String, i64>,
+    pub thresholds: RiskThresholds,
+    /// Per asset pair (`BASE/QUOTE`) notional bounds. When any are set, only
+    /// the listed pairs may trade.
+    #[serde(default)]
+    pub asset_pair_limits: BTreeMap<String, NotionalLimits>,
+    #[serde(default)]
+    pub counterparty_tiers: BTreeMap<String, CounterpartyTier>,
+    /// Per asset pair price bands around the reference price. The
+    /// orchestrator hands them to confidential-match as its per-pair bands.
+    #[serde(default)]
+    pub pricing_bands: BTreeMap<String, PricingBand>,
+    /// How matched trades are priced; confidential-match's default
+    /// (`midpoint`) when absent.
+    #[serde(default)]
+    pub pricing_rule: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct NotionalLimits {
+    pub min_notional: i64,
+    pub max_notional: i64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct RiskThresholds {
+    pub review_confidence: i64,
+    pub fail_confidence: i64,
+    #[serde(default = "default_review_risk_score")]
+    pub review_risk_score: i64,
+    #[serde(default = "default_fail_risk_score")]
+    pub fail_risk_score: i64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct CounterpartyTier {
+    pub max_notional: i64,
+    #[serde(default)]
+    pub max_risk_score: Option<i64>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct PricingBand {
+    /// Largest allowed distance from the reference price, in basis points.
+    pub max_deviation_bps: u32,
+}
+
+fn default_schema_version() -> u32 {
+    1
+}
+
+fn default_review_risk_score() -> i64 {
+    60
+}
+
+fn default_fail_risk_score() -> i64 {
+    95
+}
+
+pub fn validate_rules_bundle(rules: &Value) -> Result<RulesBundle, String> {
+    if !rules.is_object() {
+        return Err("rules must be a JSON object".to_string());
+    }
+    let bundle = RulesBundle::deserialize(rules)
+        .map_err(|e| format!("rules do not match the rules schema: {e}"))?;
+    if bundle.schema_version != RULES_SCHEMA_VERSION {
+        return Err(format!(
+            "rules.schema_version {} is not supported (expected {RULES_SCHEMA_VERSION})",
+            bundle.schema_version
+        ));
     }
 
-    let countries = obj
-        .get("countries")
-        .and_then(Value::as_array)
-        .ok_or_else(|| "rules.countries array is required".to_string())?;
-    if countries.is_empty() || countries.iter().any(|v| v.as_str().is_none()) {
+    if !limits_are_valid(&bundle.limits) {
+        return Err("rules.limits values are invalid".to_string());
+    }
+    if bundle.countries.is_empty() || bundle.countries.iter().any(|c| c.trim().is_empty()) {
         return Err("rules.countries must be a non-empty array of strings".to_string());
     }
+    if bundle.blocked_countries.iter().any(|c| c.trim().is_empty()) {
+        return Err("rules.blocked_countries must not contain empty codes".to_string());
+    }
+    if let Some((code, _)) = bundle
+        .high_risk_jurisdictions
+        .iter()
+        .find(|(code, uplift)| code.trim().is_empty() || !(0..=MAX_SCORE).contains(*uplift))
+    {
+        return Err(format!("rules.high_risk_jurisdictions.{code} is invalid"));
+    }
 
-    let thresholds = obj
-        .get("thresholds")
-        .and_then(Value::as_object)
-        .ok_or_else(|| "rules.thresholds object is required".to_string())?;
-    let fail_conf = thresholds
-        .get("fail_confidence")
-        .and_then(Value::as_i64)
-        .ok_or_else(|| "rules.thresholds.fail_confidence integer is required".to_string())?;
-    let review_conf = thresholds
-        .get("review_confidence")
-        .and_then(Value::as_i64)
-        .ok_or_else(|| "rules.thresholds.review_confidence integer is required".to_string())?;
-    if !(0..=100).contains(&review_conf)
-        || !(0..=100).contains(&fail_conf)
-        || review_conf > fail_conf
+    let t = &bundle.thresholds;
+    let in_range = |v: i64| (0..=MAX_SCORE).contains(&v);
+    if !in_range(t.review_confidence)
+        || !in_range(t.fail_confidence)
+        || t.review_confidence > t.fail_confidence
     {
         return Err("rules.thresholds values are invalid".to_string());
     }
+    if !in_range(t.review_risk_score)
+        || !in_range(t.fail_risk_score)
+        || t.review_risk_score > t.fail_risk_score
+    {
+        return Err("rules.thresholds risk scores are invalid".to_string());
+    }
+
+    for (pair, limits) in &bundle.asset_pair_limits {
+        if !asset_pair_is_valid(pair) || !limits_are_valid(limits) {
+            return Err(format!("rules.asset_pair_limits.{pair} is invalid"));
+        }
+    }
+    for (tier, rule) in &bundle.counterparty_tiers {
+        if tier.trim().is_empty()
+            || rule.max_notional <= 0
+            || rule.max_risk_score.is_some_and(|s| !in_range(s))
+        {
+            return Err(format!("rules.counterparty_tiers.{tier} is invalid"));
+        }
+    }
+    for (pair, band) in &bundle.pricing_bands {
+        if !asset_pair_is_valid(pair)
+            || band.max_deviation_bps == 0
+            || band.max_deviation_bps > MAX_DEVIATION_BPS
+        {
+            return Err(format!("rules.pricing_bands.{pair} is invalid"));
+        }
+    }
+    if let Some(rule) = &bundle.pricing_rule
+        && !PRICING_RULES.contains(&rule.as_str())
+    {
+        return Err(format!(
+            "rules.pricing_rule must be one of {}",
+            PRICING_RULES.join(", ")
+        ));
+    }
+
+    Ok(bundle)
+}
+
+fn limits_are_valid(limits: &NotionalLimits) -> bool {
+    limits.min_notional > 0 && limits.max_notional > 0 && limits.min_notional <= limits.max_notional
+}
 
-    Ok(())
+fn asset_pair_is_valid(pair: &str) -> bool {
+    matches!(pair.split_once('/'), Some((base, quote)) if !base.trim().is_empty() && !quote.trim().is_empty())
 }
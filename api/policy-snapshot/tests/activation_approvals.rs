@@ -0,0 +1,355 @@
+use axum::body::{Body, to_bytes};
+use http::Request;
+use policy_snapshot::app::{AppState, build_router};
+use policy_snapshot::config::environment::AppConfig;
+use policy_snapshot::module::policy_snapshot::model::ProposalStatus;
+use policy_snapshot::module::policy_snapshot::schema::{ActivePolicyResponse, ProposalResponse};
+use policy_snapshot::service::activation_approval_service::cancel_hash;
+use policy_snapshot::service::hash_service::sha256_hex;
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tower::util::ServiceExt;
+
+mod common;
+
+fn approvers() -> BTreeMap<String, String> {
+    common::approvers(&["alice", "bob", "carol"])
+}
+
+fn config(activation_approvers: BTreeMap<String, String>) -> AppConfig {
+    AppConfig {
+        activation_approvers,
+        ..common::config()
+    }
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("clock")
+        .as_secs() as i64
+}
+
+async fn app_with_snapshots(activation_approvers: BTreeMap<String, String>) -> axum::Router {
+    let app = build_router(AppState::new(config(activation_approvers), None).expect("app state"));
+    for (version, max_notional) in [("policy-v1", 1_000_000), ("policy-v2", 2_000_000)] {
+        let (status, _) = post(
+            &app,
+            "/v1/policy/snapshots",
+            json!({
+                "policy_version": version,
+                "policy_hash": null,
+                "rules": {
+                    "limits": {"max_notional": max_notional, "min_notional": 100},
+                    "countries": ["US"],
+                    "thresholds": {"fail_confidence": 90, "review_confidence": 70}
+                },
+                "metadata": null
+            }),
+        )
+        .await;
+        assert_eq!(status, http::StatusCode::OK);
+    }
+    app
+}
+
+async fn propose(app: &axum::Router, body: Value) -> (http::StatusCode, ProposalResponse) {
+    let (status, body) = post(app, "/v1/policy/proposals", body).await;
+    (
+        status,
+        serde_json::from_value(body).expect("parse proposal"),
+    )
+}
+
+async fn approve(
+    app: &axum::Router,
+    proposal: &ProposalResponse,
+    approver_id: &str,
+    signer: &str,
+) -> (http::StatusCode, ProposalResponse) {
+    let approval_hash = proposal.approval_hash.as_deref().expect("approval hash");
+    let id = &proposal.proposal.as_ref().expect("proposal").proposal_id;
+    let (status, body) = post(
+        app,
+        &format!("/v1/policy/proposals/{id}/approve"),
+        json!({
+            "approver_id": approver_id,
+            "signature": common::sign_approval(signer, approval_hash),
+        }),
+    )
+    .await;
+    (
+        status,
+        serde_json::from_value(body).expect("parse proposal"),
+    )
+}
+
+/// `cancelled_by` cancelling `proposal_id`, signed by `signer`.
+fn cancel_request(proposal_id: &str, cancelled_by: &str, signer: &str, reason: &str) -> Value {
+    let cancel_hash = cancel_hash(proposal_id, reason).expect("cancel hash");
+    json!({
+        "cancelled_by": cancelled_by,
+        "reason": reason,
+        "signature": common::sign_approval(signer, &cancel_hash),
+    })
+}
+
+#[tokio::test]
+async fn activation_needs_m_of_n_distinct_approvers() {
+    let app = app_with_snapshots(approvers()).await;
+
+    let (status, body) = post(
+        &app,
+        "/v1/policy/activate",
+        json!({"onchain_policy_version": "onchain-1", "policy_version": "policy-v1"}),
+    )
+    .await;
+    assert_eq!(status, http::StatusCode::BAD_REQUEST);
+    assert_eq!(body["error_code"], "APPROVAL_REQUIRED");
+
+    let (status, proposal) = propose(
+        &app,
+        json!({"onchain_policy_version": "onchain-1", "policy_version": "policy-v1", "proposed_by": "alice"}),
+    )
+    .await;
+    assert_eq!(status, http::StatusCode::OK);
+    assert_eq!(proposal.approvals_remaining, 2);
+    assert_eq!(
+        proposal.approval_hash.as_deref(),
+        Some(sha256_hex(proposal.approval_payload.as_deref().expect("payload")).as_str()),
+        "approvers sign the hash of the payload"
+    );
+
+    let (status, rejected) = approve(&app, &proposal, "alice", "alice").await;
+    assert_eq!(status, http::StatusCode::BAD_REQUEST);
+    assert_eq!(rejected.error_code.as_deref(), Some("SELF_APPROVAL"));
+    let (_, rejected) = approve(&app, &proposal, "bob", "carol").await;
+    assert_eq!(
+        rejected.error_code.as_deref(),
+        Some("BAD_APPROVAL_SIGNATURE")
+    );
+    let (_, rejected) = approve(&app, &proposal, "mallory", "mallory").await;
+    assert_eq!(rejected.error_code.as_deref(), Some("UNKNOWN_APPROVER"));
+
+    let (status, first) = approve(&app, &proposal, "bob", "bob").await;
+    assert_eq!(status, http::StatusCode::OK);
+    assert_eq!(first.approvals_remaining, 1);
+    let (_, active) = get(&app, "/v1/policy/active").await;
+    assert_eq!(active["error_code"], "ACTIVE_POLICY_NOT_SET");
+
+    let (status, duplicate) = approve(&app, &proposal, "bob", "bob").await;
+    assert_eq!(status, http::StatusCode::CONFLICT);
+    assert_eq!(duplicate.error_code.as_deref(), Some("DUPLICATE_APPROVAL"));
+
+    let (status, executed) = approve(&app, &proposal, "carol", "carol").await;
+    assert_eq!(status, http::StatusCode::OK);
+    let executed = executed.proposal.expect("proposal");
+    assert_eq!(executed.status, ProposalStatus::Executed);
+    assert_eq!(executed.approvals.len(), 2);
+    assert!(executed.activation.is_some());
+
+    let (_, active) = get(&app, "/v1/policy/active").await;
+    let active: ActivePolicyResponse = serde_json::from_value(active).expect("parse active");
+    assert_eq!(
+        active
+            .active_mapping
+            .expect("active")
+            .onchain_policy_version,
+        "onchain-1"
+    );
+
+    let (status, late) = approve(&app, &proposal, "alice", "alice").await;
+    assert_eq!(status, http::StatusCode::CONFLICT);
+    assert_eq!(late.error_code.as_deref(), Some("PROPOSAL_NOT_PENDING"));
+}
+
+#[tokio::test]
+async fn scheduled_activations_take_effect_at_effective_from() {
+    let app = app_with_snapshots(approvers()).await;
+    let (_, proposal) = propose(
+        &app,
+        json!({"onchain_policy_version": "onchain-1", "policy_version": "policy-v1", "proposed_by": "alice"}),
+    )
+    .await;
+    approve(&app, &proposal, "bob", "bob").await;
+    approve(&app, &proposal, "carol", "carol").await;
+
+    let effective_from = now() + 3600;
+    let (_, scheduled) = propose(
+        &app,
+        json!({
+            "onchain_policy_version": "onchain-2",
+            "policy_version": "policy-v2",
+            "proposed_by": "bob",
+            "effective_from": effective_from
+        }),
+    )
+    .await;
+    approve(&app, &scheduled, "alice", "alice").await;
+    let (status, executed) = approve(&app, &scheduled, "carol", "carol").await;
+    assert_eq!(status, http::StatusCode::OK);
+    assert_eq!(executed.reason, "approved; activation scheduled");
+    let activation = executed
+        .proposal
+        .and_then(|p| p.activation)
+        .expect("activation");
+    assert_eq!(activation.activated_at, effective_from);
+
+    let (_, current) = get(&app, "/v1/policy/active").await;
+    assert_eq!(current["active_mapping"]["policy_version"], "policy-v1");
+    assert_eq!(
+        current["active_mapping"]["deactivated_at"],
+        json!(effective_from)
+    );
+    let (_, later) = get(&app, &format!("/v1/policy/active/at/{effective_from}")).await;
+    assert_eq!(later["active_mapping"]["policy_version"], "policy-v2");
+
+    let (status, past) = propose(
+        &app,
+        json!({
+            "onchain_policy_version": "onchain-3",
+            "policy_version": "policy-v1",
+            "proposed_by": "bob",
+            "effective_from": 1
+        }),
+    )
+    .await;
+    assert_eq!(status, http::StatusCode::BAD_REQUEST);
+    assert_eq!(past.error_code.as_deref(), Some("INVALID_EFFECTIVE_FROM"));
+
+    // Activating before the scheduled one would reorder the timeline.
+    let (_, early) = propose(
+        &app,
+        json!({"onchain_policy_version": "onchain-3", "policy_version": "policy-v1", "proposed_by": "bob"}),
+    )
+    .await;
+    approve(&app, &early, "alice", "alice").await;
+    let (status, conflict) = approve(&app, &early, "carol", "carol").await;
+    assert_eq!(status, http::StatusCode::CONFLICT);
+    assert_eq!(
+        conflict.error_code.as_deref(),
+        Some("ACTIVATION_ORDER_CONFLICT")
+    );
+
+    // A final approval that cannot activate leaves the proposal actionable.
+    let id = &early.proposal.as_ref().expect("proposal").proposal_id;
+    let (status, body) = get(&app, &format!("/v1/policy/proposals/{id}")).await;
+    assert_eq!(status, http::StatusCode::OK);
+    assert_eq!(body["proposal"]["status"], "PENDING");
+    assert_eq!(body["proposal"]["activation"], Value::Null);
+    assert_eq!(body["approvals_remaining"], 1);
+    let (status, body) = post(
+        &app,
+        &format!("/v1/policy/proposals/{id}/cancel"),
+        cancel_request(id, "carol", "carol", "superseded"),
+    )
+    .await;
+    assert_eq!(status, http::StatusCode::OK);
+    assert_eq!(body["proposal"]["status"], "CANCELLED");
+}
+
+#[tokio::test]
+async fn pending_proposals_can_be_cancelled() {
+    let app = app_with_snapshots(approvers()).await;
+    let (_, proposal) = propose(
+        &app,
+        json!({"onchain_policy_version": "onchain-1", "policy_version": "policy-v1", "proposed_by": "alice"}),
+    )
+    .await;
+    let id = proposal
+        .proposal
+        .as_ref()
+        .expect("proposal")
+        .proposal_id
+        .clone();
+    let cancel = |by: &str, signer: &str| cancel_request(&id, by, signer, "wrong onchain version");
+    let path = format!("/v1/policy/proposals/{id}/cancel");
+
+    let (status, body) = post(&app, &path, cancel("mallory", "mallory")).await;
+    assert_eq!(status, http::StatusCode::BAD_REQUEST);
+    assert_eq!(body["error_code"], "UNKNOWN_CANCELLER");
+
+    let (status, body) = post(&app, &path, cancel("alice", "bob")).await;
+    assert_eq!(status, http::StatusCode::BAD_REQUEST);
+    assert_eq!(body["error_code"], "BAD_CANCEL_SIGNATURE");
+
+    // The signature covers the reason, so it cannot be replayed with another.
+    let mut replayed = cancel("alice", "alice");
+    replayed["reason"] = json!("some other reason");
+    let (status, body) = post(&app, &path, replayed).await;
+    assert_eq!(status, http::StatusCode::BAD_REQUEST);
+    assert_eq!(body["error_code"], "BAD_CANCEL_SIGNATURE");
+
+    let (status, body) = get(&app, &format!("/v1/policy/proposals/{id}")).await;
+    assert_eq!(status, http::StatusCode::OK);
+    assert_eq!(body["proposal"]["status"], "PENDING");
+
+    let (status, body) = post(&app, &path, cancel("alice", "alice")).await;
+    assert_eq!(status, http::StatusCode::OK);
+    assert_eq!(body["proposal"]["status"], "CANCELLED");
+    assert_eq!(body["proposal"]["cancelled_by"], "alice");
+    assert!(body["proposal"]["cancel_signature"].is_string());
+
+    let (status, rejected) = approve(&app, &proposal, "bob", "bob").await;
+    assert_eq!(status, http::StatusCode::CONFLICT);
+    assert_eq!(rejected.error_code.as_deref(), Some("PROPOSAL_NOT_PENDING"));
+
+    let (status, body) = get(&app, &format!("/v1/policy/proposals/{id}")).await;
+    assert_eq!(status, http::StatusCode::OK);
+    assert_eq!(body["approvals_remaining"], 0);
+
+    let single_party = app_with_snapshots(BTreeMap::new()).await;
+    let (status, disabled) = propose(
+        &single_party,
+        json!({"onchain_policy_version": "onchain-1", "policy_version": "policy-v1", "proposed_by": "alice"}),
+    )
+    .await;
+    assert_eq!(status, http::StatusCode::BAD_REQUEST);
+    assert_eq!(disabled.error_code.as_deref(), Some("APPROVALS_DISABLED"));
+
+    let effective_from = now() + 600;
+    let (status, body) = post(
+        &single_party,
+        "/v1/policy/activate",
+        json!({"onchain_policy_version": "onchain-1", "policy_version": "policy-v1", "effective_from": effective_from}),
+    )
+    .await;
+    assert_eq!(status, http::StatusCode::OK);
+    assert_eq!(body["reason"], "policy activation scheduled");
+    let (_, current) = get(&single_party, "/v1/policy/active").await;
+    assert_eq!(current["found"], false);
+}
+
+async fn post(app: &axum::Router, path: &str, body: Value) -> (http::StatusCode, Value) {
+    send(
+        app,
+        Request::builder()
+            .method("POST")
+            .uri(path)
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .expect("build request"),
+    )
+    .await
+}
+
+async fn get(app: &axum::Router, path: &str) -> (http::StatusCode, Value) {
+    send(
+        app,
+        Request::builder()
+            .uri(path)
+            .body(Body::empty())
+            .expect("build request"),
+    )
+    .await
+}
+
+async fn send(app: &axum::Router, request: Request<Body>) -> (http::StatusCode, Value) {
+    let response = app.clone().oneshot(request).await.expect("response");
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("read body");
+    (status, serde_json::from_slice(&body).expect("parse body"))
+}
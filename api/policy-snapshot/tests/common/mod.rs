@@ -0,0 +1,54 @@
+#![allow(dead_code)]
+
+use policy_snapshot::config::environment::AppConfig;
+use policy_snapshot::service::evidence_signing_service::EvidenceSigner;
+use std::collections::BTreeMap;
+
+/// Test config: in-memory stores, no internal auth, no evidence signing, no
+/// approvers, no PolicyManager. Tests override only the fields they exercise:
+/// `AppConfig { require_internal_auth: true, ..common::config() }`.
+pub fn config() -> AppConfig {
+    AppConfig {
+        rust_env: "test".to_string(),
+        api_host: "127.0.0.1".to_string(),
+        api_port: 0,
+        evidence_signing_key_hex: None,
+        evidence_retired_public_keys: Vec::new(),
+        mongodb_url: None,
+        mongodb_database: None,
+        redis_url: None,
+        require_internal_auth: false,
+        internal_auth_secret: None,
+        activation_approvers: Default::default(),
+        activation_required_approvals: 2,
+        policy_manager_rpc_url: None,
+        policy_manager_address: None,
+        policy_manager_from_block: 0,
+    }
+}
+
+/// Test-only secp256k1 key of a named approver.
+fn approver_key(name: &str) -> EvidenceSigner {
+    let secret = match name {
+        "alice" => 1u8,
+        "bob" => 2,
+        "carol" => 3,
+        _ => 4,
+    };
+    EvidenceSigner::from_hex(&format!("{secret:064x}")).expect("approver key")
+}
+
+/// `ACTIVATION_APPROVERS` for the named approvers: id to address.
+pub fn approvers(names: &[&str]) -> BTreeMap<String, String> {
+    names
+        .iter()
+        .map(|name| (name.to_string(), approver_key(name).key_id().to_string()))
+        .collect()
+}
+
+/// `signer`'s EIP-191 signature over a proposal's `approval_hash`.
+pub fn sign_approval(signer: &str, approval_hash: &str) -> String {
+    approver_key(signer)
+        .sign(approval_hash)
+        .expect("sign approval")
+}
@@ -0,0 +1,201 @@
+use axum::body::{Body, to_bytes};
+use http::Request;
+use k256::ecdsa::SigningKey;
+use policy_snapshot::app::{AppState, build_router};
+use policy_snapshot::config::environment::AppConfig;
+use policy_snapshot::module::policy_snapshot::model::RunPolicyEvidenceRecord;
+use policy_snapshot::module::policy_snapshot::schema::EffectivePolicyResponse;
+use policy_snapshot::service::evidence_signing_service::{
+    EvidenceKeyRing, JwkSet, verify_run_evidence,
+};
+use serde_json::{Value, json};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tower::util::ServiceExt;
+
+mod common;
+
+const KEY_A: &str = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+const KEY_B: &str = "0x8da4ef21b864d2cc526dbdb2a120bd2874c36c9d0a1fb7f8c63d7f7a8b41de8f";
+
+fn config(signing_key: Option<&str>, retired: Vec<String>) -> AppConfig {
+    AppConfig {
+        evidence_signing_key_hex: signing_key.map(str::to_string),
+        evidence_retired_public_keys: retired,
+        ..common::config()
+    }
+}
+
+fn public_key_hex(secret_hex: &str) -> String {
+    let secret = hex::decode(secret_hex.trim_start_matches("0x")).expect("hex key");
+    let key = SigningKey::from_slice(&secret).expect("signing key");
+    hex::encode(key.verifying_key().to_encoded_point(true).as_bytes())
+}
+
+/// An app with one active policy and the evidence it resolves for `run_id`.
+async fn resolve_evidence(app: &axum::Router, run_id: &str) -> RunPolicyEvidenceRecord {
+    let (status, _) = post(
+        app,
+        "/v1/policy/snapshots",
+        json!({
+            "policy_version": "policy-v1",
+            "policy_hash": null,
+            "rules": {
+                "limits": {"max_notional": 1_000_000, "min_notional": 100},
+                "countries": ["US"],
+                "thresholds": {"fail_confidence": 90, "review_confidence": 70}
+            },
+            "metadata": null
+        }),
+    )
+    .await;
+    assert_eq!(status, http::StatusCode::OK);
+    let (status, _) = post(
+        app,
+        "/v1/policy/activate",
+        json!({"onchain_policy_version": "onchain-1", "policy_version": "policy-v1"}),
+    )
+    .await;
+    assert_eq!(status, http::StatusCode::OK);
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("clock")
+        .as_secs();
+    let (status, body) = get(
+        app,
+        &format!("/v1/policy/effective/{run_id}?timestamp={now}"),
+    )
+    .await;
+    assert_eq!(status, http::StatusCode::OK, "{body}");
+    let effective: EffectivePolicyResponse = serde_json::from_value(body).expect("parse effective");
+    effective.evidence.expect("evidence")
+}
+
+async fn jwks(app: &axum::Router) -> JwkSet {
+    let (status, body) = get(app, "/v1/policy/evidence/jwks").await;
+    assert_eq!(status, http::StatusCode::OK);
+    serde_json::from_value(body).expect("parse jwks")
+}
+
+#[tokio::test]
+async fn run_evidence_is_signed_and_verifies_offline() {
+    let app =
+        build_router(AppState::new(config(Some(KEY_A), Vec::new()), None).expect("app state"));
+    let evidence = resolve_evidence(&app, "run-signed").await;
+    let key_id = evidence.evidence_signing_key_id.clone().expect("key id");
+    assert!(key_id.starts_with("0x") && key_id.len() == 42, "{key_id}");
+    assert_eq!(
+        evidence.evidence_signature.as_deref().map(str::len),
+        Some(2 + 65 * 2)
+    );
+
+    let keys = jwks(&app).await;
+    assert_eq!(keys.keys.len(), 1);
+    assert_eq!(keys.keys[0].kid, key_id);
+    assert_eq!(keys.keys[0].crv, "secp256k1");
+    assert_eq!(verify_run_evidence(&evidence, &keys), Ok(key_id.clone()));
+
+    let mut tampered = evidence.clone();
+    tampered.policy_hash = "00".repeat(32);
+    assert!(
+        verify_run_evidence(&tampered, &keys)
+            .unwrap_err()
+            .contains("evidence_hash")
+    );
+
+    let other =
+        build_router(AppState::new(config(Some(KEY_B), Vec::new()), None).expect("app state"));
+    let other_keys = jwks(&other).await;
+    let mut relabelled = evidence.clone();
+    relabelled.evidence_signing_key_id = Some(other_keys.keys[0].kid.clone());
+    assert!(
+        verify_run_evidence(&relabelled, &other_keys)
+            .unwrap_err()
+            .contains("signature was made by")
+    );
+    assert!(
+        verify_run_evidence(&evidence, &other_keys)
+            .unwrap_err()
+            .contains("not a trusted evidence key")
+    );
+
+    // A JWK whose kid claims the signer but whose coordinates are another key
+    // is not trusted.
+    let mut spoofed = other_keys.clone();
+    spoofed.keys[0].kid = key_id;
+    assert!(verify_run_evidence(&evidence, &spoofed).is_err());
+
+    let unsigned = build_router(AppState::new(config(None, Vec::new()), None).expect("app state"));
+    let evidence = resolve_evidence(&unsigned, "run-unsigned").await;
+    assert!(evidence.evidence_signature.is_none());
+    assert!(evidence.evidence_signing_key_id.is_none());
+    let (status, body) = get(&unsigned, "/v1/policy/evidence/jwks").await;
+    assert_eq!(status, http::StatusCode::NOT_FOUND);
+    assert_eq!(body["keys"], json!([]));
+}
+
+#[tokio::test]
+async fn rotated_keys_keep_old_evidence_verifiable() {
+    let before =
+        build_router(AppState::new(config(Some(KEY_A), Vec::new()), None).expect("app state"));
+    let old_evidence = resolve_evidence(&before, "run-before-rotation").await;
+
+    let after = build_router(
+        AppState::new(config(Some(KEY_B), vec![public_key_hex(KEY_A)]), None).expect("app state"),
+    );
+    let new_evidence = resolve_evidence(&after, "run-after-rotation").await;
+    let keys = jwks(&after).await;
+    assert_eq!(keys.keys.len(), 2);
+    assert_eq!(
+        Some(keys.keys[0].kid.clone()),
+        new_evidence.evidence_signing_key_id
+    );
+    assert_eq!(
+        Some(keys.keys[1].kid.clone()),
+        old_evidence.evidence_signing_key_id
+    );
+    assert!(verify_run_evidence(&old_evidence, &keys).is_ok());
+    assert!(verify_run_evidence(&new_evidence, &keys).is_ok());
+
+    assert!(EvidenceKeyRing::new(Some(KEY_A), &[public_key_hex(KEY_A)]).is_err());
+    assert!(EvidenceKeyRing::new(Some(KEY_B), &["04abcd".to_string()]).is_err());
+
+    // A bad key stops startup instead of leaving evidence unsigned.
+    let err =
+        AppState::new(config(Some("not-hex"), Vec::new()), None).expect_err("invalid signing key");
+    assert!(err.contains("EVIDENCE_SIGNING_KEY_HEX"), "{err}");
+    assert!(AppState::new(config(Some(KEY_B), vec!["04abcd".to_string()]), None).is_err());
+}
+
+async fn post(app: &axum::Router, path: &str, body: Value) -> (http::StatusCode, Value) {
+    send(
+        app,
+        Request::builder()
+            .method("POST")
+            .uri(path)
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .expect("build request"),
+    )
+    .await
+}
+
+async fn get(app: &axum::Router, path: &str) -> (http::StatusCode, Value) {
+    send(
+        app,
+        Request::builder()
+            .uri(path)
+            .body(Body::empty())
+            .expect("build request"),
+    )
+    .await
+}
+
+async fn send(app: &axum::Router, request: Request<Body>) -> (http::StatusCode, Value) {
+    let response = app.clone().oneshot(request).await.expect("response");
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("read body");
+    (status, serde_json::from_slice(&body).expect("parse body"))
+}
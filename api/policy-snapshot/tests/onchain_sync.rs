@@ -0,0 +1,422 @@
+use axum::body::{Body, to_bytes};
+use axum::extract::State;
+use axum::routing::post as post_route;
+use axum::{Json, Router};
+use ethers::abi::{Token, encode};
+use ethers::types::U256;
+use ethers::utils::{id, keccak256};
+use http::Request;
+use policy_snapshot::app::{AppState, build_router};
+use policy_snapshot::config::environment::AppConfig;
+use policy_snapshot::infra::policy_manager_client::parse_onchain_policy_version;
+use policy_snapshot::module::policy_snapshot::model::ActivePolicyRecord;
+use policy_snapshot::module::policy_snapshot::schema::{
+    DriftKind, OnchainActivePolicy, OnchainEventKind, OnchainPolicyEvent, ReconciliationResponse,
+};
+use policy_snapshot::service::policy_reconciliation_service::{OnchainView, reconcile_activations};
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use tokio::net::TcpListener;
+use tower::util::ServiceExt;
+
+mod common;
+
+const POLICY_MANAGER: &str = "0x00000000000000000000000000000000000000aa";
+
+/// Just enough of a PolicyManager node for the calls policy-snapshot makes.
+#[derive(Debug, Default)]
+struct Chain {
+    committed: BTreeMap<u64, [u8; 32]>,
+    active: u64,
+    logs: Vec<Value>,
+}
+
+type SharedChain = Arc<Mutex<Chain>>;
+
+async fn spawn_chain() -> (String, SharedChain) {
+    let chain = SharedChain::default();
+    let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+    let url = format!("http://{}", listener.local_addr().expect("addr"));
+    let router = Router::new()
+        .route("/", post_route(rpc))
+        .with_state(chain.clone());
+    tokio::spawn(async move { axum::serve(listener, router).await });
+    (url, chain)
+}
+
+async fn rpc(State(chain): State<SharedChain>, Json(req): Json<Value>) -> Json<Value> {
+    let chain = chain.lock().expect("chain");
+    let params = &req["params"];
+    let result = match req["method"].as_str().unwrap_or_default() {
+        "eth_call" => {
+            let input = params[0]["input"]
+                .as_str()
+                .or(params[0]["data"].as_str())
+                .expect("call data");
+            let input = hex::decode(input.trim_start_matches("0x")).expect("hex call data");
+            if input[..4] == id("getActivePolicy()") {
+                let hash = chain
+                    .committed
+                    .get(&chain.active)
+                    .copied()
+                    .unwrap_or_default();
+                abi_hex(&[
+                    Token::Uint(chain.active.into()),
+                    Token::FixedBytes(hash.to_vec()),
+                    Token::FixedBytes(vec![0; 32]),
+                ])
+            } else {
+                assert_eq!(input[..4], id("policyHashOf(uint64)"));
+                let version = U256::from_big_endian(&input[4..36]).as_u64();
+                match chain.committed.get(&version) {
+                    Some(hash) => abi_hex(&[Token::FixedBytes(hash.to_vec())]),
+                    None => {
+                        return Json(json!({
+                            "jsonrpc": "2.0",
+                            "id": req["id"],
+                            "error": {
+                                "code": 3,
+                                "message": "execution reverted",
+                                "data": format!("0x{}", hex::encode(id("PolicyNotFound()")))
+                            }
+                        }));
+                    }
+                }
+            }
+        }
+        "eth_getLogs" => json!(chain.logs),
+        "eth_getBlockByNumber" => {
+            let number = params[0].as_str().expect("block number");
+            let n = u64::from_str_radix(number.trim_start_matches("0x"), 16).expect("hex number");
+            json!({"number": number, "hash": null, "timestamp": format!("0x{:x}", 1_700_000_000 + n * 12)})
+        }
+        other => panic!("unexpected rpc method {other}"),
+    };
+    Json(json!({"jsonrpc": "2.0", "id": req["id"], "result": result}))
+}
+
+fn abi_hex(tokens: &[Token]) -> Value {
+    json!(format!("0x{}", hex::encode(encode(tokens))))
+}
+
+fn log(
+    signature: &str,
+    version: u64,
+    policy_hash: Option<[u8; 32]>,
+    block: u64,
+    index: u64,
+) -> Value {
+    let mut topics = vec![
+        format!("0x{}", hex::encode(keccak256(signature))),
+        format!("0x{}", hex::encode(encode(&[Token::Uint(version.into())]))),
+    ];
+    if let Some(hash) = policy_hash {
+        topics.push(format!("0x{}", hex::encode(hash)));
+    }
+    json!({
+        "address": POLICY_MANAGER,
+        "topics": topics,
+        "data": format!("0x{}", hex::encode([0u8; 32])),
+        "blockNumber": format!("0x{block:x}"),
+        "logIndex": format!("0x{index:x}"),
+        "transactionHash": format!("0x{}", hex::encode(keccak256(format!("{block}:{index}")))),
+        "removed": false
+    })
+}
+
+fn hash_bytes(policy_hash: &str) -> [u8; 32] {
+    hex::decode(policy_hash)
+        .expect("hex hash")
+        .try_into()
+        .expect("32 bytes")
+}
+
+fn config(rpc_url: Option<&str>, activation_approvers: BTreeMap<String, String>) -> AppConfig {
+    AppConfig {
+        activation_approvers,
+        activation_required_approvals: 1,
+        policy_manager_rpc_url: rpc_url.map(str::to_string),
+        policy_manager_address: rpc_url.map(|_| POLICY_MANAGER.to_string()),
+        ..common::config()
+    }
+}
+
+async fn create_snapshot(app: &Router, version: &str, max_notional: i64) -> String {
+    let (status, body) = post(
+        app,
+        "/v1/policy/snapshots",
+        json!({
+            "policy_version": version,
+            "policy_hash": null,
+            "rules": {
+                "limits": {"max_notional": max_notional, "min_notional": 100},
+                "countries": ["US"],
+                "thresholds": {"fail_confidence": 90, "review_confidence": 70}
+            },
+            "metadata": null
+        }),
+    )
+    .await;
+    assert_eq!(status, http::StatusCode::OK);
+    body["policy_hash"]
+        .as_str()
+        .expect("policy hash")
+        .to_string()
+}
+
+async fn activate(
+    app: &Router,
+    onchain_policy_version: &str,
+    policy_version: &str,
+) -> (http::StatusCode, Value) {
+    post(
+        app,
+        "/v1/policy/activate",
+        json!({"onchain_policy_version": onchain_policy_version, "policy_version": policy_version}),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn activation_is_refused_when_the_onchain_hash_disagrees() {
+    let (rpc_url, chain) = spawn_chain().await;
+    let app = build_router(
+        AppState::new(config(Some(&rpc_url), BTreeMap::new()), None).expect("app state"),
+    );
+    let v1 = create_snapshot(&app, "policy-v1", 1_000_000).await;
+    create_snapshot(&app, "policy-v2", 2_000_000).await;
+    {
+        let mut chain = chain.lock().expect("chain");
+        chain.committed.insert(1, hash_bytes(&v1));
+        chain.committed.insert(2, [0xab; 32]);
+    }
+
+    let (status, body) = activate(&app, "onchain-1", "policy-v1").await;
+    assert_eq!(status, http::StatusCode::OK, "{body}");
+
+    let (status, body) = activate(&app, "onchain-2", "policy-v2").await;
+    assert_eq!(status, http::StatusCode::CONFLICT);
+    assert_eq!(body["error_code"], "ONCHAIN_POLICY_HASH_MISMATCH");
+    assert!(
+        body["reason"]
+            .as_str()
+            .unwrap_or_default()
+            .contains("0xabab"),
+        "{body}"
+    );
+    let (status, body) = activate(&app, "3", "policy-v2").await;
+    assert_eq!(status, http::StatusCode::CONFLICT);
+    assert_eq!(body["error_code"], "ONCHAIN_POLICY_NOT_COMMITTED");
+    let (status, body) = activate(&app, "latest", "policy-v2").await;
+    assert_eq!(status, http::StatusCode::BAD_REQUEST);
+    assert_eq!(body["error_code"], "INVALID_ONCHAIN_POLICY_VERSION");
+
+    let (_, active) = get(&app, "/v1/policy/active").await;
+    assert_eq!(active["active_mapping"]["policy_version"], "policy-v1");
+
+    let approvers = common::approvers(&["alice"]);
+    let guarded =
+        build_router(AppState::new(config(Some(&rpc_url), approvers), None).expect("app state"));
+    create_snapshot(&guarded, "policy-v2", 2_000_000).await;
+    let (status, body) = post(
+        &guarded,
+        "/v1/policy/proposals",
+        json!({"onchain_policy_version": "2", "policy_version": "policy-v2", "proposed_by": "bob"}),
+    )
+    .await;
+    assert_eq!(status, http::StatusCode::CONFLICT);
+    assert_eq!(body["error_code"], "ONCHAIN_POLICY_HASH_MISMATCH");
+
+    let unreachable = build_router(
+        AppState::new(config(Some("http://127.0.0.1:1"), BTreeMap::new()), None)
+            .expect("app state"),
+    );
+    create_snapshot(&unreachable, "policy-v1", 1_000_000).await;
+    let (status, body) = activate(&unreachable, "1", "policy-v1").await;
+    assert_eq!(status, http::StatusCode::BAD_GATEWAY);
+    assert_eq!(body["error_code"], "ONCHAIN_READ_FAILED");
+}
+
+#[tokio::test]
+async fn onchain_versions_and_client_config_are_strict() {
+    assert_eq!(parse_onchain_policy_version("42"), Ok(42));
+    assert_eq!(parse_onchain_policy_version(" onchain-7 "), Ok(7));
+    for bad in [
+        "v1.2",
+        "onchain-1b",
+        "onchain-",
+        "1.2",
+        "+3",
+        "onchain-v3",
+        "",
+    ] {
+        assert!(parse_onchain_policy_version(bad).is_err(), "{bad}");
+    }
+
+    let app = build_router(AppState::new(config(None, BTreeMap::new()), None).expect("app state"));
+    create_snapshot(&app, "policy-v1", 1_000_000).await;
+    let (status, body) = activate(&app, "v1.2", "policy-v1").await;
+    assert_eq!(status, http::StatusCode::BAD_REQUEST);
+    assert_eq!(body["error_code"], "INVALID_ONCHAIN_POLICY_VERSION");
+
+    let bad_address = AppConfig {
+        policy_manager_address: Some("0xnot-an-address".to_string()),
+        ..config(Some("http://127.0.0.1:8545"), BTreeMap::new())
+    };
+    let err = AppState::new(bad_address, None).expect_err("unusable PolicyManager config");
+    assert!(err.contains("POLICY_MANAGER_ADDRESS"), "{err}");
+}
+
+#[tokio::test]
+async fn reconciliation_reports_drift_between_histories() {
+    let (rpc_url, chain) = spawn_chain().await;
+    let app = build_router(
+        AppState::new(config(Some(&rpc_url), BTreeMap::new()), None).expect("app state"),
+    );
+    let v1 = create_snapshot(&app, "policy-v1", 1_000_000).await;
+    let v2 = create_snapshot(&app, "policy-v2", 2_000_000).await;
+    {
+        let mut chain = chain.lock().expect("chain");
+        chain.committed.insert(1, hash_bytes(&v1));
+        chain.committed.insert(2, hash_bytes(&v2));
+        chain.active = 2;
+        let committed = "PolicyCommitted(uint64,bytes32,bytes32)";
+        chain.logs = vec![
+            log(committed, 1, Some(hash_bytes(&v1)), 1, 0),
+            log(committed, 2, Some(hash_bytes(&v2)), 2, 0),
+            log("PolicyActivated(uint64)", 1, None, 3, 0),
+            log("PolicyDeactivated(uint64)", 1, None, 4, 0),
+            log("PolicyActivated(uint64)", 2, None, 4, 1),
+        ];
+    }
+    let (status, _) = activate(&app, "onchain-1", "policy-v1").await;
+    assert_eq!(status, http::StatusCode::OK);
+
+    let (status, body) = get(&app, "/v1/policy/onchain/reconciliation").await;
+    assert_eq!(status, http::StatusCode::OK);
+    let report: ReconciliationResponse = serde_json::from_value(body).expect("parse report");
+    assert!(report.reconciled);
+    assert!(!report.in_sync);
+    assert_eq!(report.onchain_active.as_ref().map(|a| a.version), Some(2));
+    assert_eq!(report.onchain_events.len(), 5);
+    let activated = &report.onchain_events[4];
+    assert_eq!(activated.kind, OnchainEventKind::Activated);
+    assert_eq!(activated.block_timestamp, Some(1_700_000_048));
+    assert_eq!(
+        report.onchain_events[0].policy_hash.as_deref(),
+        Some(v1.as_str())
+    );
+    let drift = report
+        .drift
+        .iter()
+        .map(|d| (d.kind, d.onchain_policy_version))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        drift,
+        vec![
+            (DriftKind::NotActivatedOffchain, Some(2)),
+            (DriftKind::ActiveVersionMismatch, Some(2)),
+        ]
+    );
+
+    let (status, _) = activate(&app, "onchain-2", "policy-v2").await;
+    assert_eq!(status, http::StatusCode::OK);
+    let (_, body) = get(&app, "/v1/policy/onchain/reconciliation").await;
+    let report: ReconciliationResponse = serde_json::from_value(body).expect("parse report");
+    assert!(report.in_sync, "{:?}", report.drift);
+    assert_eq!(report.offchain_activations.len(), 2);
+
+    let offline =
+        build_router(AppState::new(config(None, BTreeMap::new()), None).expect("app state"));
+    let (status, body) = get(&offline, "/v1/policy/onchain/reconciliation").await;
+    assert_eq!(status, http::StatusCode::BAD_REQUEST);
+    assert_eq!(body["error_code"], "ONCHAIN_SYNC_DISABLED");
+}
+
+#[test]
+fn reconcile_flags_each_kind_of_offchain_drift() {
+    let record = |onchain: &str, hash: &str, activated_at: i64| ActivePolicyRecord {
+        onchain_policy_version: onchain.to_string(),
+        policy_version: format!("policy-{onchain}"),
+        policy_hash: hash.to_string(),
+        activated_at,
+        deactivated_at: None,
+    };
+    let history = vec![
+        record("7", &"aa".repeat(32), 100),
+        record("nightly", &"dd".repeat(32), 150),
+        record("10", &format!("0x{}", "CC".repeat(32)), 200),
+        record("9", &"ee".repeat(32), 5_000),
+    ];
+    let onchain = OnchainView {
+        active: Some(OnchainActivePolicy {
+            version: 10,
+            policy_hash: "cc".repeat(32),
+        }),
+        committed_hashes: BTreeMap::from([
+            (7, Some("bb".repeat(32))),
+            (9, None),
+            (10, Some("cc".repeat(32))),
+        ]),
+        events: vec![OnchainPolicyEvent {
+            kind: OnchainEventKind::Activated,
+            version: 10,
+            policy_hash: None,
+            block_number: Some(1),
+            block_timestamp: Some(200),
+            tx_hash: None,
+        }],
+    };
+
+    let drift = reconcile_activations(&history, history.get(2), &onchain, 1_000);
+    let kinds = drift
+        .iter()
+        .map(|d| (d.kind, d.policy_version.as_deref()))
+        .collect::<Vec<_>>();
+    // The scheduled activation of version 9 is not expected onchain yet, but
+    // its hash still has to be committed.
+    assert_eq!(
+        kinds,
+        vec![
+            (DriftKind::PolicyHashMismatch, Some("policy-7")),
+            (DriftKind::NotActivatedOnchain, Some("policy-7")),
+            (DriftKind::UnparseableVersion, Some("policy-nightly")),
+            (DriftKind::NotCommittedOnchain, Some("policy-9")),
+        ]
+    );
+    assert_eq!(drift[0].onchain_hash, Some("bb".repeat(32)));
+}
+
+async fn post(app: &Router, path: &str, body: Value) -> (http::StatusCode, Value) {
+    send(
+        app,
+        Request::builder()
+            .method("POST")
+            .uri(path)
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .expect("build request"),
+    )
+    .await
+}
+
+async fn get(app: &Router, path: &str) -> (http::StatusCode, Value) {
+    send(
+        app,
+        Request::builder()
+            .uri(path)
+            .body(Body::empty())
+            .expect("build request"),
+    )
+    .await
+}
+
+async fn send(app: &Router, request: Request<Body>) -> (http::StatusCode, Value) {
+    let response = app.clone().oneshot(request).await.expect("response");
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("read body");
+    (status, serde_json::from_slice(&body).expect("parse body"))
+}
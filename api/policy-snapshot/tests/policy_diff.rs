@@ -0,0 +1,160 @@
+use axum::body::{Body, to_bytes};
+use http::Request;
+use policy_snapshot::app::{AppState, build_router};
+use policy_snapshot::module::policy_snapshot::schema::{
+    ChangeKind, EvaluatePolicyResponse, PolicyDiffResponse, RuleOutcome,
+};
+use policy_snapshot::service::policy_diff_service::{diff_rules, summarize_changes};
+use serde_json::{Value, json};
+use tower::util::ServiceExt;
+
+mod common;
+
+fn rules_v1() -> Value {
+    json!({
+        "limits": {"max_notional": 5_000_000, "min_notional": 100},
+        "countries": ["US", "GB", "SG"],
+        "thresholds": {"fail_confidence": 90, "review_confidence": 70}
+    })
+}
+
+fn rules_v2() -> Value {
+    json!({
+        "limits": {"max_notional": 1_000_000, "min_notional": 100},
+        "countries": ["US", "GB", "CA"],
+        "thresholds": {"fail_confidence": 90, "review_confidence": 70},
+        "pricing_bands": {"ETH/USDC": {"max_deviation_bps": 25}}
+    })
+}
+
+#[test]
+fn diff_reports_structural_changes_with_a_summary() {
+    let changes = diff_rules(&rules_v1(), &rules_v2());
+    let summary = summarize_changes(&changes);
+    assert!(
+        summary.contains(&"limits.max_notional lowered from 5000000 to 1000000".to_string()),
+        "{summary:?}"
+    );
+    assert!(summary.contains(&"countries: removed SG".to_string()));
+    assert!(summary.contains(&"countries: added CA".to_string()));
+    let band = changes
+        .iter()
+        .find(|c| c.path == "pricing_bands")
+        .expect("pricing band change");
+    assert_eq!(band.kind, ChangeKind::Added);
+    assert_eq!(changes.len(), 4, "{changes:?}");
+
+    let mut reordered = rules_v1();
+    reordered["countries"] = json!(["SG", "US", "GB"]);
+    assert!(diff_rules(&rules_v1(), &reordered).is_empty());
+
+    let mut raised = rules_v1();
+    raised["thresholds"]["fail_confidence"] = json!(95);
+    assert_eq!(
+        summarize_changes(&diff_rules(&rules_v1(), &raised)),
+        vec!["thresholds.fail_confidence raised from 90 to 95".to_string()]
+    );
+}
+
+#[tokio::test]
+async fn dry_run_replays_recorded_runs_against_the_new_rules() {
+    let app = build_router(AppState::new(common::config(), None).expect("app state"));
+    for (version, rules) in [("policy-v1", rules_v1()), ("policy-v2", rules_v2())] {
+        let (status, _) = post(
+            &app,
+            "/v1/policy/snapshots",
+            json!({"policy_version": version, "policy_hash": null, "rules": rules, "metadata": null}),
+        )
+        .await;
+        assert_eq!(status, http::StatusCode::OK);
+    }
+
+    for (run_id, notional, jurisdiction) in [
+        ("run-small", "50000", "US"),
+        ("run-large", "2000000", "US"),
+        ("run-sg", "50000", "SG"),
+    ] {
+        let (status, body) = post(
+            &app,
+            "/v1/policy/evaluate",
+            json!({
+                "policy_version": "policy-v1",
+                "run_id": run_id,
+                "trade": {"asset_pair": "WBTC/USDC", "notional": notional},
+                "subjects": [{"subject_id": "cp-1", "jurisdiction": jurisdiction, "risk_score": 5}]
+            }),
+        )
+        .await;
+        assert_eq!(status, http::StatusCode::OK);
+        let evaluation: EvaluatePolicyResponse = serde_json::from_value(body).expect("parse");
+        assert_eq!(evaluation.decision, Some(RuleOutcome::Pass));
+    }
+
+    let (status, body) = get(&app, "/v1/policy/diff/policy-v1/policy-v2?dry_run=true").await;
+    assert_eq!(status, http::StatusCode::OK);
+    let diff: PolicyDiffResponse = serde_json::from_value(body).expect("parse diff");
+    assert!(diff.found);
+    assert!(!diff.identical);
+    assert_eq!(diff.changes.len(), 4);
+    let report = diff.dry_run.expect("dry run");
+    assert_eq!(report.policy_version, "policy-v2");
+    assert_eq!(report.replayed, 3);
+    assert_eq!(report.newly_rejected, 2);
+    let mut rejected = report
+        .changed
+        .iter()
+        .map(|r| (r.run_id.as_str(), r.failed_rules.clone()))
+        .collect::<Vec<_>>();
+    rejected.sort();
+    assert_eq!(
+        rejected,
+        vec![
+            ("run-large", vec!["NOTIONAL_LIMIT".to_string()]),
+            ("run-sg", vec!["JURISDICTION[cp-1]".to_string()]),
+        ]
+    );
+
+    let (status, body) = get(&app, "/v1/policy/diff/policy-v1/policy-v1").await;
+    assert_eq!(status, http::StatusCode::OK);
+    let same: PolicyDiffResponse = serde_json::from_value(body).expect("parse diff");
+    assert!(same.identical);
+    assert!(same.changes.is_empty());
+    assert!(same.dry_run.is_none());
+
+    let (status, body) = get(&app, "/v1/policy/diff/policy-v1/policy-v9").await;
+    assert_eq!(status, http::StatusCode::NOT_FOUND);
+    assert_eq!(body["error_code"], "TO_VERSION_NOT_FOUND");
+}
+
+async fn post(app: &axum::Router, path: &str, body: Value) -> (http::StatusCode, Value) {
+    send(
+        app,
+        Request::builder()
+            .method("POST")
+            .uri(path)
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .expect("build request"),
+    )
+    .await
+}
+
+async fn get(app: &axum::Router, path: &str) -> (http::StatusCode, Value) {
+    send(
+        app,
+        Request::builder()
+            .uri(path)
+            .body(Body::empty())
+            .expect("build request"),
+    )
+    .await
+}
+
+async fn send(app: &axum::Router, request: Request<Body>) -> (http::StatusCode, Value) {
+    let response = app.clone().oneshot(request).await.expect("response");
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("read body");
+    (status, serde_json::from_slice(&body).expect("parse body"))
+}
@@ -0,0 +1,378 @@
+use axum::body::{Body, to_bytes};
+use fixed_amount::Amount;
+use http::Request;
+use policy_snapshot::app::{AppState, build_router};
+use policy_snapshot::module::policy_snapshot::schema::{
+    EvaluatePolicyResponse, RuleOutcome, RuleResult, SubjectCandidate, TradeCandidate,
+};
+use policy_snapshot::service::policy_evaluation_service::evaluate_rules;
+use policy_snapshot::service::rules_validation_service::validate_rules_bundle;
+use serde_json::{Value, json};
+use tower::util::ServiceExt;
+
+mod common;
+
+fn rules() -> Value {
+    json!({
+        "schema_version": 1,
+        "limits": {"max_notional": 5_000_000, "min_notional": 100},
+        "countries": ["US", "GB", "SG"],
+        "blocked_countries": ["KP"],
+        "high_risk_jurisdictions": {"SG": 20},
+        "thresholds": {
+            "fail_confidence": 90,
+            "review_confidence": 70,
+            "review_risk_score": 50,
+            "fail_risk_score": 80
+        },
+        "asset_pair_limits": {
+            "ETH/USDC": {"max_notional": 1_000_000, "min_notional": 1_000}
+        },
+        "counterparty_tiers": {
+            "tier1": {"max_notional": 2_000_000},
+            "tier3": {"max_notional": 50_000, "max_risk_score": 30}
+        },
+        "pricing_bands": {"ETH/USDC": {"max_deviation_bps": 50}}
+    })
+}
+
+fn subject(id: &str, jurisdiction: &str, tier: &str, risk_score: i64) -> SubjectCandidate {
+    SubjectCandidate {
+        subject_id: id.to_string(),
+        jurisdiction: Some(jurisdiction.to_string()),
+        counterparty_tier: Some(tier.to_string()),
+        risk_score: Some(risk_score),
+        sanctions_confidence: None,
+    }
+}
+
+fn amount(value: &str) -> Amount {
+    value.parse().expect("amount")
+}
+
+fn trade(notional: &str, price: &str) -> TradeCandidate {
+    TradeCandidate {
+        asset_pair: "ETH/USDC".to_string(),
+        notional: amount(notional),
+        price: Some(amount(price)),
+        reference_price: Some(amount("3000")),
+    }
+}
+
+fn outcome<'a>(results: &'a [RuleResult], rule: &str, subject_id: Option<&str>) -> &'a RuleResult {
+    results
+        .iter()
+        .find(|r| r.rule == rule && r.subject_id.as_deref() == subject_id)
+        .unwrap_or_else(|| panic!("missing {rule} result for {subject_id:?}"))
+}
+
+#[test]
+fn rules_bundles_are_typed_and_versioned() {
+    let legacy = json!({
+        "limits": {"max_notional": 10_000, "min_notional": 10},
+        "countries": ["US"],
+        "thresholds": {"fail_confidence": 90, "review_confidence": 70}
+    });
+    let bundle = validate_rules_bundle(&legacy).expect("legacy bundle");
+    assert_eq!(bundle.schema_version, 1);
+    assert_eq!(bundle.thresholds.review_risk_score, 60);
+    assert!(bundle.asset_pair_limits.is_empty());
+    assert!(validate_rules_bundle(&rules()).is_ok());
+
+    let mut future = rules();
+    future["schema_version"] = json!(2);
+    assert!(
+        validate_rules_bundle(&future)
+            .unwrap_err()
+            .contains("schema_version")
+    );
+
+    let mut bad_pair = rules();
+    bad_pair["asset_pair_limits"] = json!({"ETHUSDC": {"max_notional": 10, "min_notional": 1}});
+    assert!(
+        validate_rules_bundle(&bad_pair)
+            .unwrap_err()
+            .contains("asset_pair_limits")
+    );
+
+    let mut inverted = rules();
+    inverted["thresholds"]["review_risk_score"] = json!(90);
+    assert!(validate_rules_bundle(&inverted).is_err());
+
+    let mut bad_band = rules();
+    bad_band["pricing_bands"]["ETH/USDC"]["max_deviation_bps"] = json!(0);
+    assert!(validate_rules_bundle(&bad_band).is_err());
+
+    let mut missing_limits = rules();
+    missing_limits
+        .as_object_mut()
+        .expect("object")
+        .remove("limits");
+    assert!(validate_rules_bundle(&missing_limits).is_err());
+}
+
+#[test]
+fn every_applicable_rule_reports_its_outcome() {
+    let bundle = validate_rules_bundle(&rules()).expect("bundle");
+
+    let clean = evaluate_rules(
+        &bundle,
+        Some(&trade("500000", "3010.00")),
+        &[subject("cp-1", "GB", "tier1", 10)],
+    );
+    assert_eq!(clean.decision, RuleOutcome::Pass, "{:?}", clean.results);
+    assert_eq!(clean.risk_score, 10);
+    for rule in ["NOTIONAL_LIMIT", "ASSET_PAIR_LIMIT", "PRICING_BAND"] {
+        assert_eq!(
+            outcome(&clean.results, rule, None).outcome,
+            RuleOutcome::Pass
+        );
+    }
+    for rule in ["JURISDICTION", "COUNTERPARTY_TIER", "RISK_SCORE"] {
+        assert_eq!(
+            outcome(&clean.results, rule, Some("cp-1")).outcome,
+            RuleOutcome::Pass
+        );
+    }
+
+    let risky = evaluate_rules(
+        &bundle,
+        Some(&trade("100000", "3100.00")),
+        &[
+            subject("cp-1", "SG", "tier3", 35),
+            subject("cp-2", "KP", "tier1", 0),
+        ],
+    );
+    assert_eq!(risky.decision, RuleOutcome::Fail);
+    assert_eq!(risky.risk_score, 55, "SG adds its uplift");
+    assert_eq!(
+        outcome(&risky.results, "PRICING_BAND", None).outcome,
+        RuleOutcome::Fail
+    );
+    let tier = outcome(&risky.results, "COUNTERPARTY_TIER", Some("cp-1"));
+    assert_eq!(tier.outcome, RuleOutcome::Fail);
+    assert!(
+        tier.detail.contains("exceeds tier tier3"),
+        "{}",
+        tier.detail
+    );
+    assert_eq!(
+        outcome(&risky.results, "RISK_SCORE", Some("cp-1")).outcome,
+        RuleOutcome::Review
+    );
+    assert_eq!(
+        outcome(&risky.results, "JURISDICTION", Some("cp-2")).outcome,
+        RuleOutcome::Fail
+    );
+
+    let unlisted = evaluate_rules(
+        &bundle,
+        Some(&TradeCandidate {
+            asset_pair: "BTC/USDC".to_string(),
+            notional: amount("10000"),
+            price: None,
+            reference_price: None,
+        }),
+        &[SubjectCandidate {
+            subject_id: "cp-3".to_string(),
+            jurisdiction: Some("US".to_string()),
+            counterparty_tier: None,
+            risk_score: None,
+            sanctions_confidence: Some(75),
+        }],
+    );
+    assert_eq!(
+        outcome(&unlisted.results, "ASSET_PAIR_LIMIT", None).outcome,
+        RuleOutcome::Fail
+    );
+    assert!(!unlisted.results.iter().any(|r| r.rule == "PRICING_BAND"));
+    assert_eq!(
+        outcome(&unlisted.results, "COUNTERPARTY_TIER", Some("cp-3")).outcome,
+        RuleOutcome::Review
+    );
+    assert_eq!(
+        outcome(&unlisted.results, "SANCTIONS_CONFIDENCE", Some("cp-3")).outcome,
+        RuleOutcome::Review
+    );
+}
+
+#[test]
+fn pricing_band_and_limits_are_exact() {
+    let bundle = validate_rules_bundle(&rules()).expect("bundle");
+    let band = |price: &str| {
+        let evaluation = evaluate_rules(&bundle, Some(&trade("500000", price)), &[]);
+        outcome(&evaluation.results, "PRICING_BAND", None).outcome
+    };
+    // 50 bps of 3000 is exactly 15.
+    assert_eq!(band("3015"), RuleOutcome::Pass);
+    assert_eq!(band("2985.000"), RuleOutcome::Pass);
+    assert_eq!(band("3015.0001"), RuleOutcome::Fail);
+    assert_eq!(band("2984.9999"), RuleOutcome::Fail);
+    assert_eq!(band("0"), RuleOutcome::Review);
+
+    let limit = |notional: &str| {
+        let evaluation = evaluate_rules(&bundle, Some(&trade(notional, "3000")), &[]);
+        outcome(&evaluation.results, "ASSET_PAIR_LIMIT", None).outcome
+    };
+    assert_eq!(limit("1000000.00"), RuleOutcome::Pass);
+    assert_eq!(limit("1000000.01"), RuleOutcome::Fail);
+    assert_eq!(limit("999.99"), RuleOutcome::Fail);
+
+    assert!(
+        serde_json::from_value::<TradeCandidate>(
+            json!({"asset_pair": "ETH/USDC", "notional": 1000, "price": 3000.5})
+        )
+        .is_err(),
+        "amounts are decimal strings, never JSON numbers"
+    );
+
+    let mut governed = rules();
+    governed["pricing_rule"] = json!("reference_price");
+    assert_eq!(
+        validate_rules_bundle(&governed)
+            .expect("pricing rule")
+            .pricing_rule
+            .as_deref(),
+        Some("reference_price")
+    );
+    governed["pricing_rule"] = json!("best_price");
+    assert!(
+        validate_rules_bundle(&governed)
+            .unwrap_err()
+            .contains("pricing_rule")
+    );
+}
+
+/// The same cases run against the adapter's `evaluate_intake_policy` in
+/// compliance-attestation-adapter's `tests/policy_rules.rs`.
+#[test]
+fn jurisdiction_rules_match_the_shared_cases() {
+    let fixture: Value = serde_json::from_str(include_str!("data/jurisdiction_cases.json"))
+        .expect("jurisdiction cases");
+    let mut bundle = rules();
+    for key in ["countries", "blocked_countries", "high_risk_jurisdictions"] {
+        bundle[key] = fixture["rules"][key].clone();
+    }
+    let bundle = validate_rules_bundle(&bundle).expect("bundle");
+
+    for case in fixture["cases"].as_array().expect("cases") {
+        let evaluation = evaluate_rules(
+            &bundle,
+            None,
+            &[SubjectCandidate {
+                subject_id: "cp-1".to_string(),
+                jurisdiction: case["jurisdiction"].as_str().map(str::to_string),
+                counterparty_tier: None,
+                risk_score: Some(0),
+                sanctions_confidence: None,
+            }],
+        );
+        let expected: RuleOutcome =
+            serde_json::from_value(case["outcome"].clone()).expect("outcome");
+        assert_eq!(
+            outcome(&evaluation.results, "JURISDICTION", Some("cp-1")).outcome,
+            expected,
+            "{case}"
+        );
+        assert_eq!(
+            evaluation.risk_score,
+            case["uplift"].as_i64().expect("uplift"),
+            "{case}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn evaluate_endpoint_scores_against_given_or_active_policy() {
+    let app = build_router(AppState::new(common::config(), None).expect("app state"));
+
+    let candidate = json!({
+        "trade": {"asset_pair": "ETH/USDC", "notional": "2000000", "price": "3000.00", "reference_price": "3000"},
+        "subjects": [{"subject_id": "cp-1", "jurisdiction": "US", "counterparty_tier": "tier1", "risk_score": 5}]
+    });
+    let (status, resp) = evaluate(&app, candidate.clone()).await;
+    assert_eq!(status, http::StatusCode::NOT_FOUND);
+    assert_eq!(resp.error_code.as_deref(), Some("ACTIVE_POLICY_NOT_SET"));
+
+    let (status, _) = post(
+        &app,
+        "/v1/policy/snapshots",
+        json!({"policy_version": "policy-eval-v1", "policy_hash": null, "rules": rules(), "metadata": null}),
+    )
+    .await;
+    assert_eq!(status, http::StatusCode::OK);
+
+    let mut pinned = candidate.clone();
+    pinned["policy_version"] = json!("policy-eval-v1");
+    let (status, resp) = evaluate(&app, pinned).await;
+    assert_eq!(status, http::StatusCode::OK);
+    assert!(resp.evaluated);
+    assert_eq!(resp.policy_version, "policy-eval-v1");
+    assert_eq!(resp.schema_version, 1);
+    assert_eq!(resp.decision, Some(RuleOutcome::Fail));
+    let pair_limit = outcome(&resp.results, "ASSET_PAIR_LIMIT", None);
+    assert_eq!(pair_limit.outcome, RuleOutcome::Fail);
+    assert!(
+        pair_limit.detail.contains("outside"),
+        "{}",
+        pair_limit.detail
+    );
+    assert_eq!(
+        outcome(&resp.results, "NOTIONAL_LIMIT", None).outcome,
+        RuleOutcome::Pass
+    );
+
+    let (status, activation) = post(
+        &app,
+        "/v1/policy/activate",
+        json!({"onchain_policy_version": "onchain-1", "policy_version": "policy-eval-v1"}),
+    )
+    .await;
+    assert_eq!(status, http::StatusCode::OK);
+    let (status, resp) = evaluate(&app, candidate).await;
+    assert_eq!(status, http::StatusCode::OK);
+    assert_eq!(
+        resp.policy_hash,
+        activation["active_mapping"]["policy_hash"]
+    );
+
+    for (body, code) in [
+        (json!({"subjects": []}), "EMPTY_CANDIDATE"),
+        (
+            json!({"trade": {"asset_pair": "ETH/USDC", "notional": "0"}}),
+            "INVALID_NOTIONAL",
+        ),
+        (
+            json!({"policy_version": "missing", "subjects": [{"subject_id": "cp-1"}]}),
+            "POLICY_VERSION_NOT_FOUND",
+        ),
+    ] {
+        let (status, resp) = evaluate(&app, body).await;
+        assert!(status.is_client_error());
+        assert_eq!(resp.error_code.as_deref(), Some(code));
+        assert!(!resp.evaluated);
+    }
+}
+
+async fn evaluate(app: &axum::Router, body: Value) -> (http::StatusCode, EvaluatePolicyResponse) {
+    let (status, value) = post(app, "/v1/policy/evaluate", body).await;
+    (
+        status,
+        serde_json::from_value(value).expect("parse evaluation"),
+    )
+}
+
+async fn post(app: &axum::Router, path: &str, body: Value) -> (http::StatusCode, Value) {
+    let request = Request::builder()
+        .method("POST")
+        .uri(path)
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .expect("build request");
+    let response = app.clone().oneshot(request).await.expect("response");
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("read body");
+    (status, serde_json::from_slice(&body).expect("parse body"))
+}
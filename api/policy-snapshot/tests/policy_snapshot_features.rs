@@ -1,32 +1,46 @@
 use axum::body::{Body, to_bytes};
-use http::Request;
 use hmac::{Hmac, Mac};
+use http::Request;
 use policy_snapshot::app::{AppState, build_router};
 use policy_snapshot::config::environment::AppConfig;
 use policy_snapshot::module::policy_snapshot::schema::{
     ActivatePolicyRequest, ActivatePolicyResponse, ActivePolicyResponse, CreateSnapshotRequest,
     CreateSnapshotResponse, EffectivePolicyResponse, SnapshotLookupResponse,
 };
-use sha2::Sha256;
 use serde_json::json;
-use tower::util::ServiceExt;
+use sha2::Sha256;
 use tokio::time::{Duration, sleep};
+use tower::util::ServiceExt;
 
 type HmacSha256 = Hmac<Sha256>;
 
 #[tokio::test]
 async fn snapshot_registry_active_mapping_and_deterministic_retrieval() {
-    let app = build_router(AppState::new(AppConfig {
-        rust_env: "test".to_string(),
-        api_host: "127.0.0.1".to_string(),
-        api_port: 0,
-        audit_signing_secret: Some("test-audit-secret".to_string()),
-        mongodb_url: None,
-        mongodb_database: None,
-        redis_url: None,
-        require_internal_auth: false,
-        internal_auth_secret: None,
-    }, None));
+    let app = build_router(
+        AppState::new(
+            AppConfig {
+                rust_env: "test".to_string(),
+                api_host: "127.0.0.1".to_string(),
+                api_port: 0,
+                evidence_signing_key_hex: Some(
+                    "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318".to_string(),
+                ),
+                evidence_retired_public_keys: Vec::new(),
+                mongodb_url: None,
+                mongodb_database: None,
+                redis_url: None,
+                require_internal_auth: false,
+                internal_auth_secret: None,
+                activation_approvers: Default::default(),
+                activation_required_approvals: 2,
+                policy_manager_rpc_url: None,
+                policy_manager_address: None,
+                policy_manager_from_block: 0,
+            },
+            None,
+        )
+        .expect("app state"),
+    );
 
     let create_req = CreateSnapshotRequest {
         policy_version: "policy-v1".to_string(),
@@ -86,6 +100,7 @@ async fn snapshot_registry_active_mapping_and_deterministic_retrieval() {
     let activate_req = ActivatePolicyRequest {
         onchain_policy_version: "onchain-42".to_string(),
         policy_version: "policy-v1".to_string(),
+        effective_from: None,
     };
     let activate_resp = post_json::<ActivatePolicyRequest, ActivatePolicyResponse>(
         app.clone(),
@@ -121,6 +136,7 @@ async fn snapshot_registry_active_mapping_and_deterministic_retrieval() {
     let activate_v2 = ActivatePolicyRequest {
         onchain_policy_version: "onchain-43".to_string(),
         policy_version: "policy-v2".to_string(),
+        effective_from: None,
     };
     let activate_v2_resp = post_json::<ActivatePolicyRequest, ActivatePolicyResponse>(
         app.clone(),
@@ -131,7 +147,8 @@ async fn snapshot_registry_active_mapping_and_deterministic_retrieval() {
     assert_eq!(activate_v2_resp.0, http::StatusCode::OK);
     let second_activation_time = activate_v2_resp.1.active_mapping.activated_at;
 
-    let by_version = get_json::<SnapshotLookupResponse>(app.clone(), "/v1/policy/snapshots/policy-v1").await;
+    let by_version =
+        get_json::<SnapshotLookupResponse>(app.clone(), "/v1/policy/snapshots/policy-v1").await;
     assert_eq!(by_version.0, http::StatusCode::OK);
     let snapshot = by_version.1.snapshot.expect("snapshot");
     assert_eq!(snapshot.policy_hash, first_hash);
@@ -240,17 +257,29 @@ async fn snapshot_registry_active_mapping_and_deterministic_retrieval() {
 
 #[tokio::test]
 async fn create_snapshot_rejects_policy_hash_mismatch() {
-    let app = build_router(AppState::new(AppConfig {
-        rust_env: "test".to_string(),
-        api_host: "127.0.0.1".to_string(),
-        api_port: 0,
-        audit_signing_secret: None,
-        mongodb_url: None,
-        mongodb_database: None,
-        redis_url: None,
-        require_internal_auth: false,
-        internal_auth_secret: None,
-    }, None));
+    let app = build_router(
+        AppState::new(
+            AppConfig {
+                rust_env: "test".to_string(),
+                api_host: "127.0.0.1".to_string(),
+                api_port: 0,
+                evidence_signing_key_hex: None,
+                evidence_retired_public_keys: Vec::new(),
+                mongodb_url: None,
+                mongodb_database: None,
+                redis_url: None,
+                require_internal_auth: false,
+                internal_auth_secret: None,
+                activation_approvers: Default::default(),
+                activation_required_approvals: 2,
+                policy_manager_rpc_url: None,
+                policy_manager_address: None,
+                policy_manager_from_block: 0,
+            },
+            None,
+        )
+        .expect("app state"),
+    );
 
     let req = CreateSnapshotRequest {
         policy_version: "policy-hash-mismatch".to_string(),
@@ -263,8 +292,12 @@ async fn create_snapshot_rejects_policy_hash_mismatch() {
         metadata: None,
     };
 
-    let resp =
-        post_json::<CreateSnapshotRequest, CreateSnapshotResponse>(app, "/v1/policy/snapshots", &req).await;
+    let resp = post_json::<CreateSnapshotRequest, CreateSnapshotResponse>(
+        app,
+        "/v1/policy/snapshots",
+        &req,
+    )
+    .await;
     assert_eq!(resp.0, http::StatusCode::BAD_REQUEST);
     assert_eq!(resp.1.error_code.as_deref(), Some("POLICY_HASH_MISMATCH"));
 }
@@ -272,17 +305,29 @@ async fn create_snapshot_rejects_policy_hash_mismatch() {
 #[tokio::test]
 async fn create_snapshot_requires_internal_signature_when_enabled() {
     let secret = "internal-secret";
-    let app = build_router(AppState::new(AppConfig {
-        rust_env: "test".to_string(),
-        api_host: "127.0.0.1".to_string(),
-        api_port: 0,
-        audit_signing_secret: None,
-        mongodb_url: None,
-        mongodb_database: None,
-        redis_url: None,
-        require_internal_auth: true,
-        internal_auth_secret: Some(secret.to_string()),
-    }, None));
+    let app = build_router(
+        AppState::new(
+            AppConfig {
+                rust_env: "test".to_string(),
+                api_host: "127.0.0.1".to_string(),
+                api_port: 0,
+                evidence_signing_key_hex: None,
+                evidence_retired_public_keys: Vec::new(),
+                mongodb_url: None,
+                mongodb_database: None,
+                redis_url: None,
+                require_internal_auth: true,
+                internal_auth_secret: Some(secret.to_string()),
+                activation_approvers: Default::default(),
+                activation_required_approvals: 2,
+                policy_manager_rpc_url: None,
+                policy_manager_address: None,
+                policy_manager_from_block: 0,
+            },
+            None,
+        )
+        .expect("app state"),
+    );
 
     let req = CreateSnapshotRequest {
         policy_version: "policy-auth".to_string(),
@@ -295,11 +340,17 @@ async fn create_snapshot_requires_internal_signature_when_enabled() {
         metadata: None,
     };
 
-    let missing_sig =
-        post_json::<CreateSnapshotRequest, CreateSnapshotResponse>(app.clone(), "/v1/policy/snapshots", &req)
-            .await;
+    let missing_sig = post_json::<CreateSnapshotRequest, CreateSnapshotResponse>(
+        app.clone(),
+        "/v1/policy/snapshots",
+        &req,
+    )
+    .await;
     assert_eq!(missing_sig.0, http::StatusCode::BAD_REQUEST);
-    assert_eq!(missing_sig.1.error_code.as_deref(), Some("MISSING_SIGNATURE"));
+    assert_eq!(
+        missing_sig.1.error_code.as_deref(),
+        Some("MISSING_SIGNATURE")
+    );
 
     let payload = serde_json::to_string(&req).expect("serialize req");
     let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("hmac init");
@@ -14,7 +14,12 @@ pub struct AppConfig {
     pub worker_lease_seconds: i64,
     pub worker_max_retries: i64,
     pub worker_backoff_base_seconds: i64,
+    pub worker_concurrency: i64,
     pub zk_root_dir: String,
+    pub prover_backend: String,
+    pub prover_work_dir: String,
+    pub proof_store_dir: String,
+    pub prover_workdir_retention_seconds: i64,
     pub prove_timeout_seconds: i64,
     pub prove_budget_settlement_seconds: i64,
     pub prove_budget_compliance_seconds: i64,
@@ -56,7 +61,15 @@ impl AppConfig {
             worker_lease_seconds: read_optional_i64("WORKER_LEASE_SECONDS", 30)?,
             worker_max_retries: read_optional_i64("WORKER_MAX_RETRIES", 3)?,
             worker_backoff_base_seconds: read_optional_i64("WORKER_BACKOFF_BASE_SECONDS", 2)?,
+            worker_concurrency: read_optional_i64("WORKER_CONCURRENCY", 1)?,
             zk_root_dir: read_optional_string("ZK_ROOT_DIR", "../../zk"),
+            prover_backend: read_prover_backend()?,
+            prover_work_dir: read_optional_string("PROVER_WORK_DIR", "./data/prover-jobs"),
+            proof_store_dir: read_optional_string("PROOF_STORE_DIR", "./data/proofs"),
+            prover_workdir_retention_seconds: read_optional_i64(
+                "PROVER_WORKDIR_RETENTION_SECONDS",
+                86400,
+            )?,
             prove_timeout_seconds: read_optional_i64("PROVE_TIMEOUT_SECONDS", 60)?,
             prove_budget_settlement_seconds: read_optional_i64(
                 "PROVE_BUDGET_SETTLEMENT_SECONDS",
@@ -126,6 +139,17 @@ fn read_optional_string(key: &str, default: &str) -> String {
     env::var(key).unwrap_or_else(|_| default.to_string())
 }
 
+/// Accepts whatever `proof_generate` knows how to build; blank means native.
+fn read_prover_backend() -> Result<String, String> {
+    let backend = read_optional_string("PROVER_BACKEND", "native");
+    proof_generate::prover::prover_for_backend(&backend)
+        .map_err(|e| format!("invalid PROVER_BACKEND: {e}"))?;
+    match backend.trim() {
+        "" => Ok("native".to_string()),
+        other => Ok(other.to_string()),
+    }
+}
+
 fn load_dotenv_layers() {
     for path in [".env", "../.env", "../../.env"] {
         let _ = dotenvy::from_path_override(path);
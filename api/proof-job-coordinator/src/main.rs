@@ -45,12 +45,14 @@ async fn main() {
     };
     let state = AppState::new(config, infra);
     if state.config.worker_enabled && state.infra.is_some() {
-        let worker_state = state.clone();
-        tokio::spawn(async move {
-            if let Err(e) = queue_service::run_worker(worker_state).await {
-                error!(error = %e, "queue worker exited");
-            }
-        });
+        for worker in 0..state.config.worker_concurrency.max(1) {
+            let worker_state = state.clone();
+            tokio::spawn(async move {
+                if let Err(e) = queue_service::run_worker(worker_state).await {
+                    error!(worker, error = %e, "queue worker exited");
+                }
+            });
+        }
     }
     let app = build_router(state);
     if let Err(e) = axum::serve(listener, app).await {
@@ -222,7 +222,7 @@ pub async fn get_proof_jobs_by_run(
     for job in by_id.into_values() {
         jobs.push(enrich_view(state, to_view(&job)).await?);
     }
-    jobs.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    jobs.sort_by_key(|job| std::cmp::Reverse(job.created_at));
     let found = !jobs.is_empty();
     Ok(GetProofJobsByRunResponse {
         found,
@@ -39,10 +39,10 @@ pub fn to_field_decimal(input: &str) -> u128 {
     if trimmed.is_empty() {
         return 0;
     }
-    if trimmed.bytes().all(|b| b.is_ascii_digit()) {
-        if let Ok(v) = trimmed.parse::<u128>() {
-            return v;
-        }
+    if trimmed.bytes().all(|b| b.is_ascii_digit())
+        && let Ok(v) = trimmed.parse::<u128>()
+    {
+        return v;
     }
 
     // Deterministic fallback for non-numeric identifiers.
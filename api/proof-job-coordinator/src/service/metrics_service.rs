@@ -66,17 +66,15 @@ pub fn snapshot() -> MetricsSnapshot {
         jobs_failed: JOBS_FAILED.load(Ordering::Relaxed),
         retries_scheduled: RETRY_SCHEDULED.load(Ordering::Relaxed),
         prove_duration_count: prove_count,
-        prove_duration_avg_ms: if prove_count > 0 {
-            PROVE_DURATION_TOTAL_MS.load(Ordering::Relaxed) / prove_count
-        } else {
-            0
-        },
+        prove_duration_avg_ms: PROVE_DURATION_TOTAL_MS
+            .load(Ordering::Relaxed)
+            .checked_div(prove_count)
+            .unwrap_or(0),
         queue_latency_count: queue_count,
-        queue_latency_avg_ms: if queue_count > 0 {
-            QUEUE_LATENCY_TOTAL_MS.load(Ordering::Relaxed) / queue_count
-        } else {
-            0
-        },
+        queue_latency_avg_ms: QUEUE_LATENCY_TOTAL_MS
+            .load(Ordering::Relaxed)
+            .checked_div(queue_count)
+            .unwrap_or(0),
         last_error_ts: LAST_ERROR_TS.load(Ordering::Relaxed),
     }
 }
@@ -2,6 +2,8 @@ pub mod binding_codec_service;
 pub mod hash_service;
 pub mod internal_auth_service;
 pub mod metrics_service;
+pub mod proof_artifact_service;
+pub mod proof_verification_service;
 pub mod prover_service;
 pub mod publish_service;
 pub mod queue_service;
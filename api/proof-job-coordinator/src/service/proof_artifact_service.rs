@@ -0,0 +1,112 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Fresh `<work_root>/<job_id>.<attempt_id>` for one proving attempt. The
+/// attempt id is the worker's lease token, so a worker that re-leases a job
+/// while an earlier attempt is still proving gets its own dir instead of
+/// wiping the one in use. Fails if the dir already exists.
+pub async fn prepare_job_dir(
+    work_root: &Path,
+    job_id: &str,
+    attempt_id: &str,
+) -> Result<PathBuf, String> {
+    if !is_path_segment(job_id) {
+        return Err(format!("invalid job_id for work dir: {job_id}"));
+    }
+    if !is_path_segment(attempt_id) {
+        return Err(format!("invalid attempt id for work dir: {attempt_id}"));
+    }
+    tokio::fs::create_dir_all(work_root)
+        .await
+        .map_err(|e| format!("failed to create prover work root: {e}"))?;
+    let dir = work_root.join(format!("{job_id}.{attempt_id}"));
+    tokio::fs::create_dir(&dir)
+        .await
+        .map_err(|e| format!("failed to create job work dir: {e}"))?;
+    Ok(dir)
+}
+
+fn is_path_segment(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+pub async fn remove_job_dir(dir: &Path) -> Result<(), String> {
+    tokio::fs::remove_dir_all(dir)
+        .await
+        .map_err(|e| format!("failed to remove job work dir: {e}"))
+}
+
+/// Removes job work dirs untouched for longer than `retention`. Successful
+/// jobs remove their own dir, so this only collects failed attempts kept
+/// around for inspection. Returns how many were removed.
+pub async fn sweep_job_dirs(work_root: &Path, retention: Duration) -> Result<usize, String> {
+    let mut entries = match tokio::fs::read_dir(work_root).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(format!("failed to list job work dirs: {e}")),
+    };
+    let now = SystemTime::now();
+    let mut removed = 0;
+    while let Some(entry) = entries
+        .next_entry()
+        .await
+        .map_err(|e| format!("failed to list job work dirs: {e}"))?
+    {
+        let Ok(metadata) = entry.metadata().await else {
+            continue;
+        };
+        let age = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| now.duration_since(modified).ok())
+            .unwrap_or_default();
+        if metadata.is_dir() && age > retention {
+            tokio::fs::remove_dir_all(entry.path())
+                .await
+                .map_err(|e| format!("failed to remove expired job work dir: {e}"))?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+/// Final proof and public signals under `<store_root>/<proof_hash>/`. The
+/// hash covers `proof.json`, so an existing entry already holds the same
+/// proof and is left as is.
+pub async fn store_proof(
+    store_root: &Path,
+    proof_hash: &str,
+    proof_raw: &str,
+    public_raw: &str,
+) -> Result<(PathBuf, PathBuf), String> {
+    if proof_hash.is_empty() || !proof_hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("invalid proof hash for proof store: {proof_hash}"));
+    }
+    let dir = store_root.join(proof_hash);
+    tokio::fs::create_dir_all(&dir)
+        .await
+        .map_err(|e| format!("failed to create proof store dir: {e}"))?;
+    let proof_path = dir.join("proof.json");
+    let public_path = dir.join("public.json");
+    write_once(&proof_path, proof_raw).await?;
+    write_once(&public_path, public_raw).await?;
+    Ok((proof_path, public_path))
+}
+
+/// Writes through a uniquely named temp file and a rename, so a concurrent
+/// writer of the same entry never exposes a partial file.
+async fn write_once(path: &Path, contents: &str) -> Result<(), String> {
+    if tokio::fs::try_exists(path).await.unwrap_or(false) {
+        return Ok(());
+    }
+    let tmp = path.with_extension(format!("tmp-{}", uuid::Uuid::new_v4()));
+    tokio::fs::write(&tmp, contents)
+        .await
+        .map_err(|e| format!("failed to write proof store entry: {e}"))?;
+    tokio::fs::rename(&tmp, path)
+        .await
+        .map_err(|e| format!("failed to commit proof store entry: {e}"))
+}
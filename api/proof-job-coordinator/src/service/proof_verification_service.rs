@@ -0,0 +1,38 @@
+use crate::app::AppState;
+use crate::module::proof_job::model::ProverArtifactsRecord;
+use crate::service::prover_service::resolve_dir;
+use crate::service::signal_binding_service::non_retryable;
+use publish_receipt::errors::PublishError;
+use publish_receipt::verifier::{Groth16Proof, VerifyingKey};
+
+/// Verifies the proof calldata about to be published against
+/// `zk/artifacts/<circuit>/<circuit>.vkey.json`. A proof that fails here fails
+/// the same way on every attempt, so those errors are non-retryable. A missing
+/// or unreadable key is a deploy problem rather than a bad proof, so it stays
+/// retryable and the job survives until the key is in place.
+pub fn verify_publish_proof(
+    state: &AppState,
+    artifacts: &ProverArtifactsRecord,
+    proof_hex: &str,
+    public_signals: &[String],
+) -> Result<(), String> {
+    let zk_root = resolve_dir(&state.config.zk_root_dir)?;
+    let circuit = artifacts.circuit.as_str();
+    let vkey_path = zk_root
+        .join("artifacts")
+        .join(circuit)
+        .join(format!("{circuit}.vkey.json"));
+
+    let key = VerifyingKey::load(&vkey_path)
+        .map_err(|err| format!("verifying key unavailable: {err}"))?;
+    Groth16Proof::from_hex(proof_hex)
+        .and_then(|proof| key.verify(&proof, public_signals))
+        .map_err(|err| non_retryable(error_code(&err), err.to_string()))
+}
+
+fn error_code(err: &PublishError) -> &'static str {
+    match err {
+        PublishError::MalformedProof(_) => "PROOF_MALFORMED",
+        _ => "PROOF_INVALID",
+    }
+}
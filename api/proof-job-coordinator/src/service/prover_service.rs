@@ -3,52 +3,61 @@ use crate::module::proof_job::model::{ProofJobRecord, ProverArtifactsRecord};
 use crate::module::proof_job::schema::ProofType;
 use crate::service::binding_codec_service::settlement_binding_fields;
 use crate::service::hash_service::sha256_hex;
+use crate::service::proof_artifact_service;
+use proof_generate::native::NativeProver;
+use proof_generate::prover::{CircuitArtifacts, Prover};
 use serde_json::Value;
 use std::path::{Path, PathBuf};
 use std::time::Instant;
-use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::process::Command;
 use tokio::time::{Duration, timeout};
+use tracing::warn;
 
+/// Proves in `<PROVER_WORK_DIR>/<job_id>.<attempt_id>/`, so neither other
+/// jobs nor other attempts of the same job share its witness or proof
+/// files, then moves the result into the content-addressed proof
+/// store and drops the work dir. Failed attempts keep theirs until
+/// `PROVER_WORKDIR_RETENTION_SECONDS` has passed.
 pub async fn run_prover_for_job(
     state: &AppState,
     job: &ProofJobRecord,
+    attempt_id: &str,
 ) -> Result<ProverArtifactsRecord, String> {
     let circuit = map_circuit(&job.proof_type);
-    let zk_root = resolve_zk_root(&state.config.zk_root_dir)?;
+    let zk_root = resolve_dir(&state.config.zk_root_dir)?;
+    let work_root = resolve_dir(&state.config.prover_work_dir)?;
+    let store_root = resolve_dir(&state.config.proof_store_dir)?;
+
+    // Never shorter than a proving attempt, so a live dir is not swept.
+    let retention = Duration::from_secs(
+        state
+            .config
+            .prover_workdir_retention_seconds
+            .max(state.config.prove_timeout_seconds)
+            .max(0) as u64,
+    );
+    if let Err(e) = proof_artifact_service::sweep_job_dirs(&work_root, retention).await {
+        warn!(error = %e, "job work dir sweep failed");
+    }
+    let job_dir =
+        proof_artifact_service::prepare_job_dir(&work_root, &job.job_id, attempt_id).await?;
+
     let base_fixture_path = resolve_fixture_path(&zk_root, &job.receipt_context, circuit);
     let fixture_path = if matches!(job.proof_type, ProofType::Settlement) {
-        prepare_runtime_settlement_fixture(&base_fixture_path, state, job).await?
+        prepare_runtime_settlement_fixture(&base_fixture_path, &job_dir, state, job).await?
     } else {
-        base_fixture_path
+        base_fixture_path.clone()
     };
 
-    let prove_script = zk_root.join("scripts").join("prove.sh");
     let timeout_secs = state.config.prove_timeout_seconds.max(1) as u64;
     let budget_secs = budget_for_type(state, &job.proof_type).max(1);
 
     let started = Instant::now();
-    let child = Command::new("bash")
-        .arg(prove_script.as_os_str())
-        .arg(circuit)
-        .arg(fixture_path.as_os_str())
-        .current_dir(&zk_root)
-        .output();
-
-    let output = timeout(Duration::from_secs(timeout_secs), child)
-        .await
-        .map_err(|_| format!("prove timeout after {timeout_secs}s"))?
-        .map_err(|e| format!("prove command failed to start: {e}"))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        return Err(format!(
-            "prove command failed (status={}): stdout={} stderr={}",
-            output.status,
-            trim_log(&stdout),
-            trim_log(&stderr)
-        ));
+    match state.config.prover_backend.as_str() {
+        "snarkjs" => {
+            run_prove_script(&zk_root, circuit, &fixture_path, &job_dir, timeout_secs).await?
+        }
+        _ => run_native_prover(&zk_root, circuit, &fixture_path, &job_dir, timeout_secs).await?,
     }
 
     let prove_time_seconds = started.elapsed().as_secs() as i64;
@@ -59,19 +68,10 @@ pub async fn run_prover_for_job(
         ));
     }
 
-    let proof_path = zk_root
-        .join("artifacts")
-        .join(circuit)
-        .join(format!("{circuit}.proof.json"));
-    let public_path = zk_root
-        .join("artifacts")
-        .join(circuit)
-        .join(format!("{circuit}.public.json"));
-
-    let proof_raw = tokio::fs::read_to_string(&proof_path)
+    let proof_raw = tokio::fs::read_to_string(job_dir.join(format!("{circuit}.proof.json")))
         .await
         .map_err(|e| format!("failed to read proof file: {e}"))?;
-    let public_raw = tokio::fs::read_to_string(&public_path)
+    let public_raw = tokio::fs::read_to_string(job_dir.join(format!("{circuit}.public.json")))
         .await
         .map_err(|e| format!("failed to read public file: {e}"))?;
     let proof_json: Value =
@@ -81,10 +81,16 @@ pub async fn run_prover_for_job(
 
     let proof_hash = sha256_hex(&proof_raw);
     let receipt_hash = resolve_receipt_hash(&job.receipt_context, &public_raw)?;
+    let (proof_path, public_path) =
+        proof_artifact_service::store_proof(&store_root, &proof_hash, &proof_raw, &public_raw)
+            .await?;
+    if let Err(e) = proof_artifact_service::remove_job_dir(&job_dir).await {
+        warn!(job_id = %job.job_id, error = %e, "job work dir cleanup failed");
+    }
 
     Ok(ProverArtifactsRecord {
         circuit: circuit.to_string(),
-        fixture_path: path_to_string(&fixture_path),
+        fixture_path: path_to_string(&base_fixture_path),
         proof_path: path_to_string(&proof_path),
         public_path: path_to_string(&public_path),
         proof_json,
@@ -95,6 +101,77 @@ pub async fn run_prover_for_job(
     })
 }
 
+/// Proves in-process and writes `<circuit>.proof.json`/`.public.json` into
+/// `out_dir`, the same files `prove.sh` leaves there.
+async fn run_native_prover(
+    zk_root: &Path,
+    circuit: &str,
+    fixture_path: &Path,
+    out_dir: &Path,
+    timeout_secs: u64,
+) -> Result<(), String> {
+    let raw = tokio::fs::read_to_string(fixture_path)
+        .await
+        .map_err(|e| format!("failed to read prover input: {e}"))?;
+    let input: Value =
+        serde_json::from_str(&raw).map_err(|e| format!("prover input parse failed: {e}"))?;
+    let artifacts = CircuitArtifacts::for_circuit(zk_root, circuit);
+    artifacts.ensure_exists().map_err(|e| e.to_string())?;
+
+    let proving = tokio::task::spawn_blocking(move || NativeProver.prove(&artifacts, &input));
+    let generated = timeout(Duration::from_secs(timeout_secs), proving)
+        .await
+        .map_err(|_| format!("prove timeout after {timeout_secs}s"))?
+        .map_err(|e| format!("native prover task failed: {e}"))?
+        .map_err(|e| format!("native prover failed: {e}"))?;
+
+    let proof_raw = serde_json::to_string_pretty(&generated.proof)
+        .map_err(|e| format!("failed to encode proof: {e}"))?;
+    let public_raw = serde_json::to_string_pretty(&generated.public_signals)
+        .map_err(|e| format!("failed to encode public signals: {e}"))?;
+    tokio::fs::write(out_dir.join(format!("{circuit}.proof.json")), proof_raw)
+        .await
+        .map_err(|e| format!("failed to write proof file: {e}"))?;
+    tokio::fs::write(out_dir.join(format!("{circuit}.public.json")), public_raw)
+        .await
+        .map_err(|e| format!("failed to write public file: {e}"))
+}
+
+/// The snarkjs fallback: `zk/scripts/prove.sh`, which needs Node.
+async fn run_prove_script(
+    zk_root: &Path,
+    circuit: &str,
+    fixture_path: &Path,
+    out_dir: &Path,
+    timeout_secs: u64,
+) -> Result<(), String> {
+    let prove_script = zk_root.join("scripts").join("prove.sh");
+    let child = Command::new("bash")
+        .arg(prove_script.as_os_str())
+        .arg(circuit)
+        .arg(fixture_path.as_os_str())
+        .arg(out_dir.as_os_str())
+        .current_dir(zk_root)
+        .output();
+
+    let output = timeout(Duration::from_secs(timeout_secs), child)
+        .await
+        .map_err(|_| format!("prove timeout after {timeout_secs}s"))?
+        .map_err(|e| format!("prove command failed to start: {e}"))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        return Err(format!(
+            "prove command failed (status={}): stdout={} stderr={}",
+            output.status,
+            trim_log(&stdout),
+            trim_log(&stderr)
+        ));
+    }
+    Ok(())
+}
+
 fn map_circuit(proof_type: &ProofType) -> &'static str {
     match proof_type {
         ProofType::Settlement => "settlement_valid",
@@ -111,7 +188,8 @@ fn budget_for_type(state: &AppState, proof_type: &ProofType) -> i64 {
     }
 }
 
-fn resolve_zk_root(config_path: &str) -> Result<PathBuf, String> {
+/// Relative config paths resolve against the working directory.
+pub fn resolve_dir(config_path: &str) -> Result<PathBuf, String> {
     let path = PathBuf::from(config_path);
     if path.is_absolute() {
         return Ok(path);
@@ -159,6 +237,7 @@ fn resolve_receipt_hash(receipt_context: &Value, public_raw: &str) -> Result<Str
 
 async fn prepare_runtime_settlement_fixture(
     base_fixture_path: &Path,
+    job_dir: &Path,
     state: &AppState,
     job: &ProofJobRecord,
 ) -> Result<PathBuf, String> {
@@ -219,12 +298,7 @@ async fn prepare_runtime_settlement_fixture(
         Value::String(fields.binding_hash),
     );
 
-    let mut out_path = std::env::temp_dir();
-    let nanos = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_nanos();
-    out_path.push(format!("settlement_runtime_fixture_{nanos}.json"));
+    let out_path = job_dir.join("settlement_runtime_fixture.json");
     let encoded = serde_json::to_string(&fixture)
         .map_err(|e| format!("failed to encode settlement runtime fixture: {e}"))?;
     tokio::fs::write(&out_path, encoded)
@@ -1,5 +1,6 @@
 use crate::app::AppState;
 use crate::module::proof_job::model::{OnchainPublishRecord, ProofJobRecord};
+use crate::service::proof_verification_service::verify_publish_proof;
 use ethers::abi::{Token, encode};
 use ethers::providers::{Http, Middleware, Provider};
 use ethers::types::H256;
@@ -59,6 +60,7 @@ pub async fn publish_receipt_on_sepolia(
         None => encode_proof_hex_from_json(&artifacts.proof_json)
             .map_err(|e| format!("proof hex encode failed: {e}"))?,
     };
+    verify_publish_proof(state, artifacts, &proof_hex, &public_signals)?;
 
     let req = PublishReceiptRequest {
         settlement_registry: settlement_registry.clone(),
@@ -73,7 +75,6 @@ pub async fn publish_receipt_on_sepolia(
         chain_validation: ChainValidationState {
             authorized_publisher: true,
             policy_active: true,
-            proof_valid: true,
             signal_binding_valid: true,
             duplicate_workflow_run: false,
             duplicate_receipt_hash: false,
@@ -141,7 +142,7 @@ fn value_string(v: &Value, keys: &[&str]) -> Option<String> {
         .map(ToOwned::to_owned)
 }
 
-fn encode_proof_hex_from_json(proof_json: &Value) -> Result<String, String> {
+pub fn encode_proof_hex_from_json(proof_json: &Value) -> Result<String, String> {
     let p_a = proof_json
         .get("pi_a")
         .and_then(Value::as_array)
@@ -17,6 +17,10 @@ const RETRY_ZSET_KEY: &str = "proofjobs:retry";
 const DLQ_KEY: &str = "proofjobs:dead";
 const ATTEMPTS_HASH_KEY: &str = "proofjobs:attempts";
 const LOCK_PREFIX: &str = "proofjobs:lock:";
+const RENEW_LEASE_SCRIPT: &str = "if redis.call('GET', KEYS[1]) == ARGV[1] then \
+     return redis.call('EXPIRE', KEYS[1], ARGV[2]) else return 0 end";
+const RELEASE_LEASE_SCRIPT: &str = "if redis.call('GET', KEYS[1]) == ARGV[1] then \
+     return redis.call('DEL', KEYS[1]) else return 0 end";
 
 pub async fn enqueue_proof_job(state: &AppState, job_id: &str) -> Result<(), String> {
     let Some(infra) = &state.infra else {
@@ -107,6 +111,10 @@ async fn pull_next_job(state: &AppState) -> Result<Option<String>, String> {
     Ok(res)
 }
 
+/// Holds `proofjobs:lock:<job_id>` for as long as the job runs, renewing it
+/// every third of `WORKER_LEASE_SECONDS`, so a prove that outlasts one lease
+/// term is not picked up by a second worker. The lease token doubles as the
+/// attempt id for the prover work dir.
 async fn process_job_with_lease(state: &AppState, job_id: &str) -> Result<(), String> {
     let Some(infra) = &state.infra else {
         return Ok(());
@@ -117,14 +125,15 @@ async fn process_job_with_lease(state: &AppState, job_id: &str) -> Result<(), St
         .await
         .map_err(|e| format!("redis connect failed: {e}"))?;
     let lock_key = format!("{LOCK_PREFIX}{job_id}");
-    let lock_token = format!("{}-{}", job_id, now_unix());
+    let lock_token = uuid::Uuid::new_v4().simple().to_string();
+    let lease_seconds = state.config.worker_lease_seconds.max(5);
 
     let acquired: Option<String> = redis::cmd("SET")
         .arg(&lock_key)
         .arg(&lock_token)
         .arg("NX")
         .arg("EX")
-        .arg(state.config.worker_lease_seconds.max(5))
+        .arg(lease_seconds)
         .query_async(&mut conn)
         .await
         .map_err(|e| format!("lease acquisition failed: {e}"))?;
@@ -134,13 +143,31 @@ async fn process_job_with_lease(state: &AppState, job_id: &str) -> Result<(), St
         return Ok(());
     }
 
-    let result = process_job(state, job_id).await;
+    let work = process_job(state, job_id, &lock_token);
+    tokio::pin!(work);
+    let mut renewal = tokio::time::interval(Duration::from_secs((lease_seconds / 3) as u64));
+    renewal.tick().await;
+    let result = loop {
+        tokio::select! {
+            result = &mut work => break result,
+            _ = renewal.tick() => {
+                match renew_lease(&mut conn, &lock_key, &lock_token, lease_seconds).await {
+                    Ok(true) => {}
+                    Ok(false) => break Err(format!("lease lost for job {job_id}")),
+                    Err(e) => warn!(job_id = %job_id, error = %e, "lease renewal failed"),
+                }
+            }
+        }
+    };
     if let Err(err) = &result {
         warn!(job_id = %job_id, error = %err, "processing logic returned error");
     }
 
-    let _: usize = redis::cmd("DEL")
+    let _: i64 = redis::cmd("EVAL")
+        .arg(RELEASE_LEASE_SCRIPT)
+        .arg(1)
         .arg(&lock_key)
+        .arg(&lock_token)
         .query_async(&mut conn)
         .await
         .map_err(|e| format!("lock release failed: {e}"))?;
@@ -156,7 +183,26 @@ async fn process_job_with_lease(state: &AppState, job_id: &str) -> Result<(), St
     result
 }
 
-async fn process_job(state: &AppState, job_id: &str) -> Result<(), String> {
+/// Extends the lease only while `token` still holds it.
+async fn renew_lease(
+    conn: &mut MultiplexedConnection,
+    lock_key: &str,
+    token: &str,
+    lease_seconds: i64,
+) -> Result<bool, String> {
+    let renewed: i64 = redis::cmd("EVAL")
+        .arg(RENEW_LEASE_SCRIPT)
+        .arg(1)
+        .arg(lock_key)
+        .arg(token)
+        .arg(lease_seconds)
+        .query_async(conn)
+        .await
+        .map_err(|e| format!("lease renewal failed: {e}"))?;
+    Ok(renewed == 1)
+}
+
+async fn process_job(state: &AppState, job_id: &str, attempt_id: &str) -> Result<(), String> {
     let proving = UpdateProofJobStatusRequest {
         next_status: JobStatus::Proving,
         error_code: None,
@@ -175,7 +221,7 @@ async fn process_job(state: &AppState, job_id: &str) -> Result<(), String> {
             metrics_service::record_queue_latency_ms(((now - job.created_at) * 1000) as u64);
         }
     }
-    let artifacts = prover_service::run_prover_for_job(state, &job)
+    let artifacts = prover_service::run_prover_for_job(state, &job, attempt_id)
         .await
         .map_err(|e| format!("prover failed: {e}"))?;
     metrics_service::record_prove_duration_ms((artifacts.prove_time_seconds.max(0) as u64) * 1000);
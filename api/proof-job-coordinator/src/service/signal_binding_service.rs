@@ -136,6 +136,6 @@ fn signal_to_string(v: &Value) -> Option<String> {
     }
 }
 
-fn non_retryable(code: &str, message: impl Into<String>) -> String {
+pub fn non_retryable(code: &str, message: impl Into<String>) -> String {
     format!("NON_RETRYABLE:{code}:{}", message.into())
 }
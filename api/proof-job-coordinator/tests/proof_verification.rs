@@ -0,0 +1,117 @@
+use proof_job_coordinator::app::AppState;
+use proof_job_coordinator::config::environment::AppConfig;
+use proof_job_coordinator::module::proof_job::model::ProverArtifactsRecord;
+use proof_job_coordinator::service::proof_verification_service::verify_publish_proof;
+use proof_job_coordinator::service::publish_service::encode_proof_hex_from_json;
+use serde_json::Value;
+
+fn test_state() -> AppState {
+    AppState::new(
+        AppConfig {
+            rust_env: "test".to_string(),
+            api_host: "127.0.0.1".to_string(),
+            api_port: 0,
+            mongodb_url: None,
+            mongodb_database: None,
+            redis_url: None,
+            idempotency_ttl_seconds: 3600,
+            worker_enabled: false,
+            worker_poll_seconds: 1,
+            worker_lease_seconds: 10,
+            worker_max_retries: 1,
+            worker_backoff_base_seconds: 1,
+            worker_concurrency: 1,
+            zk_root_dir: "../../zk".to_string(),
+            prover_backend: "native".to_string(),
+            prover_work_dir: "target/test-prover-jobs".to_string(),
+            proof_store_dir: "target/test-proofs".to_string(),
+            prover_workdir_retention_seconds: 86400,
+            prove_timeout_seconds: 30,
+            prove_budget_settlement_seconds: 15,
+            prove_budget_compliance_seconds: 10,
+            prove_budget_rebate_seconds: 10,
+            signal_domain_separator: "zkclear:v1".to_string(),
+            eth_sepolia_rpc_url: None,
+            private_key: None,
+            eth_sepolia_chain_id: 11155111,
+            publish_settlement_registry: None,
+            publish_publisher_address: None,
+            internal_auth_enabled: false,
+            internal_auth_secret: None,
+            wallet_auth_enabled: false,
+            wallet_auth_nonce_ttl_seconds: 300,
+            wallet_jwt_secret: None,
+            wallet_jwt_ttl_seconds: 3600,
+            wallet_role_map: String::new(),
+            wallet_default_role: "dealer".to_string(),
+            intent_gateway_base_url: "http://127.0.0.1:8080".to_string(),
+            compliance_adapter_base_url: "http://127.0.0.1:8082".to_string(),
+            policy_snapshot_base_url: "http://127.0.0.1:8083".to_string(),
+        },
+        None,
+    )
+}
+
+fn exported_artifacts(circuit: &str) -> ProverArtifactsRecord {
+    let read = |kind: &str| -> Value {
+        let path = format!("../../zk/artifacts/{circuit}/{circuit}.{kind}.json");
+        serde_json::from_str(&std::fs::read_to_string(path).expect("artifact")).expect("json")
+    };
+    ProverArtifactsRecord {
+        circuit: circuit.to_string(),
+        fixture_path: format!("fixtures/{circuit}.fixture.json"),
+        proof_path: format!("artifacts/{circuit}/{circuit}.proof.json"),
+        public_path: format!("artifacts/{circuit}/{circuit}.public.json"),
+        proof_json: read("proof"),
+        public_json: read("public"),
+        proof_hash: "proofhash".to_string(),
+        receipt_hash: "receipt-999".to_string(),
+        prove_time_seconds: 1,
+    }
+}
+
+fn signals(artifacts: &ProverArtifactsRecord) -> Vec<String> {
+    serde_json::from_value(artifacts.public_json.clone()).expect("public signals")
+}
+
+#[test]
+fn exported_settlement_proof_verifies_before_publish() {
+    let state = test_state();
+    let artifacts = exported_artifacts("settlement_valid");
+    let proof_hex = encode_proof_hex_from_json(&artifacts.proof_json).expect("calldata");
+
+    verify_publish_proof(&state, &artifacts, &proof_hex, &signals(&artifacts))
+        .expect("exported proof should verify");
+}
+
+#[test]
+fn tampered_signal_is_a_non_retryable_verification_failure() {
+    let state = test_state();
+    let artifacts = exported_artifacts("settlement_valid");
+    let proof_hex = encode_proof_hex_from_json(&artifacts.proof_json).expect("calldata");
+    let mut public_signals = signals(&artifacts);
+    public_signals[5] = "1001".to_string();
+
+    let err = verify_publish_proof(&state, &artifacts, &proof_hex, &public_signals)
+        .expect_err("must fail");
+    assert!(err.starts_with("NON_RETRYABLE:PROOF_INVALID:"));
+}
+
+#[test]
+fn malformed_proof_is_non_retryable_but_a_missing_key_is_not() {
+    let state = test_state();
+    let artifacts = exported_artifacts("settlement_valid");
+    let public_signals = signals(&artifacts);
+
+    let err = verify_publish_proof(&state, &artifacts, "0xc0ffee", &public_signals)
+        .expect_err("must fail");
+    assert!(err.starts_with("NON_RETRYABLE:PROOF_MALFORMED:"));
+
+    let mut unknown = artifacts.clone();
+    unknown.circuit = "unknown_circuit".to_string();
+    let proof_hex = encode_proof_hex_from_json(&artifacts.proof_json).expect("calldata");
+    let err =
+        verify_publish_proof(&state, &unknown, &proof_hex, &public_signals).expect_err("must fail");
+    assert!(!err.starts_with("NON_RETRYABLE:"), "{err}");
+    assert!(err.contains("verifying key unavailable"), "{err}");
+}
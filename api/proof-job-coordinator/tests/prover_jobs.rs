@@ -0,0 +1,223 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use proof_job_coordinator::app::AppState;
+use proof_job_coordinator::config::environment::AppConfig;
+use proof_job_coordinator::module::proof_job::model::ProofJobRecord;
+use proof_job_coordinator::module::proof_job::schema::{JobStatus, ProofType};
+use proof_job_coordinator::service::hash_service::sha256_hex;
+use proof_job_coordinator::service::proof_artifact_service::{
+    prepare_job_dir, store_proof, sweep_job_dirs,
+};
+use proof_job_coordinator::service::prover_service::run_prover_for_job;
+use proof_job_coordinator::service::signal_binding_service::validate_public_signal_binding;
+use serde_json::json;
+
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "zkclear-prover-jobs-test-{name}-{}",
+        uuid::Uuid::new_v4()
+    ));
+    std::fs::create_dir_all(&dir).expect("scratch dir");
+    dir
+}
+
+fn test_state(root: &Path) -> AppState {
+    AppState::new(
+        AppConfig {
+            rust_env: "test".to_string(),
+            api_host: "127.0.0.1".to_string(),
+            api_port: 0,
+            mongodb_url: None,
+            mongodb_database: None,
+            redis_url: None,
+            idempotency_ttl_seconds: 3600,
+            worker_enabled: false,
+            worker_poll_seconds: 1,
+            worker_lease_seconds: 10,
+            worker_max_retries: 1,
+            worker_backoff_base_seconds: 1,
+            worker_concurrency: 2,
+            zk_root_dir: "../../zk".to_string(),
+            prover_backend: "native".to_string(),
+            prover_work_dir: root.join("jobs").to_string_lossy().into_owned(),
+            proof_store_dir: root.join("proofs").to_string_lossy().into_owned(),
+            prover_workdir_retention_seconds: 86400,
+            prove_timeout_seconds: 60,
+            prove_budget_settlement_seconds: 60,
+            prove_budget_compliance_seconds: 60,
+            prove_budget_rebate_seconds: 60,
+            signal_domain_separator: "zkclear:v1".to_string(),
+            eth_sepolia_rpc_url: None,
+            private_key: None,
+            eth_sepolia_chain_id: 11155111,
+            publish_settlement_registry: None,
+            publish_publisher_address: None,
+            internal_auth_enabled: false,
+            internal_auth_secret: None,
+            wallet_auth_enabled: false,
+            wallet_auth_nonce_ttl_seconds: 300,
+            wallet_jwt_secret: None,
+            wallet_jwt_ttl_seconds: 3600,
+            wallet_role_map: String::new(),
+            wallet_default_role: "dealer".to_string(),
+            intent_gateway_base_url: "http://127.0.0.1:8080".to_string(),
+            compliance_adapter_base_url: "http://127.0.0.1:8082".to_string(),
+            policy_snapshot_base_url: "http://127.0.0.1:8083".to_string(),
+        },
+        None,
+    )
+}
+
+fn settlement_job(job_id: &str, workflow_run_id: &str) -> ProofJobRecord {
+    ProofJobRecord {
+        job_id: job_id.to_string(),
+        workflow_run_id: workflow_run_id.to_string(),
+        policy_version: "policy-v1".to_string(),
+        proof_type: ProofType::Settlement,
+        receipt_context: json!({
+            "receiptHash": format!("receipt-{workflow_run_id}"),
+            "binding": {
+                "workflowRunId": workflow_run_id,
+                "policyVersion": "policy-v1",
+                "receiptHash": format!("receipt-{workflow_run_id}"),
+                "domainSeparator": "zkclear:v1"
+            }
+        }),
+        idempotency_key: format!("idem-{job_id}"),
+        request_hash: "hash".to_string(),
+        created_at: 1,
+        updated_at: 1,
+        status: JobStatus::Proving,
+        last_error_code: None,
+        last_error_message: None,
+        prover_artifacts: None,
+        onchain_publish: None,
+        transitions: Vec::new(),
+    }
+}
+
+#[tokio::test]
+async fn concurrent_jobs_on_one_circuit_keep_their_own_outputs() {
+    let root = scratch_dir("concurrent");
+    let state = test_state(&root);
+    let job_a = settlement_job("job-a", "run-a");
+    let job_b = settlement_job("job-b", "run-b");
+
+    let (a, b) = tokio::join!(
+        run_prover_for_job(&state, &job_a, "lease-a"),
+        run_prover_for_job(&state, &job_b, "lease-b")
+    );
+    let (a, b) = (a.expect("job a"), b.expect("job b"));
+
+    validate_public_signal_binding(&state, &job_a, &a).expect("job a binds run-a");
+    validate_public_signal_binding(&state, &job_b, &b).expect("job b binds run-b");
+    assert_ne!(a.proof_hash, b.proof_hash);
+
+    for artifacts in [&a, &b] {
+        let proof_path = Path::new(&artifacts.proof_path);
+        assert_eq!(
+            proof_path,
+            root.join("proofs")
+                .join(&artifacts.proof_hash)
+                .join("proof.json")
+        );
+        let stored = std::fs::read_to_string(proof_path).expect("stored proof");
+        assert_eq!(sha256_hex(&stored), artifacts.proof_hash);
+    }
+    assert!(!root.join("jobs").join("job-a.lease-a").exists());
+    assert!(!root.join("jobs").join("job-b.lease-b").exists());
+}
+
+#[tokio::test]
+async fn overlapping_attempts_of_one_job_keep_their_own_work_dirs() {
+    let root = scratch_dir("overlap");
+    let state = test_state(&root);
+    let job = settlement_job("job-a", "run-a");
+
+    let (first, second) = tokio::join!(
+        run_prover_for_job(&state, &job, "lease-1"),
+        run_prover_for_job(&state, &job, "lease-2")
+    );
+    let (first, second) = (
+        first.expect("first attempt"),
+        second.expect("second attempt"),
+    );
+    validate_public_signal_binding(&state, &job, &first).expect("first attempt binds");
+    validate_public_signal_binding(&state, &job, &second).expect("second attempt binds");
+}
+
+#[tokio::test]
+async fn job_dirs_are_per_attempt_and_swept_after_retention() {
+    let root = scratch_dir("sweep");
+    let work_root = root.join("jobs");
+
+    let first = prepare_job_dir(&work_root, "job-1", "lease-1")
+        .await
+        .expect("dir");
+    std::fs::write(first.join("in-use.wtns"), b"live").expect("write");
+    let dir = prepare_job_dir(&work_root, "job-1", "lease-2")
+        .await
+        .expect("dir");
+    assert_ne!(first, dir);
+    assert!(first.join("in-use.wtns").exists());
+    assert!(
+        prepare_job_dir(&work_root, "job-1", "lease-1")
+            .await
+            .is_err()
+    );
+    std::fs::remove_dir_all(&first).expect("remove first attempt");
+
+    assert!(
+        prepare_job_dir(&work_root, "../escape", "lease-1")
+            .await
+            .is_err()
+    );
+    assert!(
+        prepare_job_dir(&work_root, "job-1", "../escape")
+            .await
+            .is_err()
+    );
+
+    assert_eq!(
+        sweep_job_dirs(&work_root, Duration::from_secs(3600))
+            .await
+            .expect("sweep"),
+        0
+    );
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    assert_eq!(
+        sweep_job_dirs(&work_root, Duration::from_millis(1))
+            .await
+            .expect("sweep"),
+        1
+    );
+    assert!(!dir.exists());
+    assert_eq!(
+        sweep_job_dirs(&root.join("missing"), Duration::ZERO)
+            .await
+            .expect("sweep"),
+        0
+    );
+}
+
+#[tokio::test]
+async fn proof_store_is_keyed_by_hash_and_write_once() {
+    let root = scratch_dir("store");
+    let proof_raw = r#"{"pi_a":["1","2","1"]}"#;
+    let hash = sha256_hex(proof_raw);
+
+    let (proof_path, public_path) = store_proof(&root, &hash, proof_raw, r#"["7"]"#)
+        .await
+        .expect("store");
+    let again = store_proof(&root, &hash, proof_raw, r#"["8"]"#)
+        .await
+        .expect("store again");
+    assert_eq!(again, (proof_path.clone(), public_path.clone()));
+    assert_eq!(
+        std::fs::read_to_string(&public_path).expect("public"),
+        r#"["7"]"#
+    );
+
+    assert!(store_proof(&root, "../x", proof_raw, "[]").await.is_err());
+}
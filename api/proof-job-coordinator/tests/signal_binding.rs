@@ -21,7 +21,12 @@ fn test_state() -> AppState {
             worker_lease_seconds: 10,
             worker_max_retries: 1,
             worker_backoff_base_seconds: 1,
+            worker_concurrency: 1,
             zk_root_dir: "../../zk".to_string(),
+            prover_backend: "native".to_string(),
+            prover_work_dir: "target/test-prover-jobs".to_string(),
+            proof_store_dir: "target/test-proofs".to_string(),
+            prover_workdir_retention_seconds: 86400,
             prove_timeout_seconds: 30,
             prove_budget_settlement_seconds: 15,
             prove_budget_compliance_seconds: 10,
@@ -0,0 +1,143 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use fixed_amount::{Amount, AmountError};
+
+use crate::models::{NormalizedIntent, ResidualIntent, Side};
+
+pub struct RestingOrder<'a> {
+    pub intent: &'a NormalizedIntent,
+    pub sequence: usize,
+    pub remaining: Amount,
+}
+
+pub struct Cross<'a> {
+    pub buy: &'a NormalizedIntent,
+    pub sell: &'a NormalizedIntent,
+    pub size: Amount,
+    /// Side of the order that was resting first (earlier time priority).
+    pub maker: Side,
+}
+
+pub struct OrderBook<'a> {
+    pub asset_pair: String,
+    pub bids: Vec<RestingOrder<'a>>,
+    pub asks: Vec<RestingOrder<'a>>,
+}
+
+/// Expects intents already rescaled to their pair's size/price scale.
+pub fn build_books(intents: &[NormalizedIntent]) -> Vec<OrderBook<'_>> {
+    let mut books: BTreeMap<&str, OrderBook<'_>> = BTreeMap::new();
+
+    for (sequence, intent) in intents.iter().enumerate() {
+        let book = books
+            .entry(intent.asset_pair.as_str())
+            .or_insert_with(|| OrderBook {
+                asset_pair: intent.asset_pair.clone(),
+                bids: Vec::new(),
+                asks: Vec::new(),
+            });
+        let order = RestingOrder {
+            intent,
+            sequence,
+            remaining: intent.size,
+        };
+        match intent.side {
+            Side::Buy => book.bids.push(order),
+            Side::Sell => book.asks.push(order),
+        }
+    }
+
+    let mut out: Vec<OrderBook<'_>> = books.into_values().collect();
+    for book in &mut out {
+        book.bids.sort_by(|a, b| {
+            b.intent
+                .limit_price
+                .cmp(&a.intent.limit_price)
+                .then_with(|| time_priority(a, b))
+        });
+        book.asks.sort_by(|a, b| {
+            a.intent
+                .limit_price
+                .cmp(&b.intent.limit_price)
+                .then_with(|| time_priority(a, b))
+        });
+    }
+    out
+}
+
+impl<'a> OrderBook<'a> {
+    /// Walks bids in price-time priority and fills each against the best
+    /// crossing asks until the bid is exhausted or the book no longer crosses.
+    /// Crosses refused by `accept` are skipped without consuming quantity.
+    pub fn cross<F>(&mut self, mut accept: F) -> Result<Vec<Cross<'a>>, AmountError>
+    where
+        F: FnMut(&Cross<'a>) -> bool,
+    {
+        let mut crosses = Vec::new();
+
+        for bid in &mut self.bids {
+            for ask in &mut self.asks {
+                if bid.remaining.is_zero() {
+                    break;
+                }
+                if ask.remaining.is_zero() {
+                    continue;
+                }
+                if bid.intent.limit_price < ask.intent.limit_price {
+                    break;
+                }
+
+                let maker = if time_priority(bid, ask) == Ordering::Less {
+                    Side::Buy
+                } else {
+                    Side::Sell
+                };
+                let cross = Cross {
+                    buy: bid.intent,
+                    sell: ask.intent,
+                    size: bid.remaining.min(ask.remaining),
+                    maker,
+                };
+                if !accept(&cross) {
+                    continue;
+                }
+                bid.remaining = bid.remaining.checked_sub(&cross.size)?;
+                ask.remaining = ask.remaining.checked_sub(&cross.size)?;
+                crosses.push(cross);
+            }
+        }
+
+        Ok(crosses)
+    }
+
+    pub fn residuals(&self) -> Result<Vec<ResidualIntent>, AmountError> {
+        let mut out: Vec<&RestingOrder<'a>> = self
+            .bids
+            .iter()
+            .chain(self.asks.iter())
+            .filter(|order| !order.remaining.is_zero())
+            .collect();
+        out.sort_by_key(|order| order.sequence);
+
+        out.into_iter()
+            .map(|order| {
+                Ok(ResidualIntent {
+                    intent_id: order.intent.intent_id.clone(),
+                    asset_pair: order.intent.asset_pair.clone(),
+                    side: order.intent.side.clone(),
+                    original_size: order.intent.size,
+                    filled_size: order.intent.size.checked_sub(&order.remaining)?,
+                    remaining_size: order.remaining,
+                })
+            })
+            .collect()
+    }
+}
+
+fn time_priority(a: &RestingOrder<'_>, b: &RestingOrder<'_>) -> Ordering {
+    a.intent
+        .submitted_at
+        .cmp(&b.intent.submitted_at)
+        .then_with(|| a.sequence.cmp(&b.sequence))
+}
@@ -0,0 +1,52 @@
+use crate::errors::MatchError;
+use crate::models::NormalizedIntent;
+
+/// Checks that both intents are live at `now` and that each side's
+/// allow/deny lists accept the other side's signer.
+pub fn check_pair(
+    buy: &NormalizedIntent,
+    sell: &NormalizedIntent,
+    now: u64,
+) -> Result<(), MatchError> {
+    ensure_live(buy, now)?;
+    ensure_live(sell, now)?;
+    ensure_counterparty_accepted(buy, sell)?;
+    ensure_counterparty_accepted(sell, buy)?;
+    Ok(())
+}
+
+fn ensure_live(intent: &NormalizedIntent, now: u64) -> Result<(), MatchError> {
+    if intent.expiry <= now {
+        return Err(MatchError::IntentExpired {
+            intent_id: intent.intent_id.clone(),
+            expiry: intent.expiry,
+            now,
+        });
+    }
+    Ok(())
+}
+
+fn ensure_counterparty_accepted(
+    intent: &NormalizedIntent,
+    counterparty: &NormalizedIntent,
+) -> Result<(), MatchError> {
+    let constraints = &intent.counterparty_constraints;
+    let listed = |list: &[String]| {
+        list.iter()
+            .any(|entry| entry.trim().eq_ignore_ascii_case(&counterparty.signer))
+    };
+
+    if listed(&constraints.deny_list) {
+        return Err(MatchError::CounterpartyDenied {
+            intent_id: intent.intent_id.clone(),
+            counterparty: counterparty.signer.clone(),
+        });
+    }
+    if !constraints.allow_list.is_empty() && !listed(&constraints.allow_list) {
+        return Err(MatchError::CounterpartyNotAllowed {
+            intent_id: intent.intent_id.clone(),
+            counterparty: counterparty.signer.clone(),
+        });
+    }
+    Ok(())
+}
@@ -1,5 +1,7 @@
 use thiserror::Error;
 
+use crate::models::RejectReason;
+
 #[derive(Debug, Error)]
 pub enum MatchError {
     #[error("invalid request: {0}")]
@@ -17,6 +19,72 @@ pub enum MatchError {
     #[error("compliance check failed")]
     ComplianceFail,
 
+    #[error("intent {intent_id} expired: expiry={expiry}, now={now}")]
+    IntentExpired {
+        intent_id: String,
+        expiry: u64,
+        now: u64,
+    },
+
+    #[error("counterparty {counterparty} is on the deny list of intent {intent_id}")]
+    CounterpartyDenied {
+        intent_id: String,
+        counterparty: String,
+    },
+
+    #[error("counterparty {counterparty} is not on the allow list of intent {intent_id}")]
+    CounterpartyNotAllowed {
+        intent_id: String,
+        counterparty: String,
+    },
+
+    #[error("reference price unavailable for {asset_pair}")]
+    ReferencePriceUnavailable { asset_pair: String },
+
+    #[error(
+        "execution price {execution_price} for {asset_pair} outside {band_bps}bps of reference {reference_price}"
+    )]
+    PriceOutOfBand {
+        asset_pair: String,
+        execution_price: String,
+        reference_price: String,
+        band_bps: u32,
+    },
+
+    #[error("notional {notional} for {asset_pair} exceeds max_notional {max}")]
+    NotionalCapExceeded {
+        asset_pair: String,
+        notional: String,
+        max: String,
+    },
+
+    #[error("arithmetic error: {0}")]
+    Arithmetic(String),
+
     #[error("no match found")]
     NoMatch,
 }
+
+impl MatchError {
+    /// Business outcomes that end the run with a `Reject` decision instead of
+    /// aborting it. Everything else is a malformed request or an outage.
+    pub fn reject_reason(&self) -> Option<RejectReason> {
+        match self {
+            MatchError::RiskThresholdFail { .. } => Some(RejectReason::RiskThresholdFail),
+            MatchError::ComplianceFail => Some(RejectReason::ComplianceFail),
+            MatchError::NoMatch => Some(RejectReason::NoMatch),
+            MatchError::IntentExpired { .. } => Some(RejectReason::IntentExpired),
+            MatchError::CounterpartyDenied { .. } => Some(RejectReason::CounterpartyDenied),
+            MatchError::CounterpartyNotAllowed { .. } => Some(RejectReason::CounterpartyNotAllowed),
+            MatchError::NotionalCapExceeded { .. } => Some(RejectReason::NotionalCapExceeded),
+            MatchError::PriceOutOfBand { .. } => Some(RejectReason::PriceOutOfBand),
+            MatchError::ReferencePriceUnavailable { .. } => {
+                Some(RejectReason::ReferencePriceUnavailable)
+            }
+            MatchError::InvalidRequest(_)
+            | MatchError::ApiUnavailable
+            | MatchError::PolicyMismatch { .. }
+            | MatchError::Arithmetic(_) => None,
+        }
+    }
+}
@@ -1,10 +1,17 @@
+use std::collections::HashSet;
+
+use fixed_amount::{Amount, AssetScales};
 use sha2::{Digest, Sha256};
 
+use crate::book::{build_books, Cross};
+use crate::constraints;
 use crate::errors::MatchError;
 use crate::models::{
-    ConfidentialMatchRequest, ConfidentialMatchResponse, MatchDecision, NormalizedIntent,
-    PolicyCheckResult, SettlementParams, Side,
+    ConfidentialMatchRequest, ConfidentialMatchResponse, ExternalSignals, MatchDecision,
+    NormalizedIntent, PolicyCheckResult, PolicyContext, PricingAudit, PricingRule, ResidualIntent,
+    SettlementParams, Side,
 };
+use crate::pricing::{self, ReferencePrice};
 
 pub fn process_confidential_match(
     req: ConfidentialMatchRequest,
@@ -12,27 +19,57 @@ pub fn process_confidential_match(
     validate_request_shape(&req)?;
     ensure_api_available(&req)?;
     ensure_policy_version(&req)?;
-    ensure_risk_and_compliance(&req)?;
-
-    let (buy, sell) = select_pair(&req.intents)?;
-    let settlement = build_settlement(buy, sell, req.policy.max_notional)?;
-    let policy_check_result = PolicyCheckResult {
-        passed: true,
-        policy_version: req.policy.policy_version,
-        reason: "all policy checks passed".to_string(),
-    };
 
-    Ok(ConfidentialMatchResponse {
-        workflow_run_id: req.workflow_run_id.clone(),
-        match_decision: MatchDecision::Accept,
-        private_settlement_params: settlement,
-        policy_check_result,
-        compliance_attestation_hash: attestation_hash(
-            &req.workflow_run_id,
-            req.policy.policy_version,
-            &req.external_signals.attestation_payload,
-        ),
-    })
+    let scales = asset_scales(&req.policy)?;
+    let intents = normalize_scales(&req.intents, &scales)?;
+    let compliance_attestation_hash = attestation_hash(
+        &req.workflow_run_id,
+        req.policy.policy_version,
+        &req.external_signals.attestation_payload,
+    );
+
+    let outcome = ensure_risk_and_compliance(&req).and_then(|()| {
+        match_books(
+            &intents,
+            &req.policy,
+            &req.external_signals,
+            &scales,
+            req.current_unix_ts,
+        )
+    });
+
+    match outcome {
+        Ok((fills, residual_intents)) => Ok(ConfidentialMatchResponse {
+            workflow_run_id: req.workflow_run_id.clone(),
+            match_decision: MatchDecision::Accept,
+            reject_reason: None,
+            private_settlement_params: fills,
+            residual_intents,
+            policy_check_result: PolicyCheckResult {
+                passed: true,
+                policy_version: req.policy.policy_version,
+                reason: "all policy checks passed".to_string(),
+            },
+            compliance_attestation_hash,
+        }),
+        Err(err) => {
+            let reason = err.to_string();
+            let reject_reason = err.reject_reason().ok_or(err)?;
+            Ok(ConfidentialMatchResponse {
+                workflow_run_id: req.workflow_run_id.clone(),
+                match_decision: MatchDecision::Reject,
+                reject_reason: Some(reject_reason),
+                private_settlement_params: Vec::new(),
+                residual_intents: unfilled(&intents)?,
+                policy_check_result: PolicyCheckResult {
+                    passed: false,
+                    policy_version: req.policy.policy_version,
+                    reason,
+                },
+                compliance_attestation_hash,
+            })
+        }
+    }
 }
 
 fn validate_request_shape(req: &ConfidentialMatchRequest) -> Result<(), MatchError> {
@@ -41,11 +78,27 @@ fn validate_request_shape(req: &ConfidentialMatchRequest) -> Result<(), MatchErr
             "workflow_run_id cannot be empty".to_string(),
         ));
     }
-    if req.intents.len() != 2 {
+    if req.intents.len() < 2 {
         return Err(MatchError::InvalidRequest(
-            "exactly two intents are required".to_string(),
+            "at least two intents are required".to_string(),
         ));
     }
+
+    let mut seen_ids = HashSet::with_capacity(req.intents.len());
+    for intent in &req.intents {
+        if !seen_ids.insert(intent.intent_id.as_str()) {
+            return Err(MatchError::InvalidRequest(format!(
+                "duplicate intent_id `{}`",
+                intent.intent_id
+            )));
+        }
+        if intent.size.is_zero() || intent.limit_price.is_zero() {
+            return Err(MatchError::InvalidRequest(format!(
+                "intent `{}` must have positive size and limit_price",
+                intent.intent_id
+            )));
+        }
+    }
     Ok(())
 }
 
@@ -79,54 +132,189 @@ fn ensure_risk_and_compliance(req: &ConfidentialMatchRequest) -> Result<(), Matc
     Ok(())
 }
 
-fn select_pair(
+fn asset_scales(policy: &PolicyContext) -> Result<AssetScales, MatchError> {
+    match &policy.asset_scales {
+        Some(overrides) => AssetScales::default()
+            .with_overrides(overrides)
+            .map_err(|e| MatchError::InvalidRequest(format!("policy.asset_scales: {e}"))),
+        None => Ok(AssetScales::default()),
+    }
+}
+
+/// Rescales every intent to its pair's size/price scale so the book compares
+/// and subtracts like-for-like units. Inputs finer than the asset allows are
+/// rejected rather than rounded.
+fn normalize_scales(
     intents: &[NormalizedIntent],
-) -> Result<(&NormalizedIntent, &NormalizedIntent), MatchError> {
-    let first = &intents[0];
-    let second = &intents[1];
-
-    let (buy, sell) = match (&first.side, &second.side) {
-        (Side::Buy, Side::Sell) => (first, second),
-        (Side::Sell, Side::Buy) => (second, first),
-        _ => return Err(MatchError::NoMatch),
-    };
+    scales: &AssetScales,
+) -> Result<Vec<NormalizedIntent>, MatchError> {
+    intents
+        .iter()
+        .map(|intent| {
+            let invalid = |e: fixed_amount::AmountError| {
+                MatchError::InvalidRequest(format!("intent `{}`: {e}", intent.intent_id))
+            };
+            let pair = scales.pair_scale(&intent.asset_pair).map_err(invalid)?;
+            Ok(NormalizedIntent {
+                size: intent.size.to_scale(pair.size_scale).map_err(invalid)?,
+                limit_price: intent
+                    .limit_price
+                    .to_scale(pair.price_scale)
+                    .map_err(invalid)?,
+                ..intent.clone()
+            })
+        })
+        .collect()
+}
 
-    if buy.asset_pair != sell.asset_pair {
-        return Err(MatchError::NoMatch);
-    }
-    if buy.limit_price < sell.limit_price {
-        return Err(MatchError::NoMatch);
+fn match_books(
+    intents: &[NormalizedIntent],
+    policy: &PolicyContext,
+    signals: &ExternalSignals,
+    scales: &AssetScales,
+    now: u64,
+) -> Result<(Vec<SettlementParams>, Vec<ResidualIntent>), MatchError> {
+    let mut fills = Vec::new();
+    let mut residuals = Vec::new();
+    let mut first_block: Option<MatchError> = None;
+
+    for mut book in build_books(intents) {
+        let pair_scale = scales
+            .pair_scale(&book.asset_pair)
+            .map_err(|e| MatchError::InvalidRequest(e.to_string()))?;
+        let reference = pricing::reference_for_pair(
+            &book.asset_pair,
+            signals.fx_quote_for(&book.asset_pair),
+            pair_scale.price_scale,
+        )?;
+        let needs_reference = policy.pricing.rule == PricingRule::ReferencePrice
+            || policy.pricing.band_bps(&book.asset_pair).is_some();
+        if needs_reference && reference.is_none() {
+            // Only this pair's book is left unfilled; other quoted pairs in
+            // the batch still match.
+            first_block.get_or_insert(MatchError::ReferencePriceUnavailable {
+                asset_pair: book.asset_pair.clone(),
+            });
+            residuals.extend(
+                book.residuals()
+                    .map_err(|e| MatchError::Arithmetic(e.to_string()))?,
+            );
+            continue;
+        }
+
+        let mut fatal = None;
+        book.cross(|cross| {
+            if let Err(block) = constraints::check_pair(cross.buy, cross.sell, now) {
+                first_block.get_or_insert(block);
+                return false;
+            }
+            if !pricing::can_execute(
+                policy.pricing.rule,
+                cross.buy,
+                cross.sell,
+                reference.as_ref(),
+            ) {
+                return false;
+            }
+            // A fill over the notional cap or outside the price band is
+            // refused on its own; its quantity stays on the book.
+            match build_settlement(cross, policy, reference.as_ref()) {
+                Ok(fill) => {
+                    fills.push(fill);
+                    true
+                }
+                Err(err) if err.reject_reason().is_some() => {
+                    first_block.get_or_insert(err);
+                    false
+                }
+                Err(err) => {
+                    fatal.get_or_insert(err);
+                    false
+                }
+            }
+        })
+        .map_err(|e| MatchError::Arithmetic(e.to_string()))?;
+        if let Some(err) = fatal {
+            return Err(err);
+        }
+        residuals.extend(
+            book.residuals()
+                .map_err(|e| MatchError::Arithmetic(e.to_string()))?,
+        );
     }
-    if buy.size <= 0.0 || sell.size <= 0.0 {
-        return Err(MatchError::NoMatch);
+
+    // A crossing pair blocked by expiry, counterparty lists, the notional cap
+    // or the price band, or a book with no reference quote, is skipped so the
+    // rest can still fill; it only surfaces when nothing did.
+    if fills.is_empty() {
+        return Err(first_block.unwrap_or(MatchError::NoMatch));
     }
 
-    Ok((buy, sell))
+    Ok((fills, residuals))
 }
 
 fn build_settlement(
-    buy: &NormalizedIntent,
-    sell: &NormalizedIntent,
-    max_notional: f64,
+    cross: &Cross<'_>,
+    policy: &PolicyContext,
+    reference: Option<&ReferencePrice>,
 ) -> Result<SettlementParams, MatchError> {
-    let execution_size = buy.size.min(sell.size);
-    let execution_price = (buy.limit_price + sell.limit_price) / 2.0;
-    let notional = execution_size * execution_price;
+    let execution_size = cross.size;
+    let execution_price = pricing::execution_price(&policy.pricing, cross, reference)?;
+    let notional = execution_size
+        .checked_mul(&execution_price)
+        .map_err(|e| MatchError::Arithmetic(e.to_string()))?;
 
-    if notional > max_notional {
-        return Err(MatchError::NoMatch);
+    if notional > policy.max_notional {
+        return Err(MatchError::NotionalCapExceeded {
+            asset_pair: cross.buy.asset_pair.clone(),
+            notional: notional.to_string(),
+            max: policy.max_notional.to_string(),
+        });
     }
 
+    let maker_intent_id = match cross.maker {
+        Side::Buy => cross.buy.intent_id.clone(),
+        Side::Sell => cross.sell.intent_id.clone(),
+    };
+
     Ok(SettlementParams {
-        asset_pair: buy.asset_pair.clone(),
-        buy_intent_id: buy.intent_id.clone(),
-        sell_intent_id: sell.intent_id.clone(),
+        asset_pair: cross.buy.asset_pair.clone(),
+        buy_intent_id: cross.buy.intent_id.clone(),
+        sell_intent_id: cross.sell.intent_id.clone(),
         execution_size,
         execution_price,
         notional,
+        pricing: PricingAudit {
+            rule: policy.pricing.rule,
+            maker_intent_id,
+            buy_limit_price: cross.buy.limit_price,
+            sell_limit_price: cross.sell.limit_price,
+            reference_price: reference.map(|r| r.price),
+            reference_provider: reference.map(|r| r.provider.clone()),
+            reference_as_of_date: reference.map(|r| r.as_of_date.clone()),
+            reference_band_bps: policy.pricing.band_bps(&cross.buy.asset_pair),
+        },
     })
 }
 
+/// A rejected run settles nothing, so every intent is reported untouched.
+fn unfilled(intents: &[NormalizedIntent]) -> Result<Vec<ResidualIntent>, MatchError> {
+    intents
+        .iter()
+        .map(|intent| {
+            Ok(ResidualIntent {
+                intent_id: intent.intent_id.clone(),
+                asset_pair: intent.asset_pair.clone(),
+                side: intent.side.clone(),
+                original_size: intent.size,
+                filled_size: Amount::zero(intent.size.scale())
+                    .map_err(|e| MatchError::Arithmetic(e.to_string()))?,
+                remaining_size: intent.size,
+            })
+        })
+        .collect()
+}
+
 fn attestation_hash(workflow_run_id: &str, policy_version: u64, payload: &str) -> String {
     let mut hasher = Sha256::new();
     hasher.update(workflow_run_id.as_bytes());
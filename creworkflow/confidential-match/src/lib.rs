@@ -1,3 +1,6 @@
+pub mod book;
+pub mod constraints;
 pub mod errors;
 pub mod handler;
 pub mod models;
+pub mod pricing;
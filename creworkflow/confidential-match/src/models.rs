@@ -1,8 +1,12 @@
-use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use fixed_amount::Amount;
+use serde::{Deserialize, Deserializer, Serialize};
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct ConfidentialMatchRequest {
     pub workflow_run_id: String,
+    pub current_unix_ts: u64,
     pub policy: PolicyContext,
     pub intents: Vec<NormalizedIntent>,
     pub external_signals: ExternalSignals,
@@ -13,7 +17,47 @@ pub struct PolicyContext {
     pub policy_version: u64,
     pub expected_policy_version: u64,
     pub max_risk_score: u32,
-    pub max_notional: f64,
+    pub max_notional: Amount,
+    /// Midpoint without a band when absent.
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub pricing: PricingPolicy,
+    /// Decimal scale per asset symbol, layered over
+    /// `fixed_amount::scale::DEFAULT_ASSET_SCALES`. Pairs whose assets have
+    /// no scale either way are rejected.
+    #[serde(default)]
+    pub asset_scales: Option<BTreeMap<String, u32>>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PricingRule {
+    #[default]
+    Midpoint,
+    MakerPrice,
+    TakerPrice,
+    ReferencePrice,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct PricingPolicy {
+    #[serde(default)]
+    pub rule: PricingRule,
+    #[serde(default)]
+    pub reference_band_bps: Option<u32>,
+    /// Governed per-pair bands (the policy snapshot's `pricing_bands`), keyed
+    /// by `BASE/QUOTE`; `reference_band_bps` covers pairs not listed here.
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub pair_band_bps: BTreeMap<String, u32>,
+}
+
+impl PricingPolicy {
+    pub fn band_bps(&self, asset_pair: &str) -> Option<u32> {
+        self.pair_band_bps
+            .iter()
+            .find(|(pair, _)| pair.eq_ignore_ascii_case(asset_pair))
+            .map(|(_, band_bps)| *band_bps)
+            .or(self.reference_band_bps)
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -22,6 +66,33 @@ pub struct ExternalSignals {
     pub compliance_passed: bool,
     pub risk_score: u32,
     pub attestation_payload: String,
+    /// Single quote, as older inputs and the compliance adapter carry it. Used
+    /// for the pair it quotes when `fx_quotes` has no entry for that pair.
+    #[serde(default)]
+    pub fx_quote: Option<FxQuote>,
+    /// Reference quotes keyed by `BASE/QUOTE` asset pair.
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub fx_quotes: BTreeMap<String, FxQuote>,
+}
+
+impl ExternalSignals {
+    pub fn fx_quote_for(&self, asset_pair: &str) -> Option<&FxQuote> {
+        self.fx_quotes
+            .iter()
+            .find(|(pair, _)| pair.eq_ignore_ascii_case(asset_pair))
+            .map(|(_, quote)| quote)
+            .or(self.fx_quote.as_ref())
+    }
+}
+
+/// Same shape as the compliance adapter's `confidential_http_service::FxQuote`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FxQuote {
+    pub provider: String,
+    pub base: String,
+    pub quote: String,
+    pub rate: f64,
+    pub as_of_date: String,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -30,8 +101,19 @@ pub struct NormalizedIntent {
     pub signer: String,
     pub asset_pair: String,
     pub side: Side,
-    pub size: f64,
-    pub limit_price: f64,
+    pub size: Amount,
+    pub limit_price: Amount,
+    pub submitted_at: u64,
+    pub expiry: u64,
+    pub counterparty_constraints: CounterpartyConstraints,
+}
+
+/// Entries are matched case-insensitively against the counterparty's signer.
+/// An empty `allow_list` allows any counterparty not on the `deny_list`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CounterpartyConstraints {
+    pub allow_list: Vec<String>,
+    pub deny_list: Vec<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
@@ -48,6 +130,38 @@ pub enum MatchDecision {
     Reject,
 }
 
+/// Machine-readable reason for a `Reject` decision. Codes line up with the
+/// `reject_policy.codes` in `workflow-spec/otc_settlement.yaml`.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RejectReason {
+    RiskThresholdFail,
+    ComplianceFail,
+    NoMatch,
+    IntentExpired,
+    CounterpartyDenied,
+    CounterpartyNotAllowed,
+    NotionalCapExceeded,
+    PriceOutOfBand,
+    ReferencePriceUnavailable,
+}
+
+impl RejectReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RejectReason::RiskThresholdFail => "RISK_THRESHOLD_FAIL",
+            RejectReason::ComplianceFail => "COMPLIANCE_FAIL",
+            RejectReason::NoMatch => "NO_MATCH",
+            RejectReason::IntentExpired => "INTENT_EXPIRED",
+            RejectReason::CounterpartyDenied => "COUNTERPARTY_DENIED",
+            RejectReason::CounterpartyNotAllowed => "COUNTERPARTY_NOT_ALLOWED",
+            RejectReason::NotionalCapExceeded => "NOTIONAL_CAP_EXCEEDED",
+            RejectReason::PriceOutOfBand => "PRICE_OUT_OF_BAND",
+            RejectReason::ReferencePriceUnavailable => "REFERENCE_PRICE_UNAVAILABLE",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct PolicyCheckResult {
     pub passed: bool,
@@ -60,16 +174,52 @@ pub struct SettlementParams {
     pub asset_pair: String,
     pub buy_intent_id: String,
     pub sell_intent_id: String,
-    pub execution_size: f64,
-    pub execution_price: f64,
-    pub notional: f64,
+    pub execution_size: Amount,
+    pub execution_price: Amount,
+    pub notional: Amount,
+    pub pricing: PricingAudit,
+}
+
+/// Everything needed to recompute `execution_price` for a fill.
+#[derive(Debug, Clone, Serialize)]
+pub struct PricingAudit {
+    pub rule: PricingRule,
+    pub maker_intent_id: String,
+    pub buy_limit_price: Amount,
+    pub sell_limit_price: Amount,
+    pub reference_price: Option<Amount>,
+    pub reference_provider: Option<String>,
+    pub reference_as_of_date: Option<String>,
+    pub reference_band_bps: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ResidualIntent {
+    pub intent_id: String,
+    pub asset_pair: String,
+    pub side: Side,
+    pub original_size: Amount,
+    pub filled_size: Amount,
+    pub remaining_size: Amount,
 }
 
 #[derive(Debug, Clone, Serialize)]
 pub struct ConfidentialMatchResponse {
     pub workflow_run_id: String,
     pub match_decision: MatchDecision,
-    pub private_settlement_params: SettlementParams,
+    pub reject_reason: Option<RejectReason>,
+    pub private_settlement_params: Vec<SettlementParams>,
+    pub residual_intents: Vec<ResidualIntent>,
     pub policy_check_result: PolicyCheckResult,
     pub compliance_attestation_hash: String,
 }
+
+/// The orchestrator maps absent input paths to `null`; treat that like a
+/// missing field.
+fn null_as_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
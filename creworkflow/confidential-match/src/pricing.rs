@@ -0,0 +1,120 @@
+use fixed_amount::Amount;
+
+use crate::book::Cross;
+use crate::errors::MatchError;
+use crate::models::{FxQuote, NormalizedIntent, PricingPolicy, PricingRule, Side};
+
+pub struct ReferencePrice {
+    pub price: Amount,
+    pub provider: String,
+    pub as_of_date: String,
+}
+
+/// Converts the FX quote into a reference price for `asset_pair` when it
+/// quotes the same base/quote. The `f64` rate is rounded once, here, to the
+/// pair's price scale; every later comparison is exact.
+pub fn reference_for_pair(
+    asset_pair: &str,
+    fx_quote: Option<&FxQuote>,
+    price_scale: u32,
+) -> Result<Option<ReferencePrice>, MatchError> {
+    let Some(quote) = fx_quote else {
+        return Ok(None);
+    };
+    let Some((base, quote_asset)) = asset_pair.split_once('/') else {
+        return Ok(None);
+    };
+    if !quote.base.eq_ignore_ascii_case(base) || !quote.quote.eq_ignore_ascii_case(quote_asset) {
+        return Ok(None);
+    }
+    if !quote.rate.is_finite() || quote.rate <= 0.0 {
+        return Err(MatchError::InvalidRequest(format!(
+            "fx quote rate for {asset_pair} must be positive"
+        )));
+    }
+
+    let price = format!("{:.*}", price_scale as usize, quote.rate)
+        .parse::<Amount>()
+        .map_err(|e| MatchError::InvalidRequest(format!("fx quote rate: {e}")))?;
+
+    Ok(Some(ReferencePrice {
+        price,
+        provider: quote.provider.clone(),
+        as_of_date: quote.as_of_date.clone(),
+    }))
+}
+
+/// Under `ReferencePrice` only pairs whose limits bracket the reference can
+/// trade; every other rule prices inside the crossed spread.
+pub fn can_execute(
+    rule: PricingRule,
+    buy: &NormalizedIntent,
+    sell: &NormalizedIntent,
+    reference: Option<&ReferencePrice>,
+) -> bool {
+    match (rule, reference) {
+        (PricingRule::ReferencePrice, Some(reference)) => {
+            sell.limit_price <= reference.price && reference.price <= buy.limit_price
+        }
+        (PricingRule::ReferencePrice, None) => false,
+        _ => true,
+    }
+}
+
+pub fn execution_price(
+    policy: &PricingPolicy,
+    cross: &Cross<'_>,
+    reference: Option<&ReferencePrice>,
+) -> Result<Amount, MatchError> {
+    let price = match policy.rule {
+        PricingRule::Midpoint => cross
+            .buy
+            .limit_price
+            .midpoint_floor(&cross.sell.limit_price)
+            .map_err(|e| MatchError::Arithmetic(e.to_string()))?,
+        PricingRule::MakerPrice => match cross.maker {
+            Side::Buy => cross.buy.limit_price,
+            Side::Sell => cross.sell.limit_price,
+        },
+        PricingRule::TakerPrice => match cross.maker {
+            Side::Buy => cross.sell.limit_price,
+            Side::Sell => cross.buy.limit_price,
+        },
+        PricingRule::ReferencePrice => {
+            reference
+                .ok_or_else(|| MatchError::ReferencePriceUnavailable {
+                    asset_pair: cross.buy.asset_pair.clone(),
+                })?
+                .price
+        }
+    };
+
+    if let Some(band_bps) = policy.band_bps(&cross.buy.asset_pair) {
+        let reference = reference.ok_or_else(|| MatchError::ReferencePriceUnavailable {
+            asset_pair: cross.buy.asset_pair.clone(),
+        })?;
+        ensure_within_band(&cross.buy.asset_pair, price, reference.price, band_bps)?;
+    }
+
+    Ok(price)
+}
+
+fn ensure_within_band(
+    asset_pair: &str,
+    price: Amount,
+    reference: Amount,
+    band_bps: u32,
+) -> Result<(), MatchError> {
+    let within = price
+        .within_bps_of(&reference, band_bps)
+        .map_err(|e| MatchError::Arithmetic(format!("price band: {e}")))?;
+    if !within {
+        return Err(MatchError::PriceOutOfBand {
+            asset_pair: asset_pair.to_string(),
+            execution_price: price.to_string(),
+            reference_price: reference.to_string(),
+            band_bps,
+        });
+    }
+    Ok(())
+}
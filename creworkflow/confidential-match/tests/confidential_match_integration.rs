@@ -1,17 +1,83 @@
 use confidential_match::errors::MatchError;
 use confidential_match::handler::process_confidential_match;
 use confidential_match::models::{
-    ConfidentialMatchRequest, ExternalSignals, MatchDecision, NormalizedIntent, PolicyContext, Side,
+    ConfidentialMatchRequest, CounterpartyConstraints, ExternalSignals, FxQuote, MatchDecision,
+    NormalizedIntent, PolicyContext, PricingPolicy, PricingRule, RejectReason, Side,
 };
+use fixed_amount::Amount;
+use std::collections::BTreeMap;
+
+const NOW: u64 = 1_750_000_100;
+
+fn amt(s: &str) -> Amount {
+    s.parse().expect("valid amount")
+}
+
+fn intent(
+    id: &str,
+    side: Side,
+    size: &str,
+    limit_price: &str,
+    submitted_at: u64,
+) -> NormalizedIntent {
+    NormalizedIntent {
+        intent_id: id.to_string(),
+        signer: format!("0x{id}"),
+        asset_pair: "ETH/USDC".to_string(),
+        side,
+        size: amt(size),
+        limit_price: amt(limit_price),
+        submitted_at,
+        expiry: NOW + 3600,
+        counterparty_constraints: open_constraints(),
+    }
+}
+
+fn open_constraints() -> CounterpartyConstraints {
+    CounterpartyConstraints {
+        allow_list: vec![],
+        deny_list: vec![],
+    }
+}
+
+fn expect_reject(req: ConfidentialMatchRequest, reason: RejectReason) -> String {
+    let intent_count = req.intents.len();
+    let out = process_confidential_match(req).expect("reject is a decision, not an error");
+
+    assert_eq!(out.match_decision, MatchDecision::Reject);
+    assert_eq!(out.reject_reason, Some(reason));
+    assert!(!out.policy_check_result.passed);
+    assert!(out.private_settlement_params.is_empty());
+    assert_eq!(out.residual_intents.len(), intent_count);
+    assert!(out.residual_intents.iter().all(|r| r.filled_size.is_zero()));
+    out.policy_check_result.reason
+}
+
+fn fx_quote(rate: f64) -> FxQuote {
+    FxQuote {
+        provider: "frankfurter".to_string(),
+        base: "ETH".to_string(),
+        quote: "USDC".to_string(),
+        rate,
+        as_of_date: "2026-10-16".to_string(),
+    }
+}
 
 fn request_template() -> ConfidentialMatchRequest {
     ConfidentialMatchRequest {
         workflow_run_id: "run-123".to_string(),
+        current_unix_ts: NOW,
         policy: PolicyContext {
             policy_version: 1,
             expected_policy_version: 1,
             max_risk_score: 70,
-            max_notional: 500_000.0,
+            max_notional: amt("500000"),
+            pricing: PricingPolicy {
+                rule: PricingRule::Midpoint,
+                reference_band_bps: None,
+                pair_band_bps: BTreeMap::new(),
+            },
+            asset_scales: None,
         },
         intents: vec![
             NormalizedIntent {
@@ -19,16 +85,22 @@ fn request_template() -> ConfidentialMatchRequest {
                 signer: "0xabc".to_string(),
                 asset_pair: "ETH/USDC".to_string(),
                 side: Side::Buy,
-                size: 5.0,
-                limit_price: 3020.0,
+                size: amt("5.0"),
+                limit_price: amt("3020.0"),
+                submitted_at: 1_750_000_000,
+                expiry: NOW + 3600,
+                counterparty_constraints: open_constraints(),
             },
             NormalizedIntent {
                 intent_id: "intent-sell".to_string(),
                 signer: "0xdef".to_string(),
                 asset_pair: "ETH/USDC".to_string(),
                 side: Side::Sell,
-                size: 4.0,
-                limit_price: 3000.0,
+                size: amt("4.0"),
+                limit_price: amt("3000.0"),
+                submitted_at: 1_750_000_001,
+                expiry: NOW + 3600,
+                counterparty_constraints: open_constraints(),
             },
         ],
         external_signals: ExternalSignals {
@@ -36,6 +108,8 @@ fn request_template() -> ConfidentialMatchRequest {
             compliance_passed: true,
             risk_score: 42,
             attestation_payload: "provider=risk_api;result=ok".to_string(),
+            fx_quote: None,
+            fx_quotes: Default::default(),
         },
     }
 }
@@ -48,10 +122,96 @@ fn confidential_match_success() {
     assert_eq!(out.match_decision, MatchDecision::Accept);
     assert!(out.policy_check_result.passed);
     assert_eq!(out.policy_check_result.policy_version, 1);
-    assert_eq!(out.private_settlement_params.execution_size, 4.0);
+    assert_eq!(out.private_settlement_params.len(), 1);
+    assert_eq!(out.private_settlement_params[0].execution_size, amt("4"));
+    assert_eq!(out.residual_intents.len(), 1);
+    assert_eq!(out.residual_intents[0].intent_id, "intent-buy");
+    assert_eq!(out.residual_intents[0].remaining_size, amt("1"));
     assert!(!out.compliance_attestation_hash.is_empty());
 }
 
+#[test]
+fn confidential_match_fills_multiple_intents_with_partials() {
+    let mut req = request_template();
+    req.intents = vec![
+        intent("buy-a", Side::Buy, "3", "3010", 10),
+        intent("buy-b", Side::Buy, "4", "3030", 11),
+        intent("sell-a", Side::Sell, "2", "3000", 12),
+        intent("sell-b", Side::Sell, "6", "3005", 13),
+    ];
+
+    let out = process_confidential_match(req).expect("should match");
+    let fills: Vec<(&str, &str, Amount)> = out
+        .private_settlement_params
+        .iter()
+        .map(|f| {
+            (
+                f.buy_intent_id.as_str(),
+                f.sell_intent_id.as_str(),
+                f.execution_size,
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        fills,
+        vec![
+            ("buy-b", "sell-a", amt("2")),
+            ("buy-b", "sell-b", amt("2")),
+            ("buy-a", "sell-b", amt("3")),
+        ]
+    );
+    assert_eq!(out.residual_intents.len(), 1);
+    assert_eq!(out.residual_intents[0].intent_id, "sell-b");
+    assert_eq!(out.residual_intents[0].filled_size, amt("5"));
+    assert_eq!(out.residual_intents[0].remaining_size, amt("1"));
+}
+
+#[test]
+fn confidential_match_uses_time_priority_at_equal_price() {
+    let mut req = request_template();
+    req.intents = vec![
+        intent("sell-late", Side::Sell, "1", "3000", 20),
+        intent("sell-early", Side::Sell, "1", "3000", 10),
+        intent("buy", Side::Buy, "1", "3000", 30),
+    ];
+
+    let out = process_confidential_match(req).expect("should match");
+    assert_eq!(out.private_settlement_params.len(), 1);
+    assert_eq!(
+        out.private_settlement_params[0].sell_intent_id,
+        "sell-early"
+    );
+    assert_eq!(out.residual_intents[0].intent_id, "sell-late");
+}
+
+#[test]
+fn confidential_match_keeps_asset_pair_books_separate() {
+    let mut req = request_template();
+    let mut btc_buy = intent("btc-buy", Side::Buy, "1", "65000", 1);
+    btc_buy.asset_pair = "BTC/USDC".to_string();
+    req.intents = vec![
+        btc_buy,
+        intent("eth-sell", Side::Sell, "1", "3000", 2),
+        intent("eth-buy", Side::Buy, "1", "3100", 3),
+    ];
+
+    let out = process_confidential_match(req).expect("should match");
+    assert_eq!(out.private_settlement_params.len(), 1);
+    assert_eq!(out.private_settlement_params[0].asset_pair, "ETH/USDC");
+    assert_eq!(out.residual_intents.len(), 1);
+    assert_eq!(out.residual_intents[0].intent_id, "btc-buy");
+}
+
+#[test]
+fn confidential_match_rejects_duplicate_intent_ids() {
+    let mut req = request_template();
+    req.intents[1].intent_id = req.intents[0].intent_id.clone();
+
+    let err = process_confidential_match(req).expect_err("must fail");
+    assert!(matches!(err, MatchError::InvalidRequest(_)));
+}
+
 #[test]
 fn confidential_match_fails_when_api_unavailable() {
     let mut req = request_template();
@@ -72,21 +232,339 @@ fn confidential_match_fails_when_policy_mismatch() {
 }
 
 #[test]
-fn confidential_match_fails_on_risk_threshold() {
+fn confidential_match_rejects_on_risk_threshold() {
     let mut req = request_template();
     req.external_signals.risk_score = 99;
     req.policy.max_risk_score = 70;
 
-    let err = process_confidential_match(req).expect_err("must fail");
-    assert!(matches!(err, MatchError::RiskThresholdFail { .. }));
+    let reason = expect_reject(req, RejectReason::RiskThresholdFail);
+    assert!(reason.contains("risk_score=99"));
+}
+
+#[test]
+fn confidential_match_rejects_on_compliance_failure() {
+    let mut req = request_template();
+    req.external_signals.compliance_passed = false;
+
+    expect_reject(req, RejectReason::ComplianceFail);
+}
+
+#[test]
+fn confidential_match_rejects_on_no_match() {
+    let mut req = request_template();
+    req.intents[0].limit_price = amt("2900.0");
+    req.intents[1].limit_price = amt("3000.0");
+
+    expect_reject(req, RejectReason::NoMatch);
+}
+
+#[test]
+fn confidential_match_rejects_fill_above_notional_cap() {
+    let mut req = request_template();
+    req.policy.max_notional = amt("10000");
+
+    let reason = expect_reject(req, RejectReason::NotionalCapExceeded);
+    assert!(reason.contains("12040.000000"));
 }
 
 #[test]
-fn confidential_match_fails_on_no_match() {
+fn confidential_match_leaves_only_the_capped_fill_unfilled() {
     let mut req = request_template();
-    req.intents[0].limit_price = 2900.0;
-    req.intents[1].limit_price = 3000.0;
+    req.policy.max_notional = amt("10000");
+    req.intents = vec![
+        intent("buy", Side::Buy, "4", "3020", 1),
+        intent("big-sell", Side::Sell, "4", "3000", 2),
+        intent("small-buy", Side::Buy, "1", "3010", 3),
+    ];
+
+    let out = process_confidential_match(req).expect("second fill fits the cap");
+    assert_eq!(out.match_decision, MatchDecision::Accept);
+    assert_eq!(out.private_settlement_params.len(), 1);
+    let fill = &out.private_settlement_params[0];
+    assert_eq!(fill.buy_intent_id, "small-buy");
+    assert_eq!(fill.notional, amt("3005").checked_mul(&amt("1")).unwrap());
+
+    let buy = out
+        .residual_intents
+        .iter()
+        .find(|r| r.intent_id == "buy")
+        .expect("capped buy is a residual");
+    assert!(buy.filled_size.is_zero());
+    let sell = out
+        .residual_intents
+        .iter()
+        .find(|r| r.intent_id == "big-sell")
+        .expect("partly filled sell");
+    assert_eq!(sell.remaining_size.to_string(), "3.0000");
+}
+
+#[test]
+fn confidential_match_reject_response_serializes_reason_code() {
+    let mut req = request_template();
+    req.external_signals.compliance_passed = false;
+
+    let out = process_confidential_match(req).expect("reject");
+    let json = serde_json::to_value(&out).expect("serialize");
+    assert_eq!(json["match_decision"], "reject");
+    assert_eq!(json["reject_reason"], "COMPLIANCE_FAIL");
+    assert_eq!(json["policy_check_result"]["passed"], false);
+}
+
+#[test]
+fn confidential_match_settles_with_exact_fixed_point_notional() {
+    let mut req = request_template();
+    req.intents = vec![
+        intent("buy", Side::Buy, "0.3", "3000.03", 1),
+        intent("sell", Side::Sell, "0.3", "3000.00", 2),
+    ];
+
+    let out = process_confidential_match(req).expect("should match");
+    let fill = &out.private_settlement_params[0];
+    assert_eq!(fill.execution_size.to_string(), "0.3000");
+    assert_eq!(fill.execution_price.to_string(), "3000.01");
+    assert_eq!(fill.notional.to_string(), "900.003000");
+    assert_eq!(
+        fill.notional.units(),
+        fill.execution_size.units() * fill.execution_price.units()
+    );
+}
+
+#[test]
+fn confidential_match_rejects_precision_finer_than_asset_scale() {
+    let mut req = request_template();
+    req.intents[0].limit_price = amt("3020.001");
 
     let err = process_confidential_match(req).expect_err("must fail");
-    assert!(matches!(err, MatchError::NoMatch));
+    assert!(matches!(err, MatchError::InvalidRequest(_)));
+}
+
+#[test]
+fn confidential_match_takes_asset_scales_from_policy() {
+    let mut req = request_template();
+    for intent in &mut req.intents {
+        intent.asset_pair = "SOL/USDC".to_string();
+    }
+
+    let err = process_confidential_match(req.clone()).expect_err("no scale for SOL");
+    assert!(matches!(err, MatchError::InvalidRequest(_)));
+
+    req.policy.asset_scales = Some([("SOL".to_string(), 39)].into());
+    let err = process_confidential_match(req.clone()).expect_err("scale too large");
+    assert!(matches!(err, MatchError::InvalidRequest(_)));
+
+    req.policy.asset_scales = Some([("sol".to_string(), 3)].into());
+    let out = process_confidential_match(req).expect("should match");
+    let fill = &out.private_settlement_params[0];
+    assert_eq!(fill.asset_pair, "SOL/USDC");
+    assert_eq!(fill.execution_size.to_string(), "4.000");
+    assert_eq!(fill.execution_price.to_string(), "3010.00");
+}
+
+#[test]
+fn confidential_match_prices_at_maker_or_taker_limit() {
+    let mut req = request_template();
+    req.policy.pricing.rule = PricingRule::MakerPrice;
+    let out = process_confidential_match(req).expect("should match");
+    let fill = &out.private_settlement_params[0];
+    assert_eq!(fill.execution_price, amt("3020"));
+    assert_eq!(fill.pricing.maker_intent_id, "intent-buy");
+
+    let mut req = request_template();
+    req.policy.pricing.rule = PricingRule::TakerPrice;
+    let out = process_confidential_match(req).expect("should match");
+    let fill = &out.private_settlement_params[0];
+    assert_eq!(fill.execution_price, amt("3000"));
+    assert_eq!(fill.pricing.rule, PricingRule::TakerPrice);
+}
+
+#[test]
+fn confidential_match_prices_at_reference_and_records_it() {
+    let mut req = request_template();
+    req.policy.pricing.rule = PricingRule::ReferencePrice;
+    req.external_signals.fx_quote = Some(fx_quote(3012.346));
+
+    let out = process_confidential_match(req).expect("should match");
+    let fill = &out.private_settlement_params[0];
+    assert_eq!(fill.execution_price.to_string(), "3012.35");
+    assert_eq!(fill.pricing.reference_price, Some(amt("3012.35")));
+    assert_eq!(
+        fill.pricing.reference_provider.as_deref(),
+        Some("frankfurter")
+    );
+    assert_eq!(fill.pricing.buy_limit_price, amt("3020"));
+    assert_eq!(fill.pricing.sell_limit_price, amt("3000"));
+}
+
+#[test]
+fn confidential_match_skips_pairs_that_do_not_bracket_reference() {
+    let mut req = request_template();
+    req.policy.pricing.rule = PricingRule::ReferencePrice;
+    req.external_signals.fx_quote = Some(fx_quote(3050.0));
+
+    expect_reject(req, RejectReason::NoMatch);
+}
+
+#[test]
+fn confidential_match_requires_reference_for_reference_pricing() {
+    let mut req = request_template();
+    req.policy.pricing.rule = PricingRule::ReferencePrice;
+
+    let reason = expect_reject(req, RejectReason::ReferencePriceUnavailable);
+    assert!(reason.contains("ETH/USDC"), "{reason}");
+}
+
+#[test]
+fn confidential_match_leaves_only_the_unquoted_book_unfilled() {
+    let mut req = request_template();
+    req.policy.pricing.rule = PricingRule::ReferencePrice;
+    req.external_signals
+        .fx_quotes
+        .insert("eth/usdc".to_string(), fx_quote(3010.0));
+    let mut btc_buy = intent("btc-buy", Side::Buy, "1", "65000", 3);
+    btc_buy.asset_pair = "BTC/USDC".to_string();
+    let mut btc_sell = intent("btc-sell", Side::Sell, "1", "64000", 4);
+    btc_sell.asset_pair = "BTC/USDC".to_string();
+    req.intents.extend([btc_buy, btc_sell]);
+
+    let out = process_confidential_match(req.clone()).expect("ETH/USDC is quoted");
+    assert_eq!(out.match_decision, MatchDecision::Accept);
+    assert_eq!(out.private_settlement_params.len(), 1);
+    assert_eq!(out.private_settlement_params[0].asset_pair, "ETH/USDC");
+    assert_eq!(
+        out.private_settlement_params[0].execution_price,
+        amt("3010")
+    );
+    let unfilled: Vec<_> = out
+        .residual_intents
+        .iter()
+        .filter(|r| r.asset_pair == "BTC/USDC")
+        .collect();
+    assert_eq!(unfilled.len(), 2);
+    assert!(unfilled.iter().all(|r| r.filled_size.is_zero()));
+
+    req.external_signals.fx_quotes.clear();
+    // No book is quoted, so the run is rejected rather than aborted.
+    expect_reject(req, RejectReason::ReferencePriceUnavailable);
+}
+
+#[test]
+fn confidential_match_defaults_pricing_for_older_inputs() {
+    let mut raw = serde_json::json!({
+        "workflow_run_id": "run-legacy",
+        "current_unix_ts": NOW,
+        "policy": {
+            "policy_version": 1,
+            "expected_policy_version": 1,
+            "max_risk_score": 70,
+            "max_notional": "500000"
+        },
+        "intents": request_template().intents,
+        "external_signals": {
+            "api_available": true,
+            "compliance_passed": true,
+            "risk_score": 42,
+            "attestation_payload": "provider=risk_api;result=ok"
+        }
+    });
+    let req: ConfidentialMatchRequest = serde_json::from_value(raw.clone()).expect("parse");
+    assert_eq!(req.policy.pricing.rule, PricingRule::Midpoint);
+    assert_eq!(req.policy.pricing.reference_band_bps, None);
+
+    raw["policy"]["pricing"] = serde_json::Value::Null;
+    let req: ConfidentialMatchRequest = serde_json::from_value(raw).expect("parse null pricing");
+    let out = process_confidential_match(req).expect("should match");
+    assert_eq!(
+        out.private_settlement_params[0].execution_price,
+        amt("3010")
+    );
+}
+
+#[test]
+fn confidential_match_enforces_reference_band() {
+    let mut req = request_template();
+    req.policy.pricing.reference_band_bps = Some(10);
+    req.external_signals.fx_quote = Some(fx_quote(3010.0));
+    process_confidential_match(req).expect("midpoint sits on the reference");
+
+    let mut req = request_template();
+    req.policy.pricing.reference_band_bps = Some(10);
+    req.external_signals.fx_quote = Some(fx_quote(2990.0));
+    let reason = expect_reject(req, RejectReason::PriceOutOfBand);
+    assert!(reason.contains("10bps"));
+}
+
+#[test]
+fn confidential_match_prefers_the_pair_band_over_the_default() {
+    let mut req = request_template();
+    req.policy.pricing.reference_band_bps = Some(10);
+    req.policy.pricing.pair_band_bps = [("eth/usdc".to_string(), 100)].into();
+    req.external_signals.fx_quote = Some(fx_quote(2990.0));
+    let out = process_confidential_match(req).expect("within the governed 100bps band");
+    assert_eq!(
+        out.private_settlement_params[0].pricing.reference_band_bps,
+        Some(100)
+    );
+
+    let mut req = request_template();
+    req.policy.pricing.reference_band_bps = Some(100);
+    req.policy.pricing.pair_band_bps = [("ETH/USDC".to_string(), 10)].into();
+    req.external_signals.fx_quote = Some(fx_quote(2990.0));
+    let reason = expect_reject(req, RejectReason::PriceOutOfBand);
+    assert!(reason.contains("10bps"));
+}
+
+#[test]
+fn confidential_match_rejects_expired_intent() {
+    let mut req = request_template();
+    req.intents[1].expiry = NOW;
+
+    let reason = expect_reject(req, RejectReason::IntentExpired);
+    assert!(reason.contains("intent-sell"));
+}
+
+#[test]
+fn confidential_match_enforces_counterparty_deny_list() {
+    let mut req = request_template();
+    req.intents[0].counterparty_constraints.deny_list = vec!["0xDEF".to_string()];
+
+    let reason = expect_reject(req, RejectReason::CounterpartyDenied);
+    assert!(reason.contains("0xdef") && reason.contains("intent-buy"));
+}
+
+#[test]
+fn confidential_match_enforces_counterparty_allow_list() {
+    let mut req = request_template();
+    req.intents[1].counterparty_constraints.allow_list = vec!["0x999".to_string()];
+
+    let reason = expect_reject(req, RejectReason::CounterpartyNotAllowed);
+    assert!(reason.contains("0xabc") && reason.contains("intent-sell"));
+
+    let mut req = request_template();
+    req.intents[1].counterparty_constraints.allow_list = vec!["0xabc".to_string()];
+    process_confidential_match(req).expect("allow-listed counterparty should match");
+}
+
+#[test]
+fn confidential_match_skips_blocked_counterparty_for_next_best() {
+    let mut req = request_template();
+    let mut buy = intent("buy", Side::Buy, "2", "3020", 10);
+    buy.counterparty_constraints.deny_list = vec!["0xsell-best".to_string()];
+    let mut stale = intent("sell-stale", Side::Sell, "2", "2990", 11);
+    stale.expiry = NOW - 1;
+    req.intents = vec![
+        buy,
+        stale,
+        intent("sell-best", Side::Sell, "2", "3000", 12),
+        intent("sell-next", Side::Sell, "2", "3010", 13),
+    ];
+
+    let out = process_confidential_match(req).expect("should match");
+    assert_eq!(out.private_settlement_params.len(), 1);
+    assert_eq!(out.private_settlement_params[0].sell_intent_id, "sell-next");
+    let residual_ids: Vec<&str> = out
+        .residual_intents
+        .iter()
+        .map(|r| r.intent_id.as_str())
+        .collect();
+    assert_eq!(residual_ids, vec!["sell-stale", "sell-best"]);
 }
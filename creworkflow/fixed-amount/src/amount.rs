@@ -0,0 +1,304 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::de::{self, Deserializer, Visitor};
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::errors::AmountError;
+
+/// Largest scale whose power of ten still fits in a `u128`.
+pub const MAX_SCALE: u32 = 38;
+
+const BPS_DENOM: u128 = 10_000;
+
+/// Non-negative fixed-point decimal: `units / 10^scale`.
+///
+/// Serialized as a decimal string with exactly `scale` fractional digits so the
+/// scale survives every JSON hop between workflow steps. JSON numbers are
+/// rejected on purpose: they would already have gone through `f64`.
+/// Equality and ordering compare numeric value, so `1.0 == 1.00`.
+#[derive(Debug, Clone, Copy)]
+pub struct Amount {
+    units: u128,
+    scale: u32,
+}
+
+impl Amount {
+    pub fn from_units(units: u128, scale: u32) -> Result<Self, AmountError> {
+        if scale > MAX_SCALE {
+            return Err(AmountError::ScaleTooLarge {
+                scale,
+                max: MAX_SCALE,
+            });
+        }
+        Ok(Self { units, scale })
+    }
+
+    pub fn zero(scale: u32) -> Result<Self, AmountError> {
+        Self::from_units(0, scale)
+    }
+
+    /// Parses `input` and rescales it to `scale`, failing instead of rounding
+    /// when the input carries more significant fractional digits.
+    pub fn parse_with_scale(input: &str, scale: u32) -> Result<Self, AmountError> {
+        input.parse::<Amount>()?.to_scale(scale)
+    }
+
+    pub fn units(&self) -> u128 {
+        self.units
+    }
+
+    pub fn scale(&self) -> u32 {
+        self.scale
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.units == 0
+    }
+
+    /// Exact rescale. Widening always succeeds (barring overflow); narrowing
+    /// only succeeds when the dropped digits are all zero.
+    pub fn to_scale(&self, scale: u32) -> Result<Self, AmountError> {
+        if scale > MAX_SCALE {
+            return Err(AmountError::ScaleTooLarge {
+                scale,
+                max: MAX_SCALE,
+            });
+        }
+        match scale.cmp(&self.scale) {
+            Ordering::Equal => Ok(*self),
+            Ordering::Greater => {
+                let factor = pow10(scale - self.scale);
+                let units = self
+                    .units
+                    .checked_mul(factor)
+                    .ok_or(AmountError::Overflow)?;
+                Ok(Self { units, scale })
+            }
+            Ordering::Less => {
+                let factor = pow10(self.scale - scale);
+                if !self.units.is_multiple_of(factor) {
+                    return Err(AmountError::PrecisionLoss {
+                        value: self.to_string(),
+                        scale,
+                    });
+                }
+                Ok(Self {
+                    units: self.units / factor,
+                    scale,
+                })
+            }
+        }
+    }
+
+    pub fn checked_add(&self, other: &Amount) -> Result<Self, AmountError> {
+        self.ensure_same_scale(other)?;
+        let units = self
+            .units
+            .checked_add(other.units)
+            .ok_or(AmountError::Overflow)?;
+        Ok(Self {
+            units,
+            scale: self.scale,
+        })
+    }
+
+    pub fn checked_sub(&self, other: &Amount) -> Result<Self, AmountError> {
+        self.ensure_same_scale(other)?;
+        let units = self
+            .units
+            .checked_sub(other.units)
+            .ok_or(AmountError::Underflow)?;
+        Ok(Self {
+            units,
+            scale: self.scale,
+        })
+    }
+
+    /// Exact product; the result scale is the sum of both scales, which is
+    /// what keeps `notional == size * price` an integer identity.
+    pub fn checked_mul(&self, other: &Amount) -> Result<Self, AmountError> {
+        let units = self
+            .units
+            .checked_mul(other.units)
+            .ok_or(AmountError::Overflow)?;
+        Self::from_units(units, self.scale + other.scale)
+    }
+
+    /// Midpoint of two same-scale amounts, rounded down to that scale. For
+    /// `a >= b` the result always stays within `[b, a]`.
+    pub fn midpoint_floor(&self, other: &Amount) -> Result<Self, AmountError> {
+        self.ensure_same_scale(other)?;
+        let sum = self
+            .units
+            .checked_add(other.units)
+            .ok_or(AmountError::Overflow)?;
+        Ok(Self {
+            units: sum / 2,
+            scale: self.scale,
+        })
+    }
+
+    /// Whether `self` deviates from `reference` by at most `band_bps` basis
+    /// points of `reference`. Both sides are widened to the larger scale and
+    /// compared as `|self - reference| * 10_000 <= reference * band_bps`, so
+    /// no rounding happens anywhere.
+    pub fn within_bps_of(&self, reference: &Amount, band_bps: u32) -> Result<bool, AmountError> {
+        let scale = self.scale.max(reference.scale);
+        let value = self.to_scale(scale)?.units;
+        let reference = reference.to_scale(scale)?.units;
+        let lhs = value
+            .abs_diff(reference)
+            .checked_mul(BPS_DENOM)
+            .ok_or(AmountError::Overflow)?;
+        let rhs = reference
+            .checked_mul(u128::from(band_bps))
+            .ok_or(AmountError::Overflow)?;
+        Ok(lhs <= rhs)
+    }
+
+    pub fn min(self, other: Amount) -> Amount {
+        if other < self {
+            other
+        } else {
+            self
+        }
+    }
+
+    fn ensure_same_scale(&self, other: &Amount) -> Result<(), AmountError> {
+        if self.scale != other.scale {
+            return Err(AmountError::ScaleMismatch {
+                left: self.scale,
+                right: other.scale,
+            });
+        }
+        Ok(())
+    }
+
+    fn split(&self) -> (u128, u128) {
+        let factor = pow10(self.scale);
+        (self.units / factor, self.units % factor)
+    }
+}
+
+/// Whole units at scale 0.
+impl From<u64> for Amount {
+    fn from(units: u64) -> Self {
+        Self {
+            units: u128::from(units),
+            scale: 0,
+        }
+    }
+}
+
+impl PartialEq for Amount {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Amount {}
+
+impl Ord for Amount {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (int_a, frac_a) = self.split();
+        let (int_b, frac_b) = other.split();
+        let scale = self.scale.max(other.scale);
+        // Fractions are < 10^scale <= 10^38, so padding cannot overflow.
+        let frac_a = frac_a * pow10(scale - self.scale);
+        let frac_b = frac_b * pow10(scale - other.scale);
+        int_a.cmp(&int_b).then(frac_a.cmp(&frac_b))
+    }
+}
+
+impl PartialOrd for Amount {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (int_part, frac_part) = self.split();
+        if self.scale == 0 {
+            write!(f, "{int_part}")
+        } else {
+            write!(
+                f,
+                "{int_part}.{frac_part:0width$}",
+                width = self.scale as usize
+            )
+        }
+    }
+}
+
+impl FromStr for Amount {
+    type Err = AmountError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let trimmed = input.trim();
+        let invalid = || AmountError::InvalidDecimal(input.to_string());
+
+        let (int_part, frac_part) = match trimmed.split_once('.') {
+            Some((int_part, frac_part)) => (int_part, frac_part),
+            None => (trimmed, ""),
+        };
+        if int_part.is_empty() || (trimmed.contains('.') && frac_part.is_empty()) {
+            return Err(invalid());
+        }
+        if !int_part.bytes().all(|b| b.is_ascii_digit())
+            || !frac_part.bytes().all(|b| b.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+
+        let scale = u32::try_from(frac_part.len()).map_err(|_| invalid())?;
+        if scale > MAX_SCALE {
+            return Err(AmountError::ScaleTooLarge {
+                scale,
+                max: MAX_SCALE,
+            });
+        }
+
+        let mut units: u128 = 0;
+        for b in int_part.bytes().chain(frac_part.bytes()) {
+            units = units
+                .checked_mul(10)
+                .and_then(|v| v.checked_add(u128::from(b - b'0')))
+                .ok_or(AmountError::Overflow)?;
+        }
+
+        Ok(Self { units, scale })
+    }
+}
+
+impl Serialize for Amount {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Amount {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct AmountVisitor;
+
+        impl Visitor<'_> for AmountVisitor {
+            type Value = Amount;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a non-negative decimal string")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Amount, E> {
+                v.parse::<Amount>().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(AmountVisitor)
+    }
+}
+
+fn pow10(exp: u32) -> u128 {
+    10u128.pow(exp)
+}
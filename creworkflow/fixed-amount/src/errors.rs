@@ -0,0 +1,28 @@
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum AmountError {
+    #[error("invalid decimal `{0}`")]
+    InvalidDecimal(String),
+
+    #[error("scale {scale} exceeds maximum of {max}")]
+    ScaleTooLarge { scale: u32, max: u32 },
+
+    #[error("precision loss rescaling `{value}` to scale {scale}")]
+    PrecisionLoss { value: String, scale: u32 },
+
+    #[error("scale mismatch: left={left}, right={right}")]
+    ScaleMismatch { left: u32, right: u32 },
+
+    #[error("amount overflow")]
+    Overflow,
+
+    #[error("amount underflow")]
+    Underflow,
+
+    #[error("unknown asset `{0}`")]
+    UnknownAsset(String),
+
+    #[error("invalid asset pair `{0}`")]
+    InvalidAssetPair(String),
+}
@@ -0,0 +1,7 @@
+pub mod amount;
+pub mod errors;
+pub mod scale;
+
+pub use amount::Amount;
+pub use errors::AmountError;
+pub use scale::{AssetScales, PairScale};
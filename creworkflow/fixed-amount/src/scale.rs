@@ -0,0 +1,92 @@
+use std::collections::BTreeMap;
+
+use crate::amount::MAX_SCALE;
+use crate::errors::AmountError;
+
+/// Default decimal scales per asset symbol. Base-asset scale drives size
+/// precision and quote-asset scale drives price precision; both are kept small
+/// enough that `size * price` fits the settlement circuit's 64-bit range checks.
+pub const DEFAULT_ASSET_SCALES: &[(&str, u32)] = &[
+    ("BTC", 6),
+    ("ETH", 4),
+    ("EUR", 2),
+    ("USD", 2),
+    ("USDC", 2),
+    ("USDT", 2),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PairScale {
+    pub size_scale: u32,
+    pub price_scale: u32,
+}
+
+impl PairScale {
+    pub fn notional_scale(&self) -> u32 {
+        self.size_scale + self.price_scale
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AssetScales {
+    scales: BTreeMap<String, u32>,
+}
+
+impl Default for AssetScales {
+    fn default() -> Self {
+        Self {
+            scales: DEFAULT_ASSET_SCALES
+                .iter()
+                .map(|(asset, scale)| (asset.to_string(), *scale))
+                .collect(),
+        }
+    }
+}
+
+impl AssetScales {
+    pub fn with_asset(mut self, asset: &str, scale: u32) -> Self {
+        self.scales.insert(asset.to_ascii_uppercase(), scale);
+        self
+    }
+
+    /// Layers configured scales over the current ones, e.g. a policy's
+    /// `asset_scales` over `DEFAULT_ASSET_SCALES`.
+    pub fn with_overrides(
+        mut self,
+        overrides: &BTreeMap<String, u32>,
+    ) -> Result<Self, AmountError> {
+        for (asset, scale) in overrides {
+            if asset.trim().is_empty() {
+                return Err(AmountError::UnknownAsset(asset.clone()));
+            }
+            if *scale > MAX_SCALE {
+                return Err(AmountError::ScaleTooLarge {
+                    scale: *scale,
+                    max: MAX_SCALE,
+                });
+            }
+            self = self.with_asset(asset.trim(), *scale);
+        }
+        Ok(self)
+    }
+
+    pub fn asset_scale(&self, asset: &str) -> Result<u32, AmountError> {
+        self.scales
+            .get(&asset.to_ascii_uppercase())
+            .copied()
+            .ok_or_else(|| AmountError::UnknownAsset(asset.to_string()))
+    }
+
+    /// Resolves `BASE/QUOTE` into the size and price scales used for matching.
+    pub fn pair_scale(&self, asset_pair: &str) -> Result<PairScale, AmountError> {
+        let (base, quote) = asset_pair
+            .split_once('/')
+            .filter(|(base, quote)| !base.is_empty() && !quote.is_empty())
+            .ok_or_else(|| AmountError::InvalidAssetPair(asset_pair.to_string()))?;
+
+        Ok(PairScale {
+            size_scale: self.asset_scale(base)?,
+            price_scale: self.asset_scale(quote)?,
+        })
+    }
+}
@@ -0,0 +1,128 @@
+use fixed_amount::{Amount, AmountError, AssetScales};
+
+fn amount(s: &str) -> Amount {
+    s.parse().expect("valid decimal")
+}
+
+#[test]
+fn parse_preserves_scale_and_round_trips() {
+    let a = amount("3025.10");
+    assert_eq!(a.units(), 302_510);
+    assert_eq!(a.scale(), 2);
+    assert_eq!(a.to_string(), "3025.10");
+    assert_eq!(amount("7").to_string(), "7");
+}
+
+#[test]
+fn parse_rejects_signs_exponents_and_garbage() {
+    for bad in ["", "-1", "+1", "1e3", ".5", "5.", "1.2.3", "abc"] {
+        assert!(
+            matches!(bad.parse::<Amount>(), Err(AmountError::InvalidDecimal(_))),
+            "{bad} should be rejected"
+        );
+    }
+}
+
+#[test]
+fn rescale_is_exact_or_fails() {
+    assert_eq!(
+        Amount::parse_with_scale("4.5", 4).unwrap().to_string(),
+        "4.5000"
+    );
+    assert_eq!(
+        Amount::parse_with_scale("4.5000", 1).unwrap().to_string(),
+        "4.5"
+    );
+    assert!(matches!(
+        Amount::parse_with_scale("4.55", 1),
+        Err(AmountError::PrecisionLoss { .. })
+    ));
+}
+
+#[test]
+fn multiplication_is_exact_where_f64_is_not() {
+    let size = amount("0.1");
+    let price = amount("0.2");
+    let notional = size.checked_mul(&price).unwrap();
+    assert_eq!(notional, amount("0.02"));
+    assert_eq!(notional.units(), size.units() * price.units());
+    assert_ne!(0.1_f64 * 0.2_f64, 0.02_f64);
+}
+
+#[test]
+fn comparison_is_by_value_across_scales() {
+    assert_eq!(amount("1.0"), amount("1.00"));
+    assert!(amount("1.05") > amount("1.0"));
+    assert!(amount("2") > amount("1.99999"));
+}
+
+#[test]
+fn midpoint_rounds_down_within_bounds() {
+    let mid = amount("3000.01")
+        .midpoint_floor(&amount("3000.00"))
+        .unwrap();
+    assert_eq!(mid, amount("3000.00"));
+    assert!(matches!(
+        amount("1.0").midpoint_floor(&amount("1.00")),
+        Err(AmountError::ScaleMismatch { .. })
+    ));
+}
+
+#[test]
+fn serde_uses_strings_and_rejects_numbers() {
+    let a: Amount = serde_json::from_str("\"12.340\"").unwrap();
+    assert_eq!(serde_json::to_string(&a).unwrap(), "\"12.340\"");
+    assert!(serde_json::from_str::<Amount>("12.34").is_err());
+}
+
+#[test]
+fn pair_scale_resolves_base_and_quote() {
+    let scales = AssetScales::default();
+    let pair = scales.pair_scale("ETH/USDC").unwrap();
+    assert_eq!(pair.size_scale, 4);
+    assert_eq!(pair.price_scale, 2);
+    assert_eq!(pair.notional_scale(), 6);
+
+    assert!(matches!(
+        scales.pair_scale("DOGE/USDC"),
+        Err(AmountError::UnknownAsset(_))
+    ));
+    assert!(matches!(
+        scales.pair_scale("ETHUSDC"),
+        Err(AmountError::InvalidAssetPair(_))
+    ));
+    assert_eq!(
+        scales
+            .with_asset("doge", 0)
+            .pair_scale("DOGE/USDC")
+            .unwrap()
+            .size_scale,
+        0
+    );
+}
+
+#[test]
+fn asset_scale_overrides_layer_over_defaults() {
+    let overrides = [("sol".to_string(), 3), ("USDC".to_string(), 6)].into();
+    let scales = AssetScales::default().with_overrides(&overrides).unwrap();
+    let pair = scales.pair_scale("SOL/USDC").unwrap();
+    assert_eq!((pair.size_scale, pair.price_scale), (3, 6));
+    assert_eq!(scales.asset_scale("ETH").unwrap(), 4);
+
+    assert!(matches!(
+        AssetScales::default().with_overrides(&[("SOL".to_string(), 39)].into()),
+        Err(AmountError::ScaleTooLarge { .. })
+    ));
+}
+
+#[test]
+fn bps_band_is_exact_at_the_boundary_and_across_scales() {
+    let reference = amount("3000.00");
+    assert!(amount("3030.00").within_bps_of(&reference, 100).unwrap());
+    assert!(amount("2970.00").within_bps_of(&reference, 100).unwrap());
+    assert!(!amount("3030.01").within_bps_of(&reference, 100).unwrap());
+    assert!(!amount("2969.999").within_bps_of(&reference, 100).unwrap());
+    assert!(amount("3000").within_bps_of(&reference, 0).unwrap());
+    assert!(!amount("3000.0001").within_bps_of(&reference, 0).unwrap());
+    assert!(Amount::from(3000).within_bps_of(&reference, 0).unwrap());
+}
@@ -1,6 +1,7 @@
 use std::collections::HashSet;
 
 use base64::Engine;
+use fixed_amount::Amount;
 use sha2::{Digest, Sha256};
 use uuid::Uuid;
 
@@ -11,9 +12,9 @@ use crate::models::{
 };
 
 pub fn process_intake(req: IntentIntakeRequest) -> Result<IntentIntakeResponse, IntakeError> {
-    if req.encrypted_intents.len() != 2 {
+    if req.encrypted_intents.len() < 2 {
         return Err(IntakeError::InvalidRequest(
-            "exactly two encrypted intents are required".to_string(),
+            "at least two encrypted intents are required".to_string(),
         ));
     }
     if req.payload_reference.trim().is_empty() {
@@ -23,23 +24,27 @@ pub fn process_intake(req: IntentIntakeRequest) -> Result<IntentIntakeResponse,
     }
 
     let mut known_nonces: HashSet<String> = req.seen_nonces.into_iter().collect();
-    let mut normalized_private_intents = Vec::with_capacity(2);
-    let mut intent_commitment_hashes = Vec::with_capacity(2);
+    let mut normalized_private_intents = Vec::with_capacity(req.encrypted_intents.len());
+    let mut intent_commitment_hashes = Vec::with_capacity(req.encrypted_intents.len());
 
     for envelope in &req.encrypted_intents {
         let payload = decrypt_payload(envelope)?;
         validate_payload(envelope, &payload, req.current_unix_ts, &mut known_nonces)?;
 
+        let size = parse_positive_amount(envelope, "size", &payload.size)?;
+        let limit_price = parse_positive_amount(envelope, "limit_price", &payload.limit_price)?;
+
         let normalized = NormalizedIntent {
             intent_id: envelope.intent_id.clone(),
             signer: envelope.signer.clone(),
             asset_pair: payload.asset_pair,
             side: payload.side,
-            size: payload.size,
-            limit_price: payload.limit_price,
+            size,
+            limit_price,
             expiry: payload.expiry,
             counterparty_constraints: payload.counterparty_constraints,
             nonce: payload.nonce,
+            submitted_at: envelope.submitted_at,
         };
 
         let commitment = compute_intent_commitment(&normalized)?;
@@ -110,6 +115,26 @@ fn validate_payload(
     Ok(())
 }
 
+fn parse_positive_amount(
+    envelope: &EncryptedIntentEnvelope,
+    field: &str,
+    raw: &str,
+) -> Result<Amount, IntakeError> {
+    let amount = raw
+        .parse::<Amount>()
+        .map_err(|e| IntakeError::MalformedPayload {
+            intent_id: envelope.intent_id.clone(),
+            reason: format!("{field}: {e}"),
+        })?;
+    if amount.is_zero() {
+        return Err(IntakeError::MalformedPayload {
+            intent_id: envelope.intent_id.clone(),
+            reason: format!("{field} must be positive"),
+        });
+    }
+    Ok(amount)
+}
+
 fn verify_signature(
     envelope: &EncryptedIntentEnvelope,
     payload: &DecryptedIntentPayload,
@@ -1,3 +1,4 @@
+use fixed_amount::Amount;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Deserialize)]
@@ -48,11 +49,12 @@ pub struct NormalizedIntent {
     pub signer: String,
     pub asset_pair: String,
     pub side: Side,
-    pub size: String,
-    pub limit_price: String,
+    pub size: Amount,
+    pub limit_price: Amount,
     pub expiry: u64,
     pub counterparty_constraints: CounterpartyConstraints,
     pub nonce: String,
+    pub submitted_at: u64,
 }
 
 #[derive(Debug, Clone, Serialize)]
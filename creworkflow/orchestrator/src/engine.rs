@@ -0,0 +1,538 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::errors::EngineError;
+use crate::expr::{self, Scope};
+use crate::journal::JournalStore;
+use crate::spec::{StepSpec, WorkflowSpec};
+use crate::steps::{StepFailure, StepRegistry};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StepStatus {
+    Completed,
+    Skipped,
+    Failed,
+}
+
+impl StepStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StepStatus::Completed => "completed",
+            StepStatus::Skipped => "skipped",
+            StepStatus::Failed => "failed",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+pub enum StepValue {
+    Single(Value),
+    /// One mapped output per `for_each` item, in item order.
+    PerItem(Vec<Value>),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StepState {
+    pub status: StepStatus,
+    pub value: StepValue,
+    pub error: Option<StepFailure>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RunStatus {
+    Completed,
+    /// A step failed and its `on_failure` branch ran to the end.
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StepTrace {
+    pub id: String,
+    pub module: String,
+    pub status: StepStatus,
+    pub iterations: Option<usize>,
+    pub error: Option<StepFailure>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RunRecord {
+    pub workflow: String,
+    pub version: String,
+    pub workflow_run_id: Option<String>,
+    pub status: RunStatus,
+    pub trace: Vec<StepTrace>,
+    pub output: Value,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProgressStatus {
+    Running,
+    /// Stopped on an error; `next` is the step to retry.
+    Halted,
+    Completed,
+    Failed,
+}
+
+/// Checkpointed state of a run: the resolved request, every finished step
+/// and the cursor to continue from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunProgress {
+    pub workflow: String,
+    pub version: String,
+    pub workflow_run_id: Option<String>,
+    pub status: ProgressStatus,
+    pub input: Value,
+    pub states: BTreeMap<String, StepState>,
+    pub trace: Vec<StepTrace>,
+    pub visited: Vec<String>,
+    /// Step to run next; `None` once the run reached the end.
+    pub next: Option<String>,
+    pub inherited: Option<Iteration>,
+    /// Mapped outputs of the `for_each` items of `next` that already ran.
+    /// A resumed step starts after them, so finished items are never re-run.
+    pub completed_items: Vec<Value>,
+    /// Finished items of `for_each` steps dropped when a failed run was
+    /// rewound, keyed by step id; each is picked up when its step runs again.
+    #[serde(default)]
+    pub carried_items: BTreeMap<String, Vec<Value>>,
+    pub error: Option<String>,
+}
+
+/// Executes a [`WorkflowSpec`] by following `on_success`/`on_failure` edges
+/// from the entrypoint and dispatching each step to its registered module.
+pub struct Engine<'a> {
+    spec: &'a WorkflowSpec,
+    registry: &'a StepRegistry,
+}
+
+/// `$item`/`$index` carried into an `on_failure` branch from the failed
+/// `for_each` iteration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Iteration {
+    pub item: Value,
+    pub index: usize,
+}
+
+impl<'a> Engine<'a> {
+    pub fn new(spec: &'a WorkflowSpec, registry: &'a StepRegistry) -> Result<Self, EngineError> {
+        spec.validate()?;
+        if let Some(step) = spec.steps.iter().find(|s| !registry.contains(&s.module)) {
+            return Err(EngineError::UnknownModule {
+                step: step.id.clone(),
+                module: step.module.clone(),
+            });
+        }
+        Ok(Self { spec, registry })
+    }
+
+    /// A failed step without `on_failure` stops the run with
+    /// [`EngineError::StepFailed`]; with one, the branch runs and the record
+    /// is returned with [`RunStatus::Failed`].
+    pub fn run(&self, input: &Value) -> Result<RunRecord, EngineError> {
+        self.drive(&mut self.start(input), None)
+    }
+
+    /// Like [`Engine::run`], but checkpoints to `journal` after every step
+    /// and every `for_each` item once the spec's `run_id` resolves.
+    pub fn run_durable(
+        &self,
+        input: &Value,
+        journal: &dyn JournalStore,
+    ) -> Result<RunRecord, EngineError> {
+        if self.spec.run_id.is_none() {
+            return Err(EngineError::InvalidSpec(
+                "durable runs need a top-level `run_id` expression".to_string(),
+            ));
+        }
+        self.drive(&mut self.start(input), Some(journal))
+    }
+
+    /// Continues a journaled run from its first incomplete step or item.
+    /// A failed run continues from the step that failed, so a transient
+    /// error does not end it for good. A completed run returns its record
+    /// without running anything.
+    pub fn resume(
+        &self,
+        workflow_run_id: &str,
+        journal: &dyn JournalStore,
+    ) -> Result<RunRecord, EngineError> {
+        let mut progress = journal.load(workflow_run_id)?;
+        if progress.workflow != self.spec.name || progress.version != self.spec.version {
+            return Err(EngineError::Journal(format!(
+                "run `{workflow_run_id}` was journaled by {} v{}, not {} v{}",
+                progress.workflow, progress.version, self.spec.name, self.spec.version
+            )));
+        }
+        match progress.status {
+            ProgressStatus::Completed => self.record(&progress),
+            ProgressStatus::Failed => {
+                rewind_to_failed_step(&mut progress)?;
+                self.drive(&mut progress, Some(journal))
+            }
+            ProgressStatus::Running | ProgressStatus::Halted => {
+                progress.status = ProgressStatus::Running;
+                progress.error = None;
+                self.drive(&mut progress, Some(journal))
+            }
+        }
+    }
+
+    fn start(&self, input: &Value) -> RunProgress {
+        RunProgress {
+            workflow: self.spec.name.clone(),
+            version: self.spec.version.clone(),
+            workflow_run_id: None,
+            status: ProgressStatus::Running,
+            input: input.clone(),
+            states: BTreeMap::new(),
+            trace: Vec::new(),
+            visited: Vec::new(),
+            next: Some(self.spec.entrypoint.clone()),
+            inherited: None,
+            completed_items: Vec::new(),
+            carried_items: BTreeMap::new(),
+            error: None,
+        }
+    }
+
+    fn drive(
+        &self,
+        progress: &mut RunProgress,
+        journal: Option<&dyn JournalStore>,
+    ) -> Result<RunRecord, EngineError> {
+        while let Some(id) = progress.next.clone() {
+            if let Err(err) = self.advance(&id, progress, journal) {
+                progress.status = ProgressStatus::Halted;
+                progress.error = Some(err.to_string());
+                self.checkpoint(progress, journal)?;
+                return Err(err);
+            }
+        }
+
+        let record = self.record(progress)?;
+        progress.status = match record.status {
+            RunStatus::Completed => ProgressStatus::Completed,
+            RunStatus::Failed => ProgressStatus::Failed,
+        };
+        self.checkpoint(progress, journal)?;
+        Ok(record)
+    }
+
+    /// Runs step `id` and moves the cursor past it. On error the cursor is
+    /// left on `id` so a resume retries it.
+    fn advance(
+        &self,
+        id: &str,
+        progress: &mut RunProgress,
+        journal: Option<&dyn JournalStore>,
+    ) -> Result<(), EngineError> {
+        if progress.visited.iter().any(|seen| seen == id) {
+            return Err(EngineError::Cycle {
+                step: id.to_string(),
+            });
+        }
+        let step = self
+            .spec
+            .step(id)
+            .ok_or_else(|| EngineError::InvalidSpec(format!("unknown step `{id}`")))?;
+
+        if progress.completed_items.is_empty() {
+            if let Some(items) = progress.carried_items.remove(id) {
+                progress.completed_items = items;
+            }
+        }
+        let outcome = self.run_step(step, progress, journal)?;
+        if let (Some(failure), None) = (&outcome.state.error, &step.on_failure) {
+            return Err(EngineError::StepFailed {
+                step: step.id.clone(),
+                code: failure.code.clone(),
+                message: failure.message.clone(),
+            });
+        }
+
+        progress.trace.push(StepTrace {
+            id: step.id.clone(),
+            module: step.module.clone(),
+            status: outcome.state.status,
+            iterations: match &outcome.state.value {
+                StepValue::PerItem(values) if step.for_each.is_some() => Some(values.len()),
+                _ => None,
+            },
+            error: outcome.state.error.clone(),
+        });
+        if outcome.state.error.is_some() {
+            progress.inherited = outcome.failed_iteration;
+            progress.next = step.on_failure.clone();
+        } else {
+            progress.inherited = None;
+            progress.next = step.on_success.clone();
+        }
+        progress.completed_items.clear();
+        progress.visited.push(step.id.clone());
+        progress.states.insert(step.id.clone(), outcome.state);
+
+        if progress.workflow_run_id.is_none() {
+            progress.workflow_run_id = self.resolve_run_id(progress)?;
+        }
+        self.checkpoint(progress, journal)
+    }
+
+    fn run_step(
+        &self,
+        step: &StepSpec,
+        progress: &mut RunProgress,
+        journal: Option<&dyn JournalStore>,
+    ) -> Result<StepOutcome, EngineError> {
+        let expression_error = |message: String| EngineError::Expression {
+            step: step.id.clone(),
+            message,
+        };
+
+        let items = {
+            let inherited = progress.inherited.as_ref();
+            let scope = Scope {
+                root: &progress.input,
+                current: None,
+                steps: &progress.states,
+                item: inherited.map(|it| &it.item),
+                index: inherited.map(|it| it.index),
+            };
+
+            if let Some(when) = &step.when {
+                if !expr::evaluate_bool(when, &scope).map_err(expression_error)? {
+                    let value = match step.for_each {
+                        Some(_) => StepValue::PerItem(Vec::new()),
+                        None => StepValue::Single(Value::Null),
+                    };
+                    return Ok(StepOutcome::finished(StepStatus::Skipped, value));
+                }
+            }
+
+            let Some(for_each) = &step.for_each else {
+                return match self.invoke(step, &scope).map_err(expression_error)? {
+                    Ok(output) => Ok(StepOutcome::finished(
+                        StepStatus::Completed,
+                        StepValue::Single(output),
+                    )),
+                    Err(failure) => Ok(StepOutcome::failed(
+                        StepValue::Single(Value::Null),
+                        failure,
+                        None,
+                    )),
+                };
+            };
+
+            match expr::evaluate(for_each, &scope).map_err(expression_error)? {
+                Value::Array(items) => items,
+                Value::Null => Vec::new(),
+                other => {
+                    return Err(expression_error(format!(
+                        "for_each must resolve to a list, got {other}"
+                    )))
+                }
+            }
+        };
+
+        let done = progress.completed_items.len();
+        for (index, item) in items.into_iter().enumerate().skip(done) {
+            let result = {
+                let item_scope = Scope {
+                    root: &progress.input,
+                    current: None,
+                    steps: &progress.states,
+                    item: Some(&item),
+                    index: Some(index),
+                };
+                self.invoke(step, &item_scope).map_err(expression_error)?
+            };
+            match result {
+                Ok(output) => {
+                    progress.completed_items.push(output);
+                    self.checkpoint(progress, journal)?;
+                }
+                Err(mut failure) => {
+                    failure.index = Some(index);
+                    return Ok(StepOutcome::failed(
+                        StepValue::PerItem(progress.completed_items.clone()),
+                        failure,
+                        Some(Iteration { item, index }),
+                    ));
+                }
+            }
+        }
+        Ok(StepOutcome::finished(
+            StepStatus::Completed,
+            StepValue::PerItem(progress.completed_items.clone()),
+        ))
+    }
+
+    /// Outer `Err` is a spec/expression problem; inner `Err` is the module
+    /// reporting a step failure.
+    fn invoke(
+        &self,
+        step: &StepSpec,
+        scope: &Scope<'_>,
+    ) -> Result<Result<Value, StepFailure>, String> {
+        let request = expr::resolve_template(&step.input, scope)?;
+        let response = match self.registry.dispatch(&step.module, request) {
+            Ok(response) => response,
+            Err(failure) => return Ok(Err(failure)),
+        };
+
+        let Some(template) = &step.output else {
+            return Ok(Ok(response));
+        };
+        let output_scope = Scope {
+            root: scope.root,
+            current: Some(&response),
+            steps: scope.steps,
+            item: scope.item,
+            index: scope.index,
+        };
+        expr::resolve_template(template, &output_scope).map(Ok)
+    }
+
+    fn resolve_run_id(&self, progress: &RunProgress) -> Result<Option<String>, EngineError> {
+        let Some(run_id) = &self.spec.run_id else {
+            return Ok(None);
+        };
+        let scope = Scope {
+            root: &progress.input,
+            current: None,
+            steps: &progress.states,
+            item: None,
+            index: None,
+        };
+        match expr::evaluate(run_id, &scope) {
+            Ok(Value::String(run_id)) => Ok(Some(run_id)),
+            Ok(_) => Ok(None),
+            Err(message) => Err(EngineError::Expression {
+                step: "run_id".to_string(),
+                message,
+            }),
+        }
+    }
+
+    /// No-op until the run has a `workflow_run_id`: nothing before that
+    /// point has side effects worth resuming.
+    fn checkpoint(
+        &self,
+        progress: &RunProgress,
+        journal: Option<&dyn JournalStore>,
+    ) -> Result<(), EngineError> {
+        match (journal, &progress.workflow_run_id) {
+            (Some(journal), Some(_)) => journal.save(progress),
+            _ => Ok(()),
+        }
+    }
+
+    fn record(&self, progress: &RunProgress) -> Result<RunRecord, EngineError> {
+        let output = match &self.spec.output {
+            Some(template) => {
+                let scope = Scope {
+                    root: &progress.input,
+                    current: None,
+                    steps: &progress.states,
+                    item: None,
+                    index: None,
+                };
+                expr::resolve_template(template, &scope).map_err(|message| {
+                    EngineError::Expression {
+                        step: "output".to_string(),
+                        message,
+                    }
+                })?
+            }
+            None => Value::Null,
+        };
+
+        // A failure without a branch halts the run, so any failed step here
+        // means an `on_failure` branch ran.
+        let failed = progress.states.values().any(|state| state.error.is_some());
+        Ok(RunRecord {
+            workflow: self.spec.name.clone(),
+            version: self.spec.version.clone(),
+            workflow_run_id: progress.workflow_run_id.clone(),
+            status: if failed {
+                RunStatus::Failed
+            } else {
+                RunStatus::Completed
+            },
+            trace: progress.trace.clone(),
+            output,
+        })
+    }
+}
+
+/// Moves the cursor back to the first failed step and drops it and every
+/// step after it. Steps before it all completed (or were skipped), so it was
+/// reached without an inherited iteration. Finished `for_each` items of the
+/// dropped steps are carried over, so settled fills and published receipts
+/// are not redone.
+fn rewind_to_failed_step(progress: &mut RunProgress) -> Result<(), EngineError> {
+    let position = progress
+        .visited
+        .iter()
+        .position(|id| {
+            progress
+                .states
+                .get(id)
+                .is_some_and(|state| state.status == StepStatus::Failed)
+        })
+        .ok_or_else(|| EngineError::Journal("failed run has no failed step".to_string()))?;
+    let failed = progress.visited[position].clone();
+
+    for id in progress.visited.split_off(position) {
+        if let Some(StepState {
+            value: StepValue::PerItem(items),
+            ..
+        }) = progress.states.remove(&id)
+        {
+            if !items.is_empty() {
+                progress.carried_items.insert(id, items);
+            }
+        }
+    }
+    progress.trace.truncate(position);
+    progress.completed_items = progress.carried_items.remove(&failed).unwrap_or_default();
+    progress.next = Some(failed);
+    progress.inherited = None;
+    progress.status = ProgressStatus::Running;
+    progress.error = None;
+    Ok(())
+}
+
+struct StepOutcome {
+    state: StepState,
+    failed_iteration: Option<Iteration>,
+}
+
+impl StepOutcome {
+    fn finished(status: StepStatus, value: StepValue) -> Self {
+        Self {
+            state: StepState {
+                status,
+                value,
+                error: None,
+            },
+            failed_iteration: None,
+        }
+    }
+
+    fn failed(value: StepValue, failure: StepFailure, iteration: Option<Iteration>) -> Self {
+        Self {
+            state: StepState {
+                status: StepStatus::Failed,
+                value,
+                error: Some(failure),
+            },
+            failed_iteration: iteration,
+        }
+    }
+}
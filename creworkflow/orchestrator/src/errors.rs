@@ -0,0 +1,32 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum EngineError {
+    #[error("invalid workflow spec: {0}")]
+    InvalidSpec(String),
+
+    #[error("step `{step}` uses unknown module `{module}`")]
+    UnknownModule { step: String, module: String },
+
+    #[error("step `{step}` visited twice; on_success/on_failure edges form a cycle")]
+    Cycle { step: String },
+
+    #[error("expression error in step `{step}`: {message}")]
+    Expression { step: String, message: String },
+
+    #[error("step `{step}` failed [{code}]: {message}")]
+    StepFailed {
+        step: String,
+        code: String,
+        message: String,
+    },
+
+    #[error("run journal error: {0}")]
+    Journal(String),
+
+    #[error("signal resolution failed: {0}")]
+    Signals(String),
+
+    #[error("governed policy resolution failed: {0}")]
+    Policy(String),
+}
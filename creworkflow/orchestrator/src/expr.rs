@@ -0,0 +1,363 @@
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+use crate::engine::{StepState, StepValue};
+
+/// What a `$` reference can see while a template is resolved.
+pub struct Scope<'a> {
+    /// The orchestrator request.
+    pub root: &'a Value,
+    /// The step's own response, set only while mapping its `output`.
+    pub current: Option<&'a Value>,
+    pub steps: &'a BTreeMap<String, StepState>,
+    pub item: Option<&'a Value>,
+    pub index: Option<usize>,
+}
+
+/// Resolves every string starting with `$` in `template` as an expression;
+/// all other values are copied as literals.
+pub fn resolve_template(template: &Value, scope: &Scope<'_>) -> Result<Value, String> {
+    match template {
+        Value::String(raw) if raw.starts_with('$') => evaluate(raw, scope),
+        Value::Array(items) => items
+            .iter()
+            .map(|item| resolve_template(item, scope))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::Array),
+        Value::Object(fields) => fields
+            .iter()
+            .map(|(key, value)| Ok((key.clone(), resolve_template(value, scope)?)))
+            .collect::<Result<serde_json::Map<_, _>, String>>()
+            .map(Value::Object),
+        other => Ok(other.clone()),
+    }
+}
+
+/// Grammar: `cond ? a : b`, `a == b`, `a != b`, references (`$.x`,
+/// `$steps.<id>.x`, `$item.x`, `$index`), calls (`$hex(..)`,
+/// `$fill_run_id(..)`), `'strings'`, integers, `true`, `false`, `null`.
+pub fn evaluate(expr: &str, scope: &Scope<'_>) -> Result<Value, String> {
+    let tokens = tokenize(expr)?;
+    let mut parser = Parser {
+        tokens: &tokens,
+        pos: 0,
+        scope,
+    };
+    let value = parser.expression()?;
+    if parser.pos != tokens.len() {
+        return Err(format!("unexpected trailing input in `{expr}`"));
+    }
+    Ok(value)
+}
+
+pub fn evaluate_bool(expr: &str, scope: &Scope<'_>) -> Result<bool, String> {
+    match evaluate(expr, scope)? {
+        Value::Bool(flag) => Ok(flag),
+        other => Err(format!("`{expr}` must evaluate to a boolean, got {other}")),
+    }
+}
+
+/// Each fill is proven and published as its own receipt, so fills after the
+/// first get a derived run id to stay unique in `SettlementRegistry`.
+pub fn fill_run_id(workflow_run_id: &str, index: usize) -> String {
+    if index == 0 {
+        workflow_run_id.to_string()
+    } else {
+        format!("{workflow_run_id}:fill-{index}")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ref(String),
+    Call(String),
+    Literal(Value),
+    Eq,
+    Ne,
+    Question,
+    Colon,
+    LParen,
+    RParen,
+    Comma,
+}
+
+fn tokenize(expr: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = expr.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            _ if c.is_whitespace() => i += 1,
+            '$' => {
+                let start = i;
+                i += 1;
+                while i < chars.len() && !chars[i].is_whitespace() && !"(),?:=!".contains(chars[i])
+                {
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                if chars.get(i) == Some(&'(') {
+                    tokens.push(Token::Call(text[1..].to_string()));
+                } else {
+                    tokens.push(Token::Ref(text));
+                }
+            }
+            '\'' => {
+                let end = chars[i + 1..]
+                    .iter()
+                    .position(|&ch| ch == '\'')
+                    .ok_or_else(|| format!("unterminated string in `{expr}`"))?;
+                let text: String = chars[i + 1..i + 1 + end].iter().collect();
+                tokens.push(Token::Literal(Value::String(text)));
+                i += end + 2;
+            }
+            '=' | '!' if chars.get(i + 1) == Some(&'=') => {
+                tokens.push(if c == '=' { Token::Eq } else { Token::Ne });
+                i += 2;
+            }
+            '?' | ':' | '(' | ')' | ',' => {
+                tokens.push(match c {
+                    '?' => Token::Question,
+                    ':' => Token::Colon,
+                    '(' => Token::LParen,
+                    ')' => Token::RParen,
+                    _ => Token::Comma,
+                });
+                i += 1;
+            }
+            _ if c.is_ascii_alphanumeric() || c == '-' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '-') {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect();
+                let literal = match word.as_str() {
+                    "true" => Value::Bool(true),
+                    "false" => Value::Bool(false),
+                    "null" => Value::Null,
+                    _ => word
+                        .parse::<i64>()
+                        .map(Value::from)
+                        .map_err(|_| format!("unexpected `{word}` in `{expr}`"))?,
+                };
+                tokens.push(Token::Literal(literal));
+            }
+            _ => return Err(format!("unexpected `{c}` in `{expr}`")),
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser<'t, 's> {
+    tokens: &'t [Token],
+    pos: usize,
+    scope: &'t Scope<'s>,
+}
+
+impl Parser<'_, '_> {
+    fn expression(&mut self) -> Result<Value, String> {
+        let condition = self.comparison()?;
+        if !self.eat(&Token::Question) {
+            return Ok(condition);
+        }
+        let then = self.comparison()?;
+        self.expect(&Token::Colon)?;
+        let otherwise = self.comparison()?;
+        match condition {
+            Value::Bool(true) => Ok(then),
+            Value::Bool(false) => Ok(otherwise),
+            other => Err(format!("ternary condition must be a boolean, got {other}")),
+        }
+    }
+
+    fn comparison(&mut self) -> Result<Value, String> {
+        let left = self.operand()?;
+        if self.eat(&Token::Eq) {
+            return Ok(Value::Bool(left == self.operand()?));
+        }
+        if self.eat(&Token::Ne) {
+            return Ok(Value::Bool(left != self.operand()?));
+        }
+        Ok(left)
+    }
+
+    fn operand(&mut self) -> Result<Value, String> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .cloned()
+            .ok_or_else(|| "unexpected end of expression".to_string())?;
+        self.pos += 1;
+        match token {
+            Token::Ref(reference) => resolve_reference(&reference, self.scope),
+            Token::Literal(value) => Ok(value),
+            Token::Call(name) => {
+                self.expect(&Token::LParen)?;
+                let mut args = Vec::new();
+                if !self.eat(&Token::RParen) {
+                    loop {
+                        args.push(self.expression()?);
+                        if self.eat(&Token::RParen) {
+                            break;
+                        }
+                        self.expect(&Token::Comma)?;
+                    }
+                }
+                call(&name, &args)
+            }
+            other => Err(format!("unexpected token {other:?}")),
+        }
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.tokens.get(self.pos) == Some(token) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &Token) -> Result<(), String> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(format!("expected {token:?}"))
+        }
+    }
+}
+
+fn resolve_reference(reference: &str, scope: &Scope<'_>) -> Result<Value, String> {
+    if reference == "$" || reference.starts_with("$.") {
+        let base = scope.current.unwrap_or(scope.root);
+        return Ok(walk(base, reference.strip_prefix("$.").unwrap_or("")));
+    }
+    if let Some(rest) = reference.strip_prefix("$steps.") {
+        let split = rest.find(['.', '[']).unwrap_or(rest.len());
+        let (id, path) = rest.split_at(split);
+        let path = path.strip_prefix('.').unwrap_or(path);
+        return Ok(resolve_step(id, path, scope));
+    }
+    if reference == "$index" {
+        return scope
+            .index
+            .map(Value::from)
+            .ok_or_else(|| "`$index` used outside for_each".to_string());
+    }
+    if reference == "$item" || reference.starts_with("$item.") || reference.starts_with("$item[") {
+        let item = scope
+            .item
+            .ok_or_else(|| "`$item` used outside for_each".to_string())?;
+        let path = &reference["$item".len()..];
+        return Ok(walk(item, path.strip_prefix('.').unwrap_or(path)));
+    }
+    Err(format!("unknown reference `{reference}`"))
+}
+
+/// A `for_each` step referenced from inside another `for_each` resolves to
+/// the same index; from anywhere else it resolves to the full list.
+fn resolve_step(id: &str, path: &str, scope: &Scope<'_>) -> Value {
+    let Some(state) = scope.steps.get(id) else {
+        return Value::Null;
+    };
+    let (head, tail) = path.split_once('.').unwrap_or((path, ""));
+    match head {
+        "status" if tail.is_empty() => return Value::String(state.status.as_str().to_string()),
+        "error" => {
+            return state
+                .error
+                .as_ref()
+                .and_then(|error| serde_json::to_value(error).ok())
+                .map(|error| walk(&error, tail))
+                .unwrap_or(Value::Null)
+        }
+        _ => {}
+    }
+    match &state.value {
+        StepValue::Single(value) => walk(value, path),
+        StepValue::PerItem(values) => match scope.index {
+            Some(index) => values
+                .get(index)
+                .map(|value| walk(value, path))
+                .unwrap_or(Value::Null),
+            None => walk(&Value::Array(values.clone()), path),
+        },
+    }
+}
+
+/// Missing keys and out-of-range indices resolve to `null`; a key applied
+/// to an array maps over its elements.
+fn walk(base: &Value, path: &str) -> Value {
+    let mut current = base.clone();
+    for segment in path_segments(path) {
+        current = match segment {
+            Segment::Key(key) => lookup_key(&current, &key),
+            Segment::Index(index) => current.get(index).cloned().unwrap_or(Value::Null),
+        };
+    }
+    current
+}
+
+fn lookup_key(value: &Value, key: &str) -> Value {
+    match value {
+        Value::Object(fields) => fields.get(key).cloned().unwrap_or(Value::Null),
+        Value::Array(items) => Value::Array(items.iter().map(|v| lookup_key(v, key)).collect()),
+        _ => Value::Null,
+    }
+}
+
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+fn path_segments(path: &str) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    for part in path.split('.').filter(|part| !part.is_empty()) {
+        let (key, mut indices) = part.split_once('[').unwrap_or((part, ""));
+        if !key.is_empty() {
+            segments.push(Segment::Key(key.to_string()));
+        }
+        while let Some((index, rest)) = indices.split_once(']') {
+            match index.parse::<usize>() {
+                Ok(index) => segments.push(Segment::Index(index)),
+                Err(_) => segments.push(Segment::Key(index.to_string())),
+            }
+            indices = rest.strip_prefix('[').unwrap_or(rest);
+        }
+    }
+    segments
+}
+
+fn call(name: &str, args: &[Value]) -> Result<Value, String> {
+    match (name, args) {
+        ("hex", [Value::Array(bytes)]) => {
+            let bytes = bytes
+                .iter()
+                .map(|b| {
+                    b.as_u64()
+                        .and_then(|b| u8::try_from(b).ok())
+                        .ok_or_else(|| format!("$hex expects bytes, got {b}"))
+                })
+                .collect::<Result<Vec<u8>, _>>()?;
+            Ok(Value::String(format!("0x{}", hex::encode(bytes))))
+        }
+        ("fill_run_id", [Value::String(run_id), index]) => {
+            let index = index
+                .as_u64()
+                .and_then(|i| usize::try_from(i).ok())
+                .ok_or_else(|| format!("$fill_run_id expects an index, got {index}"))?;
+            Ok(Value::String(fill_run_id(run_id, index)))
+        }
+        _ => Err(format!(
+            "unknown function or bad arguments: ${name}({})",
+            args.iter()
+                .map(Value::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
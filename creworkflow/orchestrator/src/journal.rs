@@ -0,0 +1,78 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate::engine::RunProgress;
+use crate::errors::EngineError;
+
+/// Where a durable run is checkpointed and read back on resume.
+pub trait JournalStore {
+    fn save(&self, progress: &RunProgress) -> Result<(), EngineError>;
+    fn load(&self, workflow_run_id: &str) -> Result<RunProgress, EngineError>;
+}
+
+/// One `<workflow_run_id>.json` per run. Entries carry decrypted intents, so
+/// files are written owner-only and replaced atomically on every checkpoint.
+pub struct FileJournal {
+    dir: PathBuf,
+}
+
+impl FileJournal {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn path(&self, workflow_run_id: &str) -> Result<PathBuf, EngineError> {
+        let valid = !workflow_run_id.is_empty()
+            && workflow_run_id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(EngineError::Journal(format!(
+                "invalid workflow_run_id `{workflow_run_id}`"
+            )));
+        }
+        Ok(self.dir.join(format!("{workflow_run_id}.json")))
+    }
+}
+
+impl JournalStore for FileJournal {
+    fn save(&self, progress: &RunProgress) -> Result<(), EngineError> {
+        let run_id = progress
+            .workflow_run_id
+            .as_deref()
+            .ok_or_else(|| EngineError::Journal("run has no workflow_run_id yet".to_string()))?;
+        let path = self.path(run_id)?;
+        let raw = serde_json::to_vec_pretty(progress)
+            .map_err(|e| EngineError::Journal(format!("serialization failed: {e}")))?;
+
+        fs::create_dir_all(&self.dir).map_err(|e| {
+            EngineError::Journal(format!("failed to create `{}`: {e}", self.dir.display()))
+        })?;
+        let tmp = path.with_extension("json.tmp");
+        let mut options = OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut file = options.open(&tmp).map_err(|e| {
+            EngineError::Journal(format!("failed to open `{}`: {e}", tmp.display()))
+        })?;
+        file.write_all(&raw)
+            .and_then(|_| file.sync_all())
+            .map_err(|e| {
+                EngineError::Journal(format!("failed to write `{}`: {e}", tmp.display()))
+            })?;
+        fs::rename(&tmp, &path).map_err(|e| {
+            EngineError::Journal(format!("failed to replace `{}`: {e}", path.display()))
+        })
+    }
+
+    fn load(&self, workflow_run_id: &str) -> Result<RunProgress, EngineError> {
+        let path = self.path(workflow_run_id)?;
+        let raw = fs::read(&path).map_err(|e| {
+            EngineError::Journal(format!("no journal for run `{workflow_run_id}`: {e}"))
+        })?;
+        serde_json::from_slice(&raw)
+            .map_err(|e| EngineError::Journal(format!("corrupt journal `{}`: {e}", path.display())))
+    }
+}
@@ -0,0 +1,8 @@
+pub mod engine;
+pub mod errors;
+pub mod expr;
+pub mod journal;
+pub mod policy;
+pub mod signals;
+pub mod spec;
+pub mod steps;
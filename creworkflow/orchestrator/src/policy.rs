@@ -0,0 +1,137 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use confidential_match::models::{PricingPolicy, PricingRule};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::errors::EngineError;
+
+#[derive(Debug, Deserialize)]
+pub struct PolicySnapshotConfig {
+    pub enabled: bool,
+    /// policy-snapshot's `GET /v1/policy/active`.
+    pub endpoint: String,
+    pub timeout_ms: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ActivePolicyResponse {
+    found: bool,
+    snapshot: Option<ActiveSnapshot>,
+    reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ActiveSnapshot {
+    canonical_rules: Value,
+}
+
+/// The pricing half of a governed rules bundle.
+#[derive(Debug, Deserialize)]
+struct GovernedPricingRules {
+    #[serde(default)]
+    pricing_rule: Option<PricingRule>,
+    #[serde(default)]
+    pricing_bands: BTreeMap<String, PricingBand>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PricingBand {
+    max_deviation_bps: u32,
+}
+
+/// Fills `governed_policy.pricing` in the run input before the workflow
+/// starts: taken from the active policy snapshot when
+/// `policy_snapshot.enabled`, so the caller cannot pick its own pricing rule
+/// or bands. Otherwise `governed_policy` is taken as given, falling back to
+/// the caller's `policy.pricing` for local runs without a snapshot service.
+pub fn resolve_governed_policy(input: &mut Value) -> Result<(), EngineError> {
+    let snapshot_cfg: Option<PolicySnapshotConfig> = input
+        .get("policy_snapshot")
+        .filter(|cfg| !cfg.is_null())
+        .map(|cfg| serde_json::from_value(cfg.clone()))
+        .transpose()
+        .map_err(|e| EngineError::Policy(format!("invalid policy_snapshot config: {e}")))?;
+
+    let pricing = match snapshot_cfg {
+        Some(cfg) if cfg.enabled => pricing_from_rules(&fetch_active_rules(&cfg)?)?,
+        _ => {
+            let given = input
+                .pointer("/governed_policy/pricing")
+                .or_else(|| input.pointer("/policy/pricing"))
+                .filter(|pricing| !pricing.is_null());
+            match given {
+                Some(pricing) => serde_json::from_value(pricing.clone())
+                    .map_err(|e| EngineError::Policy(format!("invalid pricing policy: {e}")))?,
+                None => PricingPolicy::default(),
+            }
+        }
+    };
+
+    let pricing = serde_json::to_value(pricing).map_err(|e| EngineError::Policy(e.to_string()))?;
+    match input {
+        Value::Object(fields) => {
+            fields.insert("governed_policy".to_string(), json!({ "pricing": pricing }));
+            Ok(())
+        }
+        _ => Err(EngineError::Policy(
+            "run input must be a JSON object".to_string(),
+        )),
+    }
+}
+
+/// Maps a snapshot's `canonical_rules` onto confidential-match's pricing
+/// input. Pairs without a governed band trade unbanded.
+pub fn pricing_from_rules(canonical_rules: &Value) -> Result<PricingPolicy, EngineError> {
+    let rules: GovernedPricingRules = serde_json::from_value(canonical_rules.clone())
+        .map_err(|e| EngineError::Policy(format!("invalid governed pricing rules: {e}")))?;
+    Ok(PricingPolicy {
+        rule: rules.pricing_rule.unwrap_or_default(),
+        reference_band_bps: None,
+        pair_band_bps: rules
+            .pricing_bands
+            .into_iter()
+            .map(|(pair, band)| (pair, band.max_deviation_bps))
+            .collect(),
+    })
+}
+
+fn fetch_active_rules(cfg: &PolicySnapshotConfig) -> Result<Value, EngineError> {
+    if cfg.endpoint.trim().is_empty() {
+        return Err(EngineError::Policy(
+            "policy_snapshot.endpoint cannot be empty".to_string(),
+        ));
+    }
+
+    let timeout = Duration::from_millis(cfg.timeout_ms.unwrap_or(8_000));
+    let client = reqwest::blocking::Client::builder()
+        .timeout(timeout)
+        .build()
+        .map_err(|e| EngineError::Policy(format!("failed to build http client: {e}")))?;
+
+    let response = client
+        .get(&cfg.endpoint)
+        .send()
+        .map_err(|e| EngineError::Policy(format!("policy snapshot request failed: {e}")))?;
+
+    if !response.status().is_success() {
+        return Err(EngineError::Policy(format!(
+            "policy snapshot non-success status: {}",
+            response.status()
+        )));
+    }
+
+    let active = response.json::<ActivePolicyResponse>().map_err(|e| {
+        EngineError::Policy(format!(
+            "failed to decode policy snapshot response json: {e}"
+        ))
+    })?;
+    match active.snapshot {
+        Some(snapshot) if active.found => Ok(snapshot.canonical_rules),
+        _ => Err(EngineError::Policy(format!(
+            "no active policy: {}",
+            active.reason.as_deref().unwrap_or("snapshot not found")
+        ))),
+    }
+}
@@ -0,0 +1,116 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use confidential_match::models::FxQuote;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::errors::EngineError;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ExternalSignals {
+    pub api_available: bool,
+    pub compliance_passed: bool,
+    pub risk_score: u32,
+    pub attestation_payload: String,
+    #[serde(default)]
+    pub fx_quote: Option<FxQuote>,
+    #[serde(default)]
+    pub fx_quotes: BTreeMap<String, FxQuote>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ConfidentialHttpConfig {
+    pub enabled: bool,
+    pub endpoint: String,
+    pub timeout_ms: Option<u64>,
+    pub api_key_env: Option<String>,
+    pub api_key_header: Option<String>,
+}
+
+/// Fills `external_signals` in the run input before the workflow starts:
+/// fetched over confidential HTTP when `confidential_http.enabled`, otherwise
+/// taken as given.
+pub fn resolve_external_signals(input: &mut Value) -> Result<(), EngineError> {
+    let http_cfg: Option<ConfidentialHttpConfig> = input
+        .get("confidential_http")
+        .filter(|cfg| !cfg.is_null())
+        .map(|cfg| serde_json::from_value(cfg.clone()))
+        .transpose()
+        .map_err(|e| EngineError::Signals(format!("invalid confidential_http config: {e}")))?;
+
+    let signals = match http_cfg {
+        Some(cfg) if cfg.enabled => fetch_signals_via_confidential_http(&cfg)?,
+        _ => {
+            let given = input
+                .get("external_signals")
+                .filter(|signals| !signals.is_null())
+                .ok_or_else(|| {
+                    EngineError::Signals(
+                        "external_signals missing while confidential_http disabled".to_string(),
+                    )
+                })?;
+            serde_json::from_value(given.clone())
+                .map_err(|e| EngineError::Signals(format!("invalid external_signals: {e}")))?
+        }
+    };
+
+    let signals = serde_json::to_value(signals).map_err(|e| EngineError::Signals(e.to_string()))?;
+    match input {
+        Value::Object(fields) => {
+            fields.insert("external_signals".to_string(), signals);
+            Ok(())
+        }
+        _ => Err(EngineError::Signals(
+            "run input must be a JSON object".to_string(),
+        )),
+    }
+}
+
+fn fetch_signals_via_confidential_http(
+    cfg: &ConfidentialHttpConfig,
+) -> Result<ExternalSignals, EngineError> {
+    if cfg.endpoint.trim().is_empty() {
+        return Err(EngineError::Signals(
+            "confidential_http.endpoint cannot be empty".to_string(),
+        ));
+    }
+
+    let timeout = Duration::from_millis(cfg.timeout_ms.unwrap_or(8_000));
+    let client = reqwest::blocking::Client::builder()
+        .timeout(timeout)
+        .build()
+        .map_err(|e| EngineError::Signals(format!("failed to build http client: {e}")))?;
+
+    let mut request = client.get(&cfg.endpoint);
+
+    if let Some(env_name) = &cfg.api_key_env {
+        let key = std::env::var(env_name).map_err(|_| {
+            EngineError::Signals(format!(
+                "api key env var `{env_name}` not found for confidential_http"
+            ))
+        })?;
+        let header_name = cfg
+            .api_key_header
+            .clone()
+            .unwrap_or_else(|| "x-api-key".to_string());
+        request = request.header(header_name, key);
+    }
+
+    let response = request
+        .send()
+        .map_err(|e| EngineError::Signals(format!("confidential http request failed: {e}")))?;
+
+    if !response.status().is_success() {
+        return Err(EngineError::Signals(format!(
+            "confidential http non-success status: {}",
+            response.status()
+        )));
+    }
+
+    response.json::<ExternalSignals>().map_err(|e| {
+        EngineError::Signals(format!(
+            "failed to decode confidential http response json: {e}"
+        ))
+    })
+}
@@ -0,0 +1,133 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::errors::EngineError;
+
+/// In-memory form of `workflow-spec/*.yaml`.
+///
+/// Inside `input` templates `$.` is the orchestrator request and
+/// `$steps.<id>.` a finished step's mapped output; inside a step's `output`
+/// template `$.` is that step's own response. Steps with `for_each` run once
+/// per item with `$item` and `$index` bound.
+#[derive(Debug, Clone, Deserialize)]
+pub struct WorkflowSpec {
+    pub name: String,
+    pub version: String,
+    pub description: Option<String>,
+    pub entrypoint: String,
+    /// Expression naming the run in the run journal, e.g.
+    /// `$steps.intent-intake.workflow_run_id`. Checkpoints start once it
+    /// resolves to a string.
+    pub run_id: Option<String>,
+    pub steps: Vec<StepSpec>,
+    pub output: Option<Value>,
+    pub failure_policy: Option<FailurePolicy>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct StepSpec {
+    pub id: String,
+    pub kind: String,
+    pub module: String,
+    pub when: Option<String>,
+    pub for_each: Option<String>,
+    pub input: Value,
+    pub output: Option<Value>,
+    pub on_success: Option<String>,
+    pub on_failure: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FailurePolicy {
+    pub on_step_error: String,
+    pub classify: Vec<FailureClass>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FailureClass {
+    pub code: String,
+    pub from_step: String,
+}
+
+impl WorkflowSpec {
+    pub fn from_yaml(raw: &str) -> Result<Self, EngineError> {
+        let spec: WorkflowSpec =
+            serde_yaml::from_str(raw).map_err(|e| EngineError::InvalidSpec(e.to_string()))?;
+        spec.validate()?;
+        Ok(spec)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, EngineError> {
+        let raw = fs::read_to_string(path).map_err(|e| {
+            EngineError::InvalidSpec(format!("failed to read `{}`: {e}", path.display()))
+        })?;
+        Self::from_yaml(&raw)
+    }
+
+    pub fn step(&self, id: &str) -> Option<&StepSpec> {
+        self.steps.iter().find(|step| step.id == id)
+    }
+
+    /// Structural checks only; module names are checked against a registry
+    /// when the engine is built.
+    pub fn validate(&self) -> Result<(), EngineError> {
+        let mut ids = HashSet::with_capacity(self.steps.len());
+        for step in &self.steps {
+            if step.id.trim().is_empty() || step.id.contains('.') {
+                return Err(EngineError::InvalidSpec(format!(
+                    "step id `{}` must be non-empty and contain no `.`",
+                    step.id
+                )));
+            }
+            if !ids.insert(step.id.as_str()) {
+                return Err(EngineError::InvalidSpec(format!(
+                    "duplicate step id `{}`",
+                    step.id
+                )));
+            }
+        }
+
+        let known = |id: &str| ids.contains(id);
+        if !known(&self.entrypoint) {
+            return Err(EngineError::InvalidSpec(format!(
+                "entrypoint `{}` is not a step",
+                self.entrypoint
+            )));
+        }
+        for step in &self.steps {
+            for (edge, target) in [
+                ("on_success", &step.on_success),
+                ("on_failure", &step.on_failure),
+            ] {
+                if let Some(target) = target {
+                    if !known(target) {
+                        return Err(EngineError::InvalidSpec(format!(
+                            "step `{}` {edge} targets unknown step `{target}`",
+                            step.id
+                        )));
+                    }
+                }
+            }
+        }
+
+        if let Some(policy) = &self.failure_policy {
+            if policy.on_step_error != "stop" {
+                return Err(EngineError::InvalidSpec(format!(
+                    "unsupported failure_policy.on_step_error `{}`",
+                    policy.on_step_error
+                )));
+            }
+            if let Some(class) = policy.classify.iter().find(|c| !known(&c.from_step)) {
+                return Err(EngineError::InvalidSpec(format!(
+                    "failure_policy code {} references unknown step `{}`",
+                    class.code, class.from_step
+                )));
+            }
+        }
+        Ok(())
+    }
+}
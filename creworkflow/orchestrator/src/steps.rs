@@ -0,0 +1,167 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+use confidential_match::errors::MatchError;
+use confidential_match::handler::process_confidential_match;
+use intent_intake::errors::IntakeError;
+use intent_intake::handler::process_intake;
+use proof_generate::errors::ProofError;
+use proof_generate::handler::process_proof_generate;
+use publish_receipt::errors::PublishError;
+use publish_receipt::handler::process_publish_receipt;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use settle_private::errors::SettleError;
+use settle_private::handler::process_settle_private;
+
+/// Error surfaced by a step module. `code` uses the same names as the spec's
+/// `failure_policy.classify` entries.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StepFailure {
+    pub code: String,
+    pub message: String,
+    /// `for_each` iteration that failed, if any.
+    pub index: Option<usize>,
+}
+
+impl StepFailure {
+    pub fn new(code: &str, message: impl Into<String>) -> Self {
+        Self {
+            code: code.to_string(),
+            message: message.into(),
+            index: None,
+        }
+    }
+}
+
+pub type StepHandler = Box<dyn Fn(Value) -> Result<Value, StepFailure>>;
+
+/// Maps a spec step's `module` name to the code that runs it.
+#[derive(Default)]
+pub struct StepRegistry {
+    handlers: BTreeMap<String, StepHandler>,
+}
+
+impl StepRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The five creworkflow step crates under their spec module names.
+    pub fn builtin() -> Self {
+        Self::new()
+            .register("intent-intake", |input| {
+                run_module(input, process_intake, intake_code)
+            })
+            .register("confidential-match", |input| {
+                run_module(input, process_confidential_match, match_code)
+            })
+            .register("proof-generate", |input| {
+                run_module(input, process_proof_generate, proof_code)
+            })
+            .register("settle-private", |input| {
+                run_module(input, process_settle_private, settle_code)
+            })
+            .register("publish-receipt", |input| {
+                run_module(input, process_publish_receipt, publish_code)
+            })
+    }
+
+    /// Adds or replaces the handler for `module`.
+    pub fn register<F>(mut self, module: &str, handler: F) -> Self
+    where
+        F: Fn(Value) -> Result<Value, StepFailure> + 'static,
+    {
+        self.handlers.insert(module.to_string(), Box::new(handler));
+        self
+    }
+
+    pub fn contains(&self, module: &str) -> bool {
+        self.handlers.contains_key(module)
+    }
+
+    pub fn dispatch(&self, module: &str, input: Value) -> Result<Value, StepFailure> {
+        let handler = self.handlers.get(module).ok_or_else(|| {
+            StepFailure::new("UNKNOWN_MODULE", format!("no handler for `{module}`"))
+        })?;
+        handler(input)
+    }
+}
+
+fn run_module<Req, Resp, E>(
+    input: Value,
+    handler: fn(Req) -> Result<Resp, E>,
+    code: fn(&E) -> &'static str,
+) -> Result<Value, StepFailure>
+where
+    Req: DeserializeOwned,
+    Resp: Serialize,
+    E: Display,
+{
+    let req: Req = serde_json::from_value(input)
+        .map_err(|e| StepFailure::new("INVALID_INPUT", format!("invalid step input: {e}")))?;
+    let resp = handler(req).map_err(|e| StepFailure::new(code(&e), e.to_string()))?;
+    serde_json::to_value(resp)
+        .map_err(|e| StepFailure::new("INVALID_OUTPUT", format!("serialization failed: {e}")))
+}
+
+fn intake_code(err: &IntakeError) -> &'static str {
+    match err {
+        IntakeError::InvalidRequest(_) => "INVALID_REQUEST",
+        IntakeError::MalformedPayload { .. } => "MALFORMED_PAYLOAD",
+        IntakeError::InvalidSignature { .. } => "INVALID_SIGNATURE",
+        IntakeError::StaleNonce { .. } => "STALE_NONCE",
+        IntakeError::ExpiredIntent { .. } => "EXPIRED_INTENT",
+    }
+}
+
+fn match_code(err: &MatchError) -> &'static str {
+    if let Some(reason) = err.reject_reason() {
+        return reason.as_str();
+    }
+    match err {
+        MatchError::InvalidRequest(_) => "INVALID_REQUEST",
+        MatchError::ApiUnavailable => "API_UNAVAILABLE",
+        MatchError::PolicyMismatch { .. } => "POLICY_MISMATCH",
+        MatchError::ReferencePriceUnavailable { .. } => "REFERENCE_PRICE_UNAVAILABLE",
+        MatchError::Arithmetic(_) => "ARITHMETIC",
+        _ => "MATCH_FAILURE",
+    }
+}
+
+fn proof_code(err: &ProofError) -> &'static str {
+    match err {
+        ProofError::InvalidRequest(_) => "INVALID_REQUEST",
+        ProofError::CircuitConstraintFailure(_) => "CIRCUIT_CONSTRAINT_FAILURE",
+        ProofError::WitnessGenerationFailure(_) => "WITNESS_GENERATION_FAILURE",
+        ProofError::ProvingTimeout { .. } => "PROVING_TIMEOUT",
+        ProofError::ProverCommand(_) => "PROVER_COMMAND",
+        ProofError::Artifact(_) => "ARTIFACT",
+    }
+}
+
+fn settle_code(err: &SettleError) -> &'static str {
+    match err {
+        SettleError::InvalidRequest(_) => "INVALID_REQUEST",
+        SettleError::TransferFailure => "TRANSFER_FAILURE",
+        SettleError::CounterpartySettlementConflict => "COUNTERPARTY_SETTLEMENT_CONFLICT",
+        SettleError::TimeoutRetryExhausted { .. } => "TIMEOUT_RETRY_EXHAUSTED",
+    }
+}
+
+fn publish_code(err: &PublishError) -> &'static str {
+    match err {
+        PublishError::InvalidRequest(_) => "INVALID_REQUEST",
+        PublishError::UnauthorizedCaller => "UNAUTHORIZED_CALLER",
+        PublishError::StalePolicy => "STALE_POLICY",
+        PublishError::InvalidProof => "INVALID_PROOF",
+        PublishError::MalformedProof(_) => "MALFORMED_PROOF",
+        PublishError::VerifyingKey(_) => "VERIFYING_KEY_UNAVAILABLE",
+        PublishError::InvalidSignalBinding => "INVALID_SIGNAL_BINDING",
+        PublishError::DuplicateWorkflowRun => "DUPLICATE_WORKFLOW_RUN",
+        PublishError::DuplicateReceiptHash => "DUPLICATE_RECEIPT_HASH",
+        PublishError::MissingEnv(_) => "MISSING_ENV",
+        PublishError::Onchain(_) => "ONCHAIN",
+    }
+}
@@ -0,0 +1,578 @@
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use base64::Engine as _;
+use intent_intake::models::{CounterpartyConstraints, DecryptedIntentPayload, Side};
+use orchestrator::engine::{Engine, ProgressStatus, RunStatus, StepStatus};
+use orchestrator::errors::EngineError;
+use orchestrator::expr::{self, Scope};
+use orchestrator::journal::{FileJournal, JournalStore};
+use orchestrator::policy::{pricing_from_rules, resolve_governed_policy};
+use orchestrator::spec::WorkflowSpec;
+use orchestrator::steps::{StepFailure, StepRegistry};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+const NOW: u64 = 1_750_000_000;
+
+fn otc_spec() -> WorkflowSpec {
+    let path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../workflow-spec/otc_settlement.yaml"
+    );
+    WorkflowSpec::load(path.as_ref()).expect("spec should load")
+}
+
+/// Builtin modules with proving and publishing replaced, so runs need
+/// neither snarkjs nor a chain.
+fn offline_registry() -> StepRegistry {
+    StepRegistry::builtin()
+        .register("proof-generate", |input| {
+            let run_id = input["workflow_run_id"].as_str().unwrap_or_default();
+            Ok(json!({
+                "proof_bytes": [1, 2, 255],
+                "public_signals": ["1"],
+                "proof_hash": format!("0xproof:{run_id}"),
+                "receipt_hash": format!("0xreceipt:{run_id}"),
+                "policy_version": input["policy_result"]["policy_version"],
+                "domain_binding_hash": "0x1",
+            }))
+        })
+        .register("publish-receipt", |input| {
+            Ok(json!({
+                "settlement_registry": input["settlement_registry"],
+                "tx_hash": "0xtx",
+                "onchain_receipt_event_id": "0xtx:1",
+                "stored_receipt_record": {
+                    "workflow_run_id": input["workflow_run_id"],
+                    "proof_hash": input["proof_hash"],
+                    "policy_version": input["policy_version"],
+                    "status": input["status"],
+                    "receipt_hash": input["receipt_hash"],
+                },
+                "proof_hex": input["proof_hex"],
+            }))
+        })
+}
+
+type Calls = Rc<RefCell<Vec<String>>>;
+
+/// `offline_registry` with every side-effecting module logging
+/// `module:workflow_run_id` to `calls`; `fail_second_fill` names a module
+/// whose `:fill-1` call fails with the given code.
+fn recording_registry(
+    calls: &Calls,
+    fail_second_fill: Option<(&'static str, &'static str)>,
+) -> StepRegistry {
+    let inner = Rc::new(offline_registry());
+    let mut registry = StepRegistry::builtin();
+    for module in ["proof-generate", "settle-private", "publish-receipt"] {
+        let (inner, calls) = (Rc::clone(&inner), Rc::clone(calls));
+        registry = registry.register(module, move |input| {
+            let run_id = input["workflow_run_id"].as_str().unwrap_or_default();
+            calls.borrow_mut().push(format!("{module}:{run_id}"));
+            if let Some((failing, code)) = fail_second_fill {
+                if module == failing && run_id.ends_with(":fill-1") {
+                    return Err(StepFailure::new(code, "transient failure"));
+                }
+            }
+            inner.dispatch(module, input)
+        });
+    }
+    registry
+}
+
+fn journal_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "zkclear-orchestrator-test-{name}-{}",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+fn envelope(intent_id: &str, signer: &str, side: Side, size: &str, price: &str, at: u64) -> Value {
+    let payload = DecryptedIntentPayload {
+        asset_pair: "ETH/USDC".to_string(),
+        side,
+        size: size.to_string(),
+        limit_price: price.to_string(),
+        expiry: NOW + 3600,
+        counterparty_constraints: CounterpartyConstraints {
+            allow_list: vec![],
+            deny_list: vec![],
+        },
+        nonce: format!("nonce-{intent_id}"),
+        issued_at: NOW - 100,
+    };
+    let canonical = serde_json::to_string(&payload).expect("payload");
+    let mut hasher = Sha256::new();
+    hasher.update(signer.as_bytes());
+    hasher.update(canonical.as_bytes());
+
+    json!({
+        "intent_id": intent_id,
+        "signer": signer,
+        "signature_hex": hex::encode(hasher.finalize()),
+        "ciphertext_b64": base64::engine::general_purpose::STANDARD.encode(canonical),
+        "submitted_at": at,
+    })
+}
+
+fn run_input() -> Value {
+    json!({
+        "intent_intake": {
+            "encrypted_intents": [
+                envelope("buy-1", "0x1111", Side::Buy, "5.0", "3020.0", NOW - 50),
+                envelope("sell-1", "0x2222", Side::Sell, "2.0", "3000.0", NOW - 49),
+                envelope("sell-2", "0x3333", Side::Sell, "2.0", "3010.0", NOW - 48),
+            ],
+            "current_unix_ts": NOW,
+            "seen_nonces": [],
+            "payload_reference": "s3://zkclear/test/run",
+        },
+        "policy": {
+            "policy_version": 1,
+            "expected_policy_version": 1,
+            "max_risk_score": 70,
+            "max_notional": "500000.00",
+        },
+        "governed_policy": {
+            "pricing": { "rule": "midpoint", "reference_band_bps": null },
+        },
+        "external_signals": {
+            "api_available": true,
+            "compliance_passed": true,
+            "risk_score": 42,
+            "attestation_payload": "provider=test;result=ok",
+            "fx_quote": null,
+        },
+        "proving": {
+            "proving_timeout_ms": 3000,
+            "estimated_proving_time_ms": 500,
+            "domain_separator": "zkclear-test-domain",
+            "witness_seed": "witness-seed-test",
+        },
+        "settlement_execution": {
+            "max_retries": 2,
+            "timeout_ms": 3000,
+            "estimated_execution_ms": 500,
+            "retryable_error_sequence": [false],
+        },
+        "publish": {
+            "settlement_registry": "0x3e3a14f46d13e156daa99bf234224a57b1c79da5",
+            "publisher_address": "0x6D21167d874C842386e8c484519B5ddBBaB87b43",
+        },
+    })
+}
+
+fn step_status(record: &orchestrator::engine::RunRecord, id: &str) -> StepStatus {
+    record
+        .trace
+        .iter()
+        .find(|step| step.id == id)
+        .map(|step| step.status)
+        .unwrap_or_else(|| panic!("step {id} not in trace"))
+}
+
+#[test]
+fn otc_spec_binds_to_builtin_modules() {
+    let spec = otc_spec();
+    let registry = StepRegistry::builtin();
+    Engine::new(&spec, &registry).expect("every module should be registered");
+}
+
+#[test]
+fn engine_proves_settles_and_publishes_every_fill() {
+    let spec = otc_spec();
+    let registry = offline_registry();
+    let engine = Engine::new(&spec, &registry).expect("engine");
+
+    let record = engine.run(&run_input()).expect("run should complete");
+    assert_eq!(record.status, RunStatus::Completed);
+    assert_eq!(step_status(&record, "prove-rejection"), StepStatus::Skipped);
+
+    let out = &record.output;
+    let run_id = out["workflow_run_id"].as_str().expect("run id");
+    assert_eq!(out["match_decision"], "accept");
+    assert_eq!(out["rejection"], Value::Null);
+
+    let fills = out["fills"].as_array().expect("fills");
+    assert_eq!(fills.len(), 2);
+    assert_eq!(fills[0]["fill_run_id"], run_id);
+    assert_eq!(fills[1]["fill_run_id"], format!("{run_id}:fill-1"));
+    assert_eq!(fills[1]["sell_intent_id"], "sell-2");
+    assert_eq!(fills[1]["proof_hash"], format!("0xproof:{run_id}:fill-1"));
+    assert_eq!(fills[1]["settlement_status"], "settled");
+    assert_eq!(fills[1]["publish_result"]["proof_hex"], "0x0102ff");
+    assert_eq!(
+        fills[1]["publish_result"]["stored_receipt_record"]["status"],
+        "settled"
+    );
+
+    let residuals = out["residual_intents"].as_array().expect("residuals");
+    assert_eq!(residuals.len(), 1);
+    assert_eq!(residuals[0]["intent_id"], "buy-1");
+}
+
+#[test]
+fn engine_proves_and_publishes_rejections() {
+    let spec = otc_spec();
+    let registry = offline_registry();
+    let engine = Engine::new(&spec, &registry).expect("engine");
+    let mut input = run_input();
+    input["external_signals"]["risk_score"] = json!(99);
+
+    let record = engine.run(&input).expect("reject is not a failure");
+    assert_eq!(record.status, RunStatus::Completed);
+    assert_eq!(step_status(&record, "settle-private"), StepStatus::Skipped);
+    assert_eq!(
+        step_status(&record, "publish-rejection"),
+        StepStatus::Completed
+    );
+
+    let out = &record.output;
+    assert_eq!(out["match_decision"], "reject");
+    assert_eq!(out["reject_reason"], "RISK_THRESHOLD_FAIL");
+    assert_eq!(out["fills"], json!([]));
+    assert_eq!(
+        out["rejection"]["publish_result"]["stored_receipt_record"]["status"],
+        "rejected"
+    );
+}
+
+#[test]
+fn engine_runs_on_failure_branch_for_failed_fill() {
+    let spec = otc_spec();
+    let registry = offline_registry().register("settle-private", |input| {
+        let run_id = input["workflow_run_id"].as_str().unwrap_or_default();
+        if run_id.ends_with(":fill-1") {
+            return Err(StepFailure::new("TRANSFER_FAILURE", "transfer failure"));
+        }
+        Ok(json!({ "settlement_status": "settled", "private_execution_reference_ids": [] }))
+    });
+    let engine = Engine::new(&spec, &registry).expect("engine");
+
+    let record = engine.run(&run_input()).expect("branch should run");
+    assert_eq!(record.status, RunStatus::Failed);
+    assert_eq!(step_status(&record, "settle-private"), StepStatus::Failed);
+
+    let failure = &record.output["settlement_failure"];
+    let run_id = record.output["workflow_run_id"].as_str().expect("run id");
+    assert_eq!(failure["fill_run_id"], format!("{run_id}:fill-1"));
+    assert_eq!(failure["sell_intent_id"], "sell-2");
+    assert_eq!(failure["error"]["code"], "TRANSFER_FAILURE");
+    assert_eq!(failure["error"]["index"], 1);
+    assert_eq!(
+        failure["publish_result"]["stored_receipt_record"]["proof_hash"],
+        format!("0xproof:{run_id}:fill-1")
+    );
+    assert_eq!(
+        failure["publish_result"]["stored_receipt_record"]["status"],
+        "failed"
+    );
+
+    // Fill 0 settled before fill 1 failed, so it still gets its receipt.
+    assert_eq!(
+        step_status(&record, "publish-receipt"),
+        StepStatus::Completed
+    );
+    let fills = record.output["fills"].as_array().expect("fills");
+    assert_eq!(fills.len(), 1);
+    assert_eq!(fills[0]["fill_run_id"], run_id);
+    assert_eq!(fills[0]["sell_intent_id"], "sell-1");
+    assert_eq!(fills[0]["settlement_status"], "settled");
+    assert_eq!(
+        fills[0]["publish_result"]["stored_receipt_record"]["status"],
+        "settled"
+    );
+}
+
+#[test]
+fn engine_stops_on_failure_without_branch() {
+    let spec = otc_spec();
+    let registry = offline_registry();
+    let engine = Engine::new(&spec, &registry).expect("engine");
+    let mut input = run_input();
+    input["policy"]["policy_version"] = json!(2);
+
+    let err = engine.run(&input).expect_err("must fail");
+    assert!(matches!(
+        err,
+        EngineError::StepFailed { ref step, ref code, .. }
+            if step == "confidential-match" && code == "POLICY_MISMATCH"
+    ));
+}
+
+#[test]
+fn durable_run_resumes_without_reproving_or_resettling() {
+    let spec = otc_spec();
+    let journal = FileJournal::new(journal_dir("resume"));
+    let calls = Calls::default();
+
+    let registry = recording_registry(&calls, Some(("publish-receipt", "ONCHAIN")));
+    let engine = Engine::new(&spec, &registry).expect("engine");
+    let err = engine
+        .run_durable(&run_input(), &journal)
+        .expect_err("second publish must fail");
+    assert!(matches!(
+        err,
+        EngineError::StepFailed { ref step, ref code, .. }
+            if step == "publish-receipt" && code == "ONCHAIN"
+    ));
+
+    let run_id = calls.borrow()[0]
+        .strip_prefix("proof-generate:")
+        .expect("first side effect is proving")
+        .to_string();
+    let progress = journal.load(&run_id).expect("journal written");
+    assert_eq!(progress.status, ProgressStatus::Halted);
+    assert_eq!(progress.next.as_deref(), Some("publish-receipt"));
+    assert_eq!(progress.completed_items.len(), 1);
+
+    calls.borrow_mut().clear();
+    let registry = recording_registry(&calls, None);
+    let engine = Engine::new(&spec, &registry).expect("engine");
+    let record = engine.resume(&run_id, &journal).expect("resume completes");
+    assert_eq!(
+        *calls.borrow(),
+        vec![format!("publish-receipt:{run_id}:fill-1")]
+    );
+    assert_eq!(record.status, RunStatus::Completed);
+    assert_eq!(record.workflow_run_id.as_deref(), Some(run_id.as_str()));
+
+    let fills = record.output["fills"].as_array().expect("fills");
+    assert_eq!(fills.len(), 2);
+    assert_eq!(fills[0]["fill_run_id"], run_id);
+    assert_eq!(fills[1]["fill_run_id"], format!("{run_id}:fill-1"));
+    assert_eq!(fills[1]["settlement_status"], "settled");
+
+    calls.borrow_mut().clear();
+    let again = engine.resume(&run_id, &journal).expect("finished run");
+    assert!(calls.borrow().is_empty());
+    assert_eq!(again.output, record.output);
+    assert_eq!(
+        journal.load(&run_id).expect("journal").status,
+        ProgressStatus::Completed
+    );
+}
+
+#[test]
+fn failed_run_resumes_from_the_failed_step() {
+    let spec = otc_spec();
+    let journal = FileJournal::new(journal_dir("resume-failed"));
+    let calls = Calls::default();
+
+    let registry = recording_registry(&calls, Some(("settle-private", "TRANSFER_FAILURE")));
+    let engine = Engine::new(&spec, &registry).expect("engine");
+    let record = engine
+        .run_durable(&run_input(), &journal)
+        .expect("failure branch runs");
+    assert_eq!(record.status, RunStatus::Failed);
+    let run_id = record.workflow_run_id.clone().expect("run id");
+    assert_eq!(
+        journal.load(&run_id).expect("journal").status,
+        ProgressStatus::Failed
+    );
+
+    calls.borrow_mut().clear();
+    let registry = recording_registry(&calls, None);
+    let engine = Engine::new(&spec, &registry).expect("engine");
+    let record = engine.resume(&run_id, &journal).expect("resume completes");
+    assert_eq!(
+        *calls.borrow(),
+        vec![
+            format!("settle-private:{run_id}:fill-1"),
+            format!("publish-receipt:{run_id}:fill-1"),
+        ]
+    );
+    assert_eq!(record.status, RunStatus::Completed);
+    assert_eq!(record.output["settlement_failure"], Value::Null);
+
+    let fills = record.output["fills"].as_array().expect("fills");
+    assert_eq!(fills.len(), 2);
+    assert_eq!(fills[0]["fill_run_id"], run_id);
+    assert_eq!(fills[1]["fill_run_id"], format!("{run_id}:fill-1"));
+    assert_eq!(fills[1]["settlement_status"], "settled");
+
+    calls.borrow_mut().clear();
+    let again = engine.resume(&run_id, &journal).expect("finished run");
+    assert!(calls.borrow().is_empty());
+    assert_eq!(again.output, record.output);
+}
+
+#[test]
+fn resume_checks_journal_and_spec() {
+    let yaml = |version: &str, run_id: &str| {
+        format!(
+            r#"
+name: t
+version: "{version}"
+entrypoint: a
+{run_id}
+steps:
+  - {{ id: a, kind: compute, module: echo, input: {{ id: "$.id" }} }}
+"#
+        )
+    };
+    let registry = StepRegistry::new().register("echo", Ok);
+    let journal = FileJournal::new(journal_dir("checks"));
+
+    let spec = WorkflowSpec::from_yaml(&yaml("1", "")).expect("spec");
+    let engine = Engine::new(&spec, &registry).expect("engine");
+    assert!(matches!(
+        engine.run_durable(&json!({ "id": "run-1" }), &journal),
+        Err(EngineError::InvalidSpec(_))
+    ));
+
+    let spec = WorkflowSpec::from_yaml(&yaml("1", "run_id: \"$steps.a.id\"")).expect("spec");
+    let engine = Engine::new(&spec, &registry).expect("engine");
+    engine
+        .run_durable(&json!({ "id": "run-1" }), &journal)
+        .expect("run");
+    assert!(matches!(
+        engine.resume("missing", &journal),
+        Err(EngineError::Journal(_))
+    ));
+    assert!(matches!(
+        engine.resume("../run-1", &journal),
+        Err(EngineError::Journal(_))
+    ));
+
+    let spec = WorkflowSpec::from_yaml(&yaml("2", "run_id: \"$steps.a.id\"")).expect("spec");
+    let engine = Engine::new(&spec, &registry).expect("engine");
+    assert!(matches!(
+        engine.resume("run-1", &journal),
+        Err(EngineError::Journal(ref message)) if message.contains("v1")
+    ));
+}
+
+#[test]
+fn engine_rejects_unknown_modules_and_cycles() {
+    let spec = WorkflowSpec::from_yaml(
+        r#"
+name: t
+version: "1"
+entrypoint: a
+steps:
+  - { id: a, kind: compute, module: echo, input: {}, on_success: b }
+  - { id: b, kind: compute, module: missing, input: {} }
+"#,
+    )
+    .expect("spec");
+    let registry = StepRegistry::new().register("echo", Ok);
+    assert!(matches!(
+        Engine::new(&spec, &registry),
+        Err(EngineError::UnknownModule { ref module, .. }) if module == "missing"
+    ));
+
+    let spec = WorkflowSpec::from_yaml(
+        r#"
+name: t
+version: "1"
+entrypoint: a
+steps:
+  - { id: a, kind: compute, module: echo, input: {}, on_success: b }
+  - { id: b, kind: compute, module: echo, input: {}, on_success: a }
+"#,
+    )
+    .expect("spec");
+    let engine = Engine::new(&spec, &registry).expect("engine");
+    assert!(matches!(
+        engine.run(&json!({})),
+        Err(EngineError::Cycle { ref step }) if step == "a"
+    ));
+
+    let err = WorkflowSpec::from_yaml(
+        r#"
+name: t
+version: "1"
+entrypoint: a
+steps:
+  - { id: a, kind: compute, module: echo, input: {}, on_failure: nowhere }
+"#,
+    )
+    .expect_err("must fail");
+    assert!(matches!(err, EngineError::InvalidSpec(_)));
+}
+
+#[test]
+fn expressions_resolve_references_and_operators() {
+    let root = json!({ "a": { "list": [{ "x": 1 }, { "x": 2 }] }, "flag": "on" });
+    let steps = BTreeMap::new();
+    let scope = Scope {
+        root: &root,
+        current: None,
+        steps: &steps,
+        item: None,
+        index: None,
+    };
+
+    assert_eq!(expr::evaluate("$.a.list[1].x", &scope), Ok(json!(2)));
+    assert_eq!(expr::evaluate("$.a.list.x", &scope), Ok(json!([1, 2])));
+    assert_eq!(expr::evaluate("$.missing.path", &scope), Ok(Value::Null));
+    assert_eq!(
+        expr::evaluate("$.flag == 'on' ? 'yes' : 'no'", &scope),
+        Ok(json!("yes"))
+    );
+    assert_eq!(expr::evaluate("$.flag != 'on'", &scope), Ok(json!(false)));
+    assert_eq!(
+        expr::evaluate("$fill_run_id('run', 2)", &scope),
+        Ok(json!("run:fill-2"))
+    );
+    assert!(expr::evaluate("$index", &scope).is_err());
+    assert!(expr::evaluate_bool("$.flag", &scope).is_err());
+}
+
+#[test]
+fn governed_pricing_maps_snapshot_bands() {
+    let pricing = pricing_from_rules(&json!({
+        "schema_version": 1,
+        "pricing_rule": "reference_price",
+        "pricing_bands": { "ETH/USDC": { "max_deviation_bps": 150 } },
+    }))
+    .expect("governed pricing");
+    assert_eq!(pricing.band_bps("eth/usdc"), Some(150));
+    assert_eq!(pricing.band_bps("BTC/USDC"), None);
+    assert_eq!(
+        serde_json::to_value(pricing.rule).unwrap(),
+        json!("reference_price")
+    );
+
+    let pricing = pricing_from_rules(&json!({ "schema_version": 1 })).expect("no pricing rules");
+    assert_eq!(
+        serde_json::to_value(pricing.rule).unwrap(),
+        json!("midpoint")
+    );
+    assert!(pricing.pair_band_bps.is_empty());
+
+    assert!(matches!(
+        pricing_from_rules(&json!({ "pricing_rule": "best_price" })),
+        Err(EngineError::Policy(_))
+    ));
+}
+
+#[test]
+fn governed_pricing_falls_back_to_caller_pricing_without_snapshot() {
+    let mut input = run_input();
+    input.as_object_mut().unwrap().remove("governed_policy");
+    input["policy"]["pricing"] = json!({ "rule": "maker_price", "reference_band_bps": 25 });
+    resolve_governed_policy(&mut input).expect("resolve");
+    assert_eq!(
+        input["governed_policy"]["pricing"]["rule"],
+        json!("maker_price")
+    );
+    assert_eq!(
+        input["governed_policy"]["pricing"]["reference_band_bps"],
+        json!(25)
+    );
+
+    let mut input = run_input();
+    input["policy_snapshot"] = json!({ "enabled": true, "endpoint": " " });
+    assert!(matches!(
+        resolve_governed_policy(&mut input),
+        Err(EngineError::Policy(message)) if message.contains("endpoint")
+    ));
+}
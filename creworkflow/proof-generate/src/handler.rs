@@ -1,36 +1,45 @@
-use std::env;
-use std::fs;
-use std::path::{Path, PathBuf};
-use std::process::Command;
-use std::time::{SystemTime, UNIX_EPOCH};
-
 use ethers_core::abi::{encode, Token};
 use ethers_core::types::{H256, U256};
-use serde::Deserialize;
+use fixed_amount::Amount;
 use serde_json::json;
 use sha2::{Digest, Sha256};
 
 use crate::errors::ProofError;
-use crate::models::{ProofGenerateRequest, ProofGenerateResponse};
+use crate::models::{ProofGenerateRequest, ProofGenerateResponse, SettlementParams};
+use crate::prover::{prover_from_env, zk_root, CircuitArtifacts, GeneratedProof, SnarkProof};
+
+/// `notional_public` of every rejection proof: the largest notional the
+/// settlement circuit admits with `amount_in = notional + fee` still in 64 bits.
+/// Fills at or above it are refused, so the signal alone marks a rejection.
+pub const REJECTION_NOTIONAL_SIGNAL: u64 = u64::MAX - 1;
 
 pub fn process_proof_generate(
     req: ProofGenerateRequest,
 ) -> Result<ProofGenerateResponse, ProofError> {
     validate_input(&req)?;
     enforce_timeout(&req)?;
-    enforce_constraints(&req)?;
+    let outcome = enforce_constraints(&req)?;
 
     let workflow_run_h256 = to_h256(&req.workflow_run_id);
     let domain_h256 = to_h256(&req.domain_separator);
 
-    let receipt_hash = hash_hex(&[
-        req.workflow_run_id.as_bytes(),
-        req.settlement_params.asset_pair.as_bytes(),
-        req.settlement_params.buy_intent_id.as_bytes(),
-        req.settlement_params.sell_intent_id.as_bytes(),
-        &req.policy_result.policy_version.to_be_bytes(),
-        req.domain_separator.as_bytes(),
-    ]);
+    let receipt_hash = match outcome {
+        ProvenOutcome::Settlement(params) => hash_hex(&[
+            req.workflow_run_id.as_bytes(),
+            params.asset_pair.as_bytes(),
+            params.buy_intent_id.as_bytes(),
+            params.sell_intent_id.as_bytes(),
+            &req.policy_result.policy_version.to_be_bytes(),
+            req.domain_separator.as_bytes(),
+        ]),
+        ProvenOutcome::Rejection(reason) => hash_hex(&[
+            req.workflow_run_id.as_bytes(),
+            b"rejected",
+            reason.as_bytes(),
+            &req.policy_result.policy_version.to_be_bytes(),
+            req.domain_separator.as_bytes(),
+        ]),
+    };
     let receipt_h256 = to_h256(&receipt_hash);
 
     let workflow_run_id_public = low64_from_h256(&workflow_run_h256);
@@ -44,7 +53,7 @@ pub fn process_proof_generate(
         + domain_separator_public as u128 * 19;
 
     let circuit_input = build_circuit_input(
-        &req,
+        outcome,
         workflow_run_id_public,
         receipt_hash_public,
         domain_separator_public,
@@ -53,64 +62,10 @@ pub fn process_proof_generate(
     )?;
 
     let artifacts = resolve_artifacts()?;
-    let run_id = unique_run_id();
-    let tmp_dir = env::temp_dir().join("zkclear-proof-generate");
-    fs::create_dir_all(&tmp_dir).map_err(|e| {
-        ProofError::Artifact(format!(
-            "failed to create temp dir `{}`: {e}",
-            tmp_dir.display()
-        ))
-    })?;
-
-    let input_file = tmp_dir.join(format!("input-{run_id}.json"));
-    let wtns_file = tmp_dir.join(format!("witness-{run_id}.wtns"));
-    let proof_file = tmp_dir.join(format!("proof-{run_id}.json"));
-    let public_file = tmp_dir.join(format!("public-{run_id}.json"));
-
-    fs::write(
-        &input_file,
-        serde_json::to_vec_pretty(&circuit_input)
-            .map_err(|e| ProofError::Artifact(e.to_string()))?,
-    )
-    .map_err(|e| {
-        ProofError::Artifact(format!("failed to write `{}`: {e}", input_file.display()))
-    })?;
-
-    run_snarkjs(&[
-        "wtns",
-        "calculate",
-        artifacts
-            .wasm
-            .to_str()
-            .ok_or_else(|| ProofError::Artifact("invalid wasm path".to_string()))?,
-        input_file
-            .to_str()
-            .ok_or_else(|| ProofError::Artifact("invalid input path".to_string()))?,
-        wtns_file
-            .to_str()
-            .ok_or_else(|| ProofError::Artifact("invalid wtns path".to_string()))?,
-    ])?;
-
-    run_snarkjs(&[
-        "groth16",
-        "prove",
-        artifacts
-            .zkey
-            .to_str()
-            .ok_or_else(|| ProofError::Artifact("invalid zkey path".to_string()))?,
-        wtns_file
-            .to_str()
-            .ok_or_else(|| ProofError::Artifact("invalid wtns path".to_string()))?,
-        proof_file
-            .to_str()
-            .ok_or_else(|| ProofError::Artifact("invalid proof path".to_string()))?,
-        public_file
-            .to_str()
-            .ok_or_else(|| ProofError::Artifact("invalid public path".to_string()))?,
-    ])?;
-
-    let proof_json: SnarkProof = read_json(&proof_file)?;
-    let public_signals: Vec<String> = read_json(&public_file)?;
+    let GeneratedProof {
+        proof: proof_json,
+        public_signals,
+    } = prover_from_env()?.prove(&artifacts, &circuit_input)?;
     if public_signals.len() != 6 {
         return Err(ProofError::Artifact(format!(
             "unexpected public signal length: got {}, expected 6",
@@ -181,63 +136,120 @@ fn enforce_timeout(req: &ProofGenerateRequest) -> Result<(), ProofError> {
     Ok(())
 }
 
-fn enforce_constraints(req: &ProofGenerateRequest) -> Result<(), ProofError> {
-    if !req.match_result.accepted {
+/// What the proof attests to: an accepted fill, or a rejected run bound to
+/// its reason code.
+#[derive(Clone, Copy)]
+enum ProvenOutcome<'a> {
+    Settlement(&'a SettlementParams),
+    Rejection(&'a str),
+}
+
+fn enforce_constraints(req: &ProofGenerateRequest) -> Result<ProvenOutcome<'_>, ProofError> {
+    if req.policy_result.policy_version == 0 {
         return Err(ProofError::CircuitConstraintFailure(
-            "match result not accepted".to_string(),
+            "policy version must be non-zero".to_string(),
         ));
     }
+    if !req.match_result.accepted {
+        return enforce_rejection_constraints(req);
+    }
     if !req.policy_result.passed {
         return Err(ProofError::CircuitConstraintFailure(
             "policy result not passed".to_string(),
         ));
     }
-    if req.policy_result.policy_version == 0 {
+    let params = req.settlement_params.as_ref().ok_or_else(|| {
+        ProofError::InvalidRequest("settlement_params required for accepted match".to_string())
+    })?;
+    if params.execution_size.is_zero() || params.execution_price.is_zero() {
         return Err(ProofError::CircuitConstraintFailure(
-            "policy version must be non-zero".to_string(),
+            "execution size/price must be positive".to_string(),
         ));
     }
-    if req.settlement_params.execution_size <= 0.0 || req.settlement_params.execution_price <= 0.0 {
+
+    let computed_notional = params
+        .execution_size
+        .checked_mul(&params.execution_price)
+        .map_err(|e| ProofError::CircuitConstraintFailure(format!("notional: {e}")))?;
+    if computed_notional.units() != params.notional.units()
+        || computed_notional.scale() != params.notional.scale()
+    {
         return Err(ProofError::CircuitConstraintFailure(
-            "execution size/price must be positive".to_string(),
+            "settlement notional mismatch".to_string(),
         ));
     }
+    Ok(ProvenOutcome::Settlement(params))
+}
 
-    let computed_notional =
-        req.settlement_params.execution_size * req.settlement_params.execution_price;
-    let delta = (computed_notional - req.settlement_params.notional).abs();
-    if delta > 1e-8 {
+fn enforce_rejection_constraints(
+    req: &ProofGenerateRequest,
+) -> Result<ProvenOutcome<'_>, ProofError> {
+    let reason = req
+        .match_result
+        .reject_reason
+        .as_deref()
+        .map(str::trim)
+        .filter(|reason| !reason.is_empty())
+        .ok_or_else(|| {
+            ProofError::CircuitConstraintFailure(
+                "match result not accepted and no reject_reason given".to_string(),
+            )
+        })?;
+    if req.settlement_params.is_some() {
         return Err(ProofError::CircuitConstraintFailure(
-            "settlement notional mismatch".to_string(),
+            "rejected match cannot carry settlement_params".to_string(),
         ));
     }
-    Ok(())
+    if req.policy_result.passed {
+        return Err(ProofError::CircuitConstraintFailure(
+            "rejected match must report a failed policy result".to_string(),
+        ));
+    }
+    Ok(ProvenOutcome::Rejection(reason))
 }
 
 fn build_circuit_input(
-    req: &ProofGenerateRequest,
+    outcome: ProvenOutcome<'_>,
     workflow_run_id_public: u64,
     receipt_hash_public: u64,
     domain_separator_public: u64,
     policy_version_public: u64,
     binding_hash_public: u128,
 ) -> Result<serde_json::Value, ProofError> {
-    let execution_size = req.settlement_params.execution_size.round() as u64;
-    let execution_price = req.settlement_params.execution_price.round() as u64;
-    if execution_size == 0 || execution_price == 0 {
-        return Err(ProofError::CircuitConstraintFailure(
-            "execution size/price rounded to zero".to_string(),
-        ));
-    }
-    let notional = execution_size
-        .checked_mul(execution_price)
-        .ok_or_else(|| ProofError::CircuitConstraintFailure("notional overflow".to_string()))?;
-
     let fee = 1u64;
+    let (execution_size, execution_price, notional, limit_price, max_notional) = match outcome {
+        // The circuit works on raw fixed-point units; `enforce_constraints`
+        // has already checked notional.units == size.units * price.units.
+        ProvenOutcome::Settlement(params) => {
+            let notional = circuit_u64("notional", params.notional)?;
+            if notional >= REJECTION_NOTIONAL_SIGNAL {
+                return Err(ProofError::CircuitConstraintFailure(format!(
+                    "notional {} is reserved for rejection proofs",
+                    params.notional
+                )));
+            }
+            let execution_price = circuit_u64("execution_price", params.execution_price)?;
+            (
+                circuit_u64("execution_size", params.execution_size)?,
+                execution_price,
+                notional,
+                checked_circuit_add("limit_price", execution_price, 100)?,
+                checked_circuit_add("max_notional", notional, 1000)?,
+            )
+        }
+        // Nothing moves on a rejection, but the circuit still needs positive
+        // amounts. Proving the reserved notional keeps the proof from passing
+        // for any fill; the receipt hash commits to the reason code.
+        ProvenOutcome::Rejection(_) => (
+            1,
+            REJECTION_NOTIONAL_SIGNAL,
+            REJECTION_NOTIONAL_SIGNAL,
+            REJECTION_NOTIONAL_SIGNAL,
+            REJECTION_NOTIONAL_SIGNAL,
+        ),
+    };
     let amount_out = notional;
-    let amount_in = amount_out + fee;
-    let limit_price = execution_price + 100;
-    let max_notional = notional + 1000;
+    let amount_in = checked_circuit_add("amount_in", amount_out, fee)?;
 
     Ok(json!({
         "amount_in": amount_in.to_string(),
@@ -261,43 +273,18 @@ fn build_circuit_input(
     }))
 }
 
-fn run_snarkjs(args: &[&str]) -> Result<(), ProofError> {
-    let bin = resolve_snarkjs_bin();
-    let output = Command::new(&bin)
-        .args(args)
-        .output()
-        .map_err(|e| ProofError::ProverCommand(format!("failed to execute `{bin}`: {e}")))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(ProofError::ProverCommand(format!(
-            "`{bin} {}` failed: {}",
-            args.join(" "),
-            stderr.trim()
-        )));
-    }
-    Ok(())
-}
-
-fn resolve_snarkjs_bin() -> String {
-    if let Ok(bin) = env::var("SNARKJS_BIN") {
-        if !bin.trim().is_empty() {
-            return bin;
-        }
-    }
-    let zk_root = env::var("ZK_ROOT").unwrap_or_else(|_| "../../zk".to_string());
-    let local = PathBuf::from(zk_root).join("node_modules/.bin/snarkjs");
-    if local.exists() {
-        return local.to_string_lossy().into_owned();
-    }
-    "snarkjs".to_string()
+fn circuit_u64(name: &str, value: Amount) -> Result<u64, ProofError> {
+    u64::try_from(value.units()).map_err(|_| {
+        ProofError::CircuitConstraintFailure(format!(
+            "{name} {value} exceeds the circuit's 64-bit range"
+        ))
+    })
 }
 
-fn read_json<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<T, ProofError> {
-    let raw = fs::read_to_string(path)
-        .map_err(|e| ProofError::Artifact(format!("failed to read `{}`: {e}", path.display())))?;
-    serde_json::from_str(&raw)
-        .map_err(|e| ProofError::Artifact(format!("invalid json `{}`: {e}", path.display())))
+fn checked_circuit_add(name: &str, a: u64, b: u64) -> Result<u64, ProofError> {
+    a.checked_add(b).ok_or_else(|| {
+        ProofError::CircuitConstraintFailure(format!("{name} exceeds the circuit's 64-bit range"))
+    })
 }
 
 fn hash_hex(parts: &[&[u8]]) -> String {
@@ -324,14 +311,6 @@ fn low64_from_h256(value: &H256) -> u64 {
     u64::from_be_bytes(low)
 }
 
-fn unique_run_id() -> String {
-    let nanos = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map(|d| d.as_nanos())
-        .unwrap_or(0);
-    format!("{nanos}")
-}
-
 fn encode_proof_bytes(proof: &SnarkProof) -> Result<Vec<u8>, ProofError> {
     if proof.pi_a.len() < 2 || proof.pi_b.len() < 2 || proof.pi_c.len() < 2 {
         return Err(ProofError::Artifact(
@@ -344,18 +323,18 @@ fn encode_proof_bytes(proof: &SnarkProof) -> Result<Vec<u8>, ProofError> {
         ));
     }
 
-    let p_a = vec![parse_u256(&proof.pi_a[0])?, parse_u256(&proof.pi_a[1])?];
-    let p_b = vec![
-        vec![
+    let p_a = [parse_u256(&proof.pi_a[0])?, parse_u256(&proof.pi_a[1])?];
+    let p_b = [
+        [
             parse_u256(&proof.pi_b[0][1])?,
             parse_u256(&proof.pi_b[0][0])?,
         ],
-        vec![
+        [
             parse_u256(&proof.pi_b[1][1])?,
             parse_u256(&proof.pi_b[1][0])?,
         ],
     ];
-    let p_c = vec![parse_u256(&proof.pi_c[0])?, parse_u256(&proof.pi_c[1])?];
+    let p_c = [parse_u256(&proof.pi_c[0])?, parse_u256(&proof.pi_c[1])?];
 
     Ok(encode(&[
         Token::FixedArray(vec![Token::Uint(p_a[0]), Token::Uint(p_a[1])]),
@@ -376,34 +355,8 @@ fn parse_u256(s: &str) -> Result<U256, ProofError> {
         .map_err(|e| ProofError::Artifact(format!("invalid decimal u256 `{s}`: {e}")))
 }
 
-struct ArtifactPaths {
-    wasm: PathBuf,
-    zkey: PathBuf,
-}
-
-fn resolve_artifacts() -> Result<ArtifactPaths, ProofError> {
-    let zk_root = env::var("ZK_ROOT").unwrap_or_else(|_| "../../zk".to_string());
-    let root = PathBuf::from(zk_root);
-    let wasm = root.join("artifacts/settlement_valid/settlement_valid_js/settlement_valid.wasm");
-    let zkey = root.join("artifacts/settlement_valid/settlement_valid.zkey");
-    if !wasm.exists() {
-        return Err(ProofError::Artifact(format!(
-            "missing wasm artifact `{}`",
-            wasm.display()
-        )));
-    }
-    if !zkey.exists() {
-        return Err(ProofError::Artifact(format!(
-            "missing zkey artifact `{}`",
-            zkey.display()
-        )));
-    }
-    Ok(ArtifactPaths { wasm, zkey })
-}
-
-#[derive(Debug, Deserialize)]
-struct SnarkProof {
-    pi_a: Vec<String>,
-    pi_b: Vec<Vec<String>>,
-    pi_c: Vec<String>,
+fn resolve_artifacts() -> Result<CircuitArtifacts, ProofError> {
+    let artifacts = CircuitArtifacts::for_circuit(&zk_root(), "settlement_valid");
+    artifacts.ensure_exists()?;
+    Ok(artifacts)
 }
@@ -1,3 +1,8 @@
 pub mod errors;
 pub mod handler;
 pub mod models;
+pub mod native;
+pub mod prover;
+pub mod snarkjs;
+pub mod witness;
+pub mod zkey;
@@ -1,3 +1,4 @@
+use fixed_amount::Amount;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Deserialize)]
@@ -5,7 +6,8 @@ pub struct ProofGenerateRequest {
     pub workflow_run_id: String,
     pub match_result: MatchResult,
     pub policy_result: PolicyResult,
-    pub settlement_params: SettlementParams,
+    /// Required for accepted matches; absent when proving a rejection.
+    pub settlement_params: Option<SettlementParams>,
     pub proving_timeout_ms: u64,
     pub estimated_proving_time_ms: u64,
     pub domain_separator: String,
@@ -15,6 +17,8 @@ pub struct ProofGenerateRequest {
 #[derive(Debug, Clone, Deserialize)]
 pub struct MatchResult {
     pub accepted: bool,
+    /// Reason code from confidential-match when `accepted` is false.
+    pub reject_reason: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -28,9 +32,9 @@ pub struct SettlementParams {
     pub asset_pair: String,
     pub buy_intent_id: String,
     pub sell_intent_id: String,
-    pub execution_size: f64,
-    pub execution_price: f64,
-    pub notional: f64,
+    pub execution_size: Amount,
+    pub execution_price: Amount,
+    pub notional: Amount,
 }
 
 #[derive(Debug, Clone, Serialize)]
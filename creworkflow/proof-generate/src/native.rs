@@ -0,0 +1,205 @@
+use ark_bn254::{Bn254, Fr, G1Affine, G2Affine};
+use ark_ff::{PrimeField, UniformRand};
+use ark_groth16::r1cs_to_qap::{evaluate_constraint, LibsnarkReduction, R1CSToQAP};
+use ark_groth16::Groth16;
+use ark_poly::EvaluationDomain;
+use ark_relations::r1cs::{ConstraintMatrices, ConstraintSystemRef, SynthesisError};
+use ark_std::rand::thread_rng;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::SystemTime;
+
+use crate::errors::ProofError;
+use crate::prover::{CircuitArtifacts, GeneratedProof, Prover, SnarkProof};
+use crate::witness::WitnessCalculator;
+use crate::zkey::Zkey;
+
+/// Computes the witness with the circuit's wasm and proves with arkworks
+/// over the snarkjs `.zkey`, without Node or temp files. Parsed artifacts
+/// are cached per circuit and reloaded when either file changes on disk.
+pub struct NativeProver;
+
+struct LoadedCircuit {
+    stamp: (Option<SystemTime>, Option<SystemTime>),
+    witness: WitnessCalculator,
+    zkey: Zkey,
+}
+
+type CircuitKey = (PathBuf, PathBuf);
+
+fn circuit_cache() -> &'static Mutex<HashMap<CircuitKey, Arc<LoadedCircuit>>> {
+    static CACHE: OnceLock<Mutex<HashMap<CircuitKey, Arc<LoadedCircuit>>>> = OnceLock::new();
+    CACHE.get_or_init(Default::default)
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// The wasm and zkey for `artifacts`, parsed once. Loading happens outside
+/// the lock, so a slow first load of one circuit doesn't stall the others.
+fn load_circuit(artifacts: &CircuitArtifacts) -> Result<Arc<LoadedCircuit>, ProofError> {
+    let key = (artifacts.wasm.clone(), artifacts.zkey.clone());
+    let stamp = (modified(&artifacts.wasm), modified(&artifacts.zkey));
+    let cached = circuit_cache()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(&key)
+        .filter(|loaded| loaded.stamp == stamp)
+        .cloned();
+    if let Some(loaded) = cached {
+        return Ok(loaded);
+    }
+
+    let loaded = Arc::new(LoadedCircuit {
+        stamp,
+        witness: WitnessCalculator::load(&artifacts.wasm)?,
+        zkey: Zkey::load(&artifacts.zkey)?,
+    });
+    circuit_cache()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(key, Arc::clone(&loaded));
+    Ok(loaded)
+}
+
+impl Prover for NativeProver {
+    fn prove(
+        &self,
+        artifacts: &CircuitArtifacts,
+        input: &Value,
+    ) -> Result<GeneratedProof, ProofError> {
+        let circuit = load_circuit(artifacts)?;
+        let witness = circuit.witness.calculate(input)?;
+        let zkey = &circuit.zkey;
+        let matrices = &zkey.matrices;
+        let n_vars = matrices.num_instance_variables + matrices.num_witness_variables - 1;
+        if witness.len() != n_vars {
+            return Err(ProofError::Artifact(format!(
+                "witness has {} signals but `{}` expects {n_vars}",
+                witness.len(),
+                artifacts.zkey.display()
+            )));
+        }
+
+        let mut rng = thread_rng();
+        let proof = Groth16::<Bn254, CircomReduction>::create_proof_with_reduction_and_matrices(
+            &zkey.proving_key,
+            Fr::rand(&mut rng),
+            Fr::rand(&mut rng),
+            matrices,
+            matrices.num_instance_variables,
+            matrices.num_constraints,
+            &witness,
+        )
+        .map_err(|e| ProofError::Artifact(format!("groth16 prover failed: {e}")))?;
+
+        Ok(GeneratedProof {
+            proof: SnarkProof {
+                pi_a: g1_json(&proof.a),
+                pi_b: g2_json(&proof.b),
+                pi_c: g1_json(&proof.c),
+                protocol: "groth16".to_string(),
+                curve: "bn128".to_string(),
+            },
+            public_signals: witness[1..matrices.num_instance_variables]
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+        })
+    }
+}
+
+/// The R1CS-to-QAP reduction snarkjs uses. Its `.zkey` H points are
+/// Lagrange bases over the coset shifted by a `2n`-th root of unity, so the
+/// prover hands over `A*B - C` evaluated there instead of the coefficients
+/// of `(A*B - C) / Z` that arkworks' own reduction produces.
+pub struct CircomReduction;
+
+impl R1CSToQAP for CircomReduction {
+    fn instance_map_with_evaluation<F: PrimeField, D: EvaluationDomain<F>>(
+        cs: ConstraintSystemRef<F>,
+        t: &F,
+    ) -> Result<(Vec<F>, Vec<F>, Vec<F>, F, usize, usize), SynthesisError> {
+        LibsnarkReduction::instance_map_with_evaluation::<F, D>(cs, t)
+    }
+
+    fn witness_map_from_matrices<F: PrimeField, D: EvaluationDomain<F>>(
+        matrices: &ConstraintMatrices<F>,
+        num_inputs: usize,
+        num_constraints: usize,
+        full_assignment: &[F],
+    ) -> Result<Vec<F>, SynthesisError> {
+        let domain =
+            D::new(num_constraints + num_inputs).ok_or(SynthesisError::PolynomialDegreeTooLarge)?;
+        let domain_size = domain.size();
+
+        let mut a = vec![F::zero(); domain_size];
+        let mut b = vec![F::zero(); domain_size];
+        let mut c = vec![F::zero(); domain_size];
+        for (i, (a_row, b_row)) in matrices.a.iter().zip(&matrices.b).enumerate() {
+            a[i] = evaluate_constraint(a_row, full_assignment);
+            b[i] = evaluate_constraint(b_row, full_assignment);
+            c[i] = a[i] * b[i];
+        }
+        a[num_constraints..num_constraints + num_inputs]
+            .clone_from_slice(&full_assignment[..num_inputs]);
+
+        let shift = D::new(2 * domain_size)
+            .ok_or(SynthesisError::PolynomialDegreeTooLarge)?
+            .element(1);
+        let coset = domain
+            .get_coset(shift)
+            .ok_or(SynthesisError::PolynomialDegreeTooLarge)?;
+        for evals in [&mut a, &mut b, &mut c] {
+            domain.ifft_in_place(evals);
+            coset.fft_in_place(evals);
+        }
+
+        Ok(a.iter()
+            .zip(&b)
+            .zip(&c)
+            .map(|((a, b), c)| *a * b - c)
+            .collect())
+    }
+
+    /// Only used by arkworks' own setup; circuit keys come from snarkjs.
+    fn h_query_scalars<F: PrimeField, D: EvaluationDomain<F>>(
+        max_power: usize,
+        t: F,
+        _: F,
+        delta_inverse: F,
+    ) -> Result<Vec<F>, SynthesisError> {
+        let mut scalars = (0..2 * max_power + 1)
+            .map(|i| delta_inverse * t.pow([i as u64]))
+            .collect::<Vec<_>>();
+        let domain = D::new(scalars.len()).ok_or(SynthesisError::PolynomialDegreeTooLarge)?;
+        domain.ifft_in_place(&mut scalars);
+        Ok(scalars.into_iter().skip(1).step_by(2).collect())
+    }
+}
+
+fn g1_json(point: &G1Affine) -> Vec<String> {
+    if point.infinity {
+        return vec!["0".to_string(), "1".to_string(), "0".to_string()];
+    }
+    vec![point.x.to_string(), point.y.to_string(), "1".to_string()]
+}
+
+fn g2_json(point: &G2Affine) -> Vec<Vec<String>> {
+    if point.infinity {
+        return vec![
+            vec!["0".to_string(), "0".to_string()],
+            vec!["1".to_string(), "0".to_string()],
+            vec!["0".to_string(), "0".to_string()],
+        ];
+    }
+    vec![
+        vec![point.x.c0.to_string(), point.x.c1.to_string()],
+        vec![point.y.c0.to_string(), point.y.c1.to_string()],
+        vec!["1".to_string(), "0".to_string()],
+    ]
+}
@@ -0,0 +1,102 @@
+use std::env;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::errors::ProofError;
+use crate::native::NativeProver;
+use crate::snarkjs::SnarkjsProver;
+
+/// Produces a Groth16 proof and its public signals for one circuit input.
+pub trait Prover {
+    fn prove(
+        &self,
+        artifacts: &CircuitArtifacts,
+        input: &Value,
+    ) -> Result<GeneratedProof, ProofError>;
+}
+
+/// Compiled witness generator and final proving key of one circuit.
+#[derive(Debug, Clone)]
+pub struct CircuitArtifacts {
+    pub wasm: PathBuf,
+    pub zkey: PathBuf,
+}
+
+#[derive(Debug, Clone)]
+pub struct GeneratedProof {
+    pub proof: SnarkProof,
+    pub public_signals: Vec<String>,
+}
+
+/// `proof.json` as written by `snarkjs groth16 prove`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnarkProof {
+    pub pi_a: Vec<String>,
+    pub pi_b: Vec<Vec<String>>,
+    pub pi_c: Vec<String>,
+    #[serde(default = "default_protocol")]
+    pub protocol: String,
+    #[serde(default = "default_curve")]
+    pub curve: String,
+}
+
+impl CircuitArtifacts {
+    /// `<zk_root>/artifacts/<circuit>/`, as laid out by `zk/scripts/compile.sh`
+    /// and `zk/scripts/setup.sh`.
+    pub fn for_circuit(zk_root: &Path, circuit: &str) -> Self {
+        let dir = zk_root.join("artifacts").join(circuit);
+        Self {
+            wasm: dir
+                .join(format!("{circuit}_js"))
+                .join(format!("{circuit}.wasm")),
+            zkey: dir.join(format!("{circuit}.zkey")),
+        }
+    }
+
+    pub fn ensure_exists(&self) -> Result<(), ProofError> {
+        if !self.wasm.exists() {
+            return Err(ProofError::Artifact(format!(
+                "missing wasm artifact `{}`",
+                self.wasm.display()
+            )));
+        }
+        if !self.zkey.exists() {
+            return Err(ProofError::Artifact(format!(
+                "missing zkey artifact `{}`",
+                self.zkey.display()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// `native` proves in-process; `snarkjs` shells out to the Node CLI and is
+/// kept as a fallback.
+pub fn prover_for_backend(backend: &str) -> Result<Box<dyn Prover + Send + Sync>, ProofError> {
+    match backend.trim() {
+        "" | "native" => Ok(Box::new(NativeProver)),
+        "snarkjs" => Ok(Box::new(SnarkjsProver)),
+        other => Err(ProofError::InvalidRequest(format!(
+            "unknown prover backend `{other}` (expected native or snarkjs)"
+        ))),
+    }
+}
+
+/// Backend named by `PROVER_BACKEND`, `native` when unset.
+pub fn prover_from_env() -> Result<Box<dyn Prover + Send + Sync>, ProofError> {
+    prover_for_backend(&env::var("PROVER_BACKEND").unwrap_or_default())
+}
+
+pub fn zk_root() -> PathBuf {
+    PathBuf::from(env::var("ZK_ROOT").unwrap_or_else(|_| "../../zk".to_string()))
+}
+
+fn default_protocol() -> String {
+    "groth16".to_string()
+}
+
+fn default_curve() -> String {
+    "bn128".to_string()
+}
@@ -0,0 +1,106 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::errors::ProofError;
+use crate::prover::{zk_root, CircuitArtifacts, GeneratedProof, Prover};
+
+/// Runs `snarkjs wtns calculate` and `snarkjs groth16 prove` through files
+/// in a per-call temp dir, removed when the call returns. Needs Node and
+/// snarkjs on the host.
+pub struct SnarkjsProver;
+
+impl Prover for SnarkjsProver {
+    fn prove(
+        &self,
+        artifacts: &CircuitArtifacts,
+        input: &Value,
+    ) -> Result<GeneratedProof, ProofError> {
+        let tmp_dir = tempfile::Builder::new()
+            .prefix("zkclear-proof-generate-")
+            .tempdir()
+            .map_err(|e| ProofError::Artifact(format!("failed to create temp dir: {e}")))?;
+
+        let input_file = tmp_dir.path().join("input.json");
+        let wtns_file = tmp_dir.path().join("witness.wtns");
+        let proof_file = tmp_dir.path().join("proof.json");
+        let public_file = tmp_dir.path().join("public.json");
+
+        fs::write(
+            &input_file,
+            serde_json::to_vec_pretty(input).map_err(|e| ProofError::Artifact(e.to_string()))?,
+        )
+        .map_err(|e| {
+            ProofError::Artifact(format!("failed to write `{}`: {e}", input_file.display()))
+        })?;
+
+        run_snarkjs(&[
+            "wtns",
+            "calculate",
+            path_arg(&artifacts.wasm, "wasm")?,
+            path_arg(&input_file, "input")?,
+            path_arg(&wtns_file, "wtns")?,
+        ])?;
+
+        run_snarkjs(&[
+            "groth16",
+            "prove",
+            path_arg(&artifacts.zkey, "zkey")?,
+            path_arg(&wtns_file, "wtns")?,
+            path_arg(&proof_file, "proof")?,
+            path_arg(&public_file, "public")?,
+        ])?;
+
+        Ok(GeneratedProof {
+            proof: read_json(&proof_file)?,
+            public_signals: read_json(&public_file)?,
+        })
+    }
+}
+
+fn run_snarkjs(args: &[&str]) -> Result<(), ProofError> {
+    let bin = resolve_snarkjs_bin();
+    let output = Command::new(&bin)
+        .args(args)
+        .output()
+        .map_err(|e| ProofError::ProverCommand(format!("failed to execute `{bin}`: {e}")))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(ProofError::ProverCommand(format!(
+            "`{bin} {}` failed: {}",
+            args.join(" "),
+            stderr.trim()
+        )));
+    }
+    Ok(())
+}
+
+fn resolve_snarkjs_bin() -> String {
+    if let Ok(bin) = env::var("SNARKJS_BIN") {
+        if !bin.trim().is_empty() {
+            return bin;
+        }
+    }
+    let local = zk_root().join("node_modules/.bin/snarkjs");
+    if local.exists() {
+        return local.to_string_lossy().into_owned();
+    }
+    "snarkjs".to_string()
+}
+
+fn path_arg<'a>(path: &'a Path, name: &str) -> Result<&'a str, ProofError> {
+    path.to_str()
+        .ok_or_else(|| ProofError::Artifact(format!("invalid {name} path")))
+}
+
+fn read_json<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<T, ProofError> {
+    let raw = fs::read_to_string(path)
+        .map_err(|e| ProofError::Artifact(format!("failed to read `{}`: {e}", path.display())))?;
+    serde_json::from_str(&raw)
+        .map_err(|e| ProofError::Artifact(format!("invalid json `{}`: {e}", path.display())))
+}
@@ -0,0 +1,278 @@
+use std::fs;
+use std::path::Path;
+
+use ark_bn254::Fr;
+use ark_ff::{BigInteger, PrimeField};
+use num_bigint::{BigInt, Sign};
+use serde_json::Value;
+use wasmi::{Caller, Engine, Extern, Instance, Linker, Module, Store, TypedFunc};
+
+use crate::errors::ProofError;
+
+/// Runs a circom 2 witness generator (`<circuit>_js/<circuit>.wasm`)
+/// in-process, speaking the same ABI as circom's `witness_calculator.js`.
+pub struct WitnessCalculator {
+    engine: Engine,
+    module: Module,
+}
+
+/// Messages the circuit prints before it traps, e.g. which template failed.
+#[derive(Default)]
+struct Runtime {
+    errors: String,
+}
+
+impl WitnessCalculator {
+    pub fn load(path: &Path) -> Result<Self, ProofError> {
+        let wasm = fs::read(path).map_err(|e| {
+            ProofError::Artifact(format!("failed to read `{}`: {e}", path.display()))
+        })?;
+        let engine = Engine::default();
+        let module = Module::new(&engine, &wasm[..])
+            .map_err(|e| ProofError::Artifact(format!("invalid wasm `{}`: {e}", path.display())))?;
+        Ok(Self { engine, module })
+    }
+
+    /// Full witness for `input` (a JSON object of signal name to value or
+    /// array of values), starting with the constant `1` and the public
+    /// signals.
+    pub fn calculate(&self, input: &Value) -> Result<Vec<Fr>, ProofError> {
+        let mut store = Store::new(&self.engine, Runtime::default());
+        let instance = self.instantiate(&mut store)?;
+        let export = |name: &'static str| Exported { name, instance };
+
+        let version: i32 = export("getVersion").call(&mut store, ())?;
+        if version != 2 {
+            return Err(ProofError::Artifact(format!(
+                "unsupported circom witness generator version {version}"
+            )));
+        }
+        let n32: i32 = export("getFieldNumLen32").call(&mut store, ())?;
+        let n32 = usize::try_from(n32).unwrap_or(0);
+        if n32 * 32 < Fr::MODULUS_BIT_SIZE as usize || n32 > 8 {
+            return Err(ProofError::Artifact(format!(
+                "unsupported witness field size of {n32} words"
+            )));
+        }
+        let read: TypedFunc<i32, i32> = export("readSharedRWMemory").typed(&store)?;
+        let write: TypedFunc<(i32, i32), ()> = export("writeSharedRWMemory").typed(&store)?;
+
+        export("getRawPrime").call::<(), ()>(&mut store, ())?;
+        let prime = read_words(&mut store, &read, n32)?;
+        if prime != Fr::MODULUS.to_bytes_le() {
+            return Err(ProofError::Artifact(
+                "witness generator is not compiled for BN254".to_string(),
+            ));
+        }
+
+        export("init").call::<i32, ()>(&mut store, 0)?;
+        let signals = input.as_object().ok_or_else(|| {
+            ProofError::WitnessGenerationFailure("circuit input must be a JSON object".to_string())
+        })?;
+        let size_of: TypedFunc<(i32, i32), i32> = export("getInputSignalSize").typed(&store)?;
+        let set: TypedFunc<(i32, i32, i32), ()> = export("setInputSignal").typed(&store)?;
+        for (name, value) in signals {
+            let (msb, lsb) = signal_hash(name);
+            let values = flatten_signal(name, value)?;
+            let expected = size_of
+                .call(&mut store, (msb, lsb))
+                .map_err(witness_error)?;
+            if expected < 0 {
+                return Err(ProofError::WitnessGenerationFailure(format!(
+                    "signal `{name}` is not an input of the circuit"
+                )));
+            }
+            if values.len() != expected as usize {
+                return Err(ProofError::WitnessGenerationFailure(format!(
+                    "signal `{name}` takes {expected} values, got {}",
+                    values.len()
+                )));
+            }
+            for (index, value) in values.iter().enumerate() {
+                let bytes = value.into_bigint().to_bytes_le();
+                for (word, chunk) in bytes.chunks(4).take(n32).enumerate() {
+                    let limb = u32::from_le_bytes(chunk.try_into().unwrap_or_default());
+                    write
+                        .call(&mut store, (word as i32, limb as i32))
+                        .map_err(witness_error)?;
+                }
+                set.call(&mut store, (msb, lsb, index as i32))
+                    .map_err(witness_error)?;
+            }
+        }
+
+        let witness_size: i32 = export("getWitnessSize").call(&mut store, ())?;
+        let get_witness: TypedFunc<i32, ()> = export("getWitness").typed(&store)?;
+        (0..witness_size)
+            .map(|i| {
+                get_witness.call(&mut store, i).map_err(witness_error)?;
+                let bytes = read_words(&mut store, &read, n32)?;
+                BigInt::from_bytes_le(Sign::Plus, &bytes)
+                    .to_biguint()
+                    .and_then(|value| ark_ff::BigInt::try_from(value).ok())
+                    .and_then(Fr::from_bigint)
+                    .ok_or_else(|| {
+                        ProofError::WitnessGenerationFailure(format!("witness {i} out of range"))
+                    })
+            })
+            .collect()
+    }
+
+    fn instantiate(&self, store: &mut Store<Runtime>) -> Result<Instance, ProofError> {
+        let mut linker = Linker::<Runtime>::new(&self.engine);
+        let link_error = |e: wasmi::errors::LinkerError| ProofError::Artifact(e.to_string());
+        linker
+            .func_wrap(
+                "runtime",
+                "exceptionHandler",
+                |caller: Caller<'_, Runtime>, code: i32| -> Result<(), wasmi::Error> {
+                    let reason = match code {
+                        1 => "signal not found",
+                        2 => "too many signals set",
+                        3 => "signal already set",
+                        4 => "assert failed",
+                        5 => "not enough memory",
+                        6 => "input signal array access exceeds the size",
+                        _ => "unknown error",
+                    };
+                    Err(wasmi::Error::new(format!(
+                        "{reason}{}",
+                        caller.data().errors
+                    )))
+                },
+            )
+            .map_err(link_error)?
+            .func_wrap(
+                "runtime",
+                "printErrorMessage",
+                |mut caller: Caller<'_, Runtime>| -> Result<(), wasmi::Error> {
+                    let message = read_message(&mut caller)?;
+                    caller.data_mut().errors.push_str(&format!("; {message}"));
+                    Ok(())
+                },
+            )
+            .map_err(link_error)?
+            .func_wrap(
+                "runtime",
+                "writeBufferMessage",
+                |mut caller: Caller<'_, Runtime>| -> Result<(), wasmi::Error> {
+                    // `log()` output from the circuit; drained and dropped.
+                    read_message(&mut caller).map(|_| ())
+                },
+            )
+            .map_err(link_error)?
+            .func_wrap("runtime", "showSharedRWMemory", |_: Caller<'_, Runtime>| {})
+            .map_err(link_error)?;
+
+        linker
+            .instantiate(&mut *store, &self.module)
+            .and_then(|pre| pre.start(&mut *store))
+            .map_err(|e| ProofError::Artifact(format!("failed to instantiate witness wasm: {e}")))
+    }
+}
+
+/// A named export, resolved on use so a missing one is reported by name.
+struct Exported {
+    name: &'static str,
+    instance: Instance,
+}
+
+impl Exported {
+    fn typed<P: wasmi::WasmParams, R: wasmi::WasmResults>(
+        &self,
+        store: &Store<Runtime>,
+    ) -> Result<TypedFunc<P, R>, ProofError> {
+        self.instance
+            .get_typed_func::<P, R>(store, self.name)
+            .map_err(|e| ProofError::Artifact(format!("witness wasm export `{}`: {e}", self.name)))
+    }
+
+    fn call<P: wasmi::WasmParams, R: wasmi::WasmResults>(
+        &self,
+        store: &mut Store<Runtime>,
+        params: P,
+    ) -> Result<R, ProofError> {
+        self.typed::<P, R>(store)?
+            .call(&mut *store, params)
+            .map_err(witness_error)
+    }
+}
+
+fn read_words(
+    store: &mut Store<Runtime>,
+    read: &TypedFunc<i32, i32>,
+    n32: usize,
+) -> Result<Vec<u8>, ProofError> {
+    let mut bytes = Vec::with_capacity(32);
+    for word in 0..n32 {
+        let limb = read.call(&mut *store, word as i32).map_err(witness_error)?;
+        bytes.extend_from_slice(&(limb as u32).to_le_bytes());
+    }
+    bytes.resize(32, 0);
+    Ok(bytes)
+}
+
+fn read_message(caller: &mut Caller<'_, Runtime>) -> Result<String, wasmi::Error> {
+    let next_char = caller
+        .get_export("getMessageChar")
+        .and_then(Extern::into_func)
+        .ok_or_else(|| wasmi::Error::new("witness wasm has no getMessageChar export"))?
+        .typed::<(), i32>(&*caller)?;
+    let mut message = String::new();
+    loop {
+        match next_char.call(&mut *caller, ())? {
+            0 => return Ok(message),
+            c => message.push(char::from_u32(c as u32).unwrap_or('?')),
+        }
+    }
+}
+
+fn witness_error(err: wasmi::Error) -> ProofError {
+    ProofError::WitnessGenerationFailure(err.to_string())
+}
+
+/// 64-bit FNV-1a of the signal name, split into the `(msb, lsb)` pair the
+/// generator's signal table is keyed by.
+fn signal_hash(name: &str) -> (i32, i32) {
+    let hash = name.bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    ((hash >> 32) as u32 as i32, hash as u32 as i32)
+}
+
+/// Arrays are flattened row-major; values are reduced modulo the field, so
+/// negative numbers wrap like they do in snarkjs.
+fn flatten_signal(name: &str, value: &Value) -> Result<Vec<Fr>, ProofError> {
+    match value {
+        Value::Array(items) => items
+            .iter()
+            .map(|item| flatten_signal(name, item))
+            .collect::<Result<Vec<_>, _>>()
+            .map(|nested| nested.concat()),
+        Value::String(raw) => parse_signal(name, raw).map(|v| vec![v]),
+        Value::Number(n) => parse_signal(name, &n.to_string()).map(|v| vec![v]),
+        Value::Bool(b) => Ok(vec![Fr::from(u64::from(*b))]),
+        _ => Err(ProofError::WitnessGenerationFailure(format!(
+            "signal `{name}` has an unsupported value"
+        ))),
+    }
+}
+
+fn parse_signal(name: &str, raw: &str) -> Result<Fr, ProofError> {
+    let raw = raw.trim();
+    let (negative, digits) = match raw.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, raw),
+    };
+    let parsed = match digits.strip_prefix("0x") {
+        Some(hex) => BigInt::parse_bytes(hex.as_bytes(), 16),
+        None => BigInt::parse_bytes(digits.as_bytes(), 10),
+    }
+    .ok_or_else(|| {
+        ProofError::WitnessGenerationFailure(format!("signal `{name}` is not a number: `{raw}`"))
+    })?;
+    let modulus = BigInt::from_bytes_le(Sign::Plus, &Fr::MODULUS.to_bytes_le());
+    let value = if negative { -parsed } else { parsed };
+    let (_, reduced) = ((value % &modulus) + &modulus).to_bytes_le();
+    Ok(Fr::from_le_bytes_mod_order(&reduced))
+}
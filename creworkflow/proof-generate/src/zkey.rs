@@ -0,0 +1,269 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use ark_bn254::{Bn254, Fq, Fq2, Fr, G1Affine, G2Affine};
+use ark_ff::{BigInt, PrimeField, Zero};
+use ark_groth16::{ProvingKey, VerifyingKey};
+use ark_relations::r1cs::ConstraintMatrices;
+
+use crate::errors::ProofError;
+
+/// Groth16 proving key and the A/B constraint matrices read from a snarkjs
+/// `.zkey`. Only BN254 keys are accepted.
+pub struct Zkey {
+    pub proving_key: ProvingKey<Bn254>,
+    pub matrices: ConstraintMatrices<Fr>,
+}
+
+struct Groth16Header {
+    n_vars: usize,
+    n_public: usize,
+    domain_size: usize,
+}
+
+const SECTION_HEADER: u32 = 1;
+const SECTION_GROTH16_HEADER: u32 = 2;
+const SECTION_IC: u32 = 3;
+const SECTION_COEFFS: u32 = 4;
+const SECTION_POINTS_A: u32 = 5;
+const SECTION_POINTS_B1: u32 = 6;
+const SECTION_POINTS_B2: u32 = 7;
+const SECTION_POINTS_C: u32 = 8;
+const SECTION_POINTS_H: u32 = 9;
+
+const GROTH16_PROTOCOL: u32 = 1;
+
+impl Zkey {
+    pub fn load(path: &Path) -> Result<Self, ProofError> {
+        let raw = fs::read(path).map_err(|e| {
+            ProofError::Artifact(format!("failed to read `{}`: {e}", path.display()))
+        })?;
+        Self::parse(&raw)
+            .map_err(|e| ProofError::Artifact(format!("invalid zkey `{}`: {e}", path.display())))
+    }
+
+    /// The layout is snarkjs' binary file format: a `zkey` magic, a version,
+    /// then `(id: u32, size: u64, bytes)` sections. Field elements are 32-byte
+    /// little-endian Montgomery form; points are affine with `(0, 0)` for the
+    /// identity.
+    pub fn parse(raw: &[u8]) -> Result<Self, String> {
+        let sections = read_sections(raw)?;
+        let section = |id: u32| {
+            sections
+                .get(&id)
+                .map(|bytes| Reader { bytes, pos: 0 })
+                .ok_or_else(|| format!("missing section {id}"))
+        };
+
+        let protocol = section(SECTION_HEADER)?.u32()?;
+        if protocol != GROTH16_PROTOCOL {
+            return Err(format!("unsupported protocol id {protocol}"));
+        }
+
+        let mut reader = section(SECTION_GROTH16_HEADER)?;
+        reader.modulus::<Fq>("base")?;
+        reader.modulus::<Fr>("scalar")?;
+        let header = Groth16Header {
+            n_vars: reader.u32()? as usize,
+            n_public: reader.u32()? as usize,
+            domain_size: reader.u32()? as usize,
+        };
+        let alpha_g1 = reader.g1()?;
+        let beta_g1 = reader.g1()?;
+        let beta_g2 = reader.g2()?;
+        let gamma_g2 = reader.g2()?;
+        let delta_g1 = reader.g1()?;
+        let delta_g2 = reader.g2()?;
+
+        let n_private = header
+            .n_vars
+            .checked_sub(header.n_public + 1)
+            .ok_or("fewer variables than public signals")?;
+        let proving_key = ProvingKey {
+            vk: VerifyingKey {
+                alpha_g1,
+                beta_g2,
+                gamma_g2,
+                delta_g2,
+                gamma_abc_g1: section(SECTION_IC)?.g1s(header.n_public + 1)?,
+            },
+            beta_g1,
+            delta_g1,
+            a_query: section(SECTION_POINTS_A)?.g1s(header.n_vars)?,
+            b_g1_query: section(SECTION_POINTS_B1)?.g1s(header.n_vars)?,
+            b_g2_query: section(SECTION_POINTS_B2)?.g2s(header.n_vars)?,
+            h_query: section(SECTION_POINTS_H)?.g1s(header.domain_size)?,
+            l_query: section(SECTION_POINTS_C)?.g1s(n_private)?,
+        };
+        let matrices = read_matrices(section(SECTION_COEFFS)?, &header)?;
+        Ok(Self {
+            proving_key,
+            matrices,
+        })
+    }
+}
+
+/// snarkjs appends one `A` row per public input (`1 * signal_i`) after the
+/// circuit's constraints. Those rows are dropped here because the circom
+/// QAP reduction adds them back itself.
+fn read_matrices(
+    mut reader: Reader<'_>,
+    header: &Groth16Header,
+) -> Result<ConstraintMatrices<Fr>, String> {
+    let n_coeffs = reader.u32()?;
+    let mut rows = [
+        vec![Vec::new(); header.domain_size],
+        vec![Vec::new(); header.domain_size],
+    ];
+    let mut max_constraint = 0;
+    for _ in 0..n_coeffs {
+        let matrix = reader.u32()? as usize;
+        let constraint = reader.u32()? as usize;
+        let signal = reader.u32()? as usize;
+        let value = reader.coefficient()?;
+        if matrix > 1 || constraint >= header.domain_size || signal >= header.n_vars {
+            return Err(format!(
+                "coefficient ({matrix}, {constraint}, {signal}) out of range"
+            ));
+        }
+        max_constraint = max_constraint.max(constraint);
+        rows[matrix][constraint].push((value, signal));
+    }
+
+    let num_constraints = max_constraint
+        .checked_sub(header.n_public)
+        .ok_or("coefficients do not cover the public input rows")?;
+    let [mut a, mut b] = rows;
+    a.truncate(num_constraints);
+    b.truncate(num_constraints);
+    Ok(ConstraintMatrices {
+        num_instance_variables: header.n_public + 1,
+        num_witness_variables: header.n_vars - header.n_public,
+        num_constraints,
+        a_num_non_zero: a.iter().map(Vec::len).sum(),
+        b_num_non_zero: b.iter().map(Vec::len).sum(),
+        c_num_non_zero: 0,
+        a,
+        b,
+        c: Vec::new(),
+    })
+}
+
+fn read_sections(raw: &[u8]) -> Result<HashMap<u32, &[u8]>, String> {
+    let mut reader = Reader { bytes: raw, pos: 0 };
+    if reader.take(4)? != b"zkey" {
+        return Err("not a zkey file".to_string());
+    }
+    let version = reader.u32()?;
+    if version != 1 {
+        return Err(format!("unsupported zkey version {version}"));
+    }
+    let n_sections = reader.u32()?;
+    let mut sections = HashMap::new();
+    for _ in 0..n_sections {
+        let id = reader.u32()?;
+        let size = usize::try_from(reader.u64()?).map_err(|_| "section too large")?;
+        sections.insert(id, reader.take(size)?);
+    }
+    Ok(sections)
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or("unexpected end of section")?;
+        let bytes = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        Ok(u32::from_le_bytes(
+            self.take(4)?.try_into().unwrap_or_default(),
+        ))
+    }
+
+    fn u64(&mut self) -> Result<u64, String> {
+        Ok(u64::from_le_bytes(
+            self.take(8)?.try_into().unwrap_or_default(),
+        ))
+    }
+
+    fn bigint(&mut self) -> Result<BigInt<4>, String> {
+        let bytes = self.take(32)?;
+        let mut limbs = [0u64; 4];
+        for (limb, chunk) in limbs.iter_mut().zip(bytes.chunks(8)) {
+            *limb = u64::from_le_bytes(chunk.try_into().unwrap_or_default());
+        }
+        Ok(BigInt(limbs))
+    }
+
+    fn modulus<F: PrimeField<BigInt = BigInt<4>>>(&mut self, name: &str) -> Result<(), String> {
+        let size = self.u32()?;
+        if size != 32 || self.bigint()? != F::MODULUS {
+            return Err(format!("{name} field is not BN254"));
+        }
+        Ok(())
+    }
+
+    /// Montgomery form, so the limbs are the field's internal representation.
+    fn fq(&mut self) -> Result<Fq, String> {
+        let repr = self.bigint()?;
+        if repr >= Fq::MODULUS {
+            return Err("base field element out of range".to_string());
+        }
+        Ok(Fq::new_unchecked(repr))
+    }
+
+    /// Coefficients are stored as `value * R^2 mod r`; taking the Montgomery
+    /// form twice leaves `value`.
+    fn coefficient(&mut self) -> Result<Fr, String> {
+        let repr = self.bigint()?;
+        if repr >= Fr::MODULUS {
+            return Err("coefficient out of range".to_string());
+        }
+        Ok(Fr::new_unchecked(Fr::new_unchecked(repr).into_bigint()))
+    }
+
+    fn g1(&mut self) -> Result<G1Affine, String> {
+        let (x, y) = (self.fq()?, self.fq()?);
+        if x.is_zero() && y.is_zero() {
+            return Ok(G1Affine::identity());
+        }
+        let point = G1Affine::new_unchecked(x, y);
+        if !point.is_on_curve() {
+            return Err("G1 point is not on the BN254 curve".to_string());
+        }
+        Ok(point)
+    }
+
+    fn g2(&mut self) -> Result<G2Affine, String> {
+        let x = Fq2::new(self.fq()?, self.fq()?);
+        let y = Fq2::new(self.fq()?, self.fq()?);
+        if x.is_zero() && y.is_zero() {
+            return Ok(G2Affine::identity());
+        }
+        let point = G2Affine::new_unchecked(x, y);
+        if !point.is_on_curve() {
+            return Err("G2 point is not on the BN254 twist".to_string());
+        }
+        Ok(point)
+    }
+
+    fn g1s(&mut self, count: usize) -> Result<Vec<G1Affine>, String> {
+        (0..count).map(|_| self.g1()).collect()
+    }
+
+    fn g2s(&mut self, count: usize) -> Result<Vec<G2Affine>, String> {
+        (0..count).map(|_| self.g2()).collect()
+    }
+}
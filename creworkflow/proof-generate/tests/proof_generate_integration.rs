@@ -1,23 +1,33 @@
+use std::fs;
+
+use fixed_amount::Amount;
 use proof_generate::errors::ProofError;
-use proof_generate::handler::process_proof_generate;
+use proof_generate::handler::{process_proof_generate, REJECTION_NOTIONAL_SIGNAL};
 use proof_generate::models::{MatchResult, PolicyResult, ProofGenerateRequest, SettlementParams};
+use proof_generate::native::NativeProver;
+use proof_generate::prover::{prover_for_backend, zk_root, CircuitArtifacts, Prover};
+use publish_receipt::verifier::{verifying_key_path, Groth16Proof, VerifyingKey};
+use serde_json::Value;
 
 fn request_template() -> ProofGenerateRequest {
     ProofGenerateRequest {
         workflow_run_id: "run-777".to_string(),
-        match_result: MatchResult { accepted: true },
+        match_result: MatchResult {
+            accepted: true,
+            reject_reason: None,
+        },
         policy_result: PolicyResult {
             passed: true,
             policy_version: 1,
         },
-        settlement_params: SettlementParams {
+        settlement_params: Some(SettlementParams {
             asset_pair: "ETH/USDC".to_string(),
             buy_intent_id: "intent-buy".to_string(),
             sell_intent_id: "intent-sell".to_string(),
-            execution_size: 4.0,
-            execution_price: 3000.0,
-            notional: 12000.0,
-        },
+            execution_size: "4.0000".parse().expect("size"),
+            execution_price: "3000.00".parse().expect("price"),
+            notional: "12000.000000".parse().expect("notional"),
+        }),
         proving_timeout_ms: 3000,
         estimated_proving_time_ms: 700,
         domain_separator: "zkclear-sepolia-domain-v1".to_string(),
@@ -38,6 +48,64 @@ fn proof_generate_success() {
     assert!(out.domain_binding_hash.starts_with("0x"));
 }
 
+#[test]
+fn native_proof_verifies_against_exported_key() {
+    let out = process_proof_generate(request_template()).expect("proof generation should succeed");
+
+    let key = VerifyingKey::load(&verifying_key_path("settlement_valid")).expect("vkey");
+    let proof = Groth16Proof::from_calldata(&out.proof_bytes).expect("calldata");
+    key.verify(&proof, &out.public_signals)
+        .expect("native proof must verify");
+}
+
+#[test]
+fn native_prover_handles_other_circuits() {
+    let fixture = fs::read_to_string(zk_root().join("fixtures/compliance_valid.fixture.json"))
+        .expect("fixture");
+    let input: Value = serde_json::from_str(&fixture).expect("fixture json");
+    let artifacts = CircuitArtifacts::for_circuit(&zk_root(), "compliance_valid");
+
+    let generated = NativeProver.prove(&artifacts, &input).expect("prove");
+    assert_eq!(generated.public_signals, ["7", "123456", "98765", "55555"]);
+
+    let key = VerifyingKey::load(&verifying_key_path("compliance_valid")).expect("vkey");
+    let proof_json = serde_json::to_value(&generated.proof).expect("proof json");
+    key.verify(
+        &Groth16Proof::from_snarkjs_json(&proof_json).expect("proof"),
+        &generated.public_signals,
+    )
+    .expect("native proof must verify");
+}
+
+#[test]
+fn native_prover_reports_unsatisfied_inputs_as_witness_failures() {
+    let fixture = fs::read_to_string(zk_root().join("fixtures/compliance_valid.fixture.json"))
+        .expect("fixture");
+    let artifacts = CircuitArtifacts::for_circuit(&zk_root(), "compliance_valid");
+
+    let mut input: Value = serde_json::from_str(&fixture).expect("fixture json");
+    input["attestation_hash_public"] = Value::String("654321".to_string());
+    let err = NativeProver
+        .prove(&artifacts, &input)
+        .expect_err("must fail");
+    assert!(matches!(err, ProofError::WitnessGenerationFailure(_)));
+
+    let mut input: Value = serde_json::from_str(&fixture).expect("fixture json");
+    input["not_a_signal"] = Value::String("1".to_string());
+    let err = NativeProver
+        .prove(&artifacts, &input)
+        .expect_err("must fail");
+    assert!(matches!(err, ProofError::WitnessGenerationFailure(_)));
+}
+
+#[test]
+fn prover_backend_must_be_known() {
+    assert!(prover_for_backend("native").is_ok());
+    assert!(prover_for_backend("snarkjs").is_ok());
+    let err = prover_for_backend("rapidsnark").err().expect("must fail");
+    assert!(matches!(err, ProofError::InvalidRequest(_)));
+}
+
 #[test]
 fn proof_generate_fails_on_constraint_failure() {
     let mut req = request_template();
@@ -47,6 +115,77 @@ fn proof_generate_fails_on_constraint_failure() {
     assert!(matches!(err, ProofError::CircuitConstraintFailure(_)));
 }
 
+#[test]
+fn proof_generate_rejection_requires_reason_without_settlement() {
+    let mut req = request_template();
+    req.match_result.accepted = false;
+    req.match_result.reject_reason = Some("RISK_THRESHOLD_FAIL".to_string());
+    req.policy_result.passed = false;
+
+    let err = process_proof_generate(req.clone()).expect_err("must fail");
+    assert!(matches!(err, ProofError::CircuitConstraintFailure(_)));
+
+    req.match_result.reject_reason = None;
+    req.settlement_params = None;
+    let err = process_proof_generate(req).expect_err("must fail");
+    assert!(matches!(err, ProofError::CircuitConstraintFailure(_)));
+}
+
+#[test]
+fn rejection_proof_carries_the_reserved_notional_signal() {
+    let mut req = request_template();
+    req.match_result.accepted = false;
+    req.match_result.reject_reason = Some("NO_MATCH".to_string());
+    req.policy_result.passed = false;
+    req.settlement_params = None;
+
+    let out = process_proof_generate(req).expect("rejections are proven");
+    assert_eq!(out.public_signals[5], REJECTION_NOTIONAL_SIGNAL.to_string());
+    let key = VerifyingKey::load(&verifying_key_path("settlement_valid")).expect("vkey");
+    let proof = Groth16Proof::from_calldata(&out.proof_bytes).expect("calldata");
+    key.verify(&proof, &out.public_signals)
+        .expect("rejection proof must verify");
+
+    let mut req = request_template();
+    let params = req.settlement_params.as_mut().expect("params");
+    params.execution_size = "1".parse().expect("size");
+    params.execution_price =
+        Amount::from_units(u128::from(REJECTION_NOTIONAL_SIGNAL), 0).expect("price");
+    params.notional = params.execution_price;
+    let err = process_proof_generate(req).expect_err("reserved notional");
+    assert!(matches!(err, ProofError::CircuitConstraintFailure(_)));
+}
+
+#[test]
+fn proof_generate_requires_settlement_params_when_accepted() {
+    let mut req = request_template();
+    req.settlement_params = None;
+
+    let err = process_proof_generate(req).expect_err("must fail");
+    assert!(matches!(err, ProofError::InvalidRequest(_)));
+}
+
+#[test]
+fn proof_generate_fails_on_inexact_notional() {
+    let mut req = request_template();
+    let params = req.settlement_params.as_mut().expect("params");
+    params.notional = "12000.000001".parse().expect("notional");
+
+    let err = process_proof_generate(req).expect_err("must fail");
+    assert!(matches!(err, ProofError::CircuitConstraintFailure(_)));
+}
+
+#[test]
+fn proof_generate_fails_when_units_exceed_circuit_range() {
+    let mut req = request_template();
+    let params = req.settlement_params.as_mut().expect("params");
+    params.execution_size = "40000000000.0000".parse().expect("size");
+    params.notional = "120000000000000.000000".parse().expect("notional");
+
+    let err = process_proof_generate(req).expect_err("must fail");
+    assert!(matches!(err, ProofError::CircuitConstraintFailure(_)));
+}
+
 #[test]
 fn proof_generate_fails_on_witness_generation_failure() {
     let mut req = request_template();
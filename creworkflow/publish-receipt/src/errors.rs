@@ -14,6 +14,12 @@ pub enum PublishError {
     #[error("invalid proof")]
     InvalidProof,
 
+    #[error("malformed proof: {0}")]
+    MalformedProof(String),
+
+    #[error("verifying key unavailable: {0}")]
+    VerifyingKey(String),
+
     #[error("invalid signal binding")]
     InvalidSignalBinding,
 
@@ -1,13 +1,21 @@
 use sha2::{Digest, Sha256};
 
 use crate::errors::PublishError;
-use crate::models::{PublishReceiptRequest, PublishReceiptResponse, StoredReceiptRecord};
+use crate::models::{
+    PublishReceiptRequest, PublishReceiptResponse, SettlementStatus, StoredReceiptRecord,
+};
+use crate::verifier::verify_request;
+
+/// `notional_public` that proof-generate proves for a rejected run
+/// (`proof_generate::handler::REJECTION_NOTIONAL_SIGNAL`).
+pub const REJECTION_NOTIONAL_SIGNAL: u64 = u64::MAX - 1;
 
 pub fn process_publish_receipt(
     req: PublishReceiptRequest,
 ) -> Result<PublishReceiptResponse, PublishError> {
     validate_shape(&req)?;
     enforce_contract_checks(&req)?;
+    verify_request(&req)?;
 
     let tx_hash = hash_hex(&[
         b"tx",
@@ -83,6 +91,13 @@ fn validate_shape(req: &PublishReceiptRequest) -> Result<(), PublishError> {
             "public_signals must include binding/run/receipt/policy/domain/notional".to_string(),
         ));
     }
+    let proves_rejection = req.public_signals[5] == REJECTION_NOTIONAL_SIGNAL.to_string();
+    if proves_rejection != (req.status == SettlementStatus::Rejected) {
+        return Err(PublishError::InvalidRequest(format!(
+            "status {} does not match the proof: only rejection proofs carry notional {REJECTION_NOTIONAL_SIGNAL}",
+            req.status_as_str()
+        )));
+    }
     Ok(())
 }
 
@@ -93,9 +108,6 @@ fn enforce_contract_checks(req: &PublishReceiptRequest) -> Result<(), PublishErr
     if !req.chain_validation.policy_active {
         return Err(PublishError::StalePolicy);
     }
-    if !req.chain_validation.proof_valid {
-        return Err(PublishError::InvalidProof);
-    }
     if !req.chain_validation.signal_binding_valid {
         return Err(PublishError::InvalidSignalBinding);
     }
@@ -123,10 +135,10 @@ trait StatusRender {
 impl StatusRender for PublishReceiptRequest {
     fn status_as_str(&self) -> &'static str {
         match self.status {
-            crate::models::SettlementStatus::Accepted => "accepted",
-            crate::models::SettlementStatus::Rejected => "rejected",
-            crate::models::SettlementStatus::Settled => "settled",
-            crate::models::SettlementStatus::Failed => "failed",
+            SettlementStatus::Accepted => "accepted",
+            SettlementStatus::Rejected => "rejected",
+            SettlementStatus::Settled => "settled",
+            SettlementStatus::Failed => "failed",
         }
     }
 }
@@ -2,3 +2,4 @@ pub mod chain;
 pub mod errors;
 pub mod handler;
 pub mod models;
+pub mod verifier;
@@ -4,6 +4,7 @@ use publish_receipt::chain::{process_publish_receipt_onchain, ChainConfig};
 use publish_receipt::errors::PublishError;
 use publish_receipt::handler::process_publish_receipt;
 use publish_receipt::models::PublishReceiptRequest;
+use publish_receipt::verifier::verify_request;
 use tokio::runtime::Runtime;
 
 fn main() {
@@ -23,6 +24,7 @@ fn run() -> Result<(), PublishError> {
         .map_err(|e| PublishError::InvalidRequest(format!("invalid json input: {e}")))?;
 
     let response = if let Some(cfg) = read_chain_config()? {
+        verify_request(&request)?;
         let rt = Runtime::new()
             .map_err(|e| PublishError::Onchain(format!("runtime init failed: {e}")))?;
         rt.block_on(process_publish_receipt_onchain(request, cfg))?
@@ -23,11 +23,12 @@ pub struct PublishReceiptRequest {
     pub chain_validation: ChainValidationState,
 }
 
+/// Registry state as observed by the caller. Proof validity is not part of
+/// it: `proof_hex` is verified locally against the circuit's verifying key.
 #[derive(Debug, Clone, Deserialize)]
 pub struct ChainValidationState {
     pub authorized_publisher: bool,
     pub policy_active: bool,
-    pub proof_valid: bool,
     pub signal_binding_valid: bool,
     pub duplicate_workflow_run: bool,
     pub duplicate_receipt_hash: bool,
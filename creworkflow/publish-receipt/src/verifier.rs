@@ -0,0 +1,253 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use ark_bn254::{Bn254, Fq, Fq2, Fr, G1Affine, G2Affine};
+use ark_ff::{One, PrimeField, Zero};
+use ark_groth16::{prepare_verifying_key, Groth16, PreparedVerifyingKey, Proof};
+use num_bigint::BigUint;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::errors::PublishError;
+use crate::models::PublishReceiptRequest;
+
+/// BN254 Groth16 verifying key exported by `snarkjs zkey export
+/// verificationkey` (`zk/artifacts/<circuit>/<circuit>.vkey.json`).
+pub struct VerifyingKey {
+    prepared: PreparedVerifyingKey<Bn254>,
+    n_public: usize,
+}
+
+/// A proof as snarkjs writes it or as `SettlementRegistry` receives it.
+pub struct Groth16Proof(Proof<Bn254>);
+
+#[derive(Deserialize)]
+struct SnarkjsVerifyingKey {
+    protocol: String,
+    curve: String,
+    #[serde(rename = "nPublic")]
+    n_public: usize,
+    vk_alpha_1: Vec<String>,
+    vk_beta_2: Vec<Vec<String>>,
+    vk_gamma_2: Vec<Vec<String>>,
+    vk_delta_2: Vec<Vec<String>>,
+    #[serde(rename = "IC")]
+    ic: Vec<Vec<String>>,
+}
+
+#[derive(Deserialize)]
+struct SnarkjsProof {
+    pi_a: Vec<String>,
+    pi_b: Vec<Vec<String>>,
+    pi_c: Vec<String>,
+}
+
+impl VerifyingKey {
+    pub fn load(path: &Path) -> Result<Self, PublishError> {
+        let raw = fs::read_to_string(path).map_err(|e| {
+            PublishError::VerifyingKey(format!("failed to read `{}`: {e}", path.display()))
+        })?;
+        let json: Value = serde_json::from_str(&raw).map_err(|e| {
+            PublishError::VerifyingKey(format!("invalid json `{}`: {e}", path.display()))
+        })?;
+        Self::from_snarkjs_json(&json)
+    }
+
+    pub fn from_snarkjs_json(json: &Value) -> Result<Self, PublishError> {
+        let key_error = |msg: String| PublishError::VerifyingKey(msg);
+        let vk: SnarkjsVerifyingKey = serde_json::from_value(json.clone())
+            .map_err(|e| key_error(format!("unexpected verifying key shape: {e}")))?;
+        if vk.protocol != "groth16" || vk.curve != "bn128" {
+            return Err(key_error(format!(
+                "unsupported verifying key {}/{}",
+                vk.protocol, vk.curve
+            )));
+        }
+        if vk.ic.len() != vk.n_public + 1 {
+            return Err(key_error(format!(
+                "IC has {} points for {} public signals",
+                vk.ic.len(),
+                vk.n_public
+            )));
+        }
+
+        let key = ark_groth16::VerifyingKey::<Bn254> {
+            alpha_g1: g1_point(&vk.vk_alpha_1).map_err(key_error)?,
+            beta_g2: g2_point(&vk.vk_beta_2).map_err(key_error)?,
+            gamma_g2: g2_point(&vk.vk_gamma_2).map_err(key_error)?,
+            delta_g2: g2_point(&vk.vk_delta_2).map_err(key_error)?,
+            gamma_abc_g1: vk
+                .ic
+                .iter()
+                .map(|point| g1_point(point))
+                .collect::<Result<_, _>>()
+                .map_err(key_error)?,
+        };
+        Ok(Self {
+            prepared: prepare_verifying_key(&key),
+            n_public: vk.n_public,
+        })
+    }
+
+    /// Fails with [`PublishError::InvalidProof`] unless the pairing check
+    /// passes for exactly `n_public` signals, each below the BN254 scalar
+    /// modulus (the same bound the Solidity verifier enforces).
+    pub fn verify(
+        &self,
+        proof: &Groth16Proof,
+        public_signals: &[String],
+    ) -> Result<(), PublishError> {
+        if public_signals.len() != self.n_public {
+            return Err(PublishError::MalformedProof(format!(
+                "expected {} public signals, got {}",
+                self.n_public,
+                public_signals.len()
+            )));
+        }
+        let inputs = public_signals
+            .iter()
+            .map(|signal| field_element::<Fr>(signal))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(PublishError::MalformedProof)?;
+
+        match Groth16::<Bn254>::verify_proof(&self.prepared, &proof.0, &inputs) {
+            Ok(true) => Ok(()),
+            _ => Err(PublishError::InvalidProof),
+        }
+    }
+}
+
+impl Groth16Proof {
+    /// `proof.json` from `snarkjs groth16 prove`.
+    pub fn from_snarkjs_json(json: &Value) -> Result<Self, PublishError> {
+        let proof: SnarkjsProof = serde_json::from_value(json.clone())
+            .map_err(|e| PublishError::MalformedProof(format!("unexpected proof shape: {e}")))?;
+        Ok(Self(Proof {
+            a: g1_point(&proof.pi_a).map_err(PublishError::MalformedProof)?,
+            b: g2_point(&proof.pi_b).map_err(PublishError::MalformedProof)?,
+            c: g1_point(&proof.pi_c).map_err(PublishError::MalformedProof)?,
+        }))
+    }
+
+    /// ABI-encoded `(uint256[2] a, uint256[2][2] b, uint256[2] c)` as sent to
+    /// `SettlementRegistry`, with each `b` coordinate in Solidity's
+    /// `[c1, c0]` order.
+    pub fn from_calldata(bytes: &[u8]) -> Result<Self, PublishError> {
+        if bytes.len() != 8 * 32 {
+            return Err(PublishError::MalformedProof(format!(
+                "proof calldata must be 256 bytes, got {}",
+                bytes.len()
+            )));
+        }
+        let word = |i: usize| BigUint::from_bytes_be(&bytes[i * 32..(i + 1) * 32]).to_string();
+        let a = vec![word(0), word(1), "1".to_string()];
+        let b = vec![
+            vec![word(3), word(2)],
+            vec![word(5), word(4)],
+            vec!["1".to_string(), "0".to_string()],
+        ];
+        let c = vec![word(6), word(7), "1".to_string()];
+        Ok(Self(Proof {
+            a: g1_point(&a).map_err(PublishError::MalformedProof)?,
+            b: g2_point(&b).map_err(PublishError::MalformedProof)?,
+            c: g1_point(&c).map_err(PublishError::MalformedProof)?,
+        }))
+    }
+
+    pub fn from_hex(proof_hex: &str) -> Result<Self, PublishError> {
+        let stripped = proof_hex.strip_prefix("0x").unwrap_or(proof_hex);
+        let bytes = hex::decode(stripped)
+            .map_err(|e| PublishError::MalformedProof(format!("invalid proof_hex: {e}")))?;
+        Self::from_calldata(&bytes)
+    }
+}
+
+/// Verifies the request's `proof_hex` against the settlement circuit's
+/// verifying key before anything is published.
+pub fn verify_request(req: &PublishReceiptRequest) -> Result<(), PublishError> {
+    let key = VerifyingKey::load(&verifying_key_path("settlement_valid"))?;
+    key.verify(
+        &Groth16Proof::from_hex(&req.proof_hex)?,
+        &req.public_signals,
+    )
+}
+
+/// `$ZK_ROOT/artifacts/<circuit>/<circuit>.vkey.json`, with the same
+/// `ZK_ROOT` default as proof-generate.
+pub fn verifying_key_path(circuit: &str) -> PathBuf {
+    let zk_root = env::var("ZK_ROOT").unwrap_or_else(|_| "../../zk".to_string());
+    PathBuf::from(zk_root)
+        .join("artifacts")
+        .join(circuit)
+        .join(format!("{circuit}.vkey.json"))
+}
+
+/// snarkjs points are projective `[x, y, z]` with `z = 1`, or `[0, 1, 0]`
+/// for the identity.
+fn g1_point(coords: &[String]) -> Result<G1Affine, String> {
+    let [x, y, z] = coords else {
+        return Err(format!(
+            "G1 point needs 3 coordinates, got {}",
+            coords.len()
+        ));
+    };
+    let (x, y, z) = (
+        field_element::<Fq>(x)?,
+        field_element::<Fq>(y)?,
+        field_element::<Fq>(z)?,
+    );
+    if z.is_zero() {
+        return Ok(G1Affine::identity());
+    }
+    if !z.is_one() {
+        return Err("G1 point is not affine".to_string());
+    }
+    let point = G1Affine::new_unchecked(x, y);
+    if !point.is_on_curve() || !point.is_in_correct_subgroup_assuming_on_curve() {
+        return Err("G1 point is not on the BN254 curve".to_string());
+    }
+    Ok(point)
+}
+
+fn g2_point(coords: &[Vec<String>]) -> Result<G2Affine, String> {
+    let [x, y, z] = coords else {
+        return Err(format!(
+            "G2 point needs 3 coordinates, got {}",
+            coords.len()
+        ));
+    };
+    let (x, y, z) = (fq2(x)?, fq2(y)?, fq2(z)?);
+    if z.is_zero() {
+        return Ok(G2Affine::identity());
+    }
+    if !z.is_one() {
+        return Err("G2 point is not affine".to_string());
+    }
+    let point = G2Affine::new_unchecked(x, y);
+    if !point.is_on_curve() || !point.is_in_correct_subgroup_assuming_on_curve() {
+        return Err("G2 point is not on the BN254 twist".to_string());
+    }
+    Ok(point)
+}
+
+fn fq2(coords: &[String]) -> Result<Fq2, String> {
+    let [c0, c1] = coords else {
+        return Err(format!("Fq2 element needs 2 limbs, got {}", coords.len()));
+    };
+    Ok(Fq2::new(field_element(c0)?, field_element(c1)?))
+}
+
+/// Decimal or `0x` hex; values at or above the modulus are rejected rather
+/// than reduced.
+fn field_element<F: PrimeField>(raw: &str) -> Result<F, String> {
+    let raw = raw.trim();
+    let parsed = match raw.strip_prefix("0x") {
+        Some(hex) => BigUint::parse_bytes(hex.as_bytes(), 16),
+        None => BigUint::parse_bytes(raw.as_bytes(), 10),
+    };
+    parsed
+        .and_then(|value| F::BigInt::try_from(value).ok())
+        .and_then(F::from_bigint)
+        .ok_or_else(|| format!("`{raw}` is not a field element"))
+}
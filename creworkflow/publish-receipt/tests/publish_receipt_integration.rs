@@ -1,6 +1,46 @@
+use std::fs;
+
+use num_bigint::BigUint;
 use publish_receipt::errors::PublishError;
-use publish_receipt::handler::process_publish_receipt;
+use publish_receipt::handler::{process_publish_receipt, REJECTION_NOTIONAL_SIGNAL};
 use publish_receipt::models::{ChainValidationState, PublishReceiptRequest, SettlementStatus};
+use publish_receipt::verifier::{verifying_key_path, Groth16Proof, VerifyingKey};
+use serde_json::Value;
+
+/// `zk/artifacts/<circuit>/<circuit>.<kind>.json`, as exported by the zk
+/// pipeline.
+fn artifact(circuit: &str, kind: &str) -> Value {
+    let path = format!(
+        "{}/../../zk/artifacts/{circuit}/{circuit}.{kind}.json",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    serde_json::from_str(&fs::read_to_string(&path).expect("artifact")).expect("json")
+}
+
+fn signals(circuit: &str) -> Vec<String> {
+    serde_json::from_value(artifact(circuit, "public")).expect("public signals")
+}
+
+/// The Solidity calldata layout proof-generate emits: `b` limbs swapped.
+fn calldata_hex(proof: &Value) -> String {
+    let words = [
+        &proof["pi_a"][0],
+        &proof["pi_a"][1],
+        &proof["pi_b"][0][1],
+        &proof["pi_b"][0][0],
+        &proof["pi_b"][1][1],
+        &proof["pi_b"][1][0],
+        &proof["pi_c"][0],
+        &proof["pi_c"][1],
+    ];
+    let mut out = String::from("0x");
+    for word in words {
+        let value = BigUint::parse_bytes(word.as_str().expect("coord").as_bytes(), 10)
+            .expect("decimal coordinate");
+        out.push_str(&format!("{:0>64}", value.to_str_radix(16)));
+    }
+    out
+}
 
 fn request_template() -> PublishReceiptRequest {
     PublishReceiptRequest {
@@ -11,19 +51,11 @@ fn request_template() -> PublishReceiptRequest {
         policy_version: 1,
         status: SettlementStatus::Settled,
         receipt_hash: "0xreceipt-hash-1".to_string(),
-        proof_hex: "0xc0ffee".to_string(),
-        public_signals: vec![
-            "1".to_string(),
-            "2".to_string(),
-            "3".to_string(),
-            "4".to_string(),
-            "5".to_string(),
-            "6".to_string(),
-        ],
+        proof_hex: calldata_hex(&artifact("settlement_valid", "proof")),
+        public_signals: signals("settlement_valid"),
         chain_validation: ChainValidationState {
             authorized_publisher: true,
             policy_active: true,
-            proof_valid: true,
             signal_binding_valid: true,
             duplicate_workflow_run: false,
             duplicate_receipt_hash: false,
@@ -49,12 +81,71 @@ fn publish_receipt_success() {
 #[test]
 fn publish_receipt_fails_on_invalid_proof() {
     let mut req = request_template();
-    req.chain_validation.proof_valid = false;
+    req.public_signals[5] = "1001".to_string();
 
     let err = process_publish_receipt(req).expect_err("must fail");
     assert!(matches!(err, PublishError::InvalidProof));
 }
 
+#[test]
+fn publish_receipt_requires_rejection_status_to_match_the_proof() {
+    let mut req = request_template();
+    req.status = SettlementStatus::Rejected;
+    let err = process_publish_receipt(req).expect_err("a fill proof cannot publish a rejection");
+    assert!(matches!(err, PublishError::InvalidRequest(_)));
+
+    let mut req = request_template();
+    req.public_signals[5] = REJECTION_NOTIONAL_SIGNAL.to_string();
+    let err = process_publish_receipt(req).expect_err("a rejection proof cannot publish a fill");
+    assert!(matches!(err, PublishError::InvalidRequest(_)));
+}
+
+#[test]
+fn publish_receipt_fails_on_malformed_proof() {
+    let mut req = request_template();
+    req.proof_hex = "0xc0ffee".to_string();
+    let err = process_publish_receipt(req).expect_err("must fail");
+    assert!(matches!(err, PublishError::MalformedProof(_)));
+
+    // Signals are not reduced modulo the scalar field: r + notional would
+    // alias the honest signal and must be refused, as onchain.
+    let mut req = request_template();
+    let r = BigUint::parse_bytes(
+        b"21888242871839275222246405745257275088548364400416034343698204186575808495617",
+        10,
+    )
+    .expect("modulus");
+    req.public_signals[5] = (r + 1000u32).to_string();
+    let err = process_publish_receipt(req).expect_err("must fail");
+    assert!(matches!(err, PublishError::MalformedProof(_)));
+}
+
+#[test]
+fn verifier_checks_exported_artifacts_per_circuit() {
+    let settlement_key =
+        VerifyingKey::load(&verifying_key_path("settlement_valid")).expect("settlement vkey");
+    let settlement_proof =
+        Groth16Proof::from_snarkjs_json(&artifact("settlement_valid", "proof")).expect("proof");
+    settlement_key
+        .verify(&settlement_proof, &signals("settlement_valid"))
+        .expect("exported settlement proof verifies");
+
+    let compliance_key = VerifyingKey::from_snarkjs_json(&artifact("compliance_valid", "vkey"))
+        .expect("compliance vkey");
+    let compliance_proof =
+        Groth16Proof::from_snarkjs_json(&artifact("compliance_valid", "proof")).expect("proof");
+    compliance_key
+        .verify(&compliance_proof, &signals("compliance_valid"))
+        .expect("exported compliance proof verifies");
+
+    let mut foreign_signals = signals("compliance_valid");
+    foreign_signals.extend(["0".to_string(), "0".to_string()]);
+    assert!(matches!(
+        settlement_key.verify(&compliance_proof, &foreign_signals),
+        Err(PublishError::InvalidProof)
+    ));
+}
+
 #[test]
 fn publish_receipt_fails_on_stale_policy() {
     let mut req = request_template();
@@ -8,8 +8,8 @@ trap 'rm -rf "$TMP_DIR"' EXIT
 NOW_TS=1750000000
 EXPIRY_TS=1750003600
 
-payload_buy='{"asset_pair":"ETH/USDC","side":"buy","size":"5.0","limit_price":"3020.0","expiry":1750003600,"counterparty_constraints":{"allow_list":["desk_a"],"deny_list":["desk_x"]},"nonce":"nonce-a","issued_at":1749999900}'
-payload_sell='{"asset_pair":"ETH/USDC","side":"sell","size":"4.0","limit_price":"3000.0","expiry":1750003600,"counterparty_constraints":{"allow_list":["desk_a"],"deny_list":["desk_x"]},"nonce":"nonce-b","issued_at":1749999905}'
+payload_buy='{"asset_pair":"ETH/USDC","side":"buy","size":"5.0","limit_price":"3020.0","expiry":1750003600,"counterparty_constraints":{"allow_list":["0x2222"],"deny_list":["0x9999"]},"nonce":"nonce-a","issued_at":1749999900}'
+payload_sell='{"asset_pair":"ETH/USDC","side":"sell","size":"4.0","limit_price":"3000.0","expiry":1750003600,"counterparty_constraints":{"allow_list":["0x1111"],"deny_list":["0x9999"]},"nonce":"nonce-b","issued_at":1749999905}'
 
 make_envelope() {
   local intent_id="$1"
@@ -50,7 +50,8 @@ build_input() {
     "policy_version": 1,
     "expected_policy_version": 1,
     "max_risk_score": 70,
-    "max_notional": 500000.0
+    "max_notional": "500000.00",
+    "pricing": { "rule": "midpoint", "reference_band_bps": null }
   },
   "external_signals": {
     "api_available": true,
@@ -110,20 +111,21 @@ run_case() {
 }
 
 success_input="${TMP_DIR}/success.json"
-fail_risk_input="${TMP_DIR}/fail-risk.json"
+reject_risk_input="${TMP_DIR}/reject-risk.json"
 fail_api_input="${TMP_DIR}/fail-api.json"
 
 build_input "42" "false" "\"\"" > "$success_input"
-build_input "99" "false" "\"\"" > "$fail_risk_input"
+build_input "99" "false" "\"\"" > "$reject_risk_input"
 build_input "42" "true" "\"http://127.0.0.1:9/confidential-signals\"" > "$fail_api_input"
 
 cd "$ROOT_DIR"
+export ZK_ROOT="${ZK_ROOT:-$ROOT_DIR/../zk}"
 
 echo "Running CRE workflow simulation from workspace: $ROOT_DIR"
 echo
 
 run_case "success-path" "$success_input" "false"
-run_case "failure-risk-threshold" "$fail_risk_input" "true"
+run_case "reject-risk-threshold" "$reject_risk_input" "false"
 run_case "failure-api-unavailable" "$fail_api_input" "true"
 
 echo "Simulation complete."
@@ -7,8 +7,8 @@ mkdir -p "$ARTIFACT_DIR"
 
 NOW_TS=1750000000
 
-payload_buy='{"asset_pair":"ETH/USDC","side":"buy","size":"5.0","limit_price":"3020.0","expiry":1750003600,"counterparty_constraints":{"allow_list":["desk_a"],"deny_list":["desk_x"]},"nonce":"nonce-a","issued_at":1749999900}'
-payload_sell='{"asset_pair":"ETH/USDC","side":"sell","size":"4.0","limit_price":"3000.0","expiry":1750003600,"counterparty_constraints":{"allow_list":["desk_a"],"deny_list":["desk_x"]},"nonce":"nonce-b","issued_at":1749999905}'
+payload_buy='{"asset_pair":"ETH/USDC","side":"buy","size":"5.0","limit_price":"3020.0","expiry":1750003600,"counterparty_constraints":{"allow_list":["0x2222"],"deny_list":["0x9999"]},"nonce":"nonce-a","issued_at":1749999900}'
+payload_sell='{"asset_pair":"ETH/USDC","side":"sell","size":"4.0","limit_price":"3000.0","expiry":1750003600,"counterparty_constraints":{"allow_list":["0x1111"],"deny_list":["0x9999"]},"nonce":"nonce-b","issued_at":1749999905}'
 
 make_envelope() {
   local intent_id="$1"
@@ -55,16 +55,19 @@ cat > "$STEP1_IN" <<EON
 EON
 
 cd "$ROOT_DIR"
+export ZK_ROOT="${ZK_ROOT:-$ROOT_DIR/../zk}"
 
 cargo run -q -p intent-intake < "$STEP1_IN" > "$STEP1_OUT"
 
 jq -n \
   --arg workflow_run_id "$(jq -r '.workflow_run_id' "$STEP1_OUT")" \
-  --argjson intents "$(jq '[.normalized_private_intents[] | .size = (.size|tonumber) | .limit_price = (.limit_price|tonumber)]' "$STEP1_OUT")" \
-  --argjson policy '{"policy_version":1,"expected_policy_version":1,"max_risk_score":70,"max_notional":500000.0}' \
+  --argjson current_unix_ts "$NOW_TS" \
+  --argjson intents "$(jq '.normalized_private_intents' "$STEP1_OUT")" \
+  --argjson policy '{"policy_version":1,"expected_policy_version":1,"max_risk_score":70,"max_notional":"500000.00","pricing":{"rule":"midpoint","reference_band_bps":null}}' \
   --argjson external '{"api_available":true,"compliance_passed":true,"risk_score":42,"attestation_payload":"provider=mock_conf_http;result=ok"}' \
   '{
     workflow_run_id: $workflow_run_id,
+    current_unix_ts: $current_unix_ts,
     policy: $policy,
     intents: $intents,
     external_signals: $external
@@ -74,12 +77,12 @@ cargo run -q -p confidential-match < "$STEP2_IN" > "$STEP2_OUT"
 
 jq -n \
   --arg workflow_run_id "$(jq -r '.workflow_run_id' "$STEP2_OUT")" \
-  --arg asset_pair "$(jq -r '.private_settlement_params.asset_pair' "$STEP2_OUT")" \
-  --arg buy_intent_id "$(jq -r '.private_settlement_params.buy_intent_id' "$STEP2_OUT")" \
-  --arg sell_intent_id "$(jq -r '.private_settlement_params.sell_intent_id' "$STEP2_OUT")" \
-  --argjson execution_size "$(jq '.private_settlement_params.execution_size' "$STEP2_OUT")" \
-  --argjson execution_price "$(jq '.private_settlement_params.execution_price' "$STEP2_OUT")" \
-  --argjson notional "$(jq '.private_settlement_params.notional' "$STEP2_OUT")" \
+  --arg asset_pair "$(jq -r '.private_settlement_params[0].asset_pair' "$STEP2_OUT")" \
+  --arg buy_intent_id "$(jq -r '.private_settlement_params[0].buy_intent_id' "$STEP2_OUT")" \
+  --arg sell_intent_id "$(jq -r '.private_settlement_params[0].sell_intent_id' "$STEP2_OUT")" \
+  --argjson execution_size "$(jq '.private_settlement_params[0].execution_size' "$STEP2_OUT")" \
+  --argjson execution_price "$(jq '.private_settlement_params[0].execution_price' "$STEP2_OUT")" \
+  --argjson notional "$(jq '.private_settlement_params[0].notional' "$STEP2_OUT")" \
   --argjson policy_version "$(jq '.policy_check_result.policy_version' "$STEP2_OUT")" \
   '{
     workflow_run_id: $workflow_run_id,
@@ -105,10 +108,10 @@ jq -n \
   --arg workflow_run_id "$(jq -r '.workflow_run_id' "$STEP2_OUT")" \
   --arg proof_hash "$(jq -r '.proof_hash' "$STEP3_OUT")" \
   --arg receipt_hash "$(jq -r '.receipt_hash' "$STEP3_OUT")" \
-  --arg asset "$(jq -r '.private_settlement_params.asset_pair' "$STEP2_OUT")" \
-  --arg from_account "$(jq -r '.private_settlement_params.buy_intent_id' "$STEP2_OUT")" \
-  --arg to_account "$(jq -r '.private_settlement_params.sell_intent_id' "$STEP2_OUT")" \
-  --argjson amount "$(jq '.private_settlement_params.notional' "$STEP2_OUT")" \
+  --arg asset "$(jq -r '.private_settlement_params[0].asset_pair' "$STEP2_OUT")" \
+  --arg from_account "$(jq -r '.private_settlement_params[0].buy_intent_id' "$STEP2_OUT")" \
+  --arg to_account "$(jq -r '.private_settlement_params[0].sell_intent_id' "$STEP2_OUT")" \
+  --argjson amount "$(jq '.private_settlement_params[0].notional' "$STEP2_OUT")" \
   '{
     workflow_run_id: $workflow_run_id,
     proof_bundle: { proof_hash: $proof_hash, receipt_hash: $receipt_hash, approved: true },
@@ -150,7 +153,6 @@ jq -n \
     chain_validation: {
       authorized_publisher: true,
       policy_active: true,
-      proof_valid: true,
       signal_binding_valid: true,
       duplicate_workflow_run: false,
       duplicate_receipt_hash: false
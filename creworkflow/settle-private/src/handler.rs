@@ -80,7 +80,7 @@ fn validate_request(req: &SettlePrivateRequest) -> Result<(), SettleError> {
             "proof_hash and receipt_hash are required".to_string(),
         ));
     }
-    if req.settlement_instruction.amount <= 0.0 {
+    if req.settlement_instruction.amount.is_zero() {
         return Err(SettleError::InvalidRequest(
             "settlement amount must be positive".to_string(),
         ));
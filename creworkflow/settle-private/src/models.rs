@@ -1,3 +1,4 @@
+use fixed_amount::Amount;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Deserialize)]
@@ -18,7 +19,7 @@ pub struct ProofBundle {
 #[derive(Debug, Clone, Deserialize)]
 pub struct SettlementInstruction {
     pub asset: String,
-    pub amount: f64,
+    pub amount: Amount,
     pub from_account: String,
     pub to_account: String,
     pub transfer_simulation_ok: bool,
@@ -14,7 +14,7 @@ fn request_template() -> SettlePrivateRequest {
         },
         settlement_instruction: SettlementInstruction {
             asset: "USDC".to_string(),
-            amount: 10000.0,
+            amount: "10000.000000".parse().expect("amount"),
             from_account: "vault_a".to_string(),
             to_account: "vault_b".to_string(),
             transfer_simulation_ok: true,
@@ -5,6 +5,7 @@ source "$(dirname "$0")/common.sh"
 
 CIRCUIT=${1:-settlement_valid}
 INPUT_FILE=${2:-"$FIXTURES_DIR/${CIRCUIT}.fixture.json"}
+PROOF_OUT_DIR=${3:-}
 ensure_dirs
 require_input_file "$INPUT_FILE"
 
@@ -24,9 +25,17 @@ if [[ ! -f "$ZKEY_FINAL" ]]; then
   exit 1
 fi
 
-WITNESS_FILE="$OUT_DIR/${CIRCUIT}.wtns"
-PROOF_FILE="$OUT_DIR/${CIRCUIT}.proof.json"
-PUBLIC_FILE="$OUT_DIR/${CIRCUIT}.public.json"
+# Callers proving concurrently pass their own output dir so witness/proof
+# files never collide in the shared artifacts dir.
+if [[ -n "$PROOF_OUT_DIR" ]]; then
+  mkdir -p "$PROOF_OUT_DIR"
+else
+  PROOF_OUT_DIR="$OUT_DIR"
+fi
+
+WITNESS_FILE="$PROOF_OUT_DIR/${CIRCUIT}.wtns"
+PROOF_FILE="$PROOF_OUT_DIR/${CIRCUIT}.proof.json"
+PUBLIC_FILE="$PROOF_OUT_DIR/${CIRCUIT}.public.json"
 
 node "$WITNESS_BIN" "$WASM_FILE" "$INPUT_FILE" "$WITNESS_FILE"
 run_snarkjs groth16 prove "$ZKEY_FINAL" "$WITNESS_FILE" "$PROOF_FILE" "$PUBLIC_FILE"